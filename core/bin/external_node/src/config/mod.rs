@@ -1,7 +1,9 @@
 use std::{
     env,
     ffi::OsString,
+    net::IpAddr,
     num::{NonZeroU32, NonZeroU64, NonZeroUsize},
+    path::PathBuf,
     time::Duration,
 };
 
@@ -9,7 +11,7 @@ use anyhow::Context;
 use serde::Deserialize;
 use zksync_config::{
     configs::{
-        api::{MaxResponseSize, MaxResponseSizeOverrides},
+        api::{MaxResponseSize, MaxResponseSizeOverrides, RpcApiKeys, RpcMethodWeights},
         consensus::{ConsensusConfig, ConsensusSecrets},
     },
     ObjectStoreConfig,
@@ -20,7 +22,7 @@ use zksync_dal::{ConnectionPool, Core};
 use zksync_metadata_calculator::MetadataCalculatorRecoveryConfig;
 use zksync_node_api_server::{
     tx_sender::TxSenderConfig,
-    web3::{state::InternalApiConfig, Namespace},
+    web3::{backend_jsonrpsee::ClientRateLimits, state::InternalApiConfig, Namespace},
 };
 use zksync_protobuf_config::proto;
 use zksync_snapshots_applier::SnapshotsApplierConfig;
@@ -234,6 +236,21 @@ pub(crate) struct OptionalENConfig {
     /// Method-specific overrides in MiBs for the maximum response body size.
     #[serde(default = "MaxResponseSizeOverrides::empty")]
     max_response_body_size_overrides_mb: MaxResponseSizeOverrides,
+    /// Maximum total weight of requests per minute for a single client, applied to both HTTP and WS servers.
+    /// Clients are identified by the `api_key_header` header containing one of `api_keys`, or by their IP address.
+    /// If not set, per-client rate limiting is disabled.
+    pub client_requests_per_minute_limit: Option<NonZeroU32>,
+    /// Name of the HTTP header containing client API keys used for per-client rate limiting.
+    pub api_key_header: Option<String>,
+    /// Known client API keys with their quotas, e.g. `key1=600,key2=6000`.
+    #[serde(default = "RpcApiKeys::empty")]
+    pub api_keys: RpcApiKeys,
+    /// IP addresses of reverse proxies allowed to report client IP addresses via `X-Forwarded-For` / `X-Real-IP` headers.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Relative weights of RPC methods used for rate limiting, e.g. `debug_*=20,eth_getLogs=10`.
+    #[serde(default = "RpcMethodWeights::empty")]
+    pub method_weights: RpcMethodWeights,
 
     // Other API config settings
     /// Interval between polling DB for Web3 subscriptions.
@@ -281,6 +298,11 @@ pub(crate) struct OptionalENConfig {
     /// Maximum number of transactions to be stored in the mempool cache.
     #[serde(default = "OptionalENConfig::default_mempool_cache_size")]
     pub mempool_cache_size: usize,
+    /// Capacity of the cache for responses of RPC methods returning immutable historical data (e.g., blocks
+    /// and transaction receipts in executed L1 batches) in MiBs. The default value is 128 MiB. If set to 0,
+    /// the cache will be disabled.
+    #[serde(default = "OptionalENConfig::default_response_cache_size_mb")]
+    response_cache_size_mb: usize,
     /// Enables extended tracing of RPC calls. This may negatively impact performance for nodes under high load
     /// (hundreds or thousands RPS).
     #[serde(default = "OptionalENConfig::default_extended_api_tracing")]
@@ -345,6 +367,9 @@ pub(crate) struct OptionalENConfig {
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     #[serde(default = "OptionalENConfig::default_merkle_tree_stalled_writes_timeout_sec")]
     merkle_tree_stalled_writes_timeout_sec: u64,
+    /// Interval between online consistency checks of randomly sampled Merkle tree subtrees. If not set,
+    /// online consistency checks are disabled.
+    merkle_tree_consistency_check_interval_ms: Option<NonZeroU64>,
 
     // Postgres config (new parameters)
     /// Threshold in milliseconds for the DB connection lifetime to denote it as long-living and log its details.
@@ -410,6 +435,13 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// If set, each L1 batch with the number divisible by this value will be exempt from Merkle tree pruning, so that
+    /// Merkle proofs (`zks_getProof`) can be served for it even after it's pruned. Checkpoints only apply to L1 batches
+    /// pruned after the option was enabled.
+    pub pruning_tree_checkpoint_interval: Option<NonZeroU32>,
+    /// Number of L1 batches a Merkle tree checkpoint is retained after it becomes eligible for pruning. If not set,
+    /// checkpoints are retained indefinitely. Has no effect if `pruning_tree_checkpoint_interval` is not set.
+    pub pruning_tree_checkpoint_retention: Option<u32>,
 }
 
 impl OptionalENConfig {
@@ -520,6 +552,10 @@ impl OptionalENConfig {
         10_000
     }
 
+    const fn default_response_cache_size_mb() -> usize {
+        128
+    }
+
     const fn default_extended_api_tracing() -> bool {
         true
     }
@@ -573,6 +609,11 @@ impl OptionalENConfig {
         self.latest_values_cache_size_mb * BYTES_IN_MEGABYTE
     }
 
+    /// Returns the size of response cache in bytes.
+    pub fn response_cache_size(&self) -> usize {
+        self.response_cache_size_mb * BYTES_IN_MEGABYTE
+    }
+
     /// Returns the size of block cache for Merkle tree in bytes.
     pub fn merkle_tree_block_cache_size(&self) -> usize {
         self.merkle_tree_block_cache_size_mb * BYTES_IN_MEGABYTE
@@ -588,6 +629,12 @@ impl OptionalENConfig {
         Duration::from_secs(self.merkle_tree_stalled_writes_timeout_sec)
     }
 
+    /// Returns the interval between online Merkle tree consistency checks, or `None` if these checks are disabled.
+    pub fn merkle_tree_consistency_check_interval(&self) -> Option<Duration> {
+        self.merkle_tree_consistency_check_interval_ms
+            .map(|interval| Duration::from_millis(interval.get()))
+    }
+
     pub fn long_connection_threshold(&self) -> Option<Duration> {
         self.database_long_connection_threshold_ms
             .map(Duration::from_millis)
@@ -761,6 +808,11 @@ pub(crate) struct ExperimentalENConfig {
     /// If not set, parallel persistence will be disabled.
     #[serde(default)] // Temporarily use a conservative option (sequential recovery) as default
     pub snapshots_recovery_tree_parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Path to a Merkle tree snapshot directory exported by another node. If set, the Merkle tree is imported
+    /// from this snapshot during snapshot recovery instead of being recovered from Postgres, which is much faster.
+    /// The snapshot must be taken at the L1 batch used for snapshot recovery; its root hash is verified against
+    /// the root hash of this batch.
+    pub snapshots_recovery_tree_snapshot_path: Option<PathBuf>,
 
     // Commitment generator
     /// Maximum degree of parallelism during commitment generation, i.e., the maximum number of L1 batches being processed in parallel.
@@ -786,6 +838,7 @@ impl ExperimentalENConfig {
             snapshots_recovery_l1_batch: None,
             snapshots_recovery_tree_chunk_size: Self::default_snapshots_recovery_tree_chunk_size(),
             snapshots_recovery_tree_parallel_persistence_buffer: None,
+            snapshots_recovery_tree_snapshot_path: None,
             commitment_generator_max_parallelism: None,
         }
     }
@@ -948,6 +1001,21 @@ impl From<&ExternalNodeConfig> for InternalApiConfig {
     }
 }
 
+impl From<&ExternalNodeConfig> for ClientRateLimits {
+    fn from(config: &ExternalNodeConfig) -> Self {
+        let mut limits = Self::default()
+            .with_api_keys(config.optional.api_keys.clone())
+            .with_trusted_proxies(config.optional.trusted_proxies.clone());
+        if let Some(limit) = config.optional.client_requests_per_minute_limit {
+            limits = limits.with_requests_per_minute_limit(limit);
+        }
+        if let Some(api_key_header) = &config.optional.api_key_header {
+            limits = limits.with_api_key_header(api_key_header.clone());
+        }
+        limits
+    }
+}
+
 impl From<&ExternalNodeConfig> for TxSenderConfig {
     fn from(config: &ExternalNodeConfig) -> Self {
         Self {
@@ -958,6 +1026,10 @@ impl From<&ExternalNodeConfig> for TxSenderConfig {
                 .unwrap(),
             gas_price_scale_factor: config.optional.gas_price_scale_factor,
             max_nonce_ahead: config.optional.max_nonce_ahead,
+            // Pending transactions are only stored on the main node, so the replacement policy is enforced there.
+            replacement_fee_bump_percent: 0,
+            // Mempool is only maintained by the main node, so its limits are enforced there.
+            mempool_eviction_policy: Default::default(),
             vm_execution_cache_misses_limit: config.optional.vm_execution_cache_misses_limit,
             // We set these values to the maximum since we don't know the actual values
             // and they will be enforced by the main node anyway.
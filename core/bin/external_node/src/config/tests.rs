@@ -83,6 +83,7 @@ fn parsing_optional_config_from_empty_env() {
     assert_eq!(config.vm_concurrency_limit, 2_048);
     assert_eq!(config.factory_deps_cache_size(), 128 * BYTES_IN_MEGABYTE);
     assert_eq!(config.latest_values_cache_size(), 128 * BYTES_IN_MEGABYTE);
+    assert_eq!(config.response_cache_size(), 128 * BYTES_IN_MEGABYTE);
     assert_eq!(config.merkle_tree_multi_get_chunk_size, 500);
     assert_eq!(
         config.merkle_tree_block_cache_size(),
@@ -117,6 +118,7 @@ fn parsing_optional_config_from_env() {
         ("EN_VM_CONCURRENCY_LIMIT", "1000"),
         ("EN_FACTORY_DEPS_CACHE_SIZE_MB", "64"),
         ("EN_LATEST_VALUES_CACHE_SIZE_MB", "50"),
+        ("EN_RESPONSE_CACHE_SIZE_MB", "0"),
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
@@ -125,6 +127,10 @@ fn parsing_optional_config_from_env() {
             "zks_getProof=100,eth_call=2",
         ),
         ("EN_L1_BATCH_COMMIT_DATA_GENERATOR_MODE", "Validium"),
+        ("EN_CLIENT_REQUESTS_PER_MINUTE_LIMIT", "600"),
+        ("EN_API_KEYS", "first=100,second=1000"),
+        ("EN_TRUSTED_PROXIES", "10.0.0.1,10.0.0.2"),
+        ("EN_METHOD_WEIGHTS", "debug_*=20,eth_getLogs=10"),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -146,6 +152,7 @@ fn parsing_optional_config_from_env() {
     assert_eq!(config.vm_concurrency_limit, 1_000);
     assert_eq!(config.factory_deps_cache_size(), 64 * BYTES_IN_MEGABYTE);
     assert_eq!(config.latest_values_cache_size(), 50 * BYTES_IN_MEGABYTE);
+    assert_eq!(config.response_cache_size(), 0);
     assert_eq!(config.merkle_tree_multi_get_chunk_size, 1_000);
     assert_eq!(
         config.merkle_tree_block_cache_size(),
@@ -170,6 +177,21 @@ fn parsing_optional_config_from_env() {
         config.l1_batch_commit_data_generator_mode,
         L1BatchCommitmentMode::Validium
     );
+    assert_eq!(
+        config.client_requests_per_minute_limit,
+        NonZeroU32::new(600)
+    );
+    assert_eq!(config.api_key_header, None);
+    assert_eq!(config.api_keys.iter().len(), 2);
+    assert_eq!(
+        config.trusted_proxies,
+        [
+            "10.0.0.1".parse::<IpAddr>().unwrap(),
+            "10.0.0.2".parse().unwrap()
+        ]
+    );
+    assert_eq!(config.method_weights.get("debug_traceCall").get(), 20);
+    assert_eq!(config.method_weights.get("eth_getLogs").get(), 10);
 }
 
 #[test]
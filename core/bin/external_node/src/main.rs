@@ -28,7 +28,7 @@ use zksync_node_api_server::{
     execution_sandbox::VmConcurrencyLimiter,
     healthcheck::HealthCheckHandle,
     tx_sender::{proxy::TxProxy, ApiContracts, TxSenderBuilder},
-    web3::{mempool_cache::MempoolCache, ApiBuilder, Namespace},
+    web3::{mempool_cache::MempoolCache, response_cache::ResponseCache, ApiBuilder, Namespace},
 };
 use zksync_node_consensus as consensus;
 use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
@@ -142,6 +142,10 @@ async fn run_tree(
             parallel_persistence_buffer: config
                 .experimental
                 .snapshots_recovery_tree_parallel_persistence_buffer,
+            tree_snapshot_path: config
+                .experimental
+                .snapshots_recovery_tree_snapshot_path
+                .clone(),
         },
     };
 
@@ -172,13 +176,23 @@ async fn run_tree(
     if config.optional.pruning_enabled {
         tracing::warn!("Proceeding with node state pruning for the Merkle tree. This is an experimental feature; use at your own risk");
 
-        let pruning_task =
+        let mut pruning_task =
             metadata_calculator.pruning_task(config.optional.pruning_removal_delay() / 2);
+        if let Some(interval) = config.optional.pruning_tree_checkpoint_interval {
+            pruning_task = pruning_task
+                .with_checkpoints(interval, config.optional.pruning_tree_checkpoint_retention);
+        }
         app_health.insert_component(pruning_task.health_check())?;
         let pruning_task_handle = tokio::spawn(pruning_task.run(stop_receiver.clone()));
         task_futures.push(pruning_task_handle);
     }
 
+    if let Some(check_interval) = config.optional.merkle_tree_consistency_check_interval() {
+        let consistency_checker = metadata_calculator.consistency_checker(check_interval);
+        app_health.insert_component(consistency_checker.health_check())?;
+        task_futures.push(tokio::spawn(consistency_checker.run(stop_receiver.clone())));
+    }
+
     if let Some(api_config) = api_config {
         let address = (Ipv4Addr::UNSPECIFIED, api_config.port).into();
         let tree_reader = metadata_calculator.tree_reader();
@@ -397,6 +411,7 @@ async fn run_api(
     main_node_client: Box<DynClient<L2>>,
     singleton_pool_builder: &ConnectionPoolBuilder<Core>,
     fee_params_fetcher: Arc<MainNodeFeeParamsFetcher>,
+    response_cache: ResponseCache,
     components: &HashSet<Component>,
 ) -> anyhow::Result<()> {
     let tree_reader = match tree_reader {
@@ -499,6 +514,10 @@ async fn run_api(
     task_handles.push(tokio::spawn(
         mempool_cache_update_task.run(stop_receiver.clone()),
     ));
+    let response_cache_update_task = response_cache.update_task(connection_pool.clone());
+    task_handles.push(tokio::spawn(
+        response_cache_update_task.run(stop_receiver.clone()),
+    ));
 
     // The refresh interval should be several times lower than the pruning removal delay, so that
     // soft-pruning will timely propagate to the API server.
@@ -515,7 +534,10 @@ async fn run_api(
             .with_vm_barrier(vm_barrier.clone())
             .with_sync_state(sync_state.clone())
             .with_mempool_cache(mempool_cache.clone())
+            .with_response_cache(response_cache.clone())
             .with_extended_tracing(config.optional.extended_rpc_tracing)
+            .with_client_rate_limits(config.into())
+            .with_method_weights(config.optional.method_weights.clone())
             .enable_api_namespaces(config.optional.api_namespaces());
         if let Some(tree_reader) = &tree_reader {
             builder = builder.with_tree_api(tree_reader.clone());
@@ -544,7 +566,10 @@ async fn run_api(
             .with_vm_barrier(vm_barrier)
             .with_sync_state(sync_state)
             .with_mempool_cache(mempool_cache)
+            .with_response_cache(response_cache)
             .with_extended_tracing(config.optional.extended_rpc_tracing)
+            .with_client_rate_limits(config.into())
+            .with_method_weights(config.optional.method_weights.clone())
             .enable_api_namespaces(config.optional.api_namespaces());
         if let Some(tree_reader) = tree_reader {
             builder = builder.with_tree_api(tree_reader);
@@ -573,6 +598,7 @@ async fn init_tasks(
     task_handles: &mut Vec<JoinHandle<anyhow::Result<()>>>,
     app_health: &AppHealthCheck,
     stop_receiver: watch::Receiver<bool>,
+    response_cache: ResponseCache,
     components: &HashSet<Component>,
 ) -> anyhow::Result<()> {
     let protocol_version_update_task =
@@ -669,6 +695,7 @@ async fn init_tasks(
             main_node_client,
             &singleton_pool_builder,
             fee_params_fetcher.clone(),
+            response_cache,
             components,
         )
         .await?;
@@ -937,6 +964,8 @@ async fn run_node(
         }
     });
 
+    let response_cache = ResponseCache::new(config.optional.response_cache_size() as u64);
+
     // Revert the storage if needed.
     let mut reverter = BlockReverter::new(NodeRole::External, connection_pool.clone());
     // Reverting executed batches is more-or-less safe for external nodes.
@@ -944,7 +973,11 @@ async fn run_node(
         .allow_rolling_back_executed_batches()
         .enable_rolling_back_postgres()
         .enable_rolling_back_merkle_tree(config.required.merkle_tree_path.clone())
-        .enable_rolling_back_state_keeper_cache(config.required.state_cache_path.clone());
+        .enable_rolling_back_state_keeper_cache(config.required.state_cache_path.clone())
+        .add_rollback_hook({
+            let response_cache = response_cache.clone();
+            move |last_l1_batch_to_keep| response_cache.roll_back(last_l1_batch_to_keep)
+        });
 
     let mut reorg_detector = ReorgDetector::new(main_node_client.clone(), connection_pool.clone());
     // We're checking for the reorg in the beginning because we expect that if reorg is detected during
@@ -988,6 +1021,7 @@ async fn run_node(
         &mut task_handles,
         &app_health,
         stop_receiver.clone(),
+        response_cache,
         &opt.components.0,
     )
     .await
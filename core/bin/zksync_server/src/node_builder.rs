@@ -39,7 +39,8 @@ use zksync_node_framework::{
         tee_verifier_input_producer::TeeVerifierInputProducerLayer,
         vm_runner::protective_reads::ProtectiveReadsWriterLayer,
         web3_api::{
-            caches::MempoolCacheLayer,
+            caches::{MempoolCacheLayer, ResponseCacheLayer},
+            graphql::GraphQlServerLayer,
             server::{Web3ServerLayer, Web3ServerOptionalConfig},
             tree_api_client::TreeApiClientLayer,
             tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
@@ -164,6 +165,9 @@ impl MainNodeBuilder {
             &operations_manager_env_config,
         );
         let mut layer = MetadataCalculatorLayer::new(metadata_calculator_config);
+        if let Some(interval) = merkle_tree_env_config.consistency_check_interval() {
+            layer = layer.with_consistency_check_interval(interval);
+        }
         if with_tree_api {
             let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
@@ -225,11 +229,14 @@ impl MainNodeBuilder {
         };
 
         // On main node we always use master pool sink.
-        self.node.add_layer(TxSinkLayer::MasterPoolSink);
+        self.node.add_layer(TxSinkLayer::MasterPoolSink {
+            replacement_fee_bump_percent: rpc_config.replacement_fee_bump_percent(),
+        });
         self.node.add_layer(TxSenderLayer::new(
             TxSenderConfig::new(
                 &sk_config,
                 &rpc_config,
+                &try_load_config!(self.configs.mempool_config),
                 try_load_config!(self.wallets.state_keeper)
                     .fee_account
                     .address(),
@@ -248,6 +255,9 @@ impl MainNodeBuilder {
             rpc_config.mempool_cache_size(),
             rpc_config.mempool_cache_update_interval(),
         ));
+        self.node.add_layer(ResponseCacheLayer::new(
+            rpc_config.response_cache_size() as u64
+        ));
         Ok(self)
     }
 
@@ -268,6 +278,9 @@ impl MainNodeBuilder {
             namespaces.push(Namespace::Debug)
         }
         namespaces.push(Namespace::Snapshots);
+        if rpc_config.txpool_namespace_enabled {
+            namespaces.push(Namespace::Txpool);
+        }
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -275,6 +288,8 @@ impl MainNodeBuilder {
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            client_rate_limits: (&rpc_config).into(),
+            method_weights: Some(rpc_config.method_weights.clone()),
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::http(
@@ -297,6 +312,9 @@ impl MainNodeBuilder {
             namespaces.push(Namespace::Debug)
         }
         namespaces.push(Namespace::Snapshots);
+        if rpc_config.txpool_namespace_enabled {
+            namespaces.push(Namespace::Txpool);
+        }
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -307,6 +325,8 @@ impl MainNodeBuilder {
             websocket_requests_per_minute_limit: Some(
                 rpc_config.websocket_requests_per_minute_limit(),
             ),
+            client_rate_limits: (&rpc_config).into(),
+            method_weights: Some(rpc_config.method_weights.clone()),
             replication_lag_limit: circuit_breaker_config.replication_lag_limit(),
         };
         self.node.add_layer(Web3ServerLayer::ws(
@@ -318,6 +338,22 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_graphql_api_layer(mut self) -> anyhow::Result<Self> {
+        let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
+        let port = rpc_config
+            .graphql_port
+            .context("GraphQL server port is not specified")?;
+        self.node.add_layer(
+            GraphQlServerLayer::new(
+                port,
+                self.genesis_config.l2_chain_id,
+                rpc_config.req_entities_limit(),
+            )
+            .with_client_rate_limits(&rpc_config),
+        );
+        Ok(self)
+    }
+
     fn add_eth_tx_manager_layer(mut self) -> anyhow::Result<Self> {
         let eth_sender_config = try_load_config!(self.configs.eth);
 
@@ -447,6 +483,9 @@ impl MainNodeBuilder {
                         .add_api_caches_layer()?
                         .add_ws_web3_api_layer()?;
                 }
+                Component::GraphQlApi => {
+                    self = self.add_graphql_api_layer()?;
+                }
                 Component::ContractVerificationApi => {
                     self = self.add_contract_verification_api_layer()?;
                 }
@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     fmt,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     num::{NonZeroU32, NonZeroUsize},
     str::FromStr,
     time::Duration,
@@ -123,6 +123,196 @@ impl<'de> Deserialize<'de> for MaxResponseSizeOverrides {
     }
 }
 
+/// Relative costs of RPC methods used for rate limiting.
+///
+/// Keys are either full method names (e.g., `eth_getLogs`) or namespace wildcards (e.g., `debug_*`).
+/// Full method names take precedence over wildcards. Methods not covered by any key have the unit weight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcMethodWeights(HashMap<String, NonZeroU32>);
+
+impl<S: Into<String>> FromIterator<(S, NonZeroU32)> for RpcMethodWeights {
+    fn from_iter<I: IntoIterator<Item = (S, NonZeroU32)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(method_name, weight)| (method_name.into(), weight))
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for RpcMethodWeights {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights = HashMap::new();
+        for part in s.split(',') {
+            let (method_name, weight) = part
+                .split_once('=')
+                .with_context(|| format!("Part `{part}` doesn't have form <method_name>=<int>"))?;
+            let method_name = method_name.trim();
+            if let Some(namespace) = method_name.strip_suffix('*') {
+                anyhow::ensure!(
+                    namespace.ends_with('_') && !namespace[..namespace.len() - 1].contains('_'),
+                    "Wildcard `{method_name}` must have form <namespace>_*"
+                );
+            }
+
+            let weight = weight.trim();
+            let weight: NonZeroU32 = weight.parse().with_context(|| {
+                format!("`{weight}` specified for method `{method_name}` is not a valid weight")
+            })?;
+            if let Some(prev_weight) = weights.insert(method_name.to_owned(), weight) {
+                anyhow::bail!(
+                    "Weight for `{method_name}` is redefined from {prev_weight} to {weight}"
+                );
+            }
+        }
+        Ok(Self(weights))
+    }
+}
+
+impl RpcMethodWeights {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Gets the weight for the specified method.
+    pub fn get(&self, method_name: &str) -> NonZeroU32 {
+        if let Some(&weight) = self.0.get(method_name) {
+            return weight;
+        }
+        let wildcard_weight = method_name.split_once('_').and_then(|(namespace, _)| {
+            // The number of weights is expected to be small, so a linear scan is fine.
+            self.0
+                .iter()
+                .find(|(key, _)| {
+                    key.strip_suffix("_*")
+                        .map_or(false, |key_namespace| key_namespace == namespace)
+                })
+                .map(|(_, &weight)| weight)
+        });
+        wildcard_weight.unwrap_or(NonZeroU32::MIN)
+    }
+
+    /// Iterates over all specified weights.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, NonZeroU32)> + '_ {
+        self.0
+            .iter()
+            .map(|(method_name, &weight)| (method_name.as_str(), weight))
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcMethodWeights {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParseVisitor;
+
+        impl<'v> de::Visitor<'v> for ParseVisitor {
+            type Value = RpcMethodWeights;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("comma-separated list of <method_name>=<weight> tuples, such as: debug_*=20,eth_getLogs=10")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ParseVisitor)
+    }
+}
+
+/// Known client API keys together with their rate limiting quotas (the maximum total weight of requests per minute).
+///
+/// API keys are secret, so they are not output by the `Debug` implementation.
+#[derive(Clone, PartialEq)]
+pub struct RpcApiKeys(HashMap<String, NonZeroU32>);
+
+impl fmt::Debug for RpcApiKeys {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RpcApiKeys")
+            .field("len", &self.0.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, NonZeroU32)> for RpcApiKeys {
+    fn from_iter<I: IntoIterator<Item = (S, NonZeroU32)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, quota)| (key.into(), quota))
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for RpcApiKeys {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut keys = HashMap::new();
+        for (i, part) in s.split(',').enumerate() {
+            // Keys are not included into error messages since they are secret.
+            let (key, quota) = part
+                .split_once('=')
+                .with_context(|| format!("Entry #{i} doesn't have form <api_key>=<int>"))?;
+            let key = key.trim();
+            anyhow::ensure!(!key.is_empty(), "Entry #{i} has an empty API key");
+            let quota: NonZeroU32 = quota
+                .trim()
+                .parse()
+                .with_context(|| format!("Entry #{i} has an invalid quota"))?;
+            anyhow::ensure!(
+                keys.insert(key.to_owned(), quota).is_none(),
+                "API key in entry #{i} is redefined"
+            );
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl RpcApiKeys {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over all API keys and their quotas.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, NonZeroU32)> + '_ {
+        self.0.iter().map(|(key, &quota)| (key.as_str(), quota))
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcApiKeys {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParseVisitor;
+
+        impl<'v> de::Visitor<'v> for ParseVisitor {
+            type Value = RpcApiKeys;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("comma-separated list of <api_key>=<quota> tuples")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ParseVisitor)
+    }
+}
+
 /// Response size limits for JSON-RPC servers.
 #[derive(Debug)]
 pub struct MaxResponseSize {
@@ -142,6 +332,8 @@ pub struct Web3JsonRpcConfig {
     pub ws_port: u16,
     /// URL to access WebSocket RPC server.
     pub ws_url: String,
+    /// Port to which the GraphQL server is listening. If not set, the GraphQL server is not started.
+    pub graphql_port: Option<u16>,
     /// Max possible limit of entities to be requested once.
     pub req_entities_limit: Option<u32>,
     /// Whether to support HTTP methods that install filters and query filter changes.
@@ -154,6 +346,10 @@ pub struct Web3JsonRpcConfig {
     /// different node.
     #[serde(default)]
     pub filters_disabled: bool,
+    /// Whether to serve the `txpool` namespace allowing to inspect pending transactions. Disabled by default
+    /// since the namespace exposes the mempool contents to all clients and its methods are relatively expensive.
+    #[serde(default)]
+    pub txpool_namespace_enabled: bool,
     /// Max possible limit of filters to be in the state at once.
     pub filters_limit: Option<u32>,
     /// Max possible limit of subscriptions to be in the state at once.
@@ -162,6 +358,9 @@ pub struct Web3JsonRpcConfig {
     pub pubsub_polling_interval: Option<u64>,
     /// Tx nonce: how far ahead from the committed nonce can it be.
     pub max_nonce_ahead: u32,
+    /// Minimum bump (in percent) of `max_fee_per_gas` and `max_priority_fee_per_gas` required for a transaction
+    /// to replace a pending transaction with the same nonce. Default is 10%.
+    pub replacement_fee_bump_percent: Option<u32>,
     /// The multiplier to use when suggesting gas price. Should be higher than one,
     /// otherwise if the L1 prices soar, the suggested gas price won't be sufficient to be included in block
     pub gas_price_scale_factor: f64,
@@ -200,8 +399,26 @@ pub struct Web3JsonRpcConfig {
     pub max_response_body_size_overrides_mb: MaxResponseSizeOverrides,
     /// Maximum number of requests per minute for the WebSocket server.
     /// The value is per active connection.
-    /// Note: For HTTP, rate limiting is expected to be configured on the infra level.
+    /// Note: For HTTP, per-connection limits are not applicable; use `client_requests_per_minute_limit` instead.
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    /// Maximum total weight of requests per minute for a single client. Applies to both HTTP and WebSocket servers.
+    /// Clients are identified by the value of the `api_key_header` header if it contains one of `api_keys`,
+    /// or by their IP address otherwise. The IP address is the address of the peer, or the address reported
+    /// in `X-Forwarded-For` / `X-Real-IP` headers if the peer is one of `trusted_proxies`.
+    pub client_requests_per_minute_limit: Option<NonZeroU32>,
+    /// Name of the HTTP header containing the client API key used to identify clients for rate limiting.
+    pub api_key_header: Option<String>,
+    /// Known client API keys with their quotas, e.g. `key1=600,key2=6000`. Quotas override
+    /// `client_requests_per_minute_limit` for the corresponding clients. Unknown keys are ignored.
+    #[serde(default = "RpcApiKeys::empty")]
+    pub api_keys: RpcApiKeys,
+    /// IP addresses of reverse proxies allowed to report client IP addresses in `X-Forwarded-For` / `X-Real-IP` headers.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Relative weights of RPC methods used for rate limiting, e.g. `debug_*=20,eth_getLogs=10`.
+    /// Methods not mentioned in this list have the unit weight.
+    #[serde(default = "RpcMethodWeights::empty")]
+    pub method_weights: RpcMethodWeights,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
     /// Polling period for mempool cache update - how often the mempool cache is updated from the database.
@@ -209,6 +426,9 @@ pub struct Web3JsonRpcConfig {
     pub mempool_cache_update_interval: Option<u64>,
     /// Maximum number of transactions to be stored in the mempool cache. Default is 10000.
     pub mempool_cache_size: Option<usize>,
+    /// Capacity of the cache for responses of RPC methods returning immutable historical data (e.g., blocks
+    /// and transaction receipts in executed L1 batches). Specified in MiB; 0 disables caching. Default is 128 MiB.
+    pub response_cache_size_mb: Option<usize>,
     /// List of L2 token addresses that are white-listed to use by paymasters
     /// (additionally to natively bridged tokens).
     #[serde(default)]
@@ -225,12 +445,15 @@ impl Web3JsonRpcConfig {
             http_url: "http://localhost:3050".into(),
             ws_port: 3051,
             ws_url: "ws://localhost:3051".into(),
+            graphql_port: None,
             req_entities_limit: Some(10000),
             filters_disabled: false,
+            txpool_namespace_enabled: false,
             filters_limit: Some(10000),
             subscriptions_limit: Some(10000),
             pubsub_polling_interval: Some(200),
             max_nonce_ahead: 50,
+            replacement_fee_bump_percent: None,
             gas_price_scale_factor: 1.2,
             request_timeout: Default::default(),
             account_pks: Default::default(),
@@ -247,8 +470,14 @@ impl Web3JsonRpcConfig {
             max_response_body_size_mb: Default::default(),
             max_response_body_size_overrides_mb: MaxResponseSizeOverrides::empty(),
             websocket_requests_per_minute_limit: Default::default(),
+            client_requests_per_minute_limit: Default::default(),
+            api_key_header: None,
+            api_keys: RpcApiKeys::empty(),
+            trusted_proxies: vec![],
+            method_weights: RpcMethodWeights::empty(),
             mempool_cache_update_interval: Default::default(),
             mempool_cache_size: Default::default(),
+            response_cache_size_mb: Default::default(),
             tree_api_url: None,
             whitelisted_tokens_for_aa: Default::default(),
         }
@@ -331,6 +560,10 @@ impl Web3JsonRpcConfig {
             .unwrap_or(NonZeroU32::new(6000).unwrap())
     }
 
+    pub fn api_key_header(&self) -> Option<&str> {
+        self.api_key_header.as_deref()
+    }
+
     pub fn tree_api_url(&self) -> Option<&str> {
         self.tree_api_url.as_deref()
     }
@@ -342,6 +575,15 @@ impl Web3JsonRpcConfig {
     pub fn mempool_cache_size(&self) -> usize {
         self.mempool_cache_size.unwrap_or(10_000)
     }
+
+    pub fn replacement_fee_bump_percent(&self) -> u32 {
+        self.replacement_fee_bump_percent.unwrap_or(10)
+    }
+
+    /// Returns the size of response cache in bytes.
+    pub fn response_cache_size(&self) -> usize {
+        self.response_cache_size_mb.unwrap_or(128) * super::BYTES_IN_MEGABYTE
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -421,4 +663,48 @@ mod tests {
         assert_eq!(scaled.get("zks_getProof"), Some(32_000));
         assert_eq!(scaled.get("eth_blockNumber"), None);
     }
+
+    #[test]
+    fn working_with_rpc_method_weights() {
+        let weights: RpcMethodWeights = "debug_* = 20, eth_getLogs=10,debug_traceCall=50"
+            .parse()
+            .unwrap();
+        assert_eq!(weights.iter().len(), 3);
+        assert_eq!(weights.get("eth_getLogs").get(), 10);
+        assert_eq!(weights.get("debug_traceCall").get(), 50);
+        assert_eq!(weights.get("debug_traceBlockByNumber").get(), 20);
+        assert_eq!(weights.get("eth_blockNumber").get(), 1);
+        assert_eq!(weights.get("debugger_test").get(), 1);
+
+        let err = "eth*=2".parse::<RpcMethodWeights>().unwrap_err();
+        assert!(err.to_string().contains("<namespace>_*"), "{err}");
+        let err = "eth_call=0".parse::<RpcMethodWeights>().unwrap_err();
+        assert!(err.to_string().contains("not a valid weight"), "{err}");
+        let err = "eth_call=1,eth_call=2"
+            .parse::<RpcMethodWeights>()
+            .unwrap_err();
+        assert!(err.to_string().contains("redefined"), "{err}");
+    }
+
+    #[test]
+    fn parsing_rpc_api_keys() {
+        let keys: RpcApiKeys = "first=600, second = 6000".parse().unwrap();
+        let mut keys: Vec<_> = keys.iter().collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                ("first", NonZeroU32::new(600).unwrap()),
+                ("second", NonZeroU32::new(6_000).unwrap())
+            ]
+        );
+
+        let err = "secret=0".parse::<RpcApiKeys>().unwrap_err();
+        assert!(err.to_string().contains("invalid quota"), "{err}");
+        assert!(!format!("{err:#}").contains("secret"), "{err:#}");
+        let err = "secret=1,secret=2".parse::<RpcApiKeys>().unwrap_err();
+        assert!(!format!("{err:#}").contains("secret"), "{err:#}");
+        let keys: RpcApiKeys = "secret=1".parse().unwrap();
+        assert!(!format!("{keys:?}").contains("secret"));
+    }
 }
@@ -210,12 +210,39 @@ pub struct MempoolConfig {
     pub sync_interval_ms: u64,
     pub sync_batch_size: usize,
     pub capacity: u64,
+    /// Timeout for pending transactions in seconds. Only used if `remove_stuck_txs` is set.
     pub stuck_tx_timeout: u64,
+    /// Whether to remove pending transactions older than `stuck_tx_timeout`. Such transactions are removed
+    /// from Postgres on the mempool startup, and evicted from the mempool while it's running.
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Maximum number of pending transactions per account. If not set, the number is not limited.
+    pub max_txs_per_account: Option<usize>,
+    /// Maximum difference between a transaction nonce and the account nonce in the mempool.
+    /// If not set, the difference is not limited.
+    pub max_nonce_gap: Option<u32>,
+    /// Whether to evict transactions with the lowest fees once the mempool reaches its capacity.
+    #[serde(default)]
+    pub evict_lowest_fee: bool,
 }
 
 impl MempoolConfig {
+    /// Creates a config object suitable for use in unit tests.
+    /// Values mostly repeat the values used in the localhost environment.
+    pub fn for_tests() -> Self {
+        Self {
+            sync_interval_ms: 10,
+            sync_batch_size: 1000,
+            capacity: 10_000_000,
+            stuck_tx_timeout: 86400,
+            remove_stuck_txs: true,
+            delay_interval: 100,
+            max_txs_per_account: None,
+            max_nonce_gap: None,
+            evict_lowest_fee: false,
+        }
+    }
+
     pub fn sync_interval(&self) -> Duration {
         Duration::from_millis(self.sync_interval_ms)
     }
@@ -227,4 +254,9 @@ impl MempoolConfig {
     pub fn delay_interval(&self) -> Duration {
         Duration::from_millis(self.delay_interval)
     }
+
+    /// Returns the time-to-live for pending transactions, or `None` if stuck transactions are not removed.
+    pub fn tx_ttl(&self) -> Option<Duration> {
+        self.remove_stuck_txs.then(|| self.stuck_tx_timeout())
+    }
 }
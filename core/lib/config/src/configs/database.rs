@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{num::NonZeroU32, time::Duration};
 
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
@@ -46,6 +46,10 @@ pub struct MerkleTreeConfig {
     /// Maximum number of L1 batches to be processed by the Merkle tree at a time.
     #[serde(default = "MerkleTreeConfig::default_max_l1_batches_per_iter")]
     pub max_l1_batches_per_iter: usize,
+    /// Interval between online consistency checks of randomly sampled Merkle tree subtrees. If not specified,
+    /// online consistency checks are disabled.
+    #[serde(default)]
+    pub consistency_check_interval_sec: Option<NonZeroU32>,
 }
 
 impl Default for MerkleTreeConfig {
@@ -58,6 +62,7 @@ impl Default for MerkleTreeConfig {
             memtable_capacity_mb: Self::default_memtable_capacity_mb(),
             stalled_writes_timeout_sec: Self::default_stalled_writes_timeout_sec(),
             max_l1_batches_per_iter: Self::default_max_l1_batches_per_iter(),
+            consistency_check_interval_sec: None,
         }
     }
 }
@@ -101,6 +106,12 @@ impl MerkleTreeConfig {
     pub fn stalled_writes_timeout(&self) -> Duration {
         Duration::from_secs(self.stalled_writes_timeout_sec)
     }
+
+    /// Returns the interval between online Merkle tree consistency checks, or `None` if these checks are disabled.
+    pub fn consistency_check_interval(&self) -> Option<Duration> {
+        self.consistency_check_interval_sec
+            .map(|interval| Duration::from_secs(interval.get().into()))
+    }
 }
 
 /// Database configuration.
@@ -1,3 +1,5 @@
+use std::{fmt, num::NonZeroU32, time::Duration};
+
 use serde::Deserialize;
 
 /// Configuration for the object store
@@ -16,15 +18,47 @@ pub struct ObjectStoreConfig {
     /// **Important.** Mirroring logic assumes that objects in the underlying store are immutable. If this is not the case,
     /// the mirrored objects may become stale.
     pub local_mirror_path: Option<String>,
+    /// Maximum total size of objects in the local mirror, in megabytes. Once this size is exceeded, the least recently used
+    /// objects are evicted from the mirror. If not specified, the mirror size is unbounded.
+    pub local_mirror_max_size_mb: Option<u64>,
+    /// Names of buckets (e.g., `storage_logs_snapshots`) that will be mirrored locally. If empty, all buckets are mirrored.
+    #[serde(default)]
+    pub local_mirror_buckets: Vec<String>,
+    /// Whether to compress stored objects with zstd and attach checksums to them. Checksums are verified when objects
+    /// are fetched. Objects stored with compression disabled can still be read with compression enabled, but not vice versa.
+    #[serde(default)]
+    pub compress_objects: bool,
+    /// Whether to deduplicate stored objects by their content hash. Objects stored with deduplication disabled
+    /// can still be read with deduplication enabled, but not vice versa.
+    ///
+    /// Removing or overwriting deduplicated objects does not remove their content; unreferenced content
+    /// is removed by periodic garbage collection (see `deduplication_gc_interval_sec`).
+    #[serde(default)]
+    pub deduplicate_objects: bool,
+    /// Interval between garbage collection runs for deduplicated content in buckets modified by the app.
+    /// Ignored if deduplication is disabled. If not specified, unreferenced content is never removed.
+    ///
+    /// **Important.** Garbage collection is only safe against writers using the same object store instance.
+    /// If other apps write to the same buckets with deduplication enabled, it should be enabled in at most
+    /// one of these apps, and only if the other apps do not write concurrently with it.
+    #[serde(default)]
+    pub deduplication_gc_interval_sec: Option<NonZeroU32>,
 }
 
 impl ObjectStoreConfig {
     const fn default_max_retries() -> u16 {
         5
     }
+
+    /// Returns the interval between garbage collection runs for deduplicated content, or `None` if garbage
+    /// collection is disabled.
+    pub fn deduplication_gc_interval(&self) -> Option<Duration> {
+        self.deduplication_gc_interval_sec
+            .map(|interval| Duration::from_secs(interval.get().into()))
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Clone, PartialEq, Deserialize)]
 #[serde(tag = "mode")]
 pub enum ObjectStoreMode {
     GCS {
@@ -40,4 +74,116 @@ pub enum ObjectStoreMode {
     FileBacked {
         file_backed_base_path: String,
     },
+    /// S3-compatible store (AWS S3, MinIO etc.) authenticated using the standard AWS environment variables
+    /// (`AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and optionally `AWS_SESSION_TOKEN`).
+    S3 {
+        bucket_name: String,
+        region: String,
+        /// Custom endpoint URL, e.g. for a self-hosted MinIO instance. If not specified, the AWS endpoint
+        /// for the `region` is used.
+        endpoint: Option<String>,
+        /// Whether to use path-style addressing (`{endpoint}/{bucket}/{key}`) instead of virtual-host-style one
+        /// (`{bucket}.{endpoint}/{key}`). Path-style addressing is usually required by self-hosted stores.
+        #[serde(default)]
+        path_style: bool,
+    },
+    /// S3-compatible store authenticated using static credentials.
+    S3WithStaticCredentials {
+        bucket_name: String,
+        region: String,
+        endpoint: Option<String>,
+        #[serde(default)]
+        path_style: bool,
+        access_key_id: String,
+        secret_access_key: String,
+    },
+}
+
+// Custom implementation so that the S3 secret access key doesn't leak into logs.
+impl fmt::Debug for ObjectStoreMode {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GCS { bucket_base_url } => formatter
+                .debug_struct("GCS")
+                .field("bucket_base_url", bucket_base_url)
+                .finish(),
+            Self::GCSAnonymousReadOnly { bucket_base_url } => formatter
+                .debug_struct("GCSAnonymousReadOnly")
+                .field("bucket_base_url", bucket_base_url)
+                .finish(),
+            Self::GCSWithCredentialFile {
+                bucket_base_url,
+                gcs_credential_file_path,
+            } => formatter
+                .debug_struct("GCSWithCredentialFile")
+                .field("bucket_base_url", bucket_base_url)
+                .field("gcs_credential_file_path", gcs_credential_file_path)
+                .finish(),
+            Self::FileBacked {
+                file_backed_base_path,
+            } => formatter
+                .debug_struct("FileBacked")
+                .field("file_backed_base_path", file_backed_base_path)
+                .finish(),
+            Self::S3 {
+                bucket_name,
+                region,
+                endpoint,
+                path_style,
+            } => formatter
+                .debug_struct("S3")
+                .field("bucket_name", bucket_name)
+                .field("region", region)
+                .field("endpoint", endpoint)
+                .field("path_style", path_style)
+                .finish(),
+            Self::S3WithStaticCredentials {
+                bucket_name,
+                region,
+                endpoint,
+                path_style,
+                access_key_id,
+                ..
+            } => formatter
+                .debug_struct("S3WithStaticCredentials")
+                .field("bucket_name", bucket_name)
+                .field("region", region)
+                .field("endpoint", endpoint)
+                .field("path_style", path_style)
+                .field("access_key_id", access_key_id)
+                // Skip secret fields
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_output_does_not_contain_secrets() {
+        let mode = ObjectStoreMode::S3WithStaticCredentials {
+            bucket_name: "bucket".to_owned(),
+            region: "us-east-1".to_owned(),
+            endpoint: None,
+            path_style: false,
+            access_key_id: "access-key".to_owned(),
+            secret_access_key: "super-secret".to_owned(),
+        };
+        let config = ObjectStoreConfig {
+            mode,
+            max_retries: 5,
+            local_mirror_path: None,
+            local_mirror_max_size_mb: None,
+            local_mirror_buckets: vec![],
+            compress_objects: false,
+            deduplicate_objects: false,
+            deduplication_gc_interval_sec: None,
+        };
+        let debug_output = format!("{config:?}");
+        assert!(debug_output.contains("bucket"), "{debug_output}");
+        assert!(debug_output.contains("access-key"), "{debug_output}");
+        assert!(!debug_output.contains("super-secret"), "{debug_output}");
+    }
 }
@@ -62,10 +62,12 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             ws_url: self.sample(rng),
             req_entities_limit: self.sample(rng),
             filters_disabled: self.sample(rng),
+            txpool_namespace_enabled: self.sample(rng),
             filters_limit: self.sample(rng),
             subscriptions_limit: self.sample(rng),
             pubsub_polling_interval: self.sample(rng),
             max_nonce_ahead: self.sample(rng),
+            replacement_fee_bump_percent: self.sample(rng),
             gas_price_scale_factor: self.sample(rng),
             request_timeout: self.sample_opt(|| self.sample(rng)),
             account_pks: self.sample_opt(|| self.sample_range(rng).map(|_| rng.gen()).collect()),
@@ -93,9 +95,27 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             .into_iter()
             .collect(),
             websocket_requests_per_minute_limit: self.sample(rng),
+            client_requests_per_minute_limit: self.sample(rng),
+            api_key_header: self.sample(rng),
+            api_keys: self
+                .sample_range(rng)
+                .map(|i| (format!("key{i}"), self.sample(rng)))
+                .collect(),
+            trusted_proxies: self
+                .sample_range(rng)
+                .map(|_| std::net::IpAddr::from(rng.gen::<[u8; 4]>()))
+                .collect(),
+            method_weights: [
+                ("debug_*", self.sample(rng)),
+                ("eth_getLogs", self.sample(rng)),
+            ]
+            .into_iter()
+            .collect(),
             tree_api_url: self.sample(rng),
             mempool_cache_update_interval: self.sample(rng),
             mempool_cache_size: self.sample(rng),
+            response_cache_size_mb: self.sample(rng),
+            graphql_port: self.sample(rng),
             whitelisted_tokens_for_aa: self.sample_range(rng).map(|_| rng.gen()).collect(),
         }
     }
@@ -212,6 +232,9 @@ impl Distribution<configs::chain::MempoolConfig> for EncodeDist {
             stuck_tx_timeout: self.sample(rng),
             remove_stuck_txs: self.sample(rng),
             delay_interval: self.sample(rng),
+            max_txs_per_account: self.sample(rng),
+            max_nonce_gap: self.sample(rng),
+            evict_lowest_fee: self.sample(rng),
         }
     }
 }
@@ -271,6 +294,7 @@ impl Distribution<configs::database::MerkleTreeConfig> for EncodeDist {
             memtable_capacity_mb: self.sample(rng),
             stalled_writes_timeout_sec: self.sample(rng),
             max_l1_batches_per_iter: self.sample(rng),
+            consistency_check_interval_sec: self.sample(rng),
         }
     }
 }
@@ -593,7 +617,7 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
 impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ObjectStoreMode {
         type T = configs::object_store::ObjectStoreMode;
-        match rng.gen_range(0..4) {
+        match rng.gen_range(0..6) {
             0 => T::GCS {
                 bucket_base_url: self.sample(rng),
             },
@@ -604,6 +628,20 @@ impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
             2 => T::FileBacked {
                 file_backed_base_path: self.sample(rng),
             },
+            3 => T::S3 {
+                bucket_name: self.sample(rng),
+                region: self.sample(rng),
+                endpoint: self.sample(rng),
+                path_style: self.sample(rng),
+            },
+            4 => T::S3WithStaticCredentials {
+                bucket_name: self.sample(rng),
+                region: self.sample(rng),
+                endpoint: self.sample(rng),
+                path_style: self.sample(rng),
+                access_key_id: self.sample(rng),
+                secret_access_key: self.sample(rng),
+            },
             _ => T::GCSAnonymousReadOnly {
                 bucket_base_url: self.sample(rng),
             },
@@ -617,6 +655,11 @@ impl Distribution<configs::ObjectStoreConfig> for EncodeDist {
             mode: self.sample(rng),
             max_retries: self.sample(rng),
             local_mirror_path: self.sample(rng),
+            local_mirror_max_size_mb: self.sample(rng),
+            local_mirror_buckets: self.sample_collect(rng),
+            compress_objects: self.sample(rng),
+            deduplicate_objects: self.sample(rng),
+            deduplication_gc_interval_sec: self.sample(rng),
         }
     }
 }
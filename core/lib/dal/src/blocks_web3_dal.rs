@@ -34,6 +34,19 @@ impl BlocksWeb3Dal<'_, '_> {
         &mut self,
         block_number: L2BlockNumber,
     ) -> DalResult<Option<api::Block<H256>>> {
+        let blocks = self.get_api_blocks(&[block_number]).await?;
+        Ok(blocks.into_iter().next())
+    }
+
+    /// Returns blocks with the specified numbers ordered by the block number. Missing blocks are skipped.
+    pub async fn get_api_blocks(
+        &mut self,
+        block_numbers: &[L2BlockNumber],
+    ) -> DalResult<Vec<api::Block<H256>>> {
+        let numbers: Vec<_> = block_numbers
+            .iter()
+            .map(|number| i64::from(number.0))
+            .collect();
         let rows = sqlx::query!(
             r#"
             SELECT
@@ -54,29 +67,32 @@ impl BlocksWeb3Dal<'_, '_> {
                 LEFT JOIN l1_batches ON l1_batches.number = miniblocks.l1_batch_number
                 LEFT JOIN transactions ON transactions.miniblock_number = miniblocks.number
             WHERE
-                miniblocks.number = $1
+                miniblocks.number = ANY ($1)
             ORDER BY
+                miniblocks.number ASC,
                 transactions.index_in_block ASC
             "#,
-            i64::from(block_number.0)
+            &numbers
         )
-        .instrument("get_api_block")
-        .with_arg("block_number", &block_number)
+        .instrument("get_api_blocks")
+        .with_arg("block_numbers.len", &block_numbers.len())
         .fetch_all(self.storage)
         .await?;
 
-        let block = rows.into_iter().fold(None, |prev_block, row| {
-            let mut block = prev_block.unwrap_or_else(|| {
-                // This code will be only executed for the first row in the DB response.
+        let mut blocks: Vec<api::Block<H256>> = vec![];
+        for row in rows {
+            let number = U64::from(row.number as u64);
+            if blocks.last().map_or(true, |block| block.number != number) {
+                // This code will be only executed for the first row of each block in the DB response.
                 // All other rows will only be used to extract relevant transactions.
-                api::Block {
+                blocks.push(api::Block {
                     hash: H256::from_slice(&row.block_hash),
                     parent_hash: row
                         .parent_hash
                         .as_deref()
                         .map_or_else(H256::zero, H256::from_slice),
                     uncles_hash: EMPTY_UNCLES_HASH,
-                    number: (row.number as u64).into(),
+                    number,
                     l1_batch_number: row.l1_batch_number.map(|number| (number as u64).into()),
                     base_fee_per_gas: bigdecimal_to_u256(row.base_fee_per_gas),
                     timestamp: (row.timestamp as u64).into(),
@@ -88,8 +104,9 @@ impl BlocksWeb3Dal<'_, '_> {
                         .into(),
                     // TODO: include logs
                     ..api::Block::default()
-                }
-            });
+                });
+            }
+            let block = blocks.last_mut().unwrap();
 
             if let (Some(gas_limit), Some(refunded_gas)) =
                 (row.transaction_gas_limit, row.refunded_gas)
@@ -99,11 +116,8 @@ impl BlocksWeb3Dal<'_, '_> {
             if let Some(tx_hash) = &row.tx_hash {
                 block.transactions.push(H256::from_slice(tx_hash));
             }
-
-            Some(block)
-        });
-
-        Ok(block)
+        }
+        Ok(blocks)
     }
 
     pub async fn get_block_tx_count(
@@ -785,6 +799,19 @@ mod tests {
             .get_block_tx_count(L2BlockNumber(1))
             .await;
         assert_eq!(tx_count.unwrap(), None);
+
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(2))
+            .await
+            .unwrap();
+        let blocks = conn
+            .blocks_web3_dal()
+            .get_api_blocks(&[L2BlockNumber(2), L2BlockNumber(1), L2BlockNumber(0)])
+            .await
+            .unwrap();
+        let block_numbers: Vec<_> = blocks.iter().map(|block| block.number.as_u32()).collect();
+        assert_eq!(block_numbers, [0, 2]);
+        assert_eq!(blocks[0].hash, block_hash);
     }
 
     #[tokio::test]
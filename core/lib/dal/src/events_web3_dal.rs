@@ -17,18 +17,18 @@ pub struct EventsWeb3Dal<'a, 'c> {
 }
 
 impl EventsWeb3Dal<'_, '_> {
-    /// Returns L2 block number of log for given filter and offset.
+    /// Returns the position (L2 block number and log index in the block) of the log for given filter and offset.
     /// Used to determine if there is more than `offset` logs that satisfies filter.
-    pub async fn get_log_block_number(
+    pub async fn get_log_position(
         &mut self,
         filter: &GetLogsFilter,
         offset: usize,
-    ) -> DalResult<Option<L2BlockNumber>> {
+    ) -> DalResult<Option<(L2BlockNumber, u32)>> {
         let (where_sql, arg_index) = self.build_get_logs_where_clause(filter);
 
         let query = format!(
             r#"
-                SELECT miniblock_number
+                SELECT miniblock_number, event_index_in_block
                 FROM events
                 WHERE {}
                 ORDER BY miniblock_number ASC, event_index_in_block ASC
@@ -53,20 +53,61 @@ impl EventsWeb3Dal<'_, '_> {
         }
         query = query.bind(offset as i32);
         let log = query
-            .instrument("get_log_block_number")
+            .instrument("get_log_position")
             .report_latency()
             .with_arg("filter", filter)
             .with_arg("offset", &offset)
             .fetch_optional(self.storage)
             .await?;
 
-        Ok(log.map(|row| L2BlockNumber(row.get::<i64, _>("miniblock_number") as u32)))
+        Ok(log.map(|row| {
+            let block_number = L2BlockNumber(row.get::<i64, _>("miniblock_number") as u32);
+            (
+                block_number,
+                row.get::<i32, _>("event_index_in_block") as u32,
+            )
+        }))
     }
 
     /// Returns logs for given filter.
-    #[allow(clippy::type_complexity)]
     pub async fn get_logs(&mut self, filter: GetLogsFilter, limit: usize) -> DalResult<Vec<Log>> {
         let (where_sql, arg_index) = self.build_get_logs_where_clause(&filter);
+        self.get_logs_inner(&filter, where_sql, arg_index, limit, "get_logs")
+            .await
+    }
+
+    /// Returns up to `limit` logs for given filter starting from the log with the specified index in the `from_block`
+    /// of the filter (inclusive). Logs are paginated by the `(miniblock_number, event_index_in_block)` primary key,
+    /// so the query doesn't need to skip over previously returned logs.
+    pub async fn get_logs_from_position(
+        &mut self,
+        filter: &GetLogsFilter,
+        from_log_index: u32,
+        limit: usize,
+    ) -> DalResult<Vec<Log>> {
+        let (mut where_sql, arg_index) = self.build_get_logs_where_clause(filter);
+        where_sql += &format!(
+            " AND ((miniblock_number, event_index_in_block) >= ({}, {}))",
+            filter.from_block.0, from_log_index
+        );
+        self.get_logs_inner(
+            filter,
+            where_sql,
+            arg_index,
+            limit,
+            "get_logs_from_position",
+        )
+        .await
+    }
+
+    async fn get_logs_inner(
+        &mut self,
+        filter: &GetLogsFilter,
+        where_sql: String,
+        arg_index: u8,
+        limit: usize,
+        method_name: &'static str,
+    ) -> DalResult<Vec<Log>> {
         let query = format!(
             r#"
             WITH events_select AS (
@@ -104,9 +145,9 @@ impl EventsWeb3Dal<'_, '_> {
         query = query.bind(limit as i32);
 
         let db_logs: Vec<StorageWeb3Log> = query
-            .instrument("get_logs")
+            .instrument(method_name)
             .report_latency()
-            .with_arg("filter", &filter)
+            .with_arg("filter", filter)
             .with_arg("limit", &limit)
             .fetch_all(self.storage)
             .await?;
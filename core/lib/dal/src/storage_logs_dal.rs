@@ -555,6 +555,110 @@ impl StorageLogsDal<'_, '_> {
             .collect())
     }
 
+    /// Returns all storage logs produced by transactions in the specified L2 block, ordered by `operation_number`
+    /// (i.e., in the order of their application).
+    pub async fn get_storage_logs_for_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<DbStorageLog>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hashed_key,
+                address,
+                key,
+                value,
+                operation_number,
+                tx_hash,
+                miniblock_number
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number = $1
+            ORDER BY
+                operation_number
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_storage_logs_for_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DbStorageLog {
+                hashed_key: H256::from_slice(&row.hashed_key),
+                address: H160::from_slice(&row.address),
+                key: H256::from_slice(&row.key),
+                value: H256::from_slice(&row.value),
+                operation_number: row.operation_number as u64,
+                tx_hash: H256::from_slice(&row.tx_hash),
+                l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+            })
+            .collect())
+    }
+
+    /// Returns values for the specified keys right before the storage log with the specified `operation_number`
+    /// in the specified L2 block was applied. Unlike [`Self::get_storage_values()`], this allows to obtain
+    /// intermediate values inside an L2 block (e.g., the state before a certain transaction).
+    pub async fn get_storage_values_before_operation(
+        &mut self,
+        hashed_keys: &[H256],
+        l2_block_number: L2BlockNumber,
+        operation_number: u64,
+    ) -> DalResult<HashMap<H256, Option<H256>>> {
+        let hashed_keys: Vec<_> = hashed_keys.iter().map(H256::as_bytes).collect();
+        let operation_number = i32::try_from(operation_number).expect("operation number overflow");
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                u.hashed_key AS "hashed_key!",
+                (
+                    SELECT
+                        value
+                    FROM
+                        storage_logs
+                    WHERE
+                        hashed_key = u.hashed_key
+                        AND (
+                            miniblock_number < $2
+                            OR (
+                                miniblock_number = $2
+                                AND operation_number < $3
+                            )
+                        )
+                    ORDER BY
+                        miniblock_number DESC,
+                        operation_number DESC
+                    LIMIT
+                        1
+                ) AS "value?"
+            FROM
+                UNNEST($1::bytea[]) AS u (hashed_key)
+            "#,
+            &hashed_keys as &[&[u8]],
+            i64::from(l2_block_number.0),
+            operation_number
+        )
+        .instrument("get_storage_values_before_operation")
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("operation_number", &operation_number)
+        .with_arg("hashed_keys.len", &hashed_keys.len())
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let key = H256::from_slice(&row.hashed_key);
+                let value = row.value.map(|value| H256::from_slice(&value));
+                (key, value)
+            })
+            .collect())
+    }
+
     /// Retrieves all storage log entries for testing purposes.
     pub async fn dump_all_storage_logs_for_tests(&mut self) -> Vec<DbStorageLog> {
         let rows = sqlx::query!(
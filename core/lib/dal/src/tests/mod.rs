@@ -234,6 +234,57 @@ async fn workflow_with_submit_tx_diff_hashes() {
     assert_eq!(result, L2TxSubmissionResult::Replaced);
 }
 
+#[tokio::test]
+async fn replacing_tx_with_fee_bump() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let storage = &mut connection_pool.connection().await.unwrap();
+    let mut transactions_dal = TransactionsDal { storage };
+
+    let mut tx = mock_l2_transaction();
+    tx.common_data.fee.max_priority_fee_per_gas = 100.into();
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(&tx, mock_tx_execution_metrics(), 10)
+        .await
+        .unwrap();
+    assert_eq!(result, L2TxSubmissionResult::Added);
+
+    let replacement_tx = |max_fee_per_gas: u64, max_priority_fee_per_gas: u64| {
+        let mut new_tx = mock_l2_transaction();
+        new_tx.common_data.nonce = tx.common_data.nonce;
+        new_tx.common_data.initiator_address = tx.common_data.initiator_address;
+        new_tx.common_data.fee.max_fee_per_gas = max_fee_per_gas.into();
+        new_tx.common_data.fee.max_priority_fee_per_gas = max_priority_fee_per_gas.into();
+        new_tx
+    };
+
+    // Both fees must be bumped by at least 10%.
+    for (max_fee_per_gas, max_priority_fee_per_gas) in
+        [(275_000_000, 109), (274_999_999, 110), (250_000_000, 1_000)]
+    {
+        let underpriced_tx = replacement_tx(max_fee_per_gas, max_priority_fee_per_gas);
+        let result = transactions_dal
+            .insert_transaction_l2_with_fee_bump(&underpriced_tx, mock_tx_execution_metrics(), 10)
+            .await
+            .unwrap();
+        assert_eq!(result, L2TxSubmissionResult::ReplacementUnderpriced);
+    }
+    let (pending_hash, _) = TransactionsWeb3Dal {
+        storage: &mut *transactions_dal.storage,
+    }
+    .get_pending_l2_tx_fee(tx.initiator_account(), tx.nonce())
+    .await
+    .unwrap()
+    .expect("no pending tx");
+    assert_eq!(pending_hash, tx.hash());
+
+    let new_tx = replacement_tx(275_000_000, 110);
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(&new_tx, mock_tx_execution_metrics(), 10)
+        .await
+        .unwrap();
+    assert_eq!(result, L2TxSubmissionResult::Replaced);
+}
+
 #[tokio::test]
 async fn remove_stuck_txs() {
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -35,6 +35,7 @@ pub enum L2TxSubmissionResult {
     Duplicate,
     Proxied,
     InsertionInProgress,
+    ReplacementUnderpriced,
 }
 
 impl fmt::Display for L2TxSubmissionResult {
@@ -46,6 +47,7 @@ impl fmt::Display for L2TxSubmissionResult {
             Self::Duplicate => "duplicate",
             Self::Proxied => "proxied",
             Self::InsertionInProgress => "insertion_in_progress",
+            Self::ReplacementUnderpriced => "replacement_underpriced",
         })
     }
 }
@@ -262,6 +264,30 @@ impl TransactionsDal<'_, '_> {
         &mut self,
         tx: &L2Tx,
         exec_info: TransactionExecutionMetrics,
+    ) -> DalResult<L2TxSubmissionResult> {
+        self.insert_or_replace_transaction_l2(tx, exec_info, None)
+            .await
+    }
+
+    /// Inserts an L2 transaction, replacing a pending transaction with the same initiator and nonce
+    /// only if both `max_fee_per_gas` and `max_priority_fee_per_gas` are bumped by at least `min_bump_percent`
+    /// compared to the replaced transaction. The check is performed atomically with the replacement.
+    /// If the fees are not bumped sufficiently, returns [`L2TxSubmissionResult::ReplacementUnderpriced`].
+    pub async fn insert_transaction_l2_with_fee_bump(
+        &mut self,
+        tx: &L2Tx,
+        exec_info: TransactionExecutionMetrics,
+        min_bump_percent: u32,
+    ) -> DalResult<L2TxSubmissionResult> {
+        self.insert_or_replace_transaction_l2(tx, exec_info, Some(min_bump_percent))
+            .await
+    }
+
+    async fn insert_or_replace_transaction_l2(
+        &mut self,
+        tx: &L2Tx,
+        exec_info: TransactionExecutionMetrics,
+        min_bump_percent: Option<u32>,
     ) -> DalResult<L2TxSubmissionResult> {
         let tx_hash = tx.hash();
         let is_duplicate = sqlx::query!(
@@ -321,6 +347,9 @@ impl TransactionsDal<'_, '_> {
         // Otherwise, if the subquery won't return NULL it means that there is already tx with such nonce and `initiator_address` in DB
         // and we can replace it WHERE clause conditions are met.
         // It is worth mentioning that if WHERE clause conditions are not met, None will be returned.
+        // If `min_bump_percent` is specified, a pending transaction (i.e., one not rejected by the state keeper)
+        // is only replaced if the new transaction bumps both fees by at least the specified percentage,
+        // rounded up; the same rule is implemented in `Fee::is_replacement_for()`.
         let query_result = sqlx::query!(
             r#"
             INSERT INTO
@@ -394,6 +423,14 @@ impl TransactionsDal<'_, '_> {
             WHERE
                 transactions.is_priority = FALSE
                 AND transactions.miniblock_number IS NULL
+                AND (
+                    $20::INT IS NULL
+                    OR transactions.error IS NOT NULL
+                    OR (
+                        $6 >= COALESCE(transactions.max_fee_per_gas, 0) + CEIL(COALESCE(transactions.max_fee_per_gas, 0) * $20 / 100)
+                        AND $7 >= COALESCE(transactions.max_priority_fee_per_gas, 0) + CEIL(COALESCE(transactions.max_priority_fee_per_gas, 0) * $20 / 100)
+                    )
+                )
             RETURNING
                 (
                     SELECT
@@ -423,10 +460,12 @@ impl TransactionsDal<'_, '_> {
             exec_info.gas_used as i64,
             (exec_info.initial_storage_writes + exec_info.repeated_storage_writes) as i32,
             exec_info.contracts_used as i32,
-            received_at
+            received_at,
+            min_bump_percent.map(|percent| percent as i32)
         )
         .instrument("insert_transaction_l2")
         .with_arg("tx_hash", &tx_hash)
+        .with_arg("min_bump_percent", &min_bump_percent)
         .fetch_optional(self.storage)
         .await
         .map(|option_record| option_record.map(|record| record.is_replaced));
@@ -435,6 +474,20 @@ impl TransactionsDal<'_, '_> {
             Ok(option_query_result) => match option_query_result {
                 Some(true) => L2TxSubmissionResult::Replaced,
                 Some(false) => L2TxSubmissionResult::Added,
+                None if min_bump_percent.is_some() => {
+                    // The transaction wasn't inserted either because the transaction with the same nonce
+                    // is already executed, or because the replacement is underpriced.
+                    let pending_tx = self
+                        .storage
+                        .transactions_web3_dal()
+                        .get_pending_l2_tx_fee(initiator_address, tx.nonce())
+                        .await?;
+                    if pending_tx.is_some() {
+                        L2TxSubmissionResult::ReplacementUnderpriced
+                    } else {
+                        L2TxSubmissionResult::AlreadyExecuted
+                    }
+                }
                 None => L2TxSubmissionResult::AlreadyExecuted,
             },
             Err(err) => {
@@ -1626,6 +1679,39 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Marks multiple pending transactions as rejected. Transactions that are already included into L2 blocks
+    /// are not affected.
+    pub async fn mark_txs_as_rejected(&mut self, transactions: &[(H256, String)]) -> DalResult<()> {
+        let (hashes, errors): (Vec<_>, Vec<_>) = transactions
+            .iter()
+            .map(|(hash, error)| (hash.as_bytes(), error.as_str()))
+            .unzip();
+        sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                error = data_table.error,
+                updated_at = NOW()
+            FROM
+                (
+                    SELECT
+                        UNNEST($1::bytea[]) AS hash,
+                        UNNEST($2::VARCHAR[]) AS error
+                ) AS data_table
+            WHERE
+                transactions.hash = data_table.hash
+                AND transactions.miniblock_number IS NULL
+            "#,
+            &hashes as &[&[u8]],
+            &errors as &[&str]
+        )
+        .instrument("mark_txs_as_rejected")
+        .with_arg("transactions.len", &transactions.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn reset_transactions_state(
         &mut self,
         l2_block_number: L2BlockNumber,
@@ -2244,4 +2330,41 @@ mod tests {
             .unwrap();
         assert_eq!(tx_from_db[0].hash, tx_hash);
     }
+
+    #[tokio::test]
+    async fn marking_txs_as_rejected() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let txs = [mock_l2_transaction(), mock_l2_transaction()];
+        for tx in &txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+
+        conn.transactions_dal()
+            .mark_txs_as_rejected(&[(txs[0].hash(), "evicted: Transaction expired".to_owned())])
+            .await
+            .unwrap();
+
+        let rejected_tx = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(txs[0].initiator_account(), txs[0].nonce())
+            .await
+            .unwrap();
+        assert!(rejected_tx.is_none());
+        let (pending_tx_hash, _) = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(txs[1].initiator_account(), txs[1].nonce())
+            .await
+            .unwrap()
+            .expect("pending transaction is missing");
+        assert_eq!(pending_tx_hash, txs[1].hash());
+    }
 }
@@ -4,9 +4,10 @@ use zksync_db_connection::{
     match_query_as,
 };
 use zksync_types::{
-    api, api::TransactionReceipt, Address, L2BlockNumber, L2ChainId, Transaction,
+    api, api::TransactionReceipt, fee::Fee, Address, L2BlockNumber, L2ChainId, Nonce, Transaction,
     ACCOUNT_CODE_STORAGE_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
 };
+use zksync_utils::bigdecimal_to_u256;
 
 use crate::{
     models::storage_transaction::{
@@ -324,6 +325,170 @@ impl TransactionsWeb3Dal<'_, '_> {
         Ok(hashes)
     }
 
+    /// Same as [`Self::get_pending_txs_hashes_after()`], but additionally returns the initiator address and nonce
+    /// for L2 transactions (`None` for L1 transactions). This allows to detect replaced L2 transactions.
+    pub async fn get_pending_txs_after(
+        &mut self,
+        from_timestamp: NaiveDateTime,
+        limit: Option<usize>,
+    ) -> DalResult<Vec<(NaiveDateTime, H256, Option<(Address, Nonce)>)>> {
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                transactions.hash,
+                transactions.received_at,
+                transactions.initiator_address,
+                transactions.nonce
+            FROM
+                transactions
+            WHERE
+                received_at > $1
+            ORDER BY
+                received_at ASC
+            LIMIT
+                $2
+            "#,
+            from_timestamp,
+            limit.map(|limit| limit as i64)
+        )
+        .instrument("get_pending_txs_after")
+        .with_arg("from_timestamp", &from_timestamp)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        let txs = records
+            .into_iter()
+            .map(|record| {
+                let initiator_and_nonce = record.nonce.map(|nonce| {
+                    (
+                        Address::from_slice(&record.initiator_address),
+                        Nonce(nonce as u32),
+                    )
+                });
+                (
+                    record.received_at,
+                    H256::from_slice(&record.hash),
+                    initiator_and_nonce,
+                )
+            })
+            .collect();
+        Ok(txs)
+    }
+
+    /// Returns the hash and fee of a pending L2 transaction (i.e., one not included into an L2 block and not rejected)
+    /// with the specified initiator and nonce. Such a transaction can be replaced by a new transaction with the same nonce.
+    pub async fn get_pending_l2_tx_fee(
+        &mut self,
+        initiator_address: Address,
+        nonce: Nonce,
+    ) -> DalResult<Option<(H256, Fee)>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                gas_per_pubdata_limit
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce = $2
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes(),
+            i64::from(nonce.0)
+        )
+        .instrument("get_pending_l2_tx_fee")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("nonce", &nonce)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| {
+            let fee = Fee {
+                gas_limit: row.gas_limit.map(bigdecimal_to_u256).unwrap_or_default(),
+                max_fee_per_gas: row
+                    .max_fee_per_gas
+                    .map(bigdecimal_to_u256)
+                    .unwrap_or_default(),
+                max_priority_fee_per_gas: row
+                    .max_priority_fee_per_gas
+                    .map(bigdecimal_to_u256)
+                    .unwrap_or_default(),
+                gas_per_pubdata_limit: row
+                    .gas_per_pubdata_limit
+                    .map(bigdecimal_to_u256)
+                    .unwrap_or_default(),
+            };
+            (H256::from_slice(&row.hash), fee)
+        }))
+    }
+
+    /// Returns the number of pending (i.e., not included into an L2 block and not rejected) L2 transactions
+    /// from the specified account, excluding the transaction with the specified nonce.
+    pub async fn get_pending_l2_txs_count(
+        &mut self,
+        initiator_address: Address,
+        excluded_nonce: Nonce,
+    ) -> DalResult<usize> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce <> $2
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes(),
+            i64::from(excluded_nonce.0)
+        )
+        .instrument("get_pending_l2_txs_count")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("excluded_nonce", &excluded_nonce)
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as usize)
+    }
+
+    /// Filters the provided transaction hashes, leaving only pending transactions (i.e., ones not included
+    /// into an L2 block and not rejected). The order of returned hashes is not specified.
+    pub async fn filter_pending_tx_hashes(&mut self, hashes: &[H256]) -> DalResult<Vec<H256>> {
+        let hash_bytes: Vec<_> = hashes.iter().map(H256::as_bytes).collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash
+            FROM
+                transactions
+            WHERE
+                hash = ANY ($1)
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#,
+            &hash_bytes as &[&[u8]]
+        )
+        .instrument("filter_pending_tx_hashes")
+        .with_arg("hashes.len", &hashes.len())
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| H256::from_slice(&row.hash))
+            .collect())
+    }
+
     /// `committed_next_nonce` should equal the nonce for `initiator_address` in the storage.
     pub async fn next_nonce_by_initiator_account(
         &mut self,
@@ -412,12 +577,13 @@ mod tests {
     use std::collections::HashMap;
 
     use zksync_types::{
-        fee::TransactionExecutionMetrics, l2::L2Tx, Nonce, ProtocolVersion, ProtocolVersionId,
+        fee::TransactionExecutionMetrics, l2::L2Tx, ProtocolVersion, ProtocolVersionId,
     };
 
     use super::*;
     use crate::{
         tests::{create_l2_block_header, mock_execution_result, mock_l2_transaction},
+        transactions_dal::L2TxSubmissionResult,
         ConnectionPool, Core, CoreDal,
     };
 
@@ -674,4 +840,157 @@ mod tests {
             .unwrap();
         assert_eq!(next_nonce, 2.into());
     }
+
+    #[tokio::test]
+    async fn getting_pending_l2_tx_fee() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        let tx = mock_l2_transaction();
+        let initiator = tx.initiator_account();
+        conn.transactions_dal()
+            .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+            .await
+            .unwrap();
+
+        let pending_tx = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(initiator, Nonce(0))
+            .await
+            .unwrap();
+        assert_eq!(pending_tx, Some((tx.hash(), tx.common_data.fee.clone())));
+        let pending_tx = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(initiator, Nonce(1))
+            .await
+            .unwrap();
+        assert_eq!(pending_tx, None);
+
+        let mut replacement_tx = mock_l2_transaction();
+        replacement_tx.common_data.initiator_address = initiator;
+        replacement_tx.common_data.fee.max_fee_per_gas *= 2;
+        let submission_result = conn
+            .transactions_dal()
+            .insert_transaction_l2(&replacement_tx, TransactionExecutionMetrics::default())
+            .await
+            .unwrap();
+        assert_eq!(submission_result, L2TxSubmissionResult::Replaced);
+
+        let pending_tx = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(initiator, Nonce(0))
+            .await
+            .unwrap();
+        assert_eq!(
+            pending_tx,
+            Some((
+                replacement_tx.hash(),
+                replacement_tx.common_data.fee.clone()
+            ))
+        );
+
+        let pending_txs = conn
+            .transactions_web3_dal()
+            .get_pending_txs_after(NaiveDateTime::default(), None)
+            .await
+            .unwrap();
+        let pending_txs: Vec<_> = pending_txs
+            .into_iter()
+            .map(|(_, hash, initiator_and_nonce)| (hash, initiator_and_nonce))
+            .collect();
+        assert_eq!(
+            pending_txs,
+            [(replacement_tx.hash(), Some((initiator, Nonce(0))))]
+        );
+
+        conn.transactions_dal()
+            .mark_tx_as_rejected(replacement_tx.hash(), "oops")
+            .await
+            .unwrap();
+        let pending_tx = conn
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(initiator, Nonce(0))
+            .await
+            .unwrap();
+        assert_eq!(pending_tx, None);
+    }
+
+    #[tokio::test]
+    async fn getting_pending_l2_txs_count() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        let initiator = Address::repeat_byte(1);
+        let mut tx_hashes = vec![];
+        for nonce in 0..3 {
+            let mut tx = mock_l2_transaction();
+            tx.common_data.initiator_address = initiator;
+            tx.common_data.nonce = Nonce(nonce);
+            tx_hashes.push(tx.hash());
+            conn.transactions_dal()
+                .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+        // Transaction from another account should not be counted.
+        conn.transactions_dal()
+            .insert_transaction_l2(
+                &mock_l2_transaction(),
+                TransactionExecutionMetrics::default(),
+            )
+            .await
+            .unwrap();
+
+        let count = conn
+            .transactions_web3_dal()
+            .get_pending_l2_txs_count(initiator, Nonce(3))
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+        let count = conn
+            .transactions_web3_dal()
+            .get_pending_l2_txs_count(initiator, Nonce(1))
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        conn.transactions_dal()
+            .mark_tx_as_rejected(tx_hashes[0], "oops")
+            .await
+            .unwrap();
+        let count = conn
+            .transactions_web3_dal()
+            .get_pending_l2_txs_count(initiator, Nonce(3))
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn filtering_pending_tx_hashes() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        let txs: Vec<_> = (0..3).map(|_| mock_l2_transaction()).collect();
+        for tx in &txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+        conn.transactions_dal()
+            .mark_tx_as_rejected(txs[0].hash(), "oops")
+            .await
+            .unwrap();
+
+        let mut hashes: Vec<_> = txs.iter().map(|tx| tx.hash()).collect();
+        // Unknown transactions should be filtered out.
+        hashes.push(H256::repeat_byte(0xff));
+        let mut pending_hashes = conn
+            .transactions_web3_dal()
+            .filter_pending_tx_hashes(&hashes)
+            .await
+            .unwrap();
+        pending_hashes.sort_unstable();
+        let mut expected_hashes = vec![txs[1].hash(), txs[2].hash()];
+        expected_hashes.sort_unstable();
+        assert_eq!(pending_hashes, expected_hashes);
+    }
 }
@@ -60,12 +60,15 @@ mod tests {
                 http_url: "http://127.0.0.1:3050".into(),
                 ws_port: 3051,
                 ws_url: "ws://127.0.0.1:3051".into(),
+                graphql_port: Some(3052),
                 req_entities_limit: Some(10000),
                 filters_disabled: false,
+                txpool_namespace_enabled: true,
                 filters_limit: Some(10000),
                 subscriptions_limit: Some(10000),
                 pubsub_polling_interval: Some(200),
                 max_nonce_ahead: 5,
+                replacement_fee_bump_percent: Some(15),
                 request_timeout: Some(10),
                 account_pks: Some(vec![
                     hash("0x0000000000000000000000000000000000000000000000000000000000000001"),
@@ -91,9 +94,22 @@ mod tests {
                 .into_iter()
                 .collect(),
                 websocket_requests_per_minute_limit: Some(NonZeroU32::new(10).unwrap()),
+                client_requests_per_minute_limit: Some(NonZeroU32::new(600).unwrap()),
+                api_key_header: Some("x-api-key".to_owned()),
+                api_keys: [("first", NonZeroU32::new(100).unwrap())]
+                    .into_iter()
+                    .collect(),
+                trusted_proxies: vec!["10.0.0.1".parse().unwrap(), "::1".parse().unwrap()],
+                method_weights: [
+                    ("debug_*", NonZeroU32::new(20).unwrap()),
+                    ("eth_getLogs", NonZeroU32::new(10).unwrap()),
+                ]
+                .into_iter()
+                .collect(),
                 tree_api_url: None,
                 mempool_cache_update_interval: Some(50),
                 mempool_cache_size: Some(10000),
+                response_cache_size_mb: Some(64),
                 whitelisted_tokens_for_aa: vec![
                     addr("0x0000000000000000000000000000000000000001"),
                     addr("0x0000000000000000000000000000000000000002"),
@@ -123,10 +139,12 @@ mod tests {
             API_WEB3_JSON_RPC_WS_URL="ws://127.0.0.1:3051"
             API_WEB3_JSON_RPC_REQ_ENTITIES_LIMIT=10000
             API_WEB3_JSON_RPC_FILTERS_DISABLED=false
+            API_WEB3_JSON_RPC_TXPOOL_NAMESPACE_ENABLED=true
             API_WEB3_JSON_RPC_FILTERS_LIMIT=10000
             API_WEB3_JSON_RPC_SUBSCRIPTIONS_LIMIT=10000
             API_WEB3_JSON_RPC_PUBSUB_POLLING_INTERVAL=200
             API_WEB3_JSON_RPC_MAX_NONCE_AHEAD=5
+            API_WEB3_JSON_RPC_REPLACEMENT_FEE_BUMP_PERCENT=15
             API_WEB3_JSON_RPC_GAS_PRICE_SCALE_FACTOR=1.2
             API_WEB3_JSON_RPC_REQUEST_TIMEOUT=10
             API_WEB3_JSON_RPC_ACCOUNT_PKS="0x0000000000000000000000000000000000000000000000000000000000000001,0x0000000000000000000000000000000000000000000000000000000000000002"
@@ -141,7 +159,14 @@ mod tests {
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
+            API_WEB3_JSON_RPC_CLIENT_REQUESTS_PER_MINUTE_LIMIT=600
+            API_WEB3_JSON_RPC_API_KEY_HEADER="x-api-key"
+            API_WEB3_JSON_RPC_API_KEYS="first=100"
+            API_WEB3_JSON_RPC_TRUSTED_PROXIES="10.0.0.1,::1"
+            API_WEB3_JSON_RPC_METHOD_WEIGHTS="debug_*=20, eth_getLogs=10"
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_SIZE=10000
+            API_WEB3_JSON_RPC_RESPONSE_CACHE_SIZE_MB=64
+            API_WEB3_JSON_RPC_GRAPHQL_PORT=3052
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_UPDATE_INTERVAL=50
             API_CONTRACT_VERIFICATION_PORT="3070"
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
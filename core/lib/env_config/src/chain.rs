@@ -168,6 +168,9 @@ mod tests {
             stuck_tx_timeout: 10,
             remove_stuck_txs: true,
             delay_interval: 100,
+            max_txs_per_account: Some(64),
+            max_nonce_gap: Some(32),
+            evict_lowest_fee: true,
         }
     }
 
@@ -181,6 +184,9 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_MAX_TXS_PER_ACCOUNT="64"
+            CHAIN_MEMPOOL_MAX_NONCE_GAP="32"
+            CHAIN_MEMPOOL_EVICT_LOWEST_FEE="true"
         "#;
         lock.set_env(config);
 
@@ -47,6 +47,11 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: None,
+                local_mirror_max_size_mb: None,
+                local_mirror_buckets: vec![],
+                compress_objects: false,
+                deduplicate_objects: false,
+                deduplication_gc_interval_sec: None,
             }),
             public_object_store: Some(ObjectStoreConfig {
                 mode: ObjectStoreMode::GCSWithCredentialFile {
@@ -55,6 +60,11 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: None,
+                local_mirror_max_size_mb: None,
+                local_mirror_buckets: vec![],
+                compress_objects: false,
+                deduplicate_objects: false,
+                deduplication_gc_interval_sec: None,
             }),
             availability_check_interval_in_secs: Some(1_800),
         }
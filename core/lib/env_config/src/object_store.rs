@@ -42,6 +42,8 @@ impl FromEnv for SnapshotsObjectStoreConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
 
     use super::*;
@@ -57,6 +59,11 @@ mod tests {
             },
             max_retries: 5,
             local_mirror_path: Some("/var/cache".to_owned()),
+            local_mirror_max_size_mb: None,
+            local_mirror_buckets: vec![],
+            compress_objects: false,
+            deduplicate_objects: false,
+            deduplication_gc_interval_sec: None,
         }
     }
 
@@ -81,6 +88,9 @@ mod tests {
         let config = r#"
             OBJECT_STORE_MODE="FileBacked"
             OBJECT_STORE_FILE_BACKED_BASE_PATH="artifacts"
+            OBJECT_STORE_COMPRESS_OBJECTS="true"
+            OBJECT_STORE_DEDUPLICATE_OBJECTS="true"
+            OBJECT_STORE_DEDUPLICATION_GC_INTERVAL_SEC="3600"
         "#;
         lock.set_env(config);
         let actual = ObjectStoreConfig::from_env().unwrap();
@@ -90,6 +100,39 @@ mod tests {
                 file_backed_base_path: "artifacts".to_owned(),
             }
         );
+        assert!(actual.compress_objects);
+        assert!(actual.deduplicate_objects);
+        assert_eq!(
+            actual.deduplication_gc_interval(),
+            Some(Duration::from_secs(3600))
+        );
+    }
+
+    #[test]
+    fn s3_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_MODE="S3WithStaticCredentials"
+            OBJECT_STORE_BUCKET_NAME="zksync-artifacts"
+            OBJECT_STORE_REGION="us-east-1"
+            OBJECT_STORE_ENDPOINT="http://127.0.0.1:9000"
+            OBJECT_STORE_PATH_STYLE="true"
+            OBJECT_STORE_ACCESS_KEY_ID="minioadmin"
+            OBJECT_STORE_SECRET_ACCESS_KEY="minioadmin"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+        assert_eq!(
+            actual.mode,
+            ObjectStoreMode::S3WithStaticCredentials {
+                bucket_name: "zksync-artifacts".to_owned(),
+                region: "us-east-1".to_owned(),
+                endpoint: Some("http://127.0.0.1:9000".to_owned()),
+                path_style: true,
+                access_key_id: "minioadmin".to_owned(),
+                secret_access_key: "minioadmin".to_owned(),
+            }
+        );
     }
 
     #[test]
@@ -133,6 +176,9 @@ mod tests {
             SNAPSHOTS_OBJECT_STORE_BUCKET_BASE_URL="/snapshots_base_url"
             SNAPSHOTS_OBJECT_STORE_MODE="GCS"
             SNAPSHOTS_OBJECT_STORE_MAX_RETRIES="5"
+            SNAPSHOTS_OBJECT_STORE_LOCAL_MIRROR_PATH="/var/cache"
+            SNAPSHOTS_OBJECT_STORE_LOCAL_MIRROR_MAX_SIZE_MB="1024"
+            SNAPSHOTS_OBJECT_STORE_LOCAL_MIRROR_BUCKETS="storage_logs_snapshots,proofs_fri"
         "#;
         lock.set_env(config);
         let actual = SnapshotsObjectStoreConfig::from_env().unwrap().0;
@@ -142,5 +188,10 @@ mod tests {
                 bucket_base_url: "/snapshots_base_url".to_owned(),
             }
         );
+        assert_eq!(actual.local_mirror_max_size_mb, Some(1_024));
+        assert_eq!(
+            actual.local_mirror_buckets,
+            ["storage_logs_snapshots", "proofs_fri"]
+        );
     }
 }
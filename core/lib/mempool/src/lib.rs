@@ -4,6 +4,6 @@ mod tests;
 mod types;
 
 pub use crate::{
-    mempool_store::{MempoolInfo, MempoolStats, MempoolStore},
-    types::L2TxFilter,
+    mempool_store::{MempoolContents, MempoolInfo, MempoolStats, MempoolStore},
+    types::{EvictedTx, EvictionPolicy, EvictionReason, L2TxFilter},
 };
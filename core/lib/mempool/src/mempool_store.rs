@@ -1,15 +1,67 @@
-use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet},
+};
 
 use zksync_types::{
-    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
+    helpers::unix_timestamp_ms, l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce,
+    PriorityOpId, Transaction, H256,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::types::{
+    AccountTransactions, EvictedTx, EvictionPolicy, EvictionReason, L2TxFilter, MempoolScore,
+};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
     pub stashed_accounts: Vec<Address>,
     pub purged_accounts: Vec<Address>,
+    /// L2 transactions evicted from the mempool according to the eviction policy. These transactions
+    /// should be marked as rejected in the storage, so that they are not synced to the mempool again.
+    pub evicted_txs: Vec<EvictedTx>,
+}
+
+/// Snapshot of transactions in the mempool.
+#[derive(Debug, Default, PartialEq)]
+pub struct MempoolContents {
+    /// Hashes of L1 transactions (priority operations) ordered by their priority ID.
+    pub l1_transactions: Vec<H256>,
+    /// Hashes of L2 transactions ready for execution, i.e. ones with nonces following the account nonce
+    /// without gaps.
+    pub pending: HashMap<Address, BTreeMap<Nonce, H256>>,
+    /// Hashes of L2 transactions that cannot be executed until a nonce gap is filled.
+    pub queued: HashMap<Address, BTreeMap<Nonce, H256>>,
+}
+
+impl MempoolContents {
+    /// Creates contents from L1 transaction hashes and L2 transactions grouped by the initiator account.
+    /// Each account is provided together with its next expected nonce, which is used to split transactions
+    /// into pending and queued ones.
+    pub fn new(
+        l1_transactions: Vec<H256>,
+        l2_transactions: impl IntoIterator<Item = (Address, Nonce, BTreeMap<Nonce, H256>)>,
+    ) -> Self {
+        let mut pending = HashMap::new();
+        let mut queued = HashMap::new();
+        for (account, account_nonce, mut txs) in l2_transactions {
+            let mut next_nonce = account_nonce;
+            while txs.contains_key(&next_nonce) {
+                next_nonce += 1;
+            }
+            let account_queued = txs.split_off(&next_nonce);
+            if !txs.is_empty() {
+                pending.insert(account, txs);
+            }
+            if !account_queued.is_empty() {
+                queued.insert(account, account_queued);
+            }
+        }
+        Self {
+            l1_transactions,
+            pending,
+            queued,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -30,9 +82,12 @@ pub struct MempoolStore {
     /// Next priority operation
     next_priority_id: PriorityOpId,
     stashed_accounts: Vec<Address>,
+    /// L2 transactions evicted since the last `get_mempool_info()` call.
+    evicted_txs: Vec<EvictedTx>,
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    eviction_policy: EvictionPolicy,
 }
 
 impl MempoolStore {
@@ -43,11 +98,18 @@ impl MempoolStore {
             l2_priority_queue: BTreeSet::new(),
             next_priority_id,
             stashed_accounts: vec![],
+            evicted_txs: vec![],
             size: 0,
             capacity,
+            eviction_policy: EvictionPolicy::default(),
         }
     }
 
+    pub fn with_eviction_policy(mut self, eviction_policy: EvictionPolicy) -> Self {
+        self.eviction_policy = eviction_policy;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -101,16 +163,35 @@ impl MempoolStore {
         initial_nonces: &HashMap<Address, Nonce>,
     ) {
         let account = transaction.initiator_account();
+        let nonce = transaction.common_data.nonce;
 
-        let metadata = match self.l2_transactions_per_account.entry(account) {
-            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
-            hash_map::Entry::Vacant(entry) => {
-                let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
-                entry
-                    .insert(AccountTransactions::new(account_nonce))
-                    .insert(transaction)
-            }
+        let account_nonce = match self.l2_transactions_per_account.get(&account) {
+            Some(account_txs) => account_txs.nonce(),
+            None => initial_nonces.get(&account).cloned().unwrap_or(Nonce(0)),
         };
+        // The gap is checked before creating an entry for the account, so that rejected transactions
+        // don't leave empty entries (which would pin a stale account nonce) in the mempool.
+        let nonce_gap = nonce.0.saturating_sub(account_nonce.0);
+        if self
+            .eviction_policy
+            .max_nonce_gap
+            .is_some_and(|max_gap| nonce_gap > max_gap)
+        {
+            tracing::debug!(
+                "rejecting L2 transaction from account {account:?} with nonce {nonce}: nonce gap {nonce_gap} is too large"
+            );
+            self.evicted_txs.push(EvictedTx {
+                hash: transaction.hash(),
+                reason: EvictionReason::NonceGapTooLarge,
+            });
+            return;
+        }
+
+        let account_txs = self
+            .l2_transactions_per_account
+            .entry(account)
+            .or_insert_with(|| AccountTransactions::new(account_nonce));
+        let metadata = account_txs.insert(transaction);
         if let Some(score) = metadata.previous_score {
             self.l2_priority_queue.remove(&score);
         }
@@ -120,6 +201,50 @@ impl MempoolStore {
         if metadata.is_new {
             self.size += 1;
         }
+        if metadata.is_replacement {
+            tracing::debug!("replaced L2 transaction from account {account:?} with nonce {nonce}");
+        }
+
+        if let Some(max_txs) = self.eviction_policy.max_txs_per_account {
+            while self.l2_transactions_per_account[&account].len() > max_txs {
+                self.evict_last_l2_transaction(account, EvictionReason::TooManyAccountTxs);
+            }
+        }
+    }
+
+    /// Evicts the transaction with the highest nonce for the specified account.
+    fn evict_last_l2_transaction(&mut self, account: Address, reason: EvictionReason) {
+        let last_nonce = self.l2_transactions_per_account[&account]
+            .last()
+            .expect("no transactions to evict")
+            .common_data
+            .nonce;
+        self.evict_l2_transaction(account, last_nonce, reason);
+    }
+
+    fn evict_l2_transaction(&mut self, account: Address, nonce: Nonce, reason: EvictionReason) {
+        let Some((transaction, score)) = self
+            .l2_transactions_per_account
+            .get_mut(&account)
+            .and_then(|txs| txs.remove(nonce))
+        else {
+            return;
+        };
+        tracing::debug!(
+            "evicting L2 transaction from account {account:?} with nonce {nonce}: {reason}"
+        );
+
+        if let Some(score) = score {
+            self.l2_priority_queue.remove(&score);
+        }
+        self.size = self
+            .size
+            .checked_sub(1)
+            .expect("mempool size can't be negative");
+        self.evicted_txs.push(EvictedTx {
+            hash: transaction.hash(),
+            reason,
+        });
     }
 
     /// Returns `true` if there is a transaction in the mempool satisfying the filter.
@@ -206,12 +331,36 @@ impl MempoolStore {
     }
 
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
+        self.evict_expired_transactions(unix_timestamp_ms());
+        let purged_accounts = self.gc();
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
-            purged_accounts: self.gc(),
+            purged_accounts,
+            evicted_txs: std::mem::take(&mut self.evicted_txs),
         }
     }
 
+    pub fn contents(&self) -> MempoolContents {
+        let mut l1_transactions: Vec<_> = self.l1_transactions.iter().collect();
+        l1_transactions.sort_unstable_by_key(|(&priority_id, _)| priority_id);
+        let l1_transactions = l1_transactions
+            .into_iter()
+            .map(|(_, tx)| tx.hash())
+            .collect();
+
+        let l2_transactions =
+            self.l2_transactions_per_account
+                .iter()
+                .map(|(&account, account_txs)| {
+                    let txs = account_txs
+                        .transactions()
+                        .map(|tx| (tx.common_data.nonce, tx.hash()))
+                        .collect();
+                    (account, account_txs.nonce(), txs)
+                });
+        MempoolContents::new(l1_transactions, l2_transactions)
+    }
+
     pub fn stats(&self) -> MempoolStats {
         MempoolStats {
             l1_transaction_count: self.l1_transactions.len(),
@@ -236,8 +385,67 @@ impl MempoolStore {
                 .l2_transactions_per_account
                 .iter()
                 .fold(0, |agg, (_, tnxs)| agg + tnxs.len() as u64);
+            if self.eviction_policy.evict_lowest_fee {
+                self.evict_lowest_fee_transactions();
+            }
             return drained.into_keys().collect();
         }
         vec![]
     }
+
+    /// Evicts expired transactions together with all transactions following them for the same account.
+    /// The following transactions cannot be executed without the expired ones, so keeping them would only
+    /// create nonce gaps. Transactions are evicted from the highest nonce down.
+    fn evict_expired_transactions(&mut self, now_ms: u64) {
+        if self.eviction_policy.tx_ttl.is_none() {
+            return;
+        }
+        let mut evicted_nonces: Vec<_> = self
+            .l2_transactions_per_account
+            .iter()
+            .flat_map(|(&account, txs)| {
+                let first_expired_nonce = txs
+                    .transactions()
+                    .filter(|tx| self.eviction_policy.is_expired(tx, now_ms))
+                    .map(|tx| tx.common_data.nonce)
+                    .min();
+                first_expired_nonce
+                    .into_iter()
+                    .flat_map(move |first_nonce| {
+                        txs.transactions()
+                            .map(|tx| tx.common_data.nonce)
+                            .filter(move |&nonce| nonce >= first_nonce)
+                            .map(move |nonce| (account, nonce))
+                    })
+            })
+            .collect();
+        evicted_nonces.sort_unstable_by_key(|&(account, nonce)| (account, Reverse(nonce)));
+
+        for (account, nonce) in evicted_nonces {
+            self.evict_l2_transaction(account, nonce, EvictionReason::Expired);
+        }
+    }
+
+    /// Evicts transactions with the lowest max fee per gas until the mempool size fits into its capacity.
+    /// Only the last transactions of accounts are considered for eviction, so that nonce gaps are not created.
+    fn evict_lowest_fee_transactions(&mut self) {
+        let mut last_txs: BinaryHeap<_> = self
+            .l2_transactions_per_account
+            .iter()
+            .filter_map(|(&account, txs)| {
+                let fee = txs.last()?.common_data.fee.max_fee_per_gas;
+                Some(Reverse((fee, account)))
+            })
+            .collect();
+
+        while self.size > self.capacity {
+            let Some(Reverse((_, account))) = last_txs.pop() else {
+                break;
+            };
+            self.evict_last_l2_transaction(account, EvictionReason::LowFee);
+            if let Some(tx) = self.l2_transactions_per_account[&account].last() {
+                last_txs.push(Reverse((tx.common_data.fee.max_fee_per_gas, account)));
+            }
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     iter::FromIterator,
+    time::Duration,
 };
 
 use zksync_types::{
@@ -12,7 +13,10 @@ use zksync_types::{
     H256, U256,
 };
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::MempoolStore,
+    types::{EvictionPolicy, EvictionReason, L2TxFilter},
+};
 
 #[test]
 fn basic_flow() {
@@ -200,6 +204,28 @@ fn replace_tx() {
     assert!(mempool.next_transaction(&L2TxFilter::default()).is_none());
 }
 
+#[test]
+fn replacing_underpriced_tx() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account = Address::random();
+    let filter = L2TxFilter {
+        fee_per_gas: 10,
+        gas_per_pubdata: 0,
+        ..L2TxFilter::default()
+    };
+    let underpriced_tx = gen_l2_tx_with_fee(account, Nonce(0), 5);
+    mempool.insert(vec![underpriced_tx], HashMap::new());
+    assert!(!mempool.has_next(&filter));
+
+    let replacement_tx = gen_l2_tx_with_fee(account, Nonce(0), 11);
+    mempool.insert(vec![replacement_tx], HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    assert!(mempool.has_next(&filter));
+    let tx = mempool.next_transaction(&filter).unwrap();
+    assert_eq!(tx.max_fee_per_gas(), 11.into());
+    assert!(mempool.next_transaction(&L2TxFilter::default()).is_none());
+}
+
 #[test]
 fn two_ready_txs() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
@@ -365,6 +391,238 @@ fn mempool_capacity() {
     );
 }
 
+#[test]
+fn max_txs_per_account() {
+    let policy = EvictionPolicy {
+        max_txs_per_account: Some(2),
+        ..EvictionPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_eviction_policy(policy);
+    let account = Address::random();
+    let transactions: Vec<_> = (0..4)
+        .map(|nonce| gen_l2_tx(account, Nonce(nonce)))
+        .collect();
+    let evicted_hashes = [transactions[2].hash(), transactions[3].hash()];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+
+    let evicted_txs = mempool.get_mempool_info().evicted_txs;
+    assert_eq!(evicted_txs.len(), 2);
+    for (evicted_tx, expected_hash) in evicted_txs.iter().zip(evicted_hashes) {
+        assert_eq!(evicted_tx.hash, expected_hash);
+        assert_eq!(evicted_tx.reason, EvictionReason::TooManyAccountTxs);
+    }
+
+    for nonce in 0..2 {
+        assert_eq!(
+            view(mempool.next_transaction(&L2TxFilter::default())),
+            (account, nonce)
+        );
+    }
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+    assert!(mempool.get_mempool_info().evicted_txs.is_empty());
+}
+
+#[test]
+fn max_nonce_gap() {
+    let policy = EvictionPolicy {
+        max_nonce_gap: Some(1),
+        ..EvictionPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_eviction_policy(policy);
+    let account = Address::random();
+    let far_tx = gen_l2_tx(account, Nonce(7));
+    let far_tx_hash = far_tx.hash();
+    let transactions = vec![gen_l2_tx(account, Nonce(6)), far_tx];
+    mempool.insert(transactions, HashMap::from([(account, Nonce(5))]));
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+
+    let evicted_txs = mempool.get_mempool_info().evicted_txs;
+    assert_eq!(evicted_txs.len(), 1);
+    assert_eq!(evicted_txs[0].hash, far_tx_hash);
+    assert_eq!(evicted_txs[0].reason, EvictionReason::NonceGapTooLarge);
+
+    // The gap is checked against the current account nonce in the mempool.
+    mempool.insert(vec![gen_l2_tx(account, Nonce(5))], HashMap::new());
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account, 5)
+    );
+    mempool.insert(vec![gen_l2_tx(account, Nonce(7))], HashMap::new());
+    assert!(mempool.get_mempool_info().evicted_txs.is_empty());
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+}
+
+#[test]
+fn expired_txs() {
+    let policy = EvictionPolicy {
+        tx_ttl: Some(Duration::from_secs(60)),
+        ..EvictionPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_eviction_policy(policy);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let expired_tx = gen_l2_tx_with_timestamp(account0, Nonce(0), 0);
+    let expired_tx_hash = expired_tx.hash();
+    mempool.insert(
+        vec![expired_tx, gen_l2_tx(account1, Nonce(0))],
+        HashMap::new(),
+    );
+
+    let evicted_txs = mempool.get_mempool_info().evicted_txs;
+    assert_eq!(evicted_txs.len(), 1);
+    assert_eq!(evicted_txs[0].hash, expired_tx_hash);
+    assert_eq!(evicted_txs[0].reason, EvictionReason::Expired);
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    assert_eq!(mempool.stats().l2_priority_queue_size, 1);
+
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account1, 0)
+    );
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+}
+
+#[test]
+fn expired_tx_evicts_account_tail() {
+    let policy = EvictionPolicy {
+        tx_ttl: Some(Duration::from_secs(60)),
+        ..EvictionPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_eviction_policy(policy);
+    let account = Address::random();
+    let now_ms = unix_timestamp_ms();
+    let transactions = vec![
+        gen_l2_tx_with_timestamp(account, Nonce(0), now_ms),
+        gen_l2_tx_with_timestamp(account, Nonce(1), 0),
+        gen_l2_tx_with_timestamp(account, Nonce(2), now_ms),
+        gen_l2_tx_with_timestamp(account, Nonce(3), now_ms),
+    ];
+    let evicted_hashes = [
+        transactions[3].hash(),
+        transactions[2].hash(),
+        transactions[1].hash(),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let evicted_txs = mempool.get_mempool_info().evicted_txs;
+    let evicted_tx_hashes: Vec<_> = evicted_txs.iter().map(|tx| tx.hash).collect();
+    assert_eq!(evicted_tx_hashes, evicted_hashes);
+    assert!(evicted_txs
+        .iter()
+        .all(|tx| tx.reason == EvictionReason::Expired));
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account, 0)
+    );
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+}
+
+#[test]
+fn rejected_tx_does_not_bootstrap_account() {
+    let policy = EvictionPolicy {
+        max_nonce_gap: Some(1),
+        ..EvictionPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_eviction_policy(policy);
+    let account = Address::random();
+    mempool.insert(
+        vec![gen_l2_tx(account, Nonce(10))],
+        HashMap::from([(account, Nonce(0))]),
+    );
+    assert_eq!(mempool.get_mempool_info().evicted_txs.len(), 1);
+
+    // The account nonce provided with the next insertion must be used, rather than the one from the rejected insertion.
+    mempool.insert(
+        vec![gen_l2_tx(account, Nonce(10))],
+        HashMap::from([(account, Nonce(9))]),
+    );
+    assert!(mempool.get_mempool_info().evicted_txs.is_empty());
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+}
+
+#[test]
+fn evicting_lowest_fee_txs() {
+    let policy = EvictionPolicy {
+        evict_lowest_fee: true,
+        ..EvictionPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 3).with_eviction_policy(policy);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let cheap_tx = gen_l2_tx_with_fee(account1, Nonce(0), 5);
+    let cheap_tx_hash = cheap_tx.hash();
+    let transactions = vec![
+        gen_l2_tx_with_fee(account0, Nonce(0), 10),
+        gen_l2_tx_with_fee(account0, Nonce(1), 10),
+        cheap_tx,
+        gen_l2_tx_with_fee(account2, Nonce(0), 20),
+    ];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 4);
+
+    let mempool_info = mempool.get_mempool_info();
+    assert!(mempool_info.purged_accounts.is_empty());
+    assert_eq!(mempool_info.evicted_txs.len(), 1);
+    assert_eq!(mempool_info.evicted_txs[0].hash, cheap_tx_hash);
+    assert_eq!(mempool_info.evicted_txs[0].reason, EvictionReason::LowFee);
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+
+    let mut remaining_accounts = HashSet::new();
+    while let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) {
+        remaining_accounts.insert(tx.initiator_account());
+    }
+    assert_eq!(remaining_accounts, HashSet::from([account0, account2]));
+}
+
+#[test]
+fn mempool_contents() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l1_tx(PriorityOpId(1)),
+        gen_l1_tx(PriorityOpId(0)),
+        gen_l2_tx(account0, Nonce(0)),
+        gen_l2_tx(account0, Nonce(1)),
+        gen_l2_tx(account0, Nonce(3)),
+        gen_l2_tx(account1, Nonce(1)),
+    ];
+    let hashes: Vec<_> = transactions.iter().map(Transaction::hash).collect();
+    mempool.insert(transactions, HashMap::new());
+
+    let contents = mempool.contents();
+    assert_eq!(contents.l1_transactions, [hashes[1], hashes[0]]);
+    assert_eq!(
+        contents.pending,
+        HashMap::from([(
+            account0,
+            BTreeMap::from([(Nonce(0), hashes[2]), (Nonce(1), hashes[3])])
+        )])
+    );
+    assert_eq!(
+        contents.queued,
+        HashMap::from([
+            (account0, BTreeMap::from([(Nonce(3), hashes[4])])),
+            (account1, BTreeMap::from([(Nonce(1), hashes[5])])),
+        ])
+    );
+
+    // Executed transactions are no longer in the mempool.
+    for _ in 0..3 {
+        mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    }
+    let contents = mempool.contents();
+    assert!(contents.l1_transactions.is_empty());
+    assert_eq!(
+        contents.pending,
+        HashMap::from([(account0, BTreeMap::from([(Nonce(1), hashes[3])]))])
+    );
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -381,9 +639,21 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
         Default::default(),
     );
     txn.received_timestamp_ms = received_at_ms;
+    txn.set_input(vec![], H256::random());
     txn.into()
 }
 
+fn gen_l2_tx_with_fee(address: Address, nonce: Nonce, max_fee_per_gas: u64) -> Transaction {
+    let mut tx = gen_l2_tx(address, nonce);
+    match &mut tx.common_data {
+        ExecuteTransactionCommon::L2(data) => {
+            data.fee.max_fee_per_gas = max_fee_per_gas.into();
+        }
+        _ => unreachable!(),
+    }
+    tx
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     let execute = Execute {
         contract_address: Address::repeat_byte(0x11),
@@ -402,7 +672,7 @@ fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
         op_processing_type: OpProcessingType::Common,
         priority_queue_type: PriorityQueueType::Deque,
         eth_block: 0,
-        canonical_tx_hash: H256::zero(),
+        canonical_tx_hash: H256::from_low_u64_be(priority_id.0),
         to_mint: U256::zero(),
         refund_recipient: Address::random(),
     };
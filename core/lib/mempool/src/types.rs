@@ -1,7 +1,7 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, fmt, time::Duration};
 
 use zksync_types::{
-    fee::Fee, fee_model::BatchFeeInput, l2::L2Tx, Address, Nonce, Transaction, U256,
+    fee::Fee, fee_model::BatchFeeInput, l2::L2Tx, Address, Nonce, Transaction, H256, U256,
 };
 
 /// Pending mempool transactions of account
@@ -23,6 +23,10 @@ impl AccountTransactions {
     }
 
     /// Inserts new transaction for given account. Returns insertion metadata
+    ///
+    /// A transaction with the same nonce as an existing one unconditionally replaces it. The replacement policy
+    /// (e.g., the minimum fee bump) is enforced by API servers before persisting the transaction to Postgres;
+    /// the mempool just mirrors the persisted state.
     pub fn insert(&mut self, transaction: L2Tx) -> InsertionMetadata {
         let mut metadata = InsertionMetadata::default();
         let nonce = transaction.common_data.nonce;
@@ -36,6 +40,7 @@ impl AccountTransactions {
             .insert(nonce, transaction)
             .map(|tx| Self::score_for_transaction(&tx));
         metadata.is_new = previous_score.is_none();
+        metadata.is_replacement = previous_score.is_some();
         if nonce == self.nonce {
             metadata.new_score = Some(new_score);
             metadata.previous_score = previous_score;
@@ -70,6 +75,29 @@ impl AccountTransactions {
             .map(Self::score_for_transaction)
     }
 
+    /// Removes the transaction with the given nonce. Returns the removed transaction and its score
+    /// if the transaction was pointed to by the priority queue.
+    pub fn remove(&mut self, nonce: Nonce) -> Option<(L2Tx, Option<MempoolScore>)> {
+        let transaction = self.transactions.remove(&nonce)?;
+        let score = (nonce == self.nonce).then(|| Self::score_for_transaction(&transaction));
+        Some((transaction, score))
+    }
+
+    /// Returns the transaction with the highest nonce.
+    pub fn last(&self) -> Option<&L2Tx> {
+        self.transactions
+            .values()
+            .max_by_key(|tx| tx.common_data.nonce)
+    }
+
+    pub fn transactions(&self) -> impl Iterator<Item = &L2Tx> + '_ {
+        self.transactions.values()
+    }
+
+    pub fn nonce(&self) -> Nonce {
+        self.nonce
+    }
+
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
@@ -124,6 +152,8 @@ pub(crate) struct InsertionMetadata {
     pub new_score: Option<MempoolScore>,
     pub previous_score: Option<MempoolScore>,
     pub is_new: bool,
+    /// Whether the inserted transaction has replaced a transaction with the same nonce.
+    pub is_replacement: bool,
 }
 
 /// Structure that can be used by state keeper to describe
@@ -138,6 +168,95 @@ pub struct L2TxFilter {
     pub gas_per_pubdata: u32,
 }
 
+/// Reason for evicting an L2 transaction from the mempool (or refusing to accept it).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvictionReason {
+    /// The initiator account has too many pending transactions.
+    TooManyAccountTxs,
+    /// The transaction nonce is too far ahead of the account nonce.
+    NonceGapTooLarge,
+    /// The transaction stayed in the mempool for longer than the configured TTL.
+    Expired,
+    /// The mempool is full, and the transaction has one of the lowest fees.
+    LowFee,
+}
+
+impl EvictionReason {
+    pub fn as_metric_label(&self) -> &'static str {
+        match self {
+            EvictionReason::TooManyAccountTxs => "TooManyAccountTxs",
+            EvictionReason::NonceGapTooLarge => "NonceGapTooLarge",
+            EvictionReason::Expired => "Expired",
+            EvictionReason::LowFee => "LowFee",
+        }
+    }
+}
+
+impl fmt::Display for EvictionReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvictionReason::TooManyAccountTxs => {
+                write!(f, "Too many pending transactions for the account")
+            }
+            EvictionReason::NonceGapTooLarge => write!(f, "Nonce gap is too large"),
+            EvictionReason::Expired => write!(f, "Transaction expired"),
+            EvictionReason::LowFee => write!(f, "Mempool is full, fee is too low"),
+        }
+    }
+}
+
+/// L2 transaction evicted from the mempool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvictedTx {
+    pub hash: H256,
+    pub reason: EvictionReason,
+}
+
+/// Policies limiting the number of L2 transactions in the mempool. All policies are disabled by default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EvictionPolicy {
+    /// Maximum number of pending transactions per account. Transactions with the highest nonces are evicted
+    /// once the limit is exceeded.
+    pub max_txs_per_account: Option<usize>,
+    /// Maximum difference between the transaction nonce and the account nonce.
+    pub max_nonce_gap: Option<u32>,
+    /// Time-to-live for transactions in the mempool, counted from the transaction receipt. An expired transaction
+    /// is evicted together with all subsequent transactions of the same account.
+    pub tx_ttl: Option<Duration>,
+    /// Whether to evict transactions with the lowest fees once the mempool reaches its capacity.
+    pub evict_lowest_fee: bool,
+}
+
+impl EvictionPolicy {
+    /// Checks whether a transaction can be admitted given the number of pending transactions for its initiator
+    /// (not counting the transaction itself) and the difference between the transaction nonce and the account nonce.
+    pub fn check_admission(
+        &self,
+        pending_txs: usize,
+        nonce_gap: u32,
+    ) -> Result<(), EvictionReason> {
+        if self
+            .max_txs_per_account
+            .is_some_and(|max_txs| pending_txs >= max_txs)
+        {
+            return Err(EvictionReason::TooManyAccountTxs);
+        }
+        if self
+            .max_nonce_gap
+            .is_some_and(|max_gap| nonce_gap > max_gap)
+        {
+            return Err(EvictionReason::NonceGapTooLarge);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_expired(&self, transaction: &L2Tx, now_ms: u64) -> bool {
+        self.tx_ttl.is_some_and(|ttl| {
+            now_ms.saturating_sub(transaction.received_timestamp_ms) > ttl.as_millis() as u64
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +317,25 @@ mod tests {
             "Incorrect pubdata price should be rejected"
         );
     }
+
+    #[test]
+    fn checking_admission() {
+        let policy = EvictionPolicy::default();
+        assert_eq!(policy.check_admission(1_000, 1_000), Ok(()));
+
+        let policy = EvictionPolicy {
+            max_txs_per_account: Some(2),
+            max_nonce_gap: Some(3),
+            ..EvictionPolicy::default()
+        };
+        assert_eq!(policy.check_admission(1, 3), Ok(()));
+        assert_eq!(
+            policy.check_admission(2, 0),
+            Err(EvictionReason::TooManyAccountTxs)
+        );
+        assert_eq!(
+            policy.check_admission(0, 4),
+            Err(EvictionReason::NonceGapTooLarge)
+        );
+    }
 }
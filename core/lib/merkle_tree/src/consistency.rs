@@ -80,16 +80,7 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         version: u64,
         validate_indices: bool,
     ) -> Result<(), ConsistencyError> {
-        let manifest = self.db.try_manifest()?;
-        let manifest = manifest.ok_or(ConsistencyError::MissingVersion(version))?;
-        if version >= manifest.version_count {
-            return Err(ConsistencyError::MissingVersion(version));
-        }
-
-        let root = self
-            .db
-            .try_root(version)?
-            .ok_or(ConsistencyError::MissingRoot(version))?;
+        let root = self.root_for_verification(version)?;
         let (leaf_count, root_node) = match root {
             Root::Empty => return Ok(()),
             Root::Filled { leaf_count, node } => (leaf_count.get(), node),
@@ -106,6 +97,84 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         Ok(())
     }
 
+    /// Verifies consistency of a subtree of the tree at the specified `version`. The subtree is rooted
+    /// at the node with the first `nibble_count` nibbles of `key`, or at the deepest existing node on the path
+    /// to `key` if there is no such node. Besides the subtree itself, it is checked that the subtree root hash
+    /// matches the child reference in its parent.
+    ///
+    /// Unlike [`Self::verify_consistency()`], leaf indices are not validated, since this requires
+    /// traversing the entire tree. This method is intended to be used for sampled verification
+    /// of large trees, with `key` chosen randomly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error (the first encountered one if there are multiple).
+    pub fn verify_subtree_consistency(
+        &self,
+        version: u64,
+        key: Key,
+        nibble_count: usize,
+    ) -> Result<(), ConsistencyError> {
+        let root = self.root_for_verification(version)?;
+        let Root::Filled { node, .. } = root else {
+            return Ok(());
+        };
+
+        let mut node = node;
+        let mut node_key = Nibbles::EMPTY.with_version(version);
+        let mut parent_ref = None;
+        while node_key.nibbles.nibble_count() < nibble_count {
+            let Node::Internal(internal_node) = &node else {
+                break;
+            };
+            let nibble = Nibbles::nibble(&key, node_key.nibbles.nibble_count());
+            let Some(&child_ref) = internal_node.child_ref(nibble) else {
+                break;
+            };
+            let child_nibbles = node_key
+                .nibbles
+                .push(nibble)
+                .ok_or(ConsistencyError::TerminalInternalNode { key: node_key })?;
+            let child_key = child_nibbles.with_version(child_ref.version);
+            let child = self
+                .db
+                .try_tree_node(&child_key, child_ref.is_leaf)?
+                .ok_or(ConsistencyError::MissingNode {
+                    key: child_key,
+                    is_leaf: child_ref.is_leaf,
+                })?;
+
+            parent_ref = Some((node_key, nibble, child_ref.hash));
+            node = child;
+            node_key = child_key;
+        }
+
+        let hash = self.validate_node(&node, node_key, None)?;
+        if let Some((parent_key, nibble, expected)) = parent_ref {
+            if hash != expected {
+                return Err(ConsistencyError::HashMismatch {
+                    key: parent_key,
+                    nibble,
+                    expected,
+                    actual: hash,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn root_for_verification(&self, version: u64) -> Result<Root, ConsistencyError> {
+        let manifest = self.db.try_manifest()?;
+        let manifest = manifest.ok_or(ConsistencyError::MissingVersion(version))?;
+        if version >= manifest.version_count {
+            return Err(ConsistencyError::MissingVersion(version));
+        }
+
+        self.db
+            .try_root(version)?
+            .ok_or(ConsistencyError::MissingRoot(version))
+    }
+
     fn validate_node(
         &self,
         node: &Node,
@@ -401,6 +470,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn subtree_consistency_checks() {
+        let db = prepare_database();
+        let tree = MerkleTree::new(db).unwrap();
+        for nibble_count in 0..=12 {
+            for key in [FIRST_KEY, SECOND_KEY, U256::zero(), U256::MAX] {
+                tree.verify_subtree_consistency(0, key, nibble_count)
+                    .unwrap();
+            }
+        }
+
+        let err = tree
+            .verify_subtree_consistency(1, FIRST_KEY, 3)
+            .unwrap_err();
+        assert_matches!(err, ConsistencyError::MissingVersion(1));
+    }
+
+    #[test]
+    fn subtree_hash_mismatch_error() {
+        let mut db = prepare_database();
+        let leaf_key = db.nodes_mut().find_map(|(key, node)| match node {
+            Node::Leaf(leaf) if leaf.full_key == FIRST_KEY => {
+                leaf.value_hash = ValueHash::zero();
+                Some(*key)
+            }
+            _ => None,
+        });
+        let leaf_key = leaf_key.unwrap();
+        let (parent_nibbles, leaf_nibble) = leaf_key.nibbles.split_last().unwrap();
+        let tree = MerkleTree::new(db).unwrap();
+
+        for nibble_count in [0, 3, leaf_key.nibbles.nibble_count()] {
+            let err = tree
+                .verify_subtree_consistency(0, FIRST_KEY, nibble_count)
+                .unwrap_err();
+            assert_matches!(
+                err,
+                ConsistencyError::HashMismatch { key, nibble, .. }
+                    if key.nibbles == parent_nibbles && nibble == leaf_nibble
+            );
+        }
+
+        // The subtree with the other leaf is not affected.
+        tree.verify_subtree_consistency(0, SECOND_KEY, leaf_key.nibbles.nibble_count())
+            .unwrap();
+    }
+
     #[test]
     fn full_key_mismatch_error() {
         let mut db = prepare_database();
@@ -1,5 +1,8 @@
 //! Tying the Merkle tree implementation to the problem domain.
 
+use std::{ops, path::Path};
+
+use anyhow::Context as _;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_prover_interface::inputs::{PrepareBasicCircuitsJob, StorageLogMetadata};
@@ -7,10 +10,10 @@ use zksync_types::{L1BatchNumber, StorageKey};
 
 use crate::{
     consistency::ConsistencyError,
-    storage::{PatchSet, Patched, RocksDBWrapper},
+    storage::{PatchSet, Patched, RocksDBWrapper, TreeSnapshotManifest},
     types::{
-        Key, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry, ValueHash,
-        TREE_DEPTH,
+        Key, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry, TreeRangeProof,
+        ValueHash, TREE_DEPTH,
     },
     BlockOutput, HashTree, MerkleTree, MerkleTreePruner, MerkleTreePrunerHandle, NoVersionError,
 };
@@ -105,6 +108,28 @@ impl ZkSyncTree {
         Self::new_with_mode(db, TreeMode::Lightweight)
     }
 
+    /// Imports a tree snapshot created with [`ZkSyncTreeReader::export_snapshot()`] into an empty database.
+    /// Returns the L1 batch number of the imported snapshot; after the import, a tree can be created
+    /// for `db` in any processing mode.
+    ///
+    /// The snapshot root hash is checked against `expected_root_hash`, which should be obtained from a trusted source
+    /// (e.g., the L1 batch header).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the database is not empty, the snapshot is malformed or doesn't match the expected root hash.
+    pub fn import_snapshot(
+        db: &RocksDBWrapper,
+        snapshot_dir: &Path,
+        expected_root_hash: ValueHash,
+    ) -> anyhow::Result<L1BatchNumber> {
+        let mut tree = MerkleTree::new(db.clone())?;
+        let manifest = tree.import_snapshot(snapshot_dir, Some(expected_root_hash))?;
+        let l1_batch_number =
+            u32::try_from(manifest.version).context("integer overflow for L1 batch number")?;
+        Ok(L1BatchNumber(l1_batch_number))
+    }
+
     fn new_with_mode(db: RocksDBWrapper, mode: TreeMode) -> anyhow::Result<Self> {
         Ok(Self {
             tree: MerkleTree::new(Patched::new(db))?,
@@ -441,6 +466,40 @@ impl ZkSyncTreeReader {
         self.0.entries_with_proofs(version, keys)
     }
 
+    /// Creates a Merkle range proof enumerating all entries in the specified range of hashed keys
+    /// (both bounds are inclusive), e.g. one returned by `uniform_hashed_keys_chunk()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of `key_range` is greater than its end.
+    pub fn range_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        key_range: ops::RangeInclusive<Key>,
+    ) -> Result<TreeRangeProof, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.range_proof(version, key_range)
+    }
+
+    /// Exports the tree at the specified L1 batch number into a snapshot in `output_dir`.
+    /// The snapshot can be imported using [`ZkSyncTree::import_snapshot()`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the tree version is missing or on I/O errors.
+    pub fn export_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        output_dir: &Path,
+    ) -> anyhow::Result<TreeSnapshotManifest> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.export_snapshot(version, output_dir)
+    }
+
     /// Verifies consistency of the tree at the specified L1 batch number.
     ///
     /// # Errors
@@ -453,4 +512,21 @@ impl ZkSyncTreeReader {
         let version = l1_batch_number.0.into();
         self.0.verify_consistency(version, true)
     }
+
+    /// Verifies consistency of a subtree of the tree at the specified L1 batch number.
+    /// See [`MerkleTree::verify_subtree_consistency()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first encountered verification error, should one occur.
+    pub fn verify_subtree_consistency(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        key: Key,
+        nibble_count: usize,
+    ) -> Result<(), ConsistencyError> {
+        let version = l1_batch_number.0.into();
+        self.0
+            .verify_subtree_consistency(version, key, nibble_count)
+    }
 }
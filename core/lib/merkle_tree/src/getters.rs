@@ -1,10 +1,14 @@
 //! Getters for the Merkle tree.
 
+use std::ops;
+
 use crate::{
     hasher::HasherWithStats,
     recovery::MerkleTreeRecovery,
     storage::{LoadAncestorsResult, SortedKeys, WorkingPatchSet},
-    types::{Nibbles, Node, ProfiledTreeOperation, TreeEntry, TreeEntryWithProof},
+    types::{
+        Nibbles, Node, ProfiledTreeOperation, Root, TreeEntry, TreeEntryWithProof, TreeRangeProof,
+    },
     Database, HashTree, Key, MerkleTree, NoVersionError, PruneDatabase, ValueHash,
 };
 
@@ -59,6 +63,130 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
             },
         )
     }
+
+    /// Creates a Merkle range proof for the specified key range (both bounds are inclusive).
+    /// The proof enumerates all existing entries in the range and can be verified using [`TreeRangeProof::verify()`].
+    ///
+    /// All entries in the range are loaded into memory, so the range should be reasonably small
+    /// (e.g., correspond to a single snapshot chunk).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of `key_range` is greater than its end.
+    pub fn range_proof(
+        &self,
+        version: u64,
+        key_range: ops::RangeInclusive<Key>,
+    ) -> Result<TreeRangeProof, NoVersionError> {
+        let (start_key, end_key) = key_range.into_inner();
+        assert!(
+            start_key <= end_key,
+            "Invalid key range: start key {start_key:?} is greater than end key {end_key:?}"
+        );
+
+        let (start, end) = if start_key == end_key {
+            let mut bounds = self.entries_with_proofs(version, &[start_key])?;
+            let start = bounds.pop().unwrap();
+            // ^ `unwrap()` is safe: `entries_with_proofs()` returns an entry for each requested key
+            (start.clone(), start)
+        } else {
+            let mut bounds = self.entries_with_proofs(version, &[start_key, end_key])?;
+            let end = bounds.pop().unwrap();
+            let start = bounds.pop().unwrap();
+            (start, end)
+        };
+
+        let _profiling_guard = self
+            .db
+            .start_profiling(ProfiledTreeOperation::GetRangeProof);
+        let root = load_root(&self.db, version)?;
+        let entries = load_entries_in_range(&self.db, root, start_key, end_key);
+        Ok(TreeRangeProof {
+            start,
+            entries,
+            end,
+        })
+    }
+}
+
+fn load_root(db: &impl Database, version: u64) -> Result<Root, NoVersionError> {
+    db.root(version).ok_or_else(|| {
+        let manifest = db.manifest().unwrap_or_default();
+        NoVersionError {
+            missing_version: version,
+            version_count: manifest.version_count,
+        }
+    })
+}
+
+/// Loads all existing entries with keys strictly between `start_key` and `end_key`, ordered by key.
+///
+/// Works similarly to [`WorkingPatchSet::load_ancestors()`]: the tree is traversed level by level,
+/// and nodes on each level are loaded using [`Database::tree_nodes()`]. Only subtrees intersecting
+/// with the key range are visited.
+fn load_entries_in_range(
+    db: &impl Database,
+    root: Root,
+    start_key: Key,
+    end_key: Key,
+) -> Vec<TreeEntry> {
+    let is_in_range = |key: &Key| *key > start_key && *key < end_key;
+    let mut entries = vec![];
+    let mut level = match root {
+        Root::Empty => vec![],
+        Root::Filled {
+            node: Node::Leaf(leaf),
+            ..
+        } => {
+            if is_in_range(&leaf.full_key) {
+                entries.push(leaf.into());
+            }
+            vec![]
+        }
+        Root::Filled {
+            node: Node::Internal(node),
+            ..
+        } => vec![(Nibbles::EMPTY, node)],
+    };
+
+    while !level.is_empty() {
+        let mut requested_keys = vec![];
+        for (nibbles, node) in &level {
+            let child_nibble_count = nibbles.nibble_count() + 1;
+            let min_nibbles = Nibbles::new(&start_key, child_nibble_count);
+            let max_nibbles = Nibbles::new(&end_key, child_nibble_count);
+            for (nibble, child_ref) in node.children() {
+                let child_nibbles = nibbles.push(nibble).unwrap();
+                // ^ `unwrap()` is safe: internal nodes cannot reside at the lowest tree level
+                if (min_nibbles..=max_nibbles).contains(&child_nibbles) {
+                    let child_key = child_nibbles.with_version(child_ref.version);
+                    requested_keys.push((child_key, child_ref.is_leaf));
+                }
+            }
+        }
+
+        let new_nodes = db.tree_nodes(&requested_keys);
+        level.clear();
+        for ((key, _), node) in requested_keys.into_iter().zip(new_nodes) {
+            match node.unwrap() {
+                // ^ `unwrap()` is safe: all requested nodes are referenced by their parents
+                Node::Leaf(leaf) => {
+                    if is_in_range(&leaf.full_key) {
+                        entries.push(leaf.into());
+                    }
+                }
+                Node::Internal(node) => level.push((key.nibbles, node)),
+            }
+        }
+    }
+
+    // Leaves are collected level by level, so they are not necessarily ordered by key.
+    entries.sort_unstable_by_key(|entry: &TreeEntry| entry.key);
+    entries
 }
 
 fn load_and_transform_entries<T>(
@@ -67,13 +195,7 @@ fn load_and_transform_entries<T>(
     leaf_keys: &[Key],
     mut transform: impl FnMut(&mut WorkingPatchSet, &Key, &Nibbles) -> T,
 ) -> Result<Vec<T>, NoVersionError> {
-    let root = db.root(version).ok_or_else(|| {
-        let manifest = db.manifest().unwrap_or_default();
-        NoVersionError {
-            missing_version: version,
-            version_count: manifest.version_count,
-        }
-    })?;
+    let root = load_root(db, version)?;
     let sorted_keys = SortedKeys::new(leaf_keys.iter().copied());
     let mut patch_set = WorkingPatchSet::new(version, root);
     let LoadAncestorsResult {
@@ -115,8 +237,22 @@ impl<DB: PruneDatabase, H: HashTree> MerkleTreeRecovery<DB, H> {
 
 #[cfg(test)]
 mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use zksync_types::{snapshots::uniform_hashed_keys_chunk, U256};
+
     use super::*;
-    use crate::PatchSet;
+    use crate::{BlockOutput, PatchSet};
+
+    fn create_tree_with_random_entries(count: u64) -> (MerkleTree<PatchSet>, BlockOutput) {
+        let mut rng = StdRng::seed_from_u64(123);
+        let entries = (1..=count).map(|leaf_index| {
+            let key = U256([rng.gen(), rng.gen(), rng.gen(), rng.gen()]);
+            TreeEntry::new(key, leaf_index, ValueHash::from_low_u64_be(leaf_index))
+        });
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let output = tree.extend(entries.collect()).unwrap();
+        (tree, output)
+    }
 
     #[test]
     fn entries_in_empty_tree() {
@@ -155,4 +291,137 @@ mod tests {
         assert!(entries[1].base.is_empty());
         entries[1].verify(&tree.hasher, output.root_hash);
     }
+
+    #[test]
+    fn range_proofs_in_empty_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        tree.extend(vec![]).unwrap();
+
+        let proof = tree.range_proof(0, Key::zero()..=Key::MAX).unwrap();
+        assert!(proof.start.base.is_empty());
+        assert!(proof.entries.is_empty());
+        assert!(proof.end.base.is_empty());
+        proof
+            .verify(&tree.hasher, tree.hasher.empty_tree_hash())
+            .unwrap();
+    }
+
+    #[test]
+    fn range_proofs_in_single_node_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let key = Key::from(987_654);
+        let entry = TreeEntry::new(key, 1, ValueHash::repeat_byte(1));
+        let output = tree.extend(vec![entry]).unwrap();
+
+        let proof = tree.range_proof(0, Key::zero()..=Key::MAX).unwrap();
+        assert_eq!(proof.entries, [entry]);
+        proof.verify(&tree.hasher, output.root_hash).unwrap();
+
+        let proof = tree.range_proof(0, key..=Key::MAX).unwrap();
+        assert!(proof.entries.is_empty());
+        assert_eq!(proof.existing_entries().collect::<Vec<_>>(), [&entry]);
+        proof.verify(&tree.hasher, output.root_hash).unwrap();
+
+        let proof = tree.range_proof(0, key..=key).unwrap();
+        assert_eq!(proof.existing_entries().collect::<Vec<_>>(), [&entry]);
+        proof.verify(&tree.hasher, output.root_hash).unwrap();
+
+        let proof = tree.range_proof(0, Key::zero()..=Key::from(1)).unwrap();
+        assert_eq!(proof.existing_entries().count(), 0);
+        proof.verify(&tree.hasher, output.root_hash).unwrap();
+    }
+
+    #[test]
+    fn range_proofs_for_uniform_chunks() {
+        const CHUNK_COUNT: u64 = 8;
+
+        let (tree, output) = create_tree_with_random_entries(200);
+        let mut all_entries = vec![];
+        for chunk_id in 0..CHUNK_COUNT {
+            let chunk = uniform_hashed_keys_chunk(chunk_id, CHUNK_COUNT);
+            let start_key = Key::from_big_endian(chunk.start().as_bytes());
+            let end_key = Key::from_big_endian(chunk.end().as_bytes());
+
+            let proof = tree.range_proof(0, start_key..=end_key).unwrap();
+            assert_eq!(proof.key_range(), start_key..=end_key);
+            proof.verify(&tree.hasher, output.root_hash).unwrap();
+            all_entries.extend(proof.existing_entries().copied());
+        }
+
+        assert_eq!(all_entries.len(), 200);
+        assert!(all_entries
+            .windows(2)
+            .all(|window| window[0].key < window[1].key));
+        let mut leaf_indices: Vec<_> = all_entries.iter().map(|entry| entry.leaf_index).collect();
+        leaf_indices.sort_unstable();
+        assert_eq!(leaf_indices, (1..=200).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn range_proofs_with_existing_bounds() {
+        let (tree, output) = create_tree_with_random_entries(100);
+        let all_entries = tree.range_proof(0, Key::zero()..=Key::MAX).unwrap().entries;
+        assert_eq!(all_entries.len(), 100);
+
+        for (start_idx, end_idx) in [(0, 99), (10, 20), (50, 51), (42, 42)] {
+            let (start_key, end_key) = (all_entries[start_idx].key, all_entries[end_idx].key);
+            let proof = tree.range_proof(0, start_key..=end_key).unwrap();
+            assert_eq!(proof.start.base, all_entries[start_idx]);
+            assert_eq!(proof.end.base, all_entries[end_idx]);
+            let inner_entries = all_entries.get(start_idx + 1..end_idx).unwrap_or_default();
+            assert_eq!(proof.entries, inner_entries);
+            proof.verify(&tree.hasher, output.root_hash).unwrap();
+        }
+    }
+
+    #[test]
+    fn incomplete_or_tampered_range_proofs_are_rejected() {
+        let (tree, output) = create_tree_with_random_entries(100);
+        let chunk = uniform_hashed_keys_chunk(1, 4);
+        let start_key = Key::from_big_endian(chunk.start().as_bytes());
+        let end_key = Key::from_big_endian(chunk.end().as_bytes());
+        let proof = tree.range_proof(0, start_key..=end_key).unwrap();
+        assert!(proof.entries.len() > 2);
+        proof.verify(&tree.hasher, output.root_hash).unwrap();
+
+        let mut incomplete_proof = proof.clone();
+        incomplete_proof.entries.remove(1);
+        incomplete_proof
+            .verify(&tree.hasher, output.root_hash)
+            .unwrap_err();
+
+        let mut tampered_proof = proof.clone();
+        tampered_proof.entries[0].value = ValueHash::repeat_byte(0xff);
+        tampered_proof
+            .verify(&tree.hasher, output.root_hash)
+            .unwrap_err();
+
+        let mut reordered_proof = proof.clone();
+        reordered_proof.entries.swap(0, 1);
+        let err = reordered_proof
+            .verify(&tree.hasher, output.root_hash)
+            .unwrap_err();
+        assert!(err.to_string().contains("not strictly increasing"), "{err}");
+
+        let mut proof_with_empty_entry = proof.clone();
+        let empty_key = proof.entries[0].key + 1;
+        proof_with_empty_entry
+            .entries
+            .insert(1, TreeEntry::empty(empty_key));
+        let err = proof_with_empty_entry
+            .verify(&tree.hasher, output.root_hash)
+            .unwrap_err();
+        assert!(err.to_string().contains("is empty"), "{err}");
+
+        let first_key = proof.entries[0].key;
+        let mut truncated_proof = proof;
+        truncated_proof.end = tree
+            .entries_with_proofs(0, &[first_key])
+            .unwrap()
+            .pop()
+            .unwrap();
+        truncated_proof
+            .verify(&tree.hasher, output.root_hash)
+            .unwrap_err();
+    }
 }
@@ -3,7 +3,7 @@
 use std::{fmt, iter};
 
 use once_cell::sync::Lazy;
-use zksync_crypto::hasher::{blake2::Blake2Hasher, Hasher};
+use zksync_crypto::hasher::{blake2::Blake2Hasher, keccak::KeccakHasher, Hasher};
 
 pub(crate) use self::nodes::{InternalNodeCache, MerklePath};
 pub use self::proofs::TreeRangeDigest;
@@ -111,16 +111,21 @@ impl HashTree for () {
     }
 }
 
+/// Returns the hashed bytes for a leaf: `u64::to_be_bytes(leaf_index) ++ value_hash`.
+fn leaf_bytes(value_hash: &ValueHash, leaf_index: u64) -> [u8; 40] {
+    let mut bytes = [0_u8; 40];
+    bytes[..8].copy_from_slice(&leaf_index.to_be_bytes());
+    bytes[8..].copy_from_slice(value_hash.as_ref());
+    bytes
+}
+
 impl HashTree for Blake2Hasher {
     fn name(&self) -> &'static str {
         "blake2s256"
     }
 
     fn hash_leaf(&self, value_hash: &ValueHash, leaf_index: u64) -> ValueHash {
-        let mut bytes = [0_u8; 40];
-        bytes[..8].copy_from_slice(&leaf_index.to_be_bytes());
-        bytes[8..].copy_from_slice(value_hash.as_ref());
-        self.hash_bytes(&bytes)
+        self.hash_bytes(&leaf_bytes(value_hash, leaf_index))
     }
 
     /// Compresses the hashes of 2 children in a branch node.
@@ -130,15 +135,39 @@ impl HashTree for Blake2Hasher {
 
     /// Returns the hash of an empty subtree with the given depth.
     fn empty_subtree_hash(&self, depth: usize) -> ValueHash {
-        static EMPTY_TREE_HASHES: Lazy<Vec<ValueHash>> = Lazy::new(compute_empty_tree_hashes);
+        static EMPTY_TREE_HASHES: Lazy<Vec<ValueHash>> =
+            Lazy::new(|| compute_empty_tree_hashes(&Blake2Hasher));
         EMPTY_TREE_HASHES[depth]
     }
 }
 
-fn compute_empty_tree_hashes() -> Vec<ValueHash> {
-    let empty_leaf_hash = Blake2Hasher.hash_bytes(&[0_u8; 40]);
+/// Keccak-256 hasher. Uses the same hashing scheme as [`Blake2Hasher`] (i.e., the same leaf and branch
+/// preimages); only the hash function is different. Trees using this hasher are incompatible with
+/// the default Blake2s-based trees, which is enforced via tree tags.
+impl HashTree for KeccakHasher {
+    fn name(&self) -> &'static str {
+        "keccak256"
+    }
+
+    fn hash_leaf(&self, value_hash: &ValueHash, leaf_index: u64) -> ValueHash {
+        self.hash_bytes(&leaf_bytes(value_hash, leaf_index))
+    }
+
+    fn hash_branch(&self, lhs: &ValueHash, rhs: &ValueHash) -> ValueHash {
+        self.compress(lhs, rhs)
+    }
+
+    fn empty_subtree_hash(&self, depth: usize) -> ValueHash {
+        static EMPTY_TREE_HASHES: Lazy<Vec<ValueHash>> =
+            Lazy::new(|| compute_empty_tree_hashes(&KeccakHasher));
+        EMPTY_TREE_HASHES[depth]
+    }
+}
+
+fn compute_empty_tree_hashes<H: Hasher<Hash = ValueHash>>(hasher: &H) -> Vec<ValueHash> {
+    let empty_leaf_hash = hasher.hash_bytes(&[0_u8; 40]);
     iter::successors(Some(empty_leaf_hash), |hash| {
-        Some(Blake2Hasher.hash_branch(hash, hash))
+        Some(hasher.compress(hash, hash))
     })
     .take(TREE_DEPTH + 1)
     .collect()
@@ -264,6 +293,38 @@ mod tests {
         assert_eq!(folded_hash, EXPECTED_HASH);
     }
 
+    #[test]
+    fn keccak_hashes_are_as_expected() {
+        const EXPECTED_EMPTY_LEAF_HASH: ValueHash = H256([
+            218, 167, 116, 38, 195, 12, 2, 164, 61, 159, 186, 78, 132, 26, 101, 86, 197, 36, 212,
+            112, 48, 118, 46, 177, 77, 196, 175, 137, 126, 96, 93, 155,
+        ]);
+        const EXPECTED_EMPTY_TREE_HASH: ValueHash = H256([
+            0, 195, 141, 232, 78, 14, 102, 105, 172, 246, 15, 175, 244, 94, 216, 180, 123, 246,
+            192, 92, 77, 105, 112, 199, 66, 48, 64, 244, 77, 100, 209, 17,
+        ]);
+        const EXPECTED_LEAF_HASH: ValueHash = H256([
+            56, 24, 33, 202, 80, 229, 233, 13, 35, 180, 168, 158, 105, 91, 47, 211, 255, 39, 37,
+            174, 74, 181, 100, 146, 50, 81, 12, 6, 218, 55, 27, 83,
+        ]);
+
+        let hasher: &dyn HashTree = &KeccakHasher;
+        assert_eq!(hasher.name(), "keccak256");
+        assert_eq!(hasher.empty_subtree_hash(0), EXPECTED_EMPTY_LEAF_HASH);
+        assert_eq!(hasher.empty_tree_hash(), EXPECTED_EMPTY_TREE_HASH);
+
+        let address: Address = "4b3af74f66ab1f0da3f2e4ec7a3cb99baf1af7b2".parse().unwrap();
+        let key = StorageKey::new(AccountTreeId::new(address), H256::zero());
+        let key = key.hashed_key_u256();
+        let leaf = LeafNode::new(TreeEntry::new(key, 1, H256([1; 32])));
+
+        let mut hasher_with_stats = HasherWithStats::new(hasher);
+        let leaf_hash = leaf.hash(&mut hasher_with_stats, 0);
+        assert_eq!(leaf_hash, EXPECTED_LEAF_HASH);
+        let folded_hash = hasher.fold_merkle_path(&[], leaf.into());
+        assert_eq!(folded_hash, EXPECTED_LEAF_HASH);
+    }
+
     #[test]
     fn folding_merkle_path() {
         let address: Address = "4b3af74f66ab1f0da3f2e4ec7a3cb99baf1af7b2".parse().unwrap();
@@ -8,7 +8,7 @@ use crate::{
     hasher::{HashTree, HasherWithStats},
     types::{
         BlockOutputWithProofs, Key, LeafNode, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, ValueHash, TREE_DEPTH,
+        TreeLogEntry, TreeRangeProof, ValueHash, TREE_DEPTH,
     },
     utils,
 };
@@ -96,6 +96,82 @@ impl TreeEntryWithProof {
     }
 }
 
+impl TreeRangeProof {
+    /// Verifies this range proof against the trusted root hash of the tree. If the proof verifies,
+    /// the tree contains exactly [`Self::existing_entries()`] in the [`Self::key_range()`].
+    ///
+    /// # Errors
+    ///
+    /// As the errors are not actionable, a string error with the failing condition is returned.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        let (start, end) = (&self.start, &self.end);
+        ensure!(
+            start.base.key <= end.base.key,
+            "Condition failed: `start.base.key <= end.base.key` ({:?} vs {:?})",
+            start.base.key,
+            end.base.key
+        );
+        for bound in [start, end] {
+            ensure!(bound.merkle_path.len() <= TREE_DEPTH);
+            if bound.base.leaf_index == 0 {
+                ensure!(
+                    bound.base.value.is_zero(),
+                    "Invalid missing value specification for key {:?}: leaf index is zero, but value is non-default",
+                    bound.base.key
+                );
+            }
+        }
+
+        let mut prev_key = start.base.key;
+        for entry in &self.entries {
+            ensure!(
+                entry.key > prev_key,
+                "Entry keys are not strictly increasing: {:?} follows {prev_key:?}",
+                entry.key
+            );
+            ensure!(
+                entry.key < end.base.key,
+                "Entry key {:?} is outside the proven range",
+                entry.key
+            );
+            ensure!(
+                entry.leaf_index > 0,
+                "Entry with key {:?} is empty; only existing entries must be provided",
+                entry.key
+            );
+            prev_key = entry.key;
+        }
+
+        let root_hash = if start.base.key == end.base.key {
+            ensure!(
+                start.base == end.base,
+                "Condition failed: `start.base == end.base` ({:?} vs {:?})",
+                start.base,
+                end.base
+            );
+            ensure!(start.merkle_path == end.merkle_path);
+            hasher.fold_merkle_path(&start.merkle_path, start.base)
+        } else {
+            let mut digest = TreeRangeDigest::new(hasher, start.base.key, start);
+            for &entry in &self.entries {
+                digest.update(entry);
+            }
+            digest.finalize(end)
+        };
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Condition failed: `root_hash == trusted_root_hash` ({:?} vs {:?})",
+            root_hash,
+            trusted_root_hash
+        );
+        Ok(())
+    }
+}
+
 /// Range digest in a Merkle tree allowing to compute its root hash based on the provided entries.
 ///
 /// - The entries must be ordered by key. I.e., the first entry must have the numerically smallest key,
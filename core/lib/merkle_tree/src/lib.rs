@@ -17,6 +17,8 @@
 //! implementations:
 //!
 //! - [`Blake2Hasher`] is the main implementation based on Blake2s-256
+//! - [`KeccakHasher`](zksync_crypto::hasher::keccak::KeccakHasher) is an alternative implementation
+//!   based on Keccak-256. It is not used in zksync, but can be useful for experimenting with tree variants.
 //! - `()` provides a no-op implementation useful for benchmarking.
 //!
 //! # Tree hashing specification
@@ -24,7 +26,7 @@
 //! A tree is hashed as if it was a full binary Merkle tree with `2^256` leaves:
 //!
 //! - Hash of a vacant leaf is `hash([0_u8; 40])`, where `hash` is the hash function used
-//!   (Blake2s-256).
+//!   (Blake2s-256 by default).
 //! - Hash of an occupied leaf is `hash(u64::to_be_bytes(leaf_index) ++ value_hash)`,
 //!   where `leaf_index` is a 1-based index of the leaf key provided when the leaf is inserted / updated,
 //!   `++` is byte concatenation.
@@ -51,14 +53,14 @@ use zksync_crypto::hasher::blake2::Blake2Hasher;
 pub use crate::{
     errors::NoVersionError,
     hasher::{HashTree, TreeRangeDigest},
-    pruning::{MerkleTreePruner, MerkleTreePrunerHandle},
+    pruning::{CheckpointSchedule, MerkleTreePruner, MerkleTreePrunerHandle},
     storage::{
         Database, MerkleTreeColumnFamily, PatchSet, Patched, PruneDatabase, PrunePatchSet,
-        RocksDBWrapper,
+        RocksDBWrapper, TreeSnapshotChunk, TreeSnapshotManifest,
     },
     types::{
         BlockOutput, BlockOutputWithProofs, Key, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, TreeLogEntryWithProof, ValueHash,
+        TreeLogEntry, TreeLogEntryWithProof, TreeRangeProof, ValueHash,
     },
 };
 use crate::{storage::Storage, types::Root};
@@ -318,6 +318,9 @@ struct PruningMetrics {
     /// Number of pruned node keys on a specific pruning iteration.
     #[metrics(buckets = NODE_COUNT_BUCKETS)]
     key_count: Histogram<usize>,
+    /// Number of stale node keys retained for tree checkpoints on a specific pruning iteration.
+    #[metrics(buckets = NODE_COUNT_BUCKETS)]
+    checkpointed_key_count: Histogram<usize>,
     /// Lower and upper boundaries on the new stale key versions deleted
     /// during a pruning iteration. The lower boundary is inclusive, the upper one is exclusive.
     deleted_stale_key_versions: Family<Bound, Gauge<u64>>,
@@ -330,7 +333,9 @@ static PRUNING_METRICS: Global<PruningMetrics> = Global::new();
 pub struct PruningStats {
     pub target_retained_version: u64,
     pub pruned_key_count: usize,
+    pub checkpointed_key_count: usize,
     pub deleted_stale_key_versions: ops::Range<u64>,
+    pub deleted_checkpointed_key_versions: ops::Range<u64>,
 }
 
 impl PruningStats {
@@ -339,10 +344,15 @@ impl PruningStats {
             .target_retained_version
             .set(self.target_retained_version);
         PRUNING_METRICS.key_count.observe(self.pruned_key_count);
-        PRUNING_METRICS.deleted_stale_key_versions[&Bound::Start]
-            .set(self.deleted_stale_key_versions.start);
-        PRUNING_METRICS.deleted_stale_key_versions[&Bound::End]
-            .set(self.deleted_stale_key_versions.end);
+        PRUNING_METRICS
+            .checkpointed_key_count
+            .observe(self.checkpointed_key_count);
+        if !self.deleted_stale_key_versions.is_empty() {
+            PRUNING_METRICS.deleted_stale_key_versions[&Bound::Start]
+                .set(self.deleted_stale_key_versions.start);
+            PRUNING_METRICS.deleted_stale_key_versions[&Bound::End]
+                .set(self.deleted_stale_key_versions.end);
+        }
     }
 }
 
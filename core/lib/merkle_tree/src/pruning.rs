@@ -2,6 +2,8 @@
 
 use std::{
     fmt,
+    num::NonZeroU64,
+    ops,
     sync::{
         atomic::{AtomicU64, Ordering},
         mpsc, Arc, Weak,
@@ -12,6 +14,7 @@ use std::{
 use crate::{
     metrics::{PruningStats, PRUNING_TIMINGS},
     storage::{PruneDatabase, PrunePatchSet},
+    types::NodeKey,
 };
 
 /// Error returned by [`MerkleTreePrunerHandle::set_target_retained_version()`].
@@ -52,6 +55,96 @@ impl MerkleTreePrunerHandle {
     }
 }
 
+/// Schedule of tree *checkpoints*, i.e. versions of the tree exempt from pruning.
+///
+/// A checkpoint is created for each version divisible by the [interval](Self::interval()); e.g., for interval 1,000,
+/// checkpoints are versions 0, 1,000, 2,000 etc. Checkpoints are retained either indefinitely, or for a certain
+/// number of versions after the checkpoint (see [`Self::with_retention()`]).
+///
+/// Note that the schedule only influences versions pruned after it was set; already pruned versions cannot be restored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointSchedule {
+    interval: NonZeroU64,
+    retention: Option<u64>,
+}
+
+impl CheckpointSchedule {
+    /// Creates a schedule with checkpoints at each `interval`-th version, which are retained indefinitely.
+    pub fn new(interval: NonZeroU64) -> Self {
+        Self {
+            interval,
+            retention: None,
+        }
+    }
+
+    /// Sets the retention period for checkpoints. A checkpoint at version `V` will be pruned once the pruner
+    /// is instructed to retain version `V + retention + 1` or a greater one.
+    #[must_use]
+    pub fn with_retention(mut self, retention: u64) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    /// Returns the interval between checkpoints.
+    pub fn interval(&self) -> NonZeroU64 {
+        self.interval
+    }
+
+    /// Returns the retention period for checkpoints, or `None` if checkpoints are retained indefinitely.
+    pub fn retention(&self) -> Option<u64> {
+        self.retention
+    }
+
+    /// Checks whether the specified version is a checkpoint. Does not take retention into account.
+    pub fn is_checkpoint(&self, version: u64) -> bool {
+        version % self.interval == 0
+    }
+
+    /// Returns the latest checkpoint that contains a node created in `node_version` and replaced in `replaced_in_version`.
+    fn last_checkpoint_containing(
+        &self,
+        node_version: u64,
+        replaced_in_version: u64,
+    ) -> Option<u64> {
+        let last_version = replaced_in_version.checked_sub(1)?;
+        let checkpoint = last_version - last_version % self.interval;
+        (checkpoint >= node_version).then_some(checkpoint)
+    }
+
+    /// Determines whether a stale node should be retained when pruning up to `target_retained_version`.
+    fn retention_for(
+        &self,
+        stale_key: &NodeKey,
+        replaced_in_version: u64,
+        target_retained_version: u64,
+    ) -> KeyRetention {
+        let Some(checkpoint) =
+            self.last_checkpoint_containing(stale_key.version, replaced_in_version)
+        else {
+            return KeyRetention::Prune;
+        };
+        let Some(retention) = self.retention else {
+            return KeyRetention::Forever;
+        };
+        // Saturating ops are fine: the resulting expiration version would never be reached in practice.
+        let expiration = checkpoint.saturating_add(retention).saturating_add(1);
+        if expiration > target_retained_version {
+            KeyRetention::UntilVersion(expiration)
+        } else {
+            KeyRetention::Prune
+        }
+    }
+}
+
+/// Retention of a stale node key when pruning with a [`CheckpointSchedule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyRetention {
+    Prune,
+    /// The key should be pruned once the pruner's target retained version reaches the specified value.
+    UntilVersion(u64),
+    Forever,
+}
+
 /// Component responsible for Merkle tree pruning, i.e. removing nodes not referenced by new versions
 /// of the tree. A pruner should be instantiated using a [`Clone`] of the tree database, possibly
 /// configured and then [`run()`](Self::run()) on its own thread. [`MerkleTreePrunerHandle`] provides
@@ -64,10 +157,15 @@ impl MerkleTreePrunerHandle {
 /// by a certain range of tree versions, and removes the corresponding nodes from the tree
 /// (in RocksDB, this uses simple pointwise `delete_cf()` operations). The range of versions
 /// depends on pruning policies; for now, it's passed via the pruner handle.
+///
+/// If a [`CheckpointSchedule`] is set, stale keys belonging to checkpoints are not removed. Instead, they are
+/// either retained indefinitely, or moved to a separate column family (checkpointed keys) keyed by the version
+/// after which they can be removed.
 pub struct MerkleTreePruner<DB> {
     db: DB,
     target_pruned_key_count: usize,
     poll_interval: Duration,
+    checkpoints: Option<CheckpointSchedule>,
     aborted_receiver: mpsc::Receiver<()>,
     target_retained_version: Arc<AtomicU64>,
 }
@@ -78,6 +176,7 @@ impl<DB> fmt::Debug for MerkleTreePruner<DB> {
             .debug_struct("MerkleTreePruner")
             .field("target_pruned_key_count", &self.target_pruned_key_count)
             .field("poll_interval", &self.poll_interval)
+            .field("checkpoints", &self.checkpoints)
             .field("target_retained_version", &self.target_retained_version)
             .finish_non_exhaustive()
     }
@@ -100,6 +199,7 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
             db,
             target_pruned_key_count: 500_000,
             poll_interval: Duration::from_secs(60),
+            checkpoints: None,
             aborted_receiver,
             target_retained_version,
         };
@@ -123,6 +223,11 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
         self.poll_interval = poll_interval;
     }
 
+    /// Sets the schedule of tree checkpoints exempt from pruning. By default, no checkpoints are retained.
+    pub fn set_checkpoint_schedule(&mut self, schedule: CheckpointSchedule) {
+        self.checkpoints = Some(schedule);
+    }
+
     /// Returns max version number that can be safely pruned, so that there is at least one version present after pruning.
     #[doc(hidden)] // Used in integration tests; logically private
     pub fn last_prunable_version(&self) -> Option<u64> {
@@ -131,65 +236,134 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
     }
 
     #[doc(hidden)] // Used in integration tests; logically private
-    #[allow(clippy::range_plus_one)] // exclusive range is required by `PrunePatchSet` constructor
     pub fn prune_up_to(
         &mut self,
         target_retained_version: u64,
     ) -> anyhow::Result<Option<PruningStats>> {
-        let Some(min_stale_key_version) = self.db.min_stale_key_version() else {
-            return Ok(None);
-        };
-
         // We must retain at least one tree version.
         let Some(last_prunable_version) = self.last_prunable_version() else {
             tracing::debug!("Nothing to prune; skipping");
             return Ok(None);
         };
         let target_retained_version = last_prunable_version.min(target_retained_version);
-        let stale_key_new_versions = min_stale_key_version..=target_retained_version;
-        if stale_key_new_versions.is_empty() {
-            tracing::debug!(
-                "No Merkle tree versions can be pruned; min stale key version is {min_stale_key_version}, \
-                 target retained version is {target_retained_version}"
-            );
-            return Ok(None);
-        }
-        tracing::info!("Collecting stale keys with new versions in {stale_key_new_versions:?}");
 
         let load_stale_keys_latency = PRUNING_TIMINGS.load_stale_keys.start();
-        let mut pruned_keys = vec![];
-        let mut max_stale_key_version = min_stale_key_version;
-        for version in stale_key_new_versions {
-            max_stale_key_version = version;
-            pruned_keys.extend_from_slice(&self.db.stale_keys(version));
-            if pruned_keys.len() >= self.target_pruned_key_count {
-                break;
-            }
-        }
+        let mut collected_keys = CollectedKeys::default();
+        let deleted_stale_key_versions =
+            self.collect_stale_keys(target_retained_version, &mut collected_keys);
+        let deleted_checkpointed_key_versions =
+            self.collect_expired_checkpointed_keys(target_retained_version, &mut collected_keys);
         load_stale_keys_latency.observe();
 
-        if pruned_keys.is_empty() {
+        if deleted_stale_key_versions.is_empty() && deleted_checkpointed_key_versions.is_empty() {
             tracing::debug!("No stale keys to remove; skipping");
             return Ok(None);
         }
-        let deleted_stale_key_versions = min_stale_key_version..(max_stale_key_version + 1);
         tracing::info!(
-            "Collected {} stale keys with new versions in {deleted_stale_key_versions:?}",
-            pruned_keys.len()
+            "Collected {} keys to prune and {} keys to retain for checkpoints; stale key versions: {deleted_stale_key_versions:?}, \
+             checkpointed key versions: {deleted_checkpointed_key_versions:?}",
+            collected_keys.pruned.len(),
+            collected_keys.checkpointed.len()
         );
 
         let stats = PruningStats {
             target_retained_version,
-            pruned_key_count: pruned_keys.len(),
+            pruned_key_count: collected_keys.pruned.len(),
+            checkpointed_key_count: collected_keys.checkpointed.len(),
             deleted_stale_key_versions: deleted_stale_key_versions.clone(),
+            deleted_checkpointed_key_versions: deleted_checkpointed_key_versions.clone(),
         };
-        let patch = PrunePatchSet::new(pruned_keys, deleted_stale_key_versions);
+        let patch = PrunePatchSet::new(collected_keys.pruned, deleted_stale_key_versions)
+            .with_checkpointed_keys(
+                collected_keys.checkpointed,
+                deleted_checkpointed_key_versions,
+            );
         let apply_patch_latency = PRUNING_TIMINGS.apply_patch.start();
         self.db.prune(patch)?;
         apply_patch_latency.observe();
         Ok(Some(stats))
     }
 
+    /// Collects stale keys, sorting them into pruned and retained for checkpoints. Returns the range of
+    /// processed stale key versions (empty if there are no keys to process).
+    #[allow(clippy::range_plus_one)] // exclusive range is required by `PrunePatchSet` constructor
+    fn collect_stale_keys(
+        &self,
+        target_retained_version: u64,
+        collected_keys: &mut CollectedKeys,
+    ) -> ops::Range<u64> {
+        let Some(min_stale_key_version) = self.db.min_stale_key_version() else {
+            return 0..0;
+        };
+        let stale_key_new_versions = min_stale_key_version..=target_retained_version;
+        if stale_key_new_versions.is_empty() {
+            tracing::debug!(
+                "No Merkle tree versions can be pruned; min stale key version is {min_stale_key_version}, \
+                 target retained version is {target_retained_version}"
+            );
+            return 0..0;
+        }
+        tracing::info!("Collecting stale keys with new versions in {stale_key_new_versions:?}");
+
+        let mut max_stale_key_version = min_stale_key_version;
+        for version in stale_key_new_versions {
+            max_stale_key_version = version;
+            let stale_keys = self.db.stale_keys(version);
+            if let Some(checkpoints) = &self.checkpoints {
+                for key in stale_keys {
+                    match checkpoints.retention_for(&key, version, target_retained_version) {
+                        KeyRetention::Prune => collected_keys.pruned.push(key),
+                        KeyRetention::UntilVersion(expiration) => {
+                            collected_keys.checkpointed.push((expiration, key));
+                        }
+                        // The key is removed from stale keys, so it will never be pruned.
+                        KeyRetention::Forever => {}
+                    }
+                }
+            } else {
+                collected_keys.pruned.extend_from_slice(&stale_keys);
+            }
+
+            if collected_keys.len() >= self.target_pruned_key_count {
+                break;
+            }
+        }
+        min_stale_key_version..(max_stale_key_version + 1)
+    }
+
+    /// Collects checkpointed keys that have expired. Returns the range of processed expiration versions
+    /// (empty if there are no keys to process).
+    #[allow(clippy::range_plus_one)] // exclusive range is required by `PrunePatchSet` constructor
+    fn collect_expired_checkpointed_keys(
+        &self,
+        target_retained_version: u64,
+        collected_keys: &mut CollectedKeys,
+    ) -> ops::Range<u64> {
+        if collected_keys.len() >= self.target_pruned_key_count {
+            return 0..0;
+        }
+        let Some(min_version) = self.db.min_checkpointed_key_version() else {
+            return 0..0;
+        };
+        let expired_versions = min_version..=target_retained_version;
+        if expired_versions.is_empty() {
+            return 0..0;
+        }
+        tracing::info!("Collecting checkpointed keys expired in {expired_versions:?}");
+
+        let mut max_version = min_version;
+        for version in expired_versions {
+            max_version = version;
+            collected_keys
+                .pruned
+                .extend(self.db.checkpointed_keys(version));
+            if collected_keys.len() >= self.target_pruned_key_count {
+                break;
+            }
+        }
+        min_version..(max_version + 1)
+    }
+
     fn wait_for_abort(&mut self, timeout: Duration) -> bool {
         match self.aborted_receiver.recv_timeout(timeout) {
             Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => true,
@@ -234,9 +408,27 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
     }
 }
 
+/// Keys collected during a single pruning iteration.
+#[derive(Debug, Default)]
+struct CollectedKeys {
+    pruned: Vec<NodeKey>,
+    /// Keys retained for checkpoints together with their expiration versions.
+    checkpointed: Vec<(u64, NodeKey)>,
+}
+
+impl CollectedKeys {
+    fn len(&self) -> usize {
+        self.pruned.len() + self.checkpointed.len()
+    }
+}
+
 impl PruningStats {
     fn has_more_work(&self) -> bool {
-        self.target_retained_version + 1 > self.deleted_stale_key_versions.end
+        let has_more_stale_keys = !self.deleted_stale_key_versions.is_empty()
+            && self.target_retained_version + 1 > self.deleted_stale_key_versions.end;
+        let has_more_checkpointed_keys = !self.deleted_checkpointed_key_versions.is_empty()
+            && self.target_retained_version + 1 > self.deleted_checkpointed_key_versions.end;
+        has_more_stale_keys || has_more_checkpointed_keys
     }
 }
 
@@ -247,7 +439,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        types::{Node, NodeKey},
+        types::{Nibbles, Node, NodeKey},
         Database, Key, MerkleTree, PatchSet, TreeEntry, ValueHash,
     };
 
@@ -381,6 +573,100 @@ mod tests {
         assert_no_stale_keys(&db, first_retained_version);
     }
 
+    fn create_db_with_root_hashes(version_count: u64) -> (PatchSet, Vec<ValueHash>) {
+        let mut db = PatchSet::default();
+        let mut tree = MerkleTree::new(&mut db).unwrap();
+        let kvs = generate_key_value_pairs(0..version_count * 10);
+        let root_hashes = kvs
+            .chunks(10)
+            .map(|chunk| tree.extend(chunk.to_vec()).unwrap().root_hash)
+            .collect();
+        (db, root_hashes)
+    }
+
+    #[test]
+    fn checkpoint_schedule_basics() {
+        let schedule = CheckpointSchedule::new(NonZeroU64::new(4).unwrap());
+        assert!(schedule.is_checkpoint(0));
+        assert!(schedule.is_checkpoint(8));
+        assert!(!schedule.is_checkpoint(7));
+
+        assert_eq!(schedule.last_checkpoint_containing(0, 1), Some(0));
+        assert_eq!(schedule.last_checkpoint_containing(1, 4), None);
+        assert_eq!(schedule.last_checkpoint_containing(1, 5), Some(4));
+        assert_eq!(schedule.last_checkpoint_containing(3, 10), Some(8));
+
+        let key = Nibbles::EMPTY.with_version(3);
+        assert_eq!(schedule.retention_for(&key, 10, 10), KeyRetention::Forever);
+        let schedule = schedule.with_retention(2);
+        assert_eq!(
+            schedule.retention_for(&key, 10, 10),
+            KeyRetention::UntilVersion(11)
+        );
+        assert_eq!(schedule.retention_for(&key, 10, 11), KeyRetention::Prune);
+        assert_eq!(schedule.retention_for(&key, 4, 10), KeyRetention::Prune);
+    }
+
+    #[test]
+    fn pruning_with_checkpoints() {
+        let (mut db, root_hashes) = create_db_with_root_hashes(10);
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db);
+        pruner.set_checkpoint_schedule(CheckpointSchedule::new(NonZeroU64::new(4).unwrap()));
+        let stats = pruner.prune_up_to(9).unwrap().expect("tree was not pruned");
+        assert!(stats.pruned_key_count > 0);
+        assert_eq!(stats.checkpointed_key_count, 0);
+        assert_eq!(stats.deleted_stale_key_versions, 1..10);
+        assert!(!stats.has_more_work());
+        assert_eq!(db.min_stale_key_version(), None);
+        assert_eq!(db.min_checkpointed_key_version(), None);
+
+        let tree = MerkleTree::new(&mut db).unwrap();
+        assert_eq!(tree.first_retained_version(), Some(9));
+        for version in [0, 4, 8, 9] {
+            tree.verify_consistency(version, true).unwrap();
+            assert_eq!(tree.root_hash(version), Some(root_hashes[version as usize]));
+        }
+        for version in [1, 2, 3, 5, 6, 7] {
+            assert_eq!(tree.root_hash(version), None);
+        }
+    }
+
+    #[test]
+    fn pruning_with_expiring_checkpoints() {
+        let (mut db, root_hashes) = create_db_with_root_hashes(10);
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db);
+        let schedule = CheckpointSchedule::new(NonZeroU64::new(4).unwrap()).with_retention(4);
+        pruner.set_checkpoint_schedule(schedule);
+
+        // Checkpoint 0 has expired, and checkpoint 4 should be retained until version 9.
+        let stats = pruner.prune_up_to(6).unwrap().expect("tree was not pruned");
+        assert!(stats.checkpointed_key_count > 0);
+        assert_eq!(stats.deleted_stale_key_versions, 1..7);
+        assert!(stats.deleted_checkpointed_key_versions.is_empty());
+        assert_eq!(db.min_checkpointed_key_version(), Some(9));
+
+        let tree = MerkleTree::new(&mut db).unwrap();
+        assert_eq!(tree.first_retained_version(), Some(6));
+        assert_eq!(tree.root_hash(0), None);
+        tree.verify_consistency(4, true).unwrap();
+        assert_eq!(tree.root_hash(4), Some(root_hashes[4]));
+
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db);
+        pruner.set_checkpoint_schedule(schedule);
+        let stats = pruner.prune_up_to(9).unwrap().expect("tree was not pruned");
+        assert_eq!(stats.deleted_stale_key_versions, 7..10);
+        assert_eq!(stats.deleted_checkpointed_key_versions, 9..10);
+        assert!(!stats.has_more_work());
+        assert_eq!(db.min_checkpointed_key_version(), Some(13));
+
+        let tree = MerkleTree::new(&mut db).unwrap();
+        assert_eq!(tree.root_hash(4), None);
+        for version in [8, 9] {
+            tree.verify_consistency(version, true).unwrap();
+            assert_eq!(tree.root_hash(version), Some(root_hashes[version as usize]));
+        }
+    }
+
     fn assert_no_stale_keys(db: &PatchSet, first_retained_version: u64) {
         if let Some(version) = db.min_stale_key_version() {
             assert!(version > first_retained_version);
@@ -371,6 +371,10 @@ pub struct PrunePatchSet {
     pub(super) pruned_node_keys: Vec<NodeKey>,
     /// Range of replacing versions for stale keys that need to be removed.
     pub(super) deleted_stale_key_versions: ops::Range<u64>,
+    /// Node keys retained for tree checkpoints, together with the versions after which they can be pruned.
+    pub(super) new_checkpointed_keys: Vec<(u64, NodeKey)>,
+    /// Range of expiration versions for checkpointed keys that need to be removed.
+    pub(super) deleted_checkpointed_key_versions: ops::Range<u64>,
 }
 
 impl PrunePatchSet {
@@ -381,8 +385,20 @@ impl PrunePatchSet {
         Self {
             pruned_node_keys,
             deleted_stale_key_versions,
+            new_checkpointed_keys: vec![],
+            deleted_checkpointed_key_versions: 0..0,
         }
     }
+
+    pub(crate) fn with_checkpointed_keys(
+        mut self,
+        new_checkpointed_keys: Vec<(u64, NodeKey)>,
+        deleted_checkpointed_key_versions: ops::Range<u64>,
+    ) -> Self {
+        self.new_checkpointed_keys = new_checkpointed_keys;
+        self.deleted_checkpointed_key_versions = deleted_checkpointed_key_versions;
+        self
+    }
 }
 
 /// Functionality to prune past versions of the Merkle tree.
@@ -394,6 +410,13 @@ pub trait PruneDatabase: Database {
     /// Returns a list of node keys obsoleted in the specified `version` of the tree.
     fn stale_keys(&self, version: u64) -> Vec<NodeKey>;
 
+    /// Returns the minimum expiration version for node keys retained for tree checkpoints, or `None`
+    /// if there are no such keys.
+    fn min_checkpointed_key_version(&self) -> Option<u64>;
+
+    /// Returns a list of node keys retained for tree checkpoints that expire at the specified `version`.
+    fn checkpointed_keys(&self, version: u64) -> Vec<NodeKey>;
+
     /// Atomically prunes the tree and updates information about the minimum retained version.
     ///
     /// # Errors
@@ -411,6 +434,14 @@ impl<T: PruneDatabase + ?Sized> PruneDatabase for &mut T {
         (**self).stale_keys(version)
     }
 
+    fn min_checkpointed_key_version(&self) -> Option<u64> {
+        (**self).min_checkpointed_key_version()
+    }
+
+    fn checkpointed_keys(&self, version: u64) -> Vec<NodeKey> {
+        (**self).checkpointed_keys(version)
+    }
+
     fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
         (**self).prune(patch)
     }
@@ -431,6 +462,20 @@ impl PruneDatabase for PatchSet {
             .unwrap_or_default()
     }
 
+    fn min_checkpointed_key_version(&self) -> Option<u64> {
+        self.checkpointed_keys_by_version
+            .iter()
+            .filter_map(|(&version, keys)| (!keys.is_empty()).then_some(version))
+            .min()
+    }
+
+    fn checkpointed_keys(&self, version: u64) -> Vec<NodeKey> {
+        self.checkpointed_keys_by_version
+            .get(&version)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
         for key in &patch.pruned_node_keys {
             let Some(patch) = self.patches_by_version.get_mut(&key.version) else {
@@ -445,6 +490,14 @@ impl PruneDatabase for PatchSet {
 
         self.stale_keys_by_version
             .retain(|version, _| !patch.deleted_stale_key_versions.contains(version));
+        self.checkpointed_keys_by_version
+            .retain(|version, _| !patch.deleted_checkpointed_key_versions.contains(version));
+        for (version, key) in patch.new_checkpointed_keys {
+            self.checkpointed_keys_by_version
+                .entry(version)
+                .or_default()
+                .push(key);
+        }
         Ok(())
     }
 }
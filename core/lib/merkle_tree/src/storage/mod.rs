@@ -5,6 +5,7 @@ pub use self::{
     parallel::PersistenceThreadHandle,
     patch::PatchSet,
     rocksdb::{MerkleTreeColumnFamily, RocksDBWrapper},
+    snapshot::{TreeSnapshotChunk, TreeSnapshotManifest},
 };
 pub(crate) use self::{
     parallel::MaybeParallel,
@@ -25,6 +26,7 @@ mod patch;
 mod proofs;
 mod rocksdb;
 mod serialization;
+mod snapshot;
 #[cfg(test)]
 mod tests;
 
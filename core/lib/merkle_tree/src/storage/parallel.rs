@@ -163,6 +163,7 @@ impl<DB: Database + Clone + 'static> ParallelDatabase<DB> {
                 patches_by_version: HashMap::from([(updated_version, command.patch.cloned())]),
                 updated_version: Some(updated_version),
                 stale_keys_by_version: HashMap::from([(updated_version, command.stale_keys)]),
+                checkpointed_keys_by_version: HashMap::new(),
             };
             let stage_latency =
                 RECOVERY_METRICS.stage_latency[&RecoveryStage::ParallelPersistence].start();
@@ -369,6 +370,15 @@ impl<DB: PruneDatabase> PruneDatabase for ParallelDatabase<DB> {
             .collect()
     }
 
+    fn min_checkpointed_key_version(&self) -> Option<u64> {
+        // Checkpointed keys are only written by the pruner, never by persistence commands.
+        self.inner.min_checkpointed_key_version()
+    }
+
+    fn checkpointed_keys(&self, version: u64) -> Vec<NodeKey> {
+        self.inner.checkpointed_keys(version)
+    }
+
     fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
         // Require the underlying database to be fully synced.
         self.wait_sync()
@@ -473,6 +483,20 @@ impl<DB: PruneDatabase> PruneDatabase for MaybeParallel<DB> {
         }
     }
 
+    fn min_checkpointed_key_version(&self) -> Option<u64> {
+        match self {
+            Self::Sequential(db) => db.min_checkpointed_key_version(),
+            Self::Parallel(db) => db.min_checkpointed_key_version(),
+        }
+    }
+
+    fn checkpointed_keys(&self, version: u64) -> Vec<NodeKey> {
+        match self {
+            Self::Sequential(db) => db.checkpointed_keys(version),
+            Self::Parallel(db) => db.checkpointed_keys(version),
+        }
+    }
+
     fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
         match self {
             Self::Sequential(db) => db.prune(patch),
@@ -61,6 +61,8 @@ pub struct PatchSet {
     /// is smaller than all other keys in `patches_by_version`.
     pub(super) updated_version: Option<u64>,
     pub(super) stale_keys_by_version: HashMap<u64, Vec<NodeKey>>,
+    /// Node keys retained for tree checkpoints keyed by the expiration version. Only changed by pruning.
+    pub(super) checkpointed_keys_by_version: HashMap<u64, Vec<NodeKey>>,
 }
 
 impl PatchSet {
@@ -70,6 +72,7 @@ impl PatchSet {
             patches_by_version: HashMap::new(),
             updated_version: None,
             stale_keys_by_version: HashMap::new(),
+            checkpointed_keys_by_version: HashMap::new(),
         }
     }
 
@@ -116,6 +119,7 @@ impl PatchSet {
             patches_by_version: HashMap::from([(version, partial_patch)]),
             updated_version,
             stale_keys_by_version: HashMap::from([(version, stale_keys)]),
+            checkpointed_keys_by_version: HashMap::new(),
         }
     }
 
@@ -33,16 +33,20 @@ pub enum MerkleTreeColumnFamily {
     Tree,
     /// Column family containing stale node keys that are eventually removed by the pruning logic.
     StaleKeys,
+    /// Column family containing stale node keys retained for tree checkpoints. Keys are prefixed
+    /// with the version after which they can be removed by the pruning logic.
+    CheckpointedKeys,
 }
 
 impl NamedColumnFamily for MerkleTreeColumnFamily {
     const DB_NAME: &'static str = "merkle_tree";
-    const ALL: &'static [Self] = &[Self::Tree, Self::StaleKeys];
+    const ALL: &'static [Self] = &[Self::Tree, Self::StaleKeys, Self::CheckpointedKeys];
 
     fn name(&self) -> &'static str {
         match self {
             Self::Tree => "default",
             Self::StaleKeys => "stale_keys",
+            Self::CheckpointedKeys => "checkpointed_keys",
         }
     }
 
@@ -157,6 +161,43 @@ impl RocksDBWrapper {
         })
     }
 
+    /// Returns the minimum version prefix for keys in a column family with versioned node keys
+    /// (stale or checkpointed keys).
+    fn min_key_version(&self, cf: MerkleTreeColumnFamily) -> Option<u64> {
+        let kv_bytes = self.db.prefix_iterator_cf(cf, &[]).next()?;
+        let version_prefix: [u8; 8] = kv_bytes.0[..8].try_into().unwrap();
+        Some(u64::from_be_bytes(version_prefix))
+    }
+
+    fn keys_with_version(&self, cf: MerkleTreeColumnFamily, version: u64) -> Vec<NodeKey> {
+        let version_prefix = version.to_be_bytes();
+        let keys = self
+            .db
+            .prefix_iterator_cf(cf, &version_prefix)
+            .map(|entry| {
+                let key_bytes = entry.0;
+                debug_assert_eq!(&key_bytes[..8], version_prefix);
+                NodeKey::from_db_key(&key_bytes[8..])
+            });
+        keys.collect()
+    }
+
+    /// Writes serialized nodes (including roots) to the tree column family as-is. Used when importing
+    /// tree snapshots; the caller is responsible for validating the written data.
+    pub(super) fn write_raw_nodes<'a>(
+        &mut self,
+        nodes: impl Iterator<Item = (&'a [u8], &'a [u8])>,
+    ) -> anyhow::Result<()> {
+        let tree_cf = MerkleTreeColumnFamily::Tree;
+        let mut write_batch = self.db.new_write_batch();
+        for (key, node_bytes) in nodes {
+            write_batch.put_cf(tree_cf, key, node_bytes);
+        }
+        self.db
+            .write(write_batch)
+            .context("Failed writing a batch to RocksDB")
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
@@ -297,24 +338,19 @@ impl Database for RocksDBWrapper {
 
 impl PruneDatabase for RocksDBWrapper {
     fn min_stale_key_version(&self) -> Option<u64> {
-        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
-        let kv_bytes = self.db.prefix_iterator_cf(stale_keys_cf, &[]).next()?;
-        let version_prefix: [u8; 8] = kv_bytes.0[..8].try_into().unwrap();
-        Some(u64::from_be_bytes(version_prefix))
+        self.min_key_version(MerkleTreeColumnFamily::StaleKeys)
     }
 
     fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
-        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
-        let version_prefix = version.to_be_bytes();
-        let keys = self
-            .db
-            .prefix_iterator_cf(stale_keys_cf, &version_prefix)
-            .map(|entry| {
-                let key_bytes = entry.0;
-                debug_assert_eq!(&key_bytes[..8], version_prefix);
-                NodeKey::from_db_key(&key_bytes[8..])
-            });
-        keys.collect()
+        self.keys_with_version(MerkleTreeColumnFamily::StaleKeys, version)
+    }
+
+    fn min_checkpointed_key_version(&self) -> Option<u64> {
+        self.min_key_version(MerkleTreeColumnFamily::CheckpointedKeys)
+    }
+
+    fn checkpointed_keys(&self, version: u64) -> Vec<NodeKey> {
+        self.keys_with_version(MerkleTreeColumnFamily::CheckpointedKeys, version)
     }
 
     fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
@@ -330,6 +366,17 @@ impl PruneDatabase for RocksDBWrapper {
         let last_version = &patch.deleted_stale_key_versions.end.to_be_bytes();
         write_batch.delete_range_cf(stale_keys_cf, first_version..last_version);
 
+        let checkpointed_keys_cf = MerkleTreeColumnFamily::CheckpointedKeys;
+        let first_version = &patch.deleted_checkpointed_key_versions.start.to_be_bytes() as &[_];
+        let last_version = &patch.deleted_checkpointed_key_versions.end.to_be_bytes();
+        write_batch.delete_range_cf(checkpointed_keys_cf, first_version..last_version);
+        // Expiration versions of new checkpointed keys are always greater than the deleted versions,
+        // but it doesn't matter anyway since write batch operations are applied in order.
+        for (expiration_version, key) in patch.new_checkpointed_keys {
+            let db_key = StaleNodeKey::new(key, expiration_version).to_db_key();
+            write_batch.put_cf(checkpointed_keys_cf, &db_key, &[]);
+        }
+
         self.db
             .write(write_batch)
             .context("Failed writing a batch to RocksDB")
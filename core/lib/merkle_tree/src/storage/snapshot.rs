@@ -0,0 +1,828 @@
+//! Tree snapshots: exporting a tree version into portable files and importing it into a fresh RocksDB instance.
+//!
+//! Unlike [recovery](crate::recovery), which rebuilds the tree from key–value entries and thus recomputes
+//! all node hashes, importing a snapshot copies tree nodes as-is. Still, every imported node is hashed and checked
+//! against the hash stored in its parent, and the root hash is compared with the root hash from the snapshot manifest
+//! (and, optionally, with a trusted root hash supplied by the caller). Thus, a successfully imported tree is
+//! guaranteed to have the expected root hash.
+//!
+//! # Snapshot format
+//!
+//! A snapshot is a directory containing a JSON [manifest](TreeSnapshotManifest) and one or more chunk files
+//! with tree nodes. A chunk file is a sequence of records, each of which consists of:
+//!
+//! - Record kind (1 byte): 0 for the tree root, 1 for an internal node, 2 for a leaf
+//! - LEB128-encoded length of the node key, followed by the node key in the RocksDB format
+//! - LEB128-encoded length of the node, followed by the node serialized in the RocksDB format
+//!
+//! The root record is always the first record in the first chunk, and each node is recorded after its parent.
+
+use std::{
+    collections::HashMap,
+    fs, iter,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use zksync_crypto::hasher::{blake2::Blake2Hasher, Hasher};
+
+use crate::{
+    hasher::HasherWithStats,
+    storage::{Database, PatchSet, RocksDBWrapper},
+    types::{
+        ChildRef, InternalNode, LeafNode, Manifest, Nibbles, Node, NodeKey, Root, TreeTags,
+        ValueHash, KEY_SIZE,
+    },
+    HashTree, MerkleTree, NoVersionError,
+};
+
+/// Name of the manifest file in the snapshot directory.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+/// Current version of the snapshot format.
+const FORMAT_VERSION: u32 = 1;
+/// Maximum number of node records in a single chunk file.
+const NODES_PER_CHUNK: u64 = 100_000;
+/// Number of nodes loaded from the database at once during export.
+const LOAD_BATCH_SIZE: usize = 1_000;
+
+/// Manifest of a tree snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeSnapshotManifest {
+    /// Version of the snapshot format.
+    pub format_version: u32,
+    /// Name of the hasher used in the tree.
+    pub hasher: String,
+    /// Tree version captured in the snapshot.
+    pub version: u64,
+    /// Root hash of the tree at [`Self::version`].
+    pub root_hash: ValueHash,
+    /// Number of leaves in the tree at [`Self::version`].
+    pub leaf_count: u64,
+    /// Total number of node records (including the root) in all chunks.
+    pub node_count: u64,
+    /// Chunks with tree nodes.
+    pub chunks: Vec<TreeSnapshotChunk>,
+}
+
+impl TreeSnapshotManifest {
+    /// Reads a manifest from the specified snapshot directory.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O and deserialization errors.
+    pub fn read(snapshot_dir: &Path) -> anyhow::Result<Self> {
+        let path = snapshot_dir.join(MANIFEST_FILE_NAME);
+        let bytes =
+            fs::read(&path).with_context(|| format!("failed reading manifest at {path:?}"))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed deserializing manifest at {path:?}"))
+    }
+
+    fn write(&self, snapshot_dir: &Path) -> anyhow::Result<()> {
+        let path = snapshot_dir.join(MANIFEST_FILE_NAME);
+        let bytes = serde_json::to_vec_pretty(self).context("failed serializing manifest")?;
+        fs::write(&path, bytes).with_context(|| format!("failed writing manifest to {path:?}"))
+    }
+}
+
+/// Information about a chunk file in a tree snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeSnapshotChunk {
+    /// File name relative to the snapshot directory.
+    pub file_name: String,
+    /// Number of node records in the chunk.
+    pub node_count: u64,
+    /// Chunk file length in bytes.
+    pub byte_len: u64,
+    /// Blake2s-256 checksum of the chunk file.
+    pub checksum: ValueHash,
+}
+
+impl TreeSnapshotChunk {
+    /// Resolves the chunk path in the snapshot directory. The file name must not contain any path components
+    /// (e.g., `..` or absolute paths), so that a malicious manifest cannot make the import read files
+    /// outside the snapshot directory.
+    fn path(&self, snapshot_dir: &Path) -> anyhow::Result<PathBuf> {
+        let file_name = Path::new(&self.file_name);
+        let mut components = file_name.components();
+        anyhow::ensure!(
+            matches!(
+                (components.next(), components.next()),
+                (Some(Component::Normal(_)), None)
+            ),
+            "Chunk file name {:?} is not a plain file name",
+            self.file_name
+        );
+        Ok(snapshot_dir.join(file_name))
+    }
+}
+
+/// Kind of a node record in a snapshot chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum RecordKind {
+    Root = 0,
+    Internal = 1,
+    Leaf = 2,
+}
+
+impl RecordKind {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Root),
+            1 => Some(Self::Internal),
+            2 => Some(Self::Leaf),
+            _ => None,
+        }
+    }
+}
+
+/// Node record parsed from a snapshot chunk.
+#[derive(Debug)]
+struct Record<'a> {
+    kind: RecordKind,
+    key: NodeKey,
+    raw_key: &'a [u8],
+    raw_node: &'a [u8],
+}
+
+impl<'a> Record<'a> {
+    fn parse_all(mut bytes: &'a [u8]) -> anyhow::Result<Vec<Self>> {
+        let mut records = vec![];
+        while let Some((&kind, rest)) = bytes.split_first() {
+            bytes = rest;
+            let kind = RecordKind::from_byte(kind)
+                .with_context(|| format!("unknown record kind: {kind}"))?;
+            let raw_key = read_bytes(&mut bytes).context("failed reading node key")?;
+            let key = parse_node_key(raw_key)?;
+            let raw_node =
+                read_bytes(&mut bytes).with_context(|| format!("failed reading node {key}"))?;
+            records.push(Self {
+                kind,
+                key,
+                raw_key,
+                raw_node,
+            });
+        }
+        Ok(records)
+    }
+}
+
+fn read_bytes<'a>(bytes: &mut &'a [u8]) -> anyhow::Result<&'a [u8]> {
+    let len = leb128::read::unsigned(bytes)?;
+    let len = usize::try_from(len).context("length overflow")?;
+    anyhow::ensure!(bytes.len() >= len, "unexpected end of input");
+    let (value, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(value)
+}
+
+/// Fallible version of [`NodeKey::from_db_key()`].
+fn parse_node_key(bytes: &[u8]) -> anyhow::Result<NodeKey> {
+    anyhow::ensure!(bytes.len() >= 9, "node key is too short");
+    let nibble_count = usize::from(bytes[8]);
+    anyhow::ensure!(
+        nibble_count <= 2 * KEY_SIZE && bytes.len() == 9 + (nibble_count + 1) / 2,
+        "malformed node key: {bytes:?}"
+    );
+    Ok(NodeKey::from_db_key(bytes))
+}
+
+/// Writer of snapshot chunk files.
+#[derive(Debug)]
+struct ChunkWriter<'a> {
+    snapshot_dir: &'a Path,
+    buffer: Vec<u8>,
+    node_count: u64,
+    chunks: Vec<TreeSnapshotChunk>,
+}
+
+impl<'a> ChunkWriter<'a> {
+    fn new(snapshot_dir: &'a Path) -> Self {
+        Self {
+            snapshot_dir,
+            buffer: vec![],
+            node_count: 0,
+            chunks: vec![],
+        }
+    }
+
+    fn push(&mut self, kind: RecordKind, key: NodeKey, raw_node: &[u8]) -> anyhow::Result<()> {
+        let raw_key = key.to_db_key();
+        self.buffer.push(kind as u8);
+        leb128::write::unsigned(&mut self.buffer, raw_key.len() as u64).unwrap();
+        self.buffer.extend_from_slice(&raw_key);
+        leb128::write::unsigned(&mut self.buffer, raw_node.len() as u64).unwrap();
+        self.buffer.extend_from_slice(raw_node);
+        // ^ `unwrap()`s are safe: writing to a `Vec` is infallible
+
+        self.node_count += 1;
+        if self.node_count >= NODES_PER_CHUNK {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        if self.node_count == 0 {
+            return Ok(());
+        }
+
+        let file_name = format!("nodes_{:06}.bin", self.chunks.len());
+        let path = self.snapshot_dir.join(&file_name);
+        fs::write(&path, &self.buffer)
+            .with_context(|| format!("failed writing chunk to {path:?}"))?;
+        self.chunks.push(TreeSnapshotChunk {
+            file_name,
+            node_count: self.node_count,
+            byte_len: self.buffer.len() as u64,
+            checksum: Blake2Hasher.hash_bytes(&self.buffer),
+        });
+        self.buffer.clear();
+        self.node_count = 0;
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<Vec<TreeSnapshotChunk>> {
+        self.flush()?;
+        Ok(self.chunks)
+    }
+}
+
+fn push_child_keys(keys: &mut Vec<(NodeKey, bool)>, nibbles: &Nibbles, node: &InternalNode) {
+    for (nibble, child_ref) in node.children() {
+        let child_nibbles = nibbles.push(nibble).unwrap();
+        // ^ `unwrap()` is safe: internal nodes cannot reside at the lowest tree level
+        keys.push((
+            child_nibbles.with_version(child_ref.version),
+            child_ref.is_leaf,
+        ));
+    }
+}
+
+impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
+    /// Exports the tree at the specified version into a snapshot in `output_dir`. The directory is created
+    /// if it doesn't exist; existing snapshot files in it are overwritten.
+    ///
+    /// The snapshot can be imported into a fresh RocksDB instance using [`Self::import_snapshot()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing, the tree is inconsistent (e.g., some nodes
+    /// are missing), or if an I/O error occurs.
+    pub fn export_snapshot(
+        &self,
+        version: u64,
+        output_dir: &Path,
+    ) -> anyhow::Result<TreeSnapshotManifest> {
+        let root = self.db.root(version).ok_or_else(|| NoVersionError {
+            missing_version: version,
+            version_count: self.db.manifest().unwrap_or_default().version_count,
+        })?;
+        fs::create_dir_all(output_dir)
+            .with_context(|| format!("failed creating snapshot directory {output_dir:?}"))?;
+        tracing::info!("Exporting tree snapshot at version {version} to {output_dir:?}");
+
+        let mut writer = ChunkWriter::new(output_dir);
+        let mut node_bytes = Vec::with_capacity(128);
+        root.serialize(&mut node_bytes);
+        writer.push(RecordKind::Root, NodeKey::empty(version), &node_bytes)?;
+
+        let mut pending_keys = vec![];
+        if let Root::Filled {
+            node: Node::Internal(node),
+            ..
+        } = &root
+        {
+            push_child_keys(&mut pending_keys, &Nibbles::EMPTY, node);
+        }
+        // Nodes are traversed depth-first (loading them in batches), which keeps the number of pending keys
+        // proportional to the tree depth rather than to the tree size.
+        while !pending_keys.is_empty() {
+            let batch_start = pending_keys.len().saturating_sub(LOAD_BATCH_SIZE);
+            let batch = pending_keys.split_off(batch_start);
+            let nodes = self.db.tree_nodes(&batch);
+            for ((key, is_leaf), node) in batch.into_iter().zip(nodes) {
+                let node = node.with_context(|| format!("node {key} is missing"))?;
+                node_bytes.clear();
+                node.serialize(&mut node_bytes);
+                let kind = if is_leaf {
+                    RecordKind::Leaf
+                } else {
+                    RecordKind::Internal
+                };
+                writer.push(kind, key, &node_bytes)?;
+
+                if let Node::Internal(node) = &node {
+                    push_child_keys(&mut pending_keys, &key.nibbles, node);
+                }
+            }
+        }
+
+        let chunks = writer.finish()?;
+        let manifest = TreeSnapshotManifest {
+            format_version: FORMAT_VERSION,
+            hasher: self.hasher.name().to_owned(),
+            version,
+            root_hash: root.hash(&self.hasher),
+            leaf_count: root.leaf_count(),
+            node_count: chunks.iter().map(|chunk| chunk.node_count).sum(),
+            chunks,
+        };
+        manifest.write(output_dir)?;
+        tracing::info!(
+            "Exported tree snapshot with {} nodes in {} chunks",
+            manifest.node_count,
+            manifest.chunks.len()
+        );
+        Ok(manifest)
+    }
+}
+
+impl<H: HashTree> MerkleTree<RocksDBWrapper, H> {
+    /// Imports a snapshot created with [`Self::export_snapshot()`]. The tree must be empty. After the import,
+    /// the tree has a single version equal to [`TreeSnapshotManifest::version`].
+    ///
+    /// Chunk files are verified against checksums in the manifest. Each node is hashed and compared with
+    /// the child hash in its parent node; the root hash is verified against the manifest and `expected_root_hash`
+    /// (if specified). The snapshot must contain exactly the nodes reachable from the root. The tree manifest
+    /// is written last, so an interrupted import leaves the tree empty and can be restarted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree is not empty, the snapshot is malformed or doesn't match the expected
+    /// root hash, or if an I/O error occurs.
+    pub fn import_snapshot(
+        &mut self,
+        snapshot_dir: &Path,
+        expected_root_hash: Option<ValueHash>,
+    ) -> anyhow::Result<TreeSnapshotManifest> {
+        let manifest = TreeSnapshotManifest::read(snapshot_dir)?;
+        anyhow::ensure!(
+            manifest.format_version == FORMAT_VERSION,
+            "Unsupported snapshot format version {}, expected {FORMAT_VERSION}",
+            manifest.format_version
+        );
+        anyhow::ensure!(
+            manifest.hasher == self.hasher.name(),
+            "Mismatch between the tree hasher `{}` and the hasher `{}` used in the snapshot",
+            self.hasher.name(),
+            manifest.hasher
+        );
+        if let Some(expected_root_hash) = expected_root_hash {
+            anyhow::ensure!(
+                manifest.root_hash == expected_root_hash,
+                "Snapshot root hash {:?} differs from the expected root hash {expected_root_hash:?}",
+                manifest.root_hash
+            );
+        }
+        let version_count = self
+            .db
+            .manifest()
+            .map_or(0, |manifest| manifest.version_count);
+        anyhow::ensure!(
+            version_count == 0,
+            "Cannot import a snapshot into a non-empty tree with {version_count} versions"
+        );
+        tracing::info!(
+            "Importing tree snapshot at version {} with {} nodes from {snapshot_dir:?}",
+            manifest.version,
+            manifest.node_count
+        );
+
+        let mut raw_root = None;
+        let mut node_count = 0_u64;
+        let mut leaf_count = 0_u64;
+        // Nodes referenced by the already imported parents, but not yet imported themselves.
+        let mut expected_nodes = HashMap::new();
+        for (chunk_idx, chunk) in manifest.chunks.iter().enumerate() {
+            let path = chunk.path(snapshot_dir)?;
+            let bytes = fs::read(&path).with_context(|| format!("failed reading {path:?}"))?;
+            anyhow::ensure!(
+                bytes.len() as u64 == chunk.byte_len,
+                "Unexpected length of chunk {path:?}: expected {}, got {}",
+                chunk.byte_len,
+                bytes.len()
+            );
+            let checksum = Blake2Hasher.hash_bytes(&bytes);
+            anyhow::ensure!(
+                checksum == chunk.checksum,
+                "Checksum mismatch for chunk {path:?}: expected {:?}, got {checksum:?}",
+                chunk.checksum
+            );
+
+            let mut records = Record::parse_all(&bytes)
+                .with_context(|| format!("failed parsing chunk {path:?}"))?;
+            anyhow::ensure!(
+                records.len() as u64 == chunk.node_count,
+                "Unexpected number of records in chunk {path:?}: expected {}, got {}",
+                chunk.node_count,
+                records.len()
+            );
+            node_count += chunk.node_count;
+
+            if chunk_idx == 0 {
+                anyhow::ensure!(
+                    records.first().map(|record| record.kind) == Some(RecordKind::Root),
+                    "Snapshot doesn't start with a root record"
+                );
+                let root_record = records.remove(0);
+                let root = self.verify_snapshot_root(&manifest, &root_record)?;
+                match &root {
+                    Root::Filled {
+                        node: Node::Internal(node),
+                        ..
+                    } => push_expected_children(&mut expected_nodes, root_record.key, node)?,
+                    Root::Filled {
+                        node: Node::Leaf(_),
+                        ..
+                    } => leaf_count += 1,
+                    Root::Empty => { /* no child nodes */ }
+                }
+                raw_root = Some(root_record.raw_node.to_vec());
+            }
+
+            let hasher: &dyn HashTree = &self.hasher;
+            let nodes = records
+                .par_iter()
+                .map(|record| verify_snapshot_node(hasher, manifest.version, record))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            for (record, (node, hash)) in records.iter().zip(nodes) {
+                let key = record.key;
+                let child_ref = expected_nodes
+                    .remove(&key)
+                    .with_context(|| format!("Node {key} is not referenced by its parent"))?;
+                anyhow::ensure!(
+                    child_ref.is_leaf == matches!(node, Node::Leaf(_)),
+                    "Node {key} has a kind different from the one specified in its parent"
+                );
+                anyhow::ensure!(
+                    child_ref.hash == hash,
+                    "Hash mismatch for node {key}: parent specifies {:?}, actual hash is {hash:?}",
+                    child_ref.hash
+                );
+                match &node {
+                    Node::Internal(node) => push_expected_children(&mut expected_nodes, key, node)?,
+                    Node::Leaf(_) => leaf_count += 1,
+                }
+            }
+            self.db.write_raw_nodes(
+                records
+                    .iter()
+                    .map(|record| (record.raw_key, record.raw_node)),
+            )?;
+            tracing::debug!(
+                "Imported chunk #{chunk_idx} with {} nodes",
+                chunk.node_count
+            );
+        }
+        anyhow::ensure!(
+            node_count == manifest.node_count,
+            "Unexpected number of nodes in snapshot: expected {}, got {node_count}",
+            manifest.node_count
+        );
+        if let Some(key) = expected_nodes.keys().next() {
+            anyhow::bail!(
+                "Snapshot is missing {} nodes referenced by their parents, e.g. {key}",
+                expected_nodes.len()
+            );
+        }
+        anyhow::ensure!(
+            leaf_count == manifest.leaf_count,
+            "Unexpected number of leaves in snapshot: expected {}, got {leaf_count}",
+            manifest.leaf_count
+        );
+
+        let raw_root = raw_root.context("snapshot contains no chunks")?;
+        let root_key = NodeKey::empty(manifest.version).to_db_key();
+        self.db
+            .write_raw_nodes(iter::once((root_key.as_slice(), raw_root.as_slice())))?;
+        let tree_manifest = Manifest {
+            version_count: manifest.version + 1,
+            tags: Some(TreeTags::new(&self.hasher)),
+        };
+        self.db
+            .apply_patch(PatchSet::from_manifest(tree_manifest))?;
+        tracing::info!(
+            "Imported tree snapshot at version {} with root hash {:?}",
+            manifest.version,
+            manifest.root_hash
+        );
+        Ok(manifest)
+    }
+
+    fn verify_snapshot_root(
+        &self,
+        manifest: &TreeSnapshotManifest,
+        record: &Record<'_>,
+    ) -> anyhow::Result<Root> {
+        anyhow::ensure!(
+            record.key == NodeKey::empty(manifest.version),
+            "Unexpected root key {}, expected {}",
+            record.key,
+            NodeKey::empty(manifest.version)
+        );
+        let root = Root::deserialize(record.raw_node).context("failed deserializing root")?;
+        anyhow::ensure!(
+            root.leaf_count() == manifest.leaf_count,
+            "Mismatch between the leaf count in the root ({}) and in the manifest ({})",
+            root.leaf_count(),
+            manifest.leaf_count
+        );
+        let root_hash = root.hash(&self.hasher);
+        anyhow::ensure!(
+            root_hash == manifest.root_hash,
+            "Root hash mismatch: manifest specifies {:?}, actual root hash is {root_hash:?}",
+            manifest.root_hash
+        );
+        Ok(root)
+    }
+}
+
+fn push_expected_children(
+    expected_nodes: &mut HashMap<NodeKey, ChildRef>,
+    key: NodeKey,
+    node: &InternalNode,
+) -> anyhow::Result<()> {
+    for (nibble, child_ref) in node.children() {
+        let child_nibbles = key
+            .nibbles
+            .push(nibble)
+            .with_context(|| format!("Internal node {key} is at the terminal tree level"))?;
+        expected_nodes.insert(child_nibbles.with_version(child_ref.version), *child_ref);
+    }
+    Ok(())
+}
+
+/// Deserializes a non-root node from the snapshot and computes its hash.
+fn verify_snapshot_node(
+    hasher: &dyn HashTree,
+    snapshot_version: u64,
+    record: &Record<'_>,
+) -> anyhow::Result<(Node, ValueHash)> {
+    let key = record.key;
+    anyhow::ensure!(
+        !key.is_empty() && key.version <= snapshot_version,
+        "Unexpected node key {key} for snapshot at version {snapshot_version}"
+    );
+    let node = match record.kind {
+        RecordKind::Root => anyhow::bail!("Unexpected root record with key {key}"),
+        RecordKind::Internal => InternalNode::deserialize(record.raw_node)
+            .with_context(|| format!("failed deserializing internal node {key}"))?
+            .into(),
+        RecordKind::Leaf => {
+            let leaf = LeafNode::deserialize(record.raw_node)
+                .with_context(|| format!("failed deserializing leaf {key}"))?;
+            let full_key_nibbles = Nibbles::new(&leaf.full_key, key.nibbles.nibble_count());
+            anyhow::ensure!(
+                full_key_nibbles == key.nibbles,
+                "Leaf {key} has mismatching full key {:?}",
+                leaf.full_key
+            );
+            leaf.into()
+        }
+    };
+    let level = key.nibbles.nibble_count() * 4;
+    let hash = node.hash(&mut HasherWithStats::new(hasher), level);
+    Ok((node, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::{types::TreeEntry, Key};
+
+    fn create_tree_with_versions() -> (MerkleTree<PatchSet>, Vec<TreeEntry>) {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let entries: Vec<_> = (1..=500_u64)
+            .map(|i| TreeEntry::new(Key::from(i * 7_919), i, ValueHash::from_low_u64_be(i)))
+            .collect();
+        for chunk in entries.chunks(100) {
+            tree.extend(chunk.to_vec()).unwrap();
+        }
+        (tree, entries)
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let (tree, entries) = create_tree_with_versions();
+        let snapshot_dir = TempDir::new().unwrap();
+        let manifest = tree.export_snapshot(2, snapshot_dir.path()).unwrap();
+        assert_eq!(manifest.version, 2);
+        assert_eq!(manifest.leaf_count, 300);
+        assert_eq!(manifest.root_hash, tree.root_hash(2).unwrap());
+        assert_eq!(manifest.chunks.len(), 1);
+        assert_eq!(
+            TreeSnapshotManifest::read(snapshot_dir.path()).unwrap(),
+            manifest
+        );
+
+        let db_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(db_dir.path()).unwrap();
+        let mut imported_tree = MerkleTree::new(db).unwrap();
+        let imported_manifest = imported_tree
+            .import_snapshot(snapshot_dir.path(), Some(manifest.root_hash))
+            .unwrap();
+        assert_eq!(imported_manifest, manifest);
+
+        assert_eq!(imported_tree.latest_version(), Some(2));
+        assert_eq!(imported_tree.root_hash(2), Some(manifest.root_hash));
+        assert_eq!(imported_tree.root_hash(1), None);
+        imported_tree.verify_consistency(2, true).unwrap();
+        let keys: Vec<_> = entries.iter().map(|entry| entry.key).collect();
+        assert_eq!(
+            imported_tree.entries(2, &keys).unwrap(),
+            tree.entries(2, &keys).unwrap()
+        );
+
+        // The imported tree should be able to continue from the snapshot.
+        imported_tree.extend(entries[300..400].to_vec()).unwrap();
+        assert_eq!(imported_tree.root_hash(3), tree.root_hash(3));
+    }
+
+    #[test]
+    fn snapshot_for_empty_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        tree.extend(vec![]).unwrap();
+        let snapshot_dir = TempDir::new().unwrap();
+        let manifest = tree.export_snapshot(0, snapshot_dir.path()).unwrap();
+        assert_eq!(manifest.node_count, 1);
+        assert_eq!(manifest.root_hash, tree.hasher.empty_tree_hash());
+
+        let db_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(db_dir.path()).unwrap();
+        let mut imported_tree = MerkleTree::new(db).unwrap();
+        imported_tree
+            .import_snapshot(snapshot_dir.path(), None)
+            .unwrap();
+        assert_eq!(imported_tree.latest_version(), Some(0));
+        assert_eq!(imported_tree.root_hash(0), Some(manifest.root_hash));
+    }
+
+    #[test]
+    fn exporting_missing_version() {
+        let (tree, _) = create_tree_with_versions();
+        let snapshot_dir = TempDir::new().unwrap();
+        let err = tree.export_snapshot(10, snapshot_dir.path()).unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<NoVersionError>(),
+            Some(NoVersionError {
+                missing_version: 10,
+                version_count: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn import_errors() {
+        let (tree, _) = create_tree_with_versions();
+        let snapshot_dir = TempDir::new().unwrap();
+        let manifest = tree.export_snapshot(4, snapshot_dir.path()).unwrap();
+
+        let db_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(db_dir.path()).unwrap();
+        let mut imported_tree = MerkleTree::new(db).unwrap();
+        let err = imported_tree
+            .import_snapshot(snapshot_dir.path(), Some(ValueHash::zero()))
+            .unwrap_err();
+        assert!(err.to_string().contains("expected root hash"), "{err}");
+
+        let chunk_path = snapshot_dir.path().join(&manifest.chunks[0].file_name);
+        let mut chunk_bytes = fs::read(&chunk_path).unwrap();
+        *chunk_bytes.last_mut().unwrap() ^= 1;
+        fs::write(&chunk_path, &chunk_bytes).unwrap();
+        let err = imported_tree
+            .import_snapshot(snapshot_dir.path(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"), "{err}");
+        assert_eq!(imported_tree.latest_version(), None);
+
+        // Restore the snapshot and import it. The second import should fail since the tree is not empty.
+        tree.export_snapshot(4, snapshot_dir.path()).unwrap();
+        imported_tree
+            .import_snapshot(snapshot_dir.path(), None)
+            .unwrap();
+        let err = imported_tree
+            .import_snapshot(snapshot_dir.path(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("non-empty tree"), "{err}");
+    }
+
+    #[test]
+    fn tampered_root_is_detected() {
+        let (tree, _) = create_tree_with_versions();
+        let snapshot_dir = TempDir::new().unwrap();
+        let mut manifest = tree.export_snapshot(4, snapshot_dir.path()).unwrap();
+        manifest.root_hash = tree.root_hash(3).unwrap();
+        manifest.write(snapshot_dir.path()).unwrap();
+
+        let db_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(db_dir.path()).unwrap();
+        let mut imported_tree = MerkleTree::new(db).unwrap();
+        let err = imported_tree
+            .import_snapshot(snapshot_dir.path(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("Root hash mismatch"), "{err}");
+        assert_eq!(imported_tree.latest_version(), None);
+    }
+
+    #[test]
+    fn tampered_leaf_is_detected() {
+        let (tree, _) = create_tree_with_versions();
+        let snapshot_dir = TempDir::new().unwrap();
+        let mut manifest = tree.export_snapshot(4, snapshot_dir.path()).unwrap();
+
+        // Rewrite the chunk with a modified leaf value and a valid checksum.
+        let chunk_path = manifest.chunks[0].path(snapshot_dir.path()).unwrap();
+        let chunk_bytes = fs::read(&chunk_path).unwrap();
+        let records = Record::parse_all(&chunk_bytes).unwrap();
+        let mut writer = ChunkWriter::new(snapshot_dir.path());
+        let mut is_tampered = false;
+        for record in &records {
+            if record.kind == RecordKind::Leaf && !is_tampered {
+                let mut leaf = LeafNode::deserialize(record.raw_node).unwrap();
+                leaf.value_hash = ValueHash::repeat_byte(0xff);
+                let mut node_bytes = vec![];
+                Node::from(leaf).serialize(&mut node_bytes);
+                writer.push(record.kind, record.key, &node_bytes).unwrap();
+                is_tampered = true;
+            } else {
+                writer
+                    .push(record.kind, record.key, record.raw_node)
+                    .unwrap();
+            }
+        }
+        assert!(is_tampered);
+        manifest.chunks = writer.finish().unwrap();
+        manifest.write(snapshot_dir.path()).unwrap();
+
+        let db_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(db_dir.path()).unwrap();
+        let mut imported_tree = MerkleTree::new(db).unwrap();
+        let err = imported_tree
+            .import_snapshot(snapshot_dir.path(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("Hash mismatch"), "{err}");
+        assert_eq!(imported_tree.latest_version(), None);
+    }
+
+    #[test]
+    fn missing_node_is_detected() {
+        let (tree, _) = create_tree_with_versions();
+        let snapshot_dir = TempDir::new().unwrap();
+        let mut manifest = tree.export_snapshot(4, snapshot_dir.path()).unwrap();
+
+        let chunk_path = manifest.chunks[0].path(snapshot_dir.path()).unwrap();
+        let chunk_bytes = fs::read(&chunk_path).unwrap();
+        let records = Record::parse_all(&chunk_bytes).unwrap();
+        let mut writer = ChunkWriter::new(snapshot_dir.path());
+        for record in &records[..records.len() - 1] {
+            writer
+                .push(record.kind, record.key, record.raw_node)
+                .unwrap();
+        }
+        manifest.chunks = writer.finish().unwrap();
+        manifest.node_count -= 1;
+        manifest.write(snapshot_dir.path()).unwrap();
+
+        let db_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(db_dir.path()).unwrap();
+        let mut imported_tree = MerkleTree::new(db).unwrap();
+        let err = imported_tree
+            .import_snapshot(snapshot_dir.path(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("missing 1 nodes"), "{err}");
+        assert_eq!(imported_tree.latest_version(), None);
+    }
+
+    #[test]
+    fn chunk_paths_outside_snapshot_dir_are_rejected() {
+        let (tree, _) = create_tree_with_versions();
+        let snapshot_dir = TempDir::new().unwrap();
+        let mut manifest = tree.export_snapshot(4, snapshot_dir.path()).unwrap();
+        let chunk_file_name = manifest.chunks[0].file_name.clone();
+
+        for file_name in [
+            format!("../{chunk_file_name}"),
+            format!("nested/{chunk_file_name}"),
+            "/etc/passwd".to_owned(),
+            "..".to_owned(),
+        ] {
+            manifest.chunks[0].file_name = file_name;
+            manifest.write(snapshot_dir.path()).unwrap();
+
+            let db_dir = TempDir::new().unwrap();
+            let db = RocksDBWrapper::new(db_dir.path()).unwrap();
+            let mut imported_tree = MerkleTree::new(db).unwrap();
+            let err = imported_tree
+                .import_snapshot(snapshot_dir.path(), None)
+                .unwrap_err();
+            assert!(err.to_string().contains("not a plain file name"), "{err}");
+        }
+    }
+}
@@ -577,6 +577,8 @@ pub enum ProfiledTreeOperation {
     GetEntries,
     /// Getting entries from the tree with Merkle proofs.
     GetEntriesWithProofs,
+    /// Getting a Merkle range proof for a key range.
+    GetRangeProof,
 }
 
 impl ProfiledTreeOperation {
@@ -585,6 +587,7 @@ impl ProfiledTreeOperation {
             Self::LoadAncestors => "load_ancestors",
             Self::GetEntries => "get_entries",
             Self::GetEntriesWithProofs => "get_entries_with_proofs",
+            Self::GetRangeProof => "get_range_proof",
         }
     }
 }
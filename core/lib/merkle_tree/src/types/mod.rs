@@ -1,5 +1,7 @@
 //! Basic storage types.
 
+use std::ops;
+
 use zksync_types::{H256, U256};
 
 pub(crate) use self::internal::{
@@ -127,6 +129,40 @@ pub struct TreeEntryWithProof {
     pub merkle_path: Vec<ValueHash>,
 }
 
+/// Merkle range proof proving that a contiguous range of keys in a tree contains the specified entries
+/// and no other entries.
+///
+/// The range is bounded by the start and end keys (both inclusive), for which entries are supplied together
+/// with Merkle proofs. Entries for keys strictly between the bounds do not need proofs; the proof is verified
+/// by recomputing the tree root hash with a [`TreeRangeDigest`](crate::TreeRangeDigest). Thus, omitting
+/// an existing entry or adding a non-existing one leads to a root hash mismatch.
+#[derive(Debug, Clone)]
+pub struct TreeRangeProof {
+    /// Entry for the start key of the range together with a Merkle proof. May be [empty](TreeEntry::is_empty()).
+    pub start: TreeEntryWithProof,
+    /// All existing entries with keys strictly between the start and end keys, ordered by key.
+    pub entries: Vec<TreeEntry>,
+    /// Entry for the end key of the range together with a Merkle proof. May be [empty](TreeEntry::is_empty()).
+    pub end: TreeEntryWithProof,
+}
+
+impl TreeRangeProof {
+    /// Returns the key range covered by this proof.
+    pub fn key_range(&self) -> ops::RangeInclusive<Key> {
+        self.start.base.key..=self.end.base.key
+    }
+
+    /// Iterates over all existing entries in the proven range, including the start and end entries
+    /// if they are not empty.
+    pub fn existing_entries(&self) -> impl Iterator<Item = &TreeEntry> + '_ {
+        let start = Some(&self.start.base).filter(|entry| !entry.is_empty());
+        let end = (self.end.base.key != self.start.base.key)
+            .then_some(&self.end.base)
+            .filter(|entry| !entry.is_empty());
+        start.into_iter().chain(&self.entries).chain(end)
+    }
+}
+
 /// Output of inserting a block of entries into a Merkle tree.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BlockOutput {
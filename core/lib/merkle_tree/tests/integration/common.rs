@@ -19,13 +19,25 @@ pub fn generate_key_value_pairs(indexes: impl Iterator<Item = u64>) -> Vec<TreeE
 }
 
 pub fn compute_tree_hash(kvs: impl Iterator<Item = TreeEntry>) -> H256 {
+    compute_tree_hash_with_hasher(&Blake2Hasher, kvs)
+}
+
+pub fn compute_tree_hash_with_hasher<H>(hasher: &H, kvs: impl Iterator<Item = TreeEntry>) -> H256
+where
+    H: Hasher<Hash = H256> + HashTree,
+{
     let kvs_with_indices = kvs.map(|entry| (entry.key, entry.value, entry.leaf_index));
-    compute_tree_hash_with_indices(kvs_with_indices)
+    compute_tree_hash_with_indices(hasher, kvs_with_indices)
 }
 
 // The extended version of computations used in `InternalNode`.
-fn compute_tree_hash_with_indices(kvs: impl Iterator<Item = (U256, H256, u64)>) -> H256 {
-    let hasher = Blake2Hasher;
+fn compute_tree_hash_with_indices<H>(
+    hasher: &H,
+    kvs: impl Iterator<Item = (U256, H256, u64)>,
+) -> H256
+where
+    H: Hasher<Hash = H256> + HashTree,
+{
     let mut empty_tree_hash = hasher.hash_bytes(&[0_u8; 40]);
     let level = kvs.map(|(key, value, leaf_index)| {
         let mut bytes = [0_u8; 40];
@@ -112,6 +124,6 @@ impl TreeMap {
             .0
             .iter()
             .map(|(key, (value, leaf_index))| (*key, *value, *leaf_index));
-        compute_tree_hash_with_indices(entries)
+        compute_tree_hash_with_indices(&Blake2Hasher, entries)
     }
 }
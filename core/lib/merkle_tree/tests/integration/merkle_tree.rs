@@ -4,7 +4,7 @@ use std::{cmp, mem};
 
 use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 use test_casing::test_casing;
-use zksync_crypto::hasher::blake2::Blake2Hasher;
+use zksync_crypto::hasher::{blake2::Blake2Hasher, keccak::KeccakHasher};
 use zksync_merkle_tree::{
     Database, HashTree, MerkleTree, PatchSet, Patched, TreeEntry, TreeInstruction, TreeLogEntry,
     TreeRangeDigest,
@@ -12,7 +12,8 @@ use zksync_merkle_tree::{
 use zksync_types::{AccountTreeId, Address, StorageKey, H256, U256};
 
 use crate::common::{
-    compute_tree_hash, convert_to_writes, generate_key_value_pairs, ENTRIES_AND_HASH,
+    compute_tree_hash, compute_tree_hash_with_hasher, convert_to_writes, generate_key_value_pairs,
+    ENTRIES_AND_HASH,
 };
 
 #[test]
@@ -543,6 +544,39 @@ fn range_proofs_with_random_ranges() {
     }
 }
 
+#[test_casing(8, KV_COUNTS)]
+fn tree_with_keccak_hasher(kv_count: u64) {
+    let mut tree = MerkleTree::with_hasher(PatchSet::default(), KeccakHasher).unwrap();
+    let kvs = generate_key_value_pairs(0..kv_count);
+    let expected_hash = compute_tree_hash_with_hasher(&KeccakHasher, kvs.iter().copied());
+    assert_ne!(expected_hash, compute_tree_hash(kvs.iter().copied()));
+
+    let instructions = convert_to_writes(&kvs);
+    let output = tree.extend_with_proofs(instructions.clone()).unwrap();
+    assert_eq!(output.root_hash(), Some(expected_hash));
+    output
+        .verify_proofs(&KeccakHasher, KeccakHasher.empty_tree_hash(), &instructions)
+        .unwrap();
+    // Proofs must not verify with a different hasher.
+    output
+        .verify_proofs(&Blake2Hasher, Blake2Hasher.empty_tree_hash(), &instructions)
+        .unwrap_err();
+
+    let keys: Vec<_> = kvs.iter().map(|entry| entry.key).collect();
+    let entries = tree.entries_with_proofs(0, &keys).unwrap();
+    for (input_entry, entry) in kvs.iter().zip(entries) {
+        assert_eq!(entry.base, *input_entry);
+        entry.verify(&KeccakHasher, expected_hash);
+    }
+
+    let range_proof = tree.range_proof(0, U256::zero()..=U256::MAX).unwrap();
+    assert_eq!(range_proof.entries.len(), kvs.len());
+    range_proof.verify(&KeccakHasher, expected_hash).unwrap();
+    range_proof
+        .verify(&Blake2Hasher, compute_tree_hash(kvs.iter().copied()))
+        .unwrap_err();
+}
+
 /// RocksDB-specific tests.
 mod rocksdb {
     use std::collections::BTreeMap;
@@ -675,6 +709,28 @@ mod rocksdb {
         );
     }
 
+    #[test]
+    fn tree_with_keccak_hasher_tags() {
+        let Harness { db, dir } = Harness::new();
+        let mut tree = MerkleTree::with_hasher(db, KeccakHasher).unwrap();
+        let output = tree
+            .extend(vec![TreeEntry::new(U256::zero(), 1, H256::zero())])
+            .unwrap();
+        drop(tree);
+
+        let db = RocksDBWrapper::new(dir.path()).unwrap();
+        let tree = MerkleTree::with_hasher(db, KeccakHasher).unwrap();
+        assert_eq!(tree.latest_root_hash(), output.root_hash);
+        drop(tree);
+
+        let db = RocksDBWrapper::new(dir.path()).unwrap();
+        let err = MerkleTree::new(db).unwrap_err().to_string();
+        assert!(
+            err.contains("Mismatch between the provided tree hasher `blake2s256`"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn tree_tags_mismatch_with_cold_restart() {
         let Harness { db, dir } = Harness::new();
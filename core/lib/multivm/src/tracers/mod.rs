@@ -3,9 +3,11 @@ mod multivm_dispatcher;
 pub mod old_tracers;
 pub mod prestate_tracer;
 pub mod storage_invocation;
+pub mod struct_log_tracer;
 pub mod validator;
 
 pub use call_tracer::CallTracer;
 pub use multivm_dispatcher::TracerDispatcher;
 pub use prestate_tracer::PrestateTracer;
 pub use storage_invocation::StorageInvocations;
+pub use struct_log_tracer::StructLogTracer;
@@ -4,12 +4,15 @@ use once_cell::sync::OnceCell;
 use zksync_state::{StoragePtr, WriteStorage};
 use zksync_types::{
     get_code_key, get_nonce_key, web3::keccak256, AccountTreeId, Address, StorageKey, StorageValue,
-    H160, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
 use zksync_utils::{address_to_h256, h256_to_u256};
 
+use crate::glue::tracers::IntoOldVmTracer;
+
 pub mod vm_1_4_1;
 pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
 pub mod vm_latest;
 pub mod vm_refunds_enhancement;
 pub mod vm_virtual_blocks;
@@ -45,24 +48,68 @@ impl fmt::Display for Account {
     }
 }
 
-type State = HashMap<Address, Account>;
+pub type State = HashMap<Address, Account>;
+
+/// Result of [`PrestateTracer`]: `(pre, post)` states. In the diff mode, `pre` and `post` contain only accounts
+/// that were modified; otherwise, `pre` is empty and `post` contains the state of all touched accounts
+/// before the execution.
+pub type PrestateTracerResult = (State, State);
 
 #[derive(Debug, Clone)]
 pub struct PrestateTracer {
     pub pre: State,
     pub post: State,
     pub config: PrestateTracerConfig,
-    pub result: Arc<OnceCell<(State, State)>>,
+    pub result: Arc<OnceCell<PrestateTracerResult>>,
+    /// Storage values modified before the traced execution, snapshotted when the tracer is initialized.
+    /// Together with the storage read cache, allows to recover the state before the execution.
+    initial_modified_values: HashMap<StorageKey, StorageValue>,
 }
 
 impl PrestateTracer {
-    pub fn new(diff_mode: bool, result: Arc<OnceCell<(State, State)>>) -> Self {
+    pub fn new(diff_mode: bool, result: Arc<OnceCell<PrestateTracerResult>>) -> Self {
         Self {
             pre: Default::default(),
             post: Default::default(),
             config: PrestateTracerConfig { diff_mode },
             result,
+            initial_modified_values: HashMap::new(),
+        }
+    }
+
+    fn initialize<S: WriteStorage>(&mut self, storage: &StoragePtr<S>) {
+        self.initial_modified_values = storage.borrow().modified_storage_keys().clone();
+    }
+
+    /// Computes the tracer result. `touched_keys` are the storage keys read or written during the execution;
+    /// they are only used outside the diff mode.
+    fn finalize<S: WriteStorage>(
+        &mut self,
+        storage: &StoragePtr<S>,
+        touched_keys: impl Iterator<Item = StorageKey>,
+    ) {
+        let mut storage = storage.borrow_mut();
+        let modified_keys: Vec<_> = storage.modified_storage_keys().keys().copied().collect();
+        if self.config.diff_mode {
+            let slots = group_slots_by_account(modified_keys.into_iter());
+            self.pre = collect_account_states(&slots, |key| {
+                initial_value(&self.initial_modified_values, &mut *storage, key)
+            });
+            self.post = collect_account_states(&slots, |key| storage.read_value(key));
+        } else {
+            let slots = group_slots_by_account(touched_keys.chain(modified_keys));
+            self.pre = State::new();
+            self.post = collect_account_states(&slots, |key| {
+                initial_value(&self.initial_modified_values, &mut *storage, key)
+            });
         }
+        drop(storage);
+        process_result(
+            &self.result,
+            self.pre.clone(),
+            self.post.clone(),
+            self.config.diff_mode,
+        );
     }
 }
 
@@ -71,42 +118,59 @@ pub struct PrestateTracerConfig {
     diff_mode: bool,
 }
 
-pub fn process_modified_storage_keys<S>(
-    prestate: State,
-    storage: &StoragePtr<S>,
-) -> HashMap<H160, Account>
-where
-    S: WriteStorage,
-{
-    let cloned_storage = &storage.clone();
-    let mut initial_storage_ref = cloned_storage.as_ref().borrow_mut();
-
-    initial_storage_ref
-        .modified_storage_keys()
-        .clone()
+/// Old VMs are not supported by this tracer.
+impl IntoOldVmTracer for PrestateTracer {}
+
+/// Returns the value of a storage key before the traced execution.
+fn initial_value<S: WriteStorage>(
+    initial_modified_values: &HashMap<StorageKey, StorageValue>,
+    storage: &mut S,
+    key: &StorageKey,
+) -> StorageValue {
+    if let Some(&value) = initial_modified_values.get(key) {
+        return value;
+    }
+    // The storage caches the original value of each key on the first read or write, so if the key is not cached,
+    // it wasn't modified and can be read directly.
+    if let Some(&value) = storage.read_storage_keys().get(key) {
+        value
+    } else {
+        storage.read_value(key)
+    }
+}
+
+fn group_slots_by_account(keys: impl Iterator<Item = StorageKey>) -> HashMap<Address, Vec<H256>> {
+    let mut slots = HashMap::<_, Vec<_>>::new();
+    for key in keys {
+        let account_slots = slots.entry(*key.address()).or_default();
+        if !account_slots.contains(key.key()) {
+            account_slots.push(*key.key());
+        }
+    }
+    slots
+}
+
+fn collect_account_states(
+    slots: &HashMap<Address, Vec<H256>>,
+    mut read_value: impl FnMut(&StorageKey) -> StorageValue,
+) -> State {
+    slots
         .iter()
-        .filter(|k| !prestate.contains_key(k.0.account().address()))
-        .map(|k| {
-            (
-                *(k.0.account().address()),
-                Account {
-                    balance: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_balance_key(k.0.account())),
-                    )),
-                    code: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_code_key(k.0.account().address())),
-                    )),
-                    nonce: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_nonce_key(k.0.account().address())),
-                    )),
-                    storage: Some(get_storage_if_present(
-                        k.0.account(),
-                        initial_storage_ref.modified_storage_keys(),
-                    )),
-                },
-            )
+        .map(|(address, slots)| {
+            let account_id = AccountTreeId::new(*address);
+            let storage = slots
+                .iter()
+                .map(|&slot| (slot, read_value(&StorageKey::new(account_id, slot))))
+                .collect();
+            let account = Account {
+                balance: Some(h256_to_u256(read_value(&get_balance_key(&account_id)))),
+                code: Some(h256_to_u256(read_value(&get_code_key(address)))),
+                nonce: Some(h256_to_u256(read_value(&get_nonce_key(address)))),
+                storage: Some(storage),
+            };
+            (*address, account)
         })
-        .collect::<State>()
+        .collect()
 }
 
 fn get_balance_key(account: &AccountTreeId) -> StorageKey {
@@ -116,53 +180,15 @@ fn get_balance_key(account: &AccountTreeId) -> StorageKey {
     StorageKey::new(AccountTreeId::new(L2_BASE_TOKEN_ADDRESS), balance_key)
 }
 
-fn get_storage_if_present(
-    account: &AccountTreeId,
-    modified_storage_keys: &HashMap<StorageKey, StorageValue>,
-) -> HashMap<H256, H256> {
-    //check if there is a Storage Key struct with an account field that matches the account and return the key as the key and the Storage Value as the value
-    modified_storage_keys
-        .iter()
-        .filter(|(k, _)| k.account() == account)
-        .map(|(k, v)| (*k.key(), *v))
-        .collect()
-}
-
-fn process_result(result: &Arc<OnceCell<(State, State)>>, mut pre: State, post: State) {
-    pre.retain(|k, v| {
-        if let Some(post_v) = post.get(k) {
-            if v != post_v {
-                return true;
-            }
-        }
-        false
-    });
+fn process_result(
+    result: &Arc<OnceCell<PrestateTracerResult>>,
+    mut pre: State,
+    mut post: State,
+    diff_mode: bool,
+) {
+    if diff_mode {
+        pre.retain(|address, account| post.get(address) != Some(account));
+        post.retain(|address, _| pre.contains_key(address));
+    }
     result.set((pre, post)).unwrap();
 }
-
-fn get_account_data<T: StorageAccess>(
-    account_key: &StorageKey,
-    state: &T,
-    storage: &HashMap<StorageKey, StorageValue>,
-) -> (Address, Account) {
-    let address = *(account_key.account().address());
-    let balance = state.read_from_storage(&get_balance_key(account_key.account()));
-    let code = state.read_from_storage(&get_code_key(account_key.account().address()));
-    let nonce = state.read_from_storage(&get_nonce_key(account_key.account().address()));
-    let storage = get_storage_if_present(account_key.account(), storage);
-
-    (
-        address,
-        Account {
-            balance: Some(balance),
-            code: Some(code),
-            nonce: Some(nonce),
-            storage: Some(storage),
-        },
-    )
-}
-
-// Define a trait that abstracts storage access
-trait StorageAccess {
-    fn read_from_storage(&self, key: &StorageKey) -> U256;
-}
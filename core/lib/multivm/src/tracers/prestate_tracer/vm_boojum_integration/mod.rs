@@ -0,0 +1,31 @@
+use zksync_state::WriteStorage;
+
+use super::PrestateTracer;
+use crate::{
+    interface::dyn_tracers::vm_1_4_0::DynTracer,
+    vm_boojum_integration::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PrestateTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
+    fn initialize_tracer(&mut self, state: &mut ZkSyncVmState<S, H>) {
+        self.initialize(&state.storage.storage.inner().get_ptr());
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: crate::interface::tracer::VmExecutionStopReason,
+    ) {
+        let touched_keys = state
+            .storage
+            .read_keys
+            .inner()
+            .keys()
+            .chain(state.storage.written_keys.inner().keys())
+            .copied();
+        self.finalize(&state.storage.storage.inner().get_ptr(), touched_keys);
+    }
+}
@@ -0,0 +1,118 @@
+//! Opcode-level tracer producing `structLogs`-style traces.
+
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_state::WriteStorage;
+use zksync_types::{
+    api::{StructLog, StructLoggerConfig},
+    StorageKey,
+};
+
+use crate::{glue::tracers::IntoOldVmTracer, interface::dyn_tracers};
+
+pub mod vm_latest;
+
+/// Tracer recording every instruction executed outside the bootloader.
+///
+/// Only the latest VM version is supported; for older versions, the tracer is a no-op and produces an empty trace.
+#[derive(Debug, Clone)]
+pub struct StructLogTracer {
+    config: StructLoggerConfig,
+    logs: Vec<StructLog>,
+    pending_step: Option<PendingStep>,
+    result: Arc<OnceCell<Vec<StructLog>>>,
+}
+
+/// Instruction that has started, but not finished executing.
+#[derive(Debug, Clone)]
+struct PendingStep {
+    log: StructLog,
+    /// Storage slot accessed by the instruction. The slot value is read after the instruction is executed.
+    storage_key: Option<StorageKey>,
+}
+
+impl StructLogTracer {
+    pub fn new(config: StructLoggerConfig, result: Arc<OnceCell<Vec<StructLog>>>) -> Self {
+        Self {
+            config,
+            logs: vec![],
+            pending_step: None,
+            result,
+        }
+    }
+
+    fn is_limit_reached(&self) -> bool {
+        self.config.limit != 0 && self.logs.len() >= self.config.limit
+    }
+
+    fn store_result(&mut self) {
+        let result = std::mem::take(&mut self.logs);
+        self.result.set(result).unwrap();
+    }
+}
+
+impl IntoOldVmTracer for StructLogTracer {}
+
+impl<S, H: crate::vm_1_4_2::HistoryMode>
+    dyn_tracers::vm_1_4_1::DynTracer<S, crate::vm_1_4_2::SimpleMemory<H>> for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_1_4_2::HistoryMode> crate::vm_1_4_2::VmTracer<S, H>
+    for StructLogTracer
+{
+}
+
+impl<S, H: crate::vm_1_4_1::HistoryMode>
+    dyn_tracers::vm_1_4_1::DynTracer<S, crate::vm_1_4_1::SimpleMemory<H>> for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_1_4_1::HistoryMode> crate::vm_1_4_1::VmTracer<S, H>
+    for StructLogTracer
+{
+}
+
+impl<S, H: crate::vm_boojum_integration::HistoryMode>
+    dyn_tracers::vm_1_4_0::DynTracer<S, crate::vm_boojum_integration::SimpleMemory<H>>
+    for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_boojum_integration::HistoryMode>
+    crate::vm_boojum_integration::VmTracer<S, H> for StructLogTracer
+{
+}
+
+impl<S, H: crate::vm_refunds_enhancement::HistoryMode>
+    dyn_tracers::vm_1_3_3::DynTracer<S, crate::vm_refunds_enhancement::SimpleMemory<H>>
+    for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_refunds_enhancement::HistoryMode>
+    crate::vm_refunds_enhancement::VmTracer<S, H> for StructLogTracer
+{
+}
+
+impl<S, H: crate::vm_virtual_blocks::HistoryMode>
+    dyn_tracers::vm_1_3_3::DynTracer<S, crate::vm_virtual_blocks::SimpleMemory<H>>
+    for StructLogTracer
+{
+}
+
+impl<H: crate::vm_virtual_blocks::HistoryMode> crate::vm_virtual_blocks::ExecutionEndTracer<H>
+    for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_virtual_blocks::HistoryMode>
+    crate::vm_virtual_blocks::ExecutionProcessing<S, H> for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_virtual_blocks::HistoryMode>
+    crate::vm_virtual_blocks::VmTracer<S, H> for StructLogTracer
+{
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use zk_evm_1_5_0::{
+    tracing::{AfterExecutionData, BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode, UMAOpcode},
+};
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_system_constants::BOOTLOADER_ADDRESS;
+use zksync_types::{
+    api::{MemoryRegion, StructLog, StructLogMemoryWrite},
+    AccountTreeId, StorageKey,
+};
+use zksync_utils::u256_to_h256;
+
+use crate::{
+    interface::{tracer::VmExecutionStopReason, traits::tracers::dyn_tracers::vm_1_5_0::DynTracer},
+    tracers::struct_log_tracer::{PendingStep, StructLogTracer},
+    vm_latest::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let current_frame = &state.vm_local_state.callstack.current;
+        if current_frame.this_address == BOOTLOADER_ADDRESS || self.is_limit_reached() {
+            return;
+        }
+
+        let config = self.config;
+        let opcode = data.opcode.variant.opcode;
+        let registers = (!config.disable_stack).then(|| {
+            let registers = state.vm_local_state.registers.iter();
+            registers.map(|register| register.value).collect()
+        });
+
+        let memory_region = match opcode {
+            Opcode::UMA(UMAOpcode::HeapWrite) => Some(MemoryRegion::Heap),
+            Opcode::UMA(UMAOpcode::AuxHeapWrite) => Some(MemoryRegion::AuxHeap),
+            _ => None,
+        };
+        let memory_write = memory_region
+            .filter(|_| !config.disable_memory)
+            .map(|region| StructLogMemoryWrite {
+                region,
+                // For heap writes, the offset is contained in the lowest 32 bits of the first operand.
+                offset: data.src0_value.value.low_u32(),
+                value: data.src1_value.value,
+            });
+
+        let storage_key = match opcode {
+            Opcode::Log(LogOpcode::StorageRead | LogOpcode::StorageWrite)
+                if !config.disable_storage =>
+            {
+                let account = AccountTreeId::new(current_frame.this_address);
+                Some(StorageKey::new(
+                    account,
+                    u256_to_h256(data.src0_value.value),
+                ))
+            }
+            _ => None,
+        };
+
+        let log = StructLog {
+            pc: current_frame.pc,
+            op: format!("{opcode:?}"),
+            gas: current_frame.ergs_remaining.into(),
+            gas_cost: 0,
+            depth: state.vm_local_state.callstack.inner.len(),
+            address: current_frame.this_address,
+            registers,
+            memory_write,
+            storage: None,
+        };
+        self.pending_step = Some(PendingStep { log, storage_key });
+    }
+
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        _data: AfterExecutionData,
+        _memory: &SimpleMemory<H>,
+        storage: StoragePtr<S>,
+    ) {
+        let Some(PendingStep {
+            mut log,
+            storage_key,
+        }) = self.pending_step.take()
+        else {
+            return;
+        };
+
+        // Instructions changing the call frame (far calls, returns, panics etc.) have their cost
+        // accounted for in the parent or child frame, so we don't compute it here.
+        if state.vm_local_state.callstack.inner.len() == log.depth {
+            let ergs_remaining = u64::from(state.vm_local_state.callstack.current.ergs_remaining);
+            log.gas_cost = log.gas.saturating_sub(ergs_remaining);
+        }
+        if let Some(key) = storage_key {
+            let value = storage.borrow_mut().read_value(&key);
+            log.storage = Some(HashMap::from([(*key.key(), value)]));
+        }
+        self.logs.push(log);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result()
+    }
+}
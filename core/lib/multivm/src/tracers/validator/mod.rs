@@ -7,8 +7,10 @@ use zksync_system_constants::{
     L2_BASE_TOKEN_ADDRESS, MSG_VALUE_SIMULATOR_ADDRESS, SYSTEM_CONTEXT_ADDRESS,
 };
 use zksync_types::{
-    vm_trace::ViolatedValidationRule, web3::keccak256, AccountTreeId, Address, StorageKey,
-    VmVersion, H256, U256,
+    api::{ValidationCallContext, ValidationViolation},
+    vm_trace::ViolatedValidationRule,
+    web3::keccak256,
+    AccountTreeId, Address, StorageKey, VmVersion, H256, U256,
 };
 use zksync_utils::{be_bytes_to_safe_address, u256_to_account_address, u256_to_h256};
 
@@ -44,6 +46,9 @@ pub struct ValidationTracer<H> {
     computational_gas_limit: u32,
     vm_version: VmVersion,
     pub result: Arc<OnceCell<ViolatedValidationRule>>,
+    /// Violations collected if the tracer is configured to not stop on the first violation.
+    violations: Vec<ValidationViolation>,
+    violations_result: Option<Arc<OnceCell<Vec<ValidationViolation>>>>,
     _marker: PhantomData<fn(H) -> H>,
 }
 
@@ -70,13 +75,34 @@ impl<H> ValidationTracer<H> {
                 computational_gas_limit: params.computational_gas_limit,
                 vm_version,
                 result: result.clone(),
+                violations: vec![],
+                violations_result: None,
                 _marker: Default::default(),
             },
             result,
         )
     }
 
-    fn process_validation_round_result(&mut self, result: ValidationRoundResult) {
+    /// Configures the tracer to continue execution after a validation rule is violated, collecting all distinct
+    /// violations into `result`. Call contexts of violations are only recorded for the latest VM version;
+    /// for older versions, the tracer still stops on the first violation and doesn't populate `result`.
+    pub fn collecting_violations(
+        mut self,
+        result: Arc<OnceCell<Vec<ValidationViolation>>>,
+    ) -> Self {
+        self.violations_result = Some(result);
+        self
+    }
+
+    fn is_collecting_violations(&self) -> bool {
+        self.violations_result.is_some()
+    }
+
+    fn process_validation_round_result(
+        &mut self,
+        result: ValidationRoundResult,
+        context: Option<ValidationCallContext>,
+    ) {
         match result {
             Ok(NewTrustedValidationItems {
                 new_allowed_slots,
@@ -86,6 +112,17 @@ impl<H> ValidationTracer<H> {
                 self.trusted_addresses.extend(new_trusted_addresses);
             }
             Err(err) => {
+                if self.is_collecting_violations() {
+                    let violation = ValidationViolation::new(&err, context);
+                    let is_new = self
+                        .violations
+                        .iter()
+                        .all(|recorded| recorded.rule != violation.rule);
+                    if is_new {
+                        self.violations.push(violation);
+                    }
+                }
+
                 if self.result.get().is_some() {
                     tracing::trace!("Validation error is already set, skipping");
                     return;
@@ -180,6 +217,13 @@ impl<H> ValidationTracer<H> {
         }
     }
 
+    fn store_violations(&mut self) {
+        if let Some(result) = &self.violations_result {
+            let violations = std::mem::take(&mut self.violations);
+            result.set(violations).ok();
+        }
+    }
+
     pub fn params(&self) -> ValidationTracerParams {
         ValidationTracerParams {
             user_address: self.user_address,
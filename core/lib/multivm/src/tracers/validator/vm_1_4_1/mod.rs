@@ -145,7 +145,7 @@ impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H::Vm1_4_1>>
 
             let validation_round_result =
                 self.check_user_restrictions_vm_1_4_1(state, data, memory, storage);
-            self.process_validation_round_result(validation_round_result);
+            self.process_validation_round_result(validation_round_result, None);
         }
 
         let hook = VmHook::from_opcode_memory(&state, &data);
@@ -145,7 +145,7 @@ impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H::VmBoojumInteg
 
             let validation_round_result =
                 self.check_user_restrictions_vm_boojum_integration(state, data, memory, storage);
-            self.process_validation_round_result(validation_round_result);
+            self.process_validation_round_result(validation_round_result, None);
         }
 
         let hook = VmHook::from_opcode_memory(&state, &data);
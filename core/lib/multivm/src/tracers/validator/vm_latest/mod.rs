@@ -5,14 +5,15 @@ use zk_evm_1_5_0::{
 use zksync_state::{StoragePtr, WriteStorage};
 use zksync_system_constants::KECCAK256_PRECOMPILE_ADDRESS;
 use zksync_types::{
-    get_code_key, vm_trace::ViolatedValidationRule, AccountTreeId, StorageKey, H256,
+    api::ValidationCallContext, get_code_key, vm_trace::ViolatedValidationRule, AccountTreeId,
+    StorageKey, H256,
 };
 use zksync_utils::{h256_to_account_address, u256_to_account_address, u256_to_h256};
 
 use crate::{
     interface::{
         traits::tracers::dyn_tracers::vm_1_5_0::DynTracer,
-        types::tracer::{TracerExecutionStatus, TracerExecutionStopReason},
+        types::tracer::{TracerExecutionStatus, TracerExecutionStopReason, VmExecutionStopReason},
         Halt,
     },
     tracers::validator::{
@@ -143,7 +144,10 @@ impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H::Vm1_5_0>>
 
             let validation_round_result =
                 self.check_user_restrictions_vm_latest(state, data, memory, storage);
-            self.process_validation_round_result(validation_round_result);
+            let context = validation_round_result
+                .is_err()
+                .then(|| call_context(state));
+            self.process_validation_round_result(validation_round_result, context);
         }
 
         let hook = VmHook::from_opcode_memory(&state, &data, self.vm_version.try_into().unwrap());
@@ -187,6 +191,9 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H::Vm1_5_0> for ValidationTrac
         if self.should_stop_execution {
             return TracerExecutionStatus::Stop(TracerExecutionStopReason::Finish);
         }
+        if self.is_collecting_violations() {
+            return TracerExecutionStatus::Continue;
+        }
         if let Some(result) = self.result.get() {
             return TracerExecutionStatus::Stop(TracerExecutionStopReason::Abort(
                 Halt::TracerCustom(format!("Validation error: {:#?}", result)),
@@ -194,4 +201,24 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H::Vm1_5_0> for ValidationTrac
         }
         TracerExecutionStatus::Continue
     }
+
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H::Vm1_5_0>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_violations();
+    }
+}
+
+fn call_context(state: VmLocalStateData<'_>) -> ValidationCallContext {
+    let current_frame = &state.vm_local_state.callstack.current;
+    ValidationCallContext {
+        address: current_frame.this_address,
+        code_address: current_frame.code_address,
+        msg_sender: current_frame.msg_sender,
+        pc: current_frame.pc,
+        depth: state.vm_local_state.callstack.inner.len(),
+    }
 }
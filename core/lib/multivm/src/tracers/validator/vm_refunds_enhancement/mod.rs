@@ -145,7 +145,7 @@ impl<S: WriteStorage, H: HistoryMode>
 
             let validation_round_result =
                 self.check_user_restrictions_vm_refunds_enhancement(state, data, memory, storage);
-            self.process_validation_round_result(validation_round_result);
+            self.process_validation_round_result(validation_round_result, None);
         }
 
         let hook = VmHook::from_opcode_memory(&state, &data);
@@ -141,7 +141,7 @@ impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H::VmVirtualBloc
 
             let validation_round_result =
                 self.check_user_restrictions_vm_virtual_blocks(state, data, memory, storage);
-            self.process_validation_round_result(validation_round_result);
+            self.process_validation_round_result(validation_round_result, None);
         }
 
         let hook = VmHook::from_opcode_memory(&state, &data);
@@ -23,6 +23,7 @@ mod rollbacks;
 mod sekp256r1;
 mod simple_execution;
 mod storage;
+mod struct_log_tracer;
 mod tester;
 mod tracing_execution_error;
 mod transfer;
@@ -1,8 +1,13 @@
 use std::sync::Arc;
 
 use once_cell::sync::OnceCell;
+use zksync_state::ReadStorage;
 use zksync_test_account::TxType;
-use zksync_types::{utils::deployed_address_create, Execute, U256};
+use zksync_types::{
+    utils::{deployed_address_create, storage_key_for_eth_balance},
+    Address, Execute, H256, L2_BASE_TOKEN_ADDRESS, U256,
+};
+use zksync_utils::h256_to_u256;
 
 use crate::{
     interface::{TxExecutionMode, VmExecutionMode, VmInterface},
@@ -141,3 +146,57 @@ fn test_prestate_tracer_diff_mode() {
         Some(U256::from(200000))
     );
 }
+
+#[test]
+fn test_prestate_tracer_diff_mode_reports_original_values() {
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .build();
+
+    let account = &mut vm.rich_accounts[0];
+    let sender = account.address;
+    let recipient = Address::repeat_byte(0x23);
+    let sender_balance_key = storage_key_for_eth_balance(&sender);
+    let recipient_balance_key = storage_key_for_eth_balance(&recipient);
+    let initial_sender_balance = vm.storage.borrow_mut().read_value(&sender_balance_key);
+
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: recipient,
+            calldata: vec![],
+            value: U256::from(100_000),
+            factory_deps: None,
+        },
+        None,
+    );
+    vm.vm.push_transaction(tx);
+    let prestate_tracer_result = Arc::new(OnceCell::default());
+    let prestate_tracer = PrestateTracer::new(true, prestate_tracer_result.clone());
+    vm.vm.inspect(
+        prestate_tracer.into_tracer_pointer().into(),
+        VmExecutionMode::OneTx,
+    );
+
+    let (pre, post) = Arc::try_unwrap(prestate_tracer_result)
+        .unwrap()
+        .take()
+        .unwrap_or_default();
+    // Balances are stored in the base token contract storage, which is first modified during the traced execution.
+    let pre_storage = pre[&L2_BASE_TOKEN_ADDRESS].storage.as_ref().unwrap();
+    assert_eq!(
+        pre_storage[sender_balance_key.key()],
+        initial_sender_balance
+    );
+    assert_eq!(pre_storage[recipient_balance_key.key()], H256::zero());
+    let post_storage = post[&L2_BASE_TOKEN_ADDRESS].storage.as_ref().unwrap();
+    assert_eq!(
+        h256_to_u256(post_storage[recipient_balance_key.key()]),
+        U256::from(100_000)
+    );
+    assert!(
+        h256_to_u256(post_storage[sender_balance_key.key()]) < h256_to_u256(initial_sender_balance)
+    );
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_system_constants::BOOTLOADER_ADDRESS;
+use zksync_test_account::TxType;
+use zksync_types::api::{StructLog, StructLoggerConfig};
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    tracers::StructLogTracer,
+    vm_latest::{
+        constants::BATCH_COMPUTATIONAL_GAS_LIMIT, tests::tester::VmTesterBuilder, HistoryEnabled,
+        ToTracerPointer,
+    },
+};
+
+fn trace_test_contract_call(config: StructLoggerConfig) -> Vec<StructLog> {
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .build();
+
+    vm.deploy_test_contract();
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_test_contract_transaction(
+        vm.test_contract.unwrap(),
+        false,
+        Default::default(),
+        true,
+        TxType::L2,
+    );
+    vm.vm.push_transaction(tx);
+
+    let result = Arc::new(OnceCell::default());
+    let tracer = StructLogTracer::new(config, result.clone());
+    let res = vm
+        .vm
+        .inspect(tracer.into_tracer_pointer().into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed(), "{:?}", res.result);
+
+    Arc::try_unwrap(result).unwrap().take().unwrap_or_default()
+}
+
+#[test]
+fn test_struct_log_tracer() {
+    let logs = trace_test_contract_call(StructLoggerConfig::default());
+    assert!(!logs.is_empty());
+    assert!(logs.iter().all(|log| log.address != BOOTLOADER_ADDRESS));
+    assert!(logs.iter().all(|log| log.registers.is_some()));
+    assert!(logs.iter().any(|log| log.storage.is_some()));
+    assert!(logs.iter().any(|log| log.memory_write.is_some()));
+    assert!(logs.iter().any(|log| log.gas_cost > 0));
+}
+
+#[test]
+fn test_struct_log_tracer_with_disabled_fields_and_limit() {
+    let config = StructLoggerConfig {
+        disable_storage: true,
+        disable_stack: true,
+        disable_memory: true,
+        limit: 10,
+    };
+    let logs = trace_test_contract_call(config);
+    assert_eq!(logs.len(), 10);
+    for log in &logs {
+        assert!(log.registers.is_none());
+        assert!(log.memory_write.is_none());
+        assert!(log.storage.is_none());
+    }
+}
@@ -0,0 +1,260 @@
+//! Object store middleware compressing objects and verifying their integrity.
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use tokio::task;
+
+use crate::raw::{Bucket, KeysPage, ObjectStore, ObjectStoreError};
+
+/// Magic bytes prepended to packed objects. Legacy objects are either gzip-compressed (start with `1f 8b`)
+/// or `bincode`-serialized, so they are extremely unlikely to start with this sequence.
+const MAGIC: [u8; 7] = *b"\xffZKSOBJ";
+/// Current version of the packed object format.
+const FORMAT_VERSION: u8 = 1;
+/// Length of the packed object header: magic, version, compression method and payload checksum.
+const HEADER_LEN: usize = MAGIC.len() + 2 + CHECKSUM_LEN;
+const CHECKSUM_LEN: usize = 32;
+
+/// Compression method used for a packed object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum Compression {
+    /// No compression; used if compression does not reduce the object size (e.g., for gzipped objects).
+    None = 0,
+    Zstd = 1,
+}
+
+impl Compression {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::None),
+            1 => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// Packs an object: compresses it with zstd and prepends the header with the checksum of the uncompressed object.
+fn pack(value: &[u8]) -> Result<Vec<u8>, ObjectStoreError> {
+    let checksum = Sha256::digest(value);
+    // Level 0 corresponds to the default zstd compression level.
+    let compressed = zstd::bulk::compress(value, 0)
+        .map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+    let (compression, payload) = if compressed.len() < value.len() {
+        (Compression::Zstd, compressed.as_slice())
+    } else {
+        (Compression::None, value)
+    };
+
+    let mut packed = Vec::with_capacity(HEADER_LEN + payload.len());
+    packed.extend_from_slice(&MAGIC);
+    packed.push(FORMAT_VERSION);
+    packed.push(compression as u8);
+    packed.extend_from_slice(&checksum);
+    packed.extend_from_slice(payload);
+    Ok(packed)
+}
+
+/// Unpacks an object packed with [`pack()`]. Objects without the packed object header are returned as-is.
+fn unpack(bucket: Bucket, key: &str, packed: Vec<u8>) -> Result<Vec<u8>, ObjectStoreError> {
+    if !packed.starts_with(&MAGIC) {
+        return Ok(packed); // legacy object
+    }
+    let corrupted =
+        |message: String| ObjectStoreError::Corrupted(format!("{bucket}/{key}: {message}").into());
+
+    if packed.len() < HEADER_LEN {
+        return Err(corrupted(format!(
+            "object is too short ({} bytes) to contain a header",
+            packed.len()
+        )));
+    }
+    let version = packed[MAGIC.len()];
+    if version != FORMAT_VERSION {
+        return Err(corrupted(format!("unsupported format version {version}")));
+    }
+    let compression_byte = packed[MAGIC.len() + 1];
+    let compression = Compression::from_byte(compression_byte)
+        .ok_or_else(|| corrupted(format!("unknown compression method {compression_byte}")))?;
+    let checksum = &packed[MAGIC.len() + 2..HEADER_LEN];
+    let payload = &packed[HEADER_LEN..];
+
+    let value = match compression {
+        Compression::None => payload.to_vec(),
+        Compression::Zstd => zstd::stream::decode_all(payload)
+            .map_err(|err| corrupted(format!("failed decompressing object: {err}")))?,
+    };
+    let actual_checksum = Sha256::digest(&value);
+    if actual_checksum.as_slice() != checksum {
+        return Err(corrupted(format!(
+            "checksum mismatch: expected {}, got {}",
+            hex::encode(checksum),
+            hex::encode(actual_checksum)
+        )));
+    }
+    Ok(value)
+}
+
+/// [`ObjectStore`] middleware that transparently compresses objects with zstd and attaches a checksum to them.
+/// The checksum is verified when objects are fetched; a mismatch is signalled with [`ObjectStoreError::Corrupted`].
+///
+/// Objects put into the store before the middleware was enabled are returned unchanged. Conversely, objects
+/// put via this middleware cannot be read correctly without it.
+#[derive(Debug)]
+pub struct CompressingObjectStore<S> {
+    inner: S,
+}
+
+impl<S: ObjectStore> CompressingObjectStore<S> {
+    /// Wraps the provided store.
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<S: ObjectStore> ObjectStore for CompressingObjectStore<S> {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let packed = self.inner.get_raw(bucket, key).await?;
+        let key = key.to_owned();
+        // (De)compression is CPU-bound and objects can be large, so it's performed on a blocking thread.
+        task::spawn_blocking(move || unpack(bucket, &key, packed))
+            .await
+            .expect("unpacking object panicked")
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let packed = task::spawn_blocking(move || pack(&value))
+            .await
+            .expect("packing object panicked")?;
+        self.inner.put_raw(bucket, key, packed).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        self.inner.exists_raw(bucket, key).await
+    }
+
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        self.inner.list_keys_raw(bucket, prefix, page_token).await
+    }
+
+    async fn remove_batch_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        self.inner.remove_batch_raw(bucket, keys).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    #[test]
+    fn packing_compressible_object() {
+        let value = vec![42; 1_024];
+        let packed = pack(&value).unwrap();
+        assert!(packed.len() < value.len());
+        assert_eq!(packed[MAGIC.len() + 1], Compression::Zstd as u8);
+        let unpacked = unpack(Bucket::ProofsFri, "test", packed).unwrap();
+        assert_eq!(unpacked, value);
+    }
+
+    #[test]
+    fn packing_incompressible_object() {
+        let value: Vec<u8> = (0..=255).collect();
+        let packed = pack(&value).unwrap();
+        assert_eq!(packed.len(), HEADER_LEN + value.len());
+        assert_eq!(packed[MAGIC.len() + 1], Compression::None as u8);
+        let unpacked = unpack(Bucket::ProofsFri, "test", packed).unwrap();
+        assert_eq!(unpacked, value);
+    }
+
+    #[test]
+    fn unpacking_legacy_objects() {
+        let gzip_header = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0];
+        let unpacked = unpack(Bucket::StorageSnapshot, "test", gzip_header.clone()).unwrap();
+        assert_eq!(unpacked, gzip_header);
+        let unpacked = unpack(Bucket::StorageSnapshot, "test", vec![]).unwrap();
+        assert!(unpacked.is_empty());
+    }
+
+    #[test]
+    fn detecting_corrupted_objects() {
+        let value = vec![42; 1_024];
+        let packed = pack(&value).unwrap();
+
+        let mut corrupted = packed.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        let err = unpack(Bucket::ProofsFri, "test", corrupted).unwrap_err();
+        assert_matches!(err, ObjectStoreError::Corrupted(_));
+        assert!(!err.is_transient());
+
+        let mut corrupted = packed.clone();
+        corrupted[MAGIC.len() + 2] ^= 1; // corrupt the checksum
+        let err = unpack(Bucket::ProofsFri, "test", corrupted).unwrap_err();
+        assert_matches!(err, ObjectStoreError::Corrupted(_));
+
+        let truncated = packed[..HEADER_LEN - 1].to_vec();
+        let err = unpack(Bucket::ProofsFri, "test", truncated).unwrap_err();
+        assert_matches!(err, ObjectStoreError::Corrupted(_));
+    }
+
+    #[tokio::test]
+    async fn compressing_store_basics() {
+        let store = CompressingObjectStore::new(MockObjectStore::default());
+        store
+            .inner
+            .put_raw(Bucket::ProofsFri, "legacy", vec![1, 2, 3])
+            .await
+            .unwrap();
+        let object = store.get_raw(Bucket::ProofsFri, "legacy").await.unwrap();
+        assert_eq!(object, [1, 2, 3]);
+
+        let value = vec![42; 1_024];
+        store
+            .put_raw(Bucket::ProofsFri, "test", value.clone())
+            .await
+            .unwrap();
+        let raw_object = store
+            .inner
+            .get_raw(Bucket::ProofsFri, "test")
+            .await
+            .unwrap();
+        assert!(raw_object.starts_with(&MAGIC));
+        let object = store.get_raw(Bucket::ProofsFri, "test").await.unwrap();
+        assert_eq!(object, value);
+
+        let mut corrupted = raw_object;
+        corrupted.truncate(corrupted.len() - 1);
+        store
+            .inner
+            .put_raw(Bucket::ProofsFri, "test", corrupted)
+            .await
+            .unwrap();
+        let err = store.get_raw(Bucket::ProofsFri, "test").await.unwrap_err();
+        assert_matches!(err, ObjectStoreError::Corrupted(_));
+    }
+}
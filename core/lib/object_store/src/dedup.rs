@@ -0,0 +1,557 @@
+//! Object store middleware deduplicating objects by their content.
+
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use tokio::{sync::RwLock, task::JoinHandle};
+
+use crate::raw::{Bucket, KeysPage, ObjectStore, ObjectStoreError};
+
+/// Magic bytes for pointer objects stored under logical keys. Chosen to not collide with legacy objects
+/// (gzip-compressed or `bincode`-serialized) and with objects packed by [`CompressingObjectStore`].
+///
+/// [`CompressingObjectStore`]: crate::CompressingObjectStore
+const POINTER_MAGIC: [u8; 7] = *b"\xffZKSPTR";
+const POINTER_VERSION: u8 = 1;
+const HASH_LEN: usize = 32;
+const POINTER_LEN: usize = POINTER_MAGIC.len() + 1 + HASH_LEN;
+
+/// Prefix for all keys used internally by the store.
+const INTERNAL_KEY_PREFIX: &str = "__dedup_";
+const CONTENT_KEY_PREFIX: &str = "__dedup_content_";
+
+type ContentHash = [u8; HASH_LEN];
+
+fn content_key(hash: &ContentHash) -> String {
+    format!("{CONTENT_KEY_PREFIX}{}", hex::encode(hash))
+}
+
+fn encode_pointer(hash: &ContentHash) -> Vec<u8> {
+    let mut pointer = Vec::with_capacity(POINTER_LEN);
+    pointer.extend_from_slice(&POINTER_MAGIC);
+    pointer.push(POINTER_VERSION);
+    pointer.extend_from_slice(hash);
+    pointer
+}
+
+/// Decodes a pointer. Returns `None` if the object is not a pointer (i.e., it is stored inline).
+fn decode_pointer(object: &[u8]) -> Option<ContentHash> {
+    if object.len() != POINTER_LEN || !object.starts_with(&POINTER_MAGIC) {
+        return None;
+    }
+    if object[POINTER_MAGIC.len()] != POINTER_VERSION {
+        return None;
+    }
+    object[POINTER_MAGIC.len() + 1..].try_into().ok()
+}
+
+#[derive(Debug, Default)]
+struct GcState {
+    /// Buckets modified via the store since the last periodic garbage collection.
+    modified_buckets: HashSet<Bucket>,
+    /// Content written during an ongoing garbage collection, keyed by the collected bucket.
+    protected_content: HashMap<Bucket, HashSet<ContentHash>>,
+}
+
+impl GcState {
+    fn record_write(&mut self, bucket: Bucket, hash: Option<ContentHash>) {
+        self.modified_buckets.insert(bucket);
+        if let (Some(protected), Some(hash)) = (self.protected_content.get_mut(&bucket), hash) {
+            protected.insert(hash);
+        }
+    }
+}
+
+/// [`ObjectStore`] middleware that stores payloads by their content hash (SHA-256), and only stores small pointers
+/// under logical keys. Thus, objects with identical contents (e.g., factory dependencies repeated across snapshots)
+/// are stored once per bucket.
+///
+/// Content objects are immutable and are only ever written with the same value for the same key, so concurrent
+/// writers (including ones in different processes) cannot corrupt each other's data. Removing or overwriting
+/// a logical key only affects its pointer; content that is no longer referenced is removed
+/// by [garbage collection](Self::collect_garbage()), which can be run
+/// [periodically](Self::spawn_garbage_collection()). Objects smaller than
+/// [the configured threshold](Self::with_min_object_size()) are stored inline, as are objects put into the store
+/// before the middleware was enabled.
+///
+/// Since content objects never change, it is safe to wrap a store with a local mirror into this middleware:
+/// mirrored content is never stale, and mirrored pointers are only as stale as any other mirrored object.
+/// Note that a stale pointer may reference content that was already garbage-collected; in this case, reads
+/// return [`ObjectStoreError::Corrupted`].
+#[derive(Debug)]
+pub struct DeduplicatingObjectStore<S> {
+    inner: S,
+    min_object_size: usize,
+    /// Shared by writers; exclusively held by garbage collection while it snapshots or removes content.
+    write_lock: RwLock<()>,
+    /// Serializes garbage collection runs.
+    gc_lock: tokio::sync::Mutex<()>,
+    gc_state: Mutex<GcState>,
+}
+
+impl<S: ObjectStore> DeduplicatingObjectStore<S> {
+    /// Default minimum size of deduplicated objects.
+    pub const DEFAULT_MIN_OBJECT_SIZE: usize = 1_024;
+
+    /// Wraps the provided store.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            min_object_size: Self::DEFAULT_MIN_OBJECT_SIZE,
+            write_lock: RwLock::default(),
+            gc_lock: tokio::sync::Mutex::default(),
+            gc_state: Mutex::default(),
+        }
+    }
+
+    /// Sets the minimum size of deduplicated objects. Smaller objects are stored inline.
+    #[must_use]
+    pub fn with_min_object_size(mut self, size: usize) -> Self {
+        self.min_object_size = size;
+        self
+    }
+
+    async fn list_all_keys(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let mut keys = vec![];
+        let mut page_token = None;
+        loop {
+            let page = self
+                .inner
+                .list_keys_raw(bucket, prefix, page_token.as_deref())
+                .await?;
+            keys.extend(page.keys);
+            page_token = page.next_page_token;
+            if page_token.is_none() {
+                return Ok(keys);
+            }
+        }
+    }
+
+    fn gc_state(&self) -> std::sync::MutexGuard<'_, GcState> {
+        self.gc_state
+            .lock()
+            .expect("garbage collection state is poisoned")
+    }
+
+    /// Removes content objects in the specified bucket that are not referenced by any logical key.
+    /// Returns the number of removed objects.
+    ///
+    /// Garbage collection is safe to run concurrently with writes via this store: content written
+    /// after garbage collection has started is never removed by it. Writers are only blocked while
+    /// garbage collection waits for in-flight writes and while it removes unreferenced content.
+    ///
+    /// **Important.** Writes to the bucket from other store instances (e.g., from other processes) are not tracked;
+    /// such a write may reuse content removed by a concurrent garbage collection, leaving the written pointer dangling.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors specific to the storage.
+    pub async fn collect_garbage(&self, bucket: Bucket) -> Result<usize, ObjectStoreError> {
+        let _gc_guard = self.gc_lock.lock().await;
+        {
+            // Wait for in-flight writes; their pointers will be visible to the scan below.
+            let _write_guard = self.write_lock.write().await;
+            self.gc_state()
+                .protected_content
+                .insert(bucket, HashSet::new());
+        }
+        let result = self.collect_garbage_inner(bucket).await;
+        self.gc_state().protected_content.remove(&bucket);
+        result
+    }
+
+    async fn collect_garbage_inner(&self, bucket: Bucket) -> Result<usize, ObjectStoreError> {
+        let mut referenced = HashSet::new();
+        for key in self.list_all_keys(bucket, "").await? {
+            if key.starts_with(INTERNAL_KEY_PREFIX) {
+                continue;
+            }
+            let object = match self.inner.get_raw(bucket, &key).await {
+                Ok(object) => object,
+                // The key was removed after listing; it doesn't reference any content.
+                Err(ObjectStoreError::KeyNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            if let Some(hash) = decode_pointer(&object) {
+                referenced.insert(content_key(&hash));
+            }
+        }
+
+        let content_keys = self.list_all_keys(bucket, CONTENT_KEY_PREFIX).await?;
+
+        // Content cannot be written while we hold the lock, so all content written during the scan is protected.
+        let _write_guard = self.write_lock.write().await;
+        referenced.extend(
+            self.gc_state().protected_content[&bucket]
+                .iter()
+                .map(content_key),
+        );
+        let unreferenced: Vec<_> = content_keys
+            .into_iter()
+            .filter(|key| !referenced.contains(key))
+            .collect();
+        if !unreferenced.is_empty() {
+            tracing::debug!(
+                "removing {} unreferenced content objects from bucket {bucket}",
+                unreferenced.len()
+            );
+            self.inner.remove_batch_raw(bucket, &unreferenced).await?;
+        }
+        Ok(unreferenced.len())
+    }
+
+    async fn collect_garbage_in_modified_buckets(&self) {
+        let buckets = mem::take(&mut self.gc_state().modified_buckets);
+        for bucket in buckets {
+            match self.collect_garbage(bucket).await {
+                Ok(removed_count) => {
+                    tracing::debug!(
+                        "removed {removed_count} unreferenced content objects from bucket {bucket}"
+                    );
+                }
+                Err(err) => {
+                    tracing::warn!("failed collecting garbage in bucket {bucket}: {err}");
+                    // Retry collection during the next run.
+                    self.gc_state().modified_buckets.insert(bucket);
+                }
+            }
+        }
+    }
+
+    /// Spawns a Tokio task that [collects garbage](Self::collect_garbage()) with the specified interval
+    /// in all buckets modified via this store. The task terminates once the store is dropped.
+    pub fn spawn_garbage_collection(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let store = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(store) = store.upgrade() else {
+                    tracing::debug!(
+                        "deduplicating object store is dropped; stopping garbage collection"
+                    );
+                    return;
+                };
+                store.collect_garbage_in_modified_buckets().await;
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl<S: ObjectStore> ObjectStore for DeduplicatingObjectStore<S> {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let object = self.inner.get_raw(bucket, key).await?;
+        let Some(hash) = decode_pointer(&object) else {
+            return Ok(object); // the object is stored inline
+        };
+        match self.inner.get_raw(bucket, &content_key(&hash)).await {
+            Err(ObjectStoreError::KeyNotFound(err)) => Err(ObjectStoreError::Corrupted(
+                format!("content for {bucket}/{key} is missing: {err}").into(),
+            )),
+            result => result,
+        }
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        if value.len() < self.min_object_size {
+            self.gc_state().record_write(bucket, None);
+            return self.inner.put_raw(bucket, key, value).await;
+        }
+
+        let hash: ContentHash = Sha256::digest(&value).into();
+        let _write_guard = self.write_lock.read().await;
+        self.gc_state().record_write(bucket, Some(hash));
+        // Content must be written before the pointer, so that a failure cannot lead to a dangling pointer;
+        // at worst, content will be leaked until the next garbage collection.
+        if self.inner.exists_raw(bucket, &content_key(&hash)).await? {
+            tracing::trace!("content for {bucket}/{key} is already stored");
+        } else {
+            // Concurrent writers may race here, but they will write identical values.
+            self.inner
+                .put_raw(bucket, &content_key(&hash), value)
+                .await?;
+        }
+        self.inner.put_raw(bucket, key, encode_pointer(&hash)).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.gc_state().record_write(bucket, None);
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        self.inner.exists_raw(bucket, key).await
+    }
+
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        let mut page = self.inner.list_keys_raw(bucket, prefix, page_token).await?;
+        page.keys
+            .retain(|key| !key.starts_with(INTERNAL_KEY_PREFIX));
+        Ok(page)
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+    use tokio::task::JoinSet;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    const BUCKET: Bucket = Bucket::StorageSnapshot;
+
+    fn create_store() -> DeduplicatingObjectStore<MockObjectStore> {
+        DeduplicatingObjectStore::new(MockObjectStore::default()).with_min_object_size(4)
+    }
+
+    async fn internal_keys(store: &DeduplicatingObjectStore<MockObjectStore>) -> Vec<String> {
+        store
+            .inner
+            .list_keys_raw(BUCKET, INTERNAL_KEY_PREFIX, None)
+            .await
+            .unwrap()
+            .keys
+    }
+
+    #[test]
+    fn pointer_roundtrip() {
+        let hash = [42; HASH_LEN];
+        let pointer = encode_pointer(&hash);
+        assert_eq!(pointer.len(), POINTER_LEN);
+        assert_eq!(decode_pointer(&pointer), Some(hash));
+        assert_eq!(decode_pointer(&pointer[1..]), None);
+        assert_eq!(decode_pointer(&[0; POINTER_LEN]), None);
+    }
+
+    #[tokio::test]
+    async fn deduplicating_objects() {
+        let store = create_store();
+        let value = vec![1; 100];
+        store.put_raw(BUCKET, "first", value.clone()).await.unwrap();
+        store
+            .put_raw(BUCKET, "second", value.clone())
+            .await
+            .unwrap();
+        store.put_raw(BUCKET, "small", vec![1]).await.unwrap();
+
+        assert_eq!(store.get_raw(BUCKET, "first").await.unwrap(), value);
+        assert_eq!(store.get_raw(BUCKET, "second").await.unwrap(), value);
+        assert_eq!(store.get_raw(BUCKET, "small").await.unwrap(), [1]);
+        assert_eq!(store.inner.get_raw(BUCKET, "small").await.unwrap(), [1]);
+        assert_eq!(
+            store.inner.get_raw(BUCKET, "first").await.unwrap().len(),
+            POINTER_LEN
+        );
+
+        let hash: ContentHash = Sha256::digest(&value).into();
+        assert_eq!(internal_keys(&store).await, [content_key(&hash)]);
+        let page = store.list_keys_raw(BUCKET, "", None).await.unwrap();
+        assert_eq!(page.keys, ["first", "second", "small"]);
+
+        store.remove_raw(BUCKET, "first").await.unwrap();
+        assert_matches!(
+            store.get_raw(BUCKET, "first").await.unwrap_err(),
+            ObjectStoreError::KeyNotFound(_)
+        );
+        assert_eq!(store.get_raw(BUCKET, "second").await.unwrap(), value);
+        assert_eq!(store.collect_garbage(BUCKET).await.unwrap(), 0);
+        assert_eq!(store.get_raw(BUCKET, "second").await.unwrap(), value);
+
+        store.remove_raw(BUCKET, "second").await.unwrap();
+        // Content is only removed during garbage collection.
+        assert_eq!(internal_keys(&store).await, [content_key(&hash)]);
+        assert_eq!(store.collect_garbage(BUCKET).await.unwrap(), 1);
+        assert!(internal_keys(&store).await.is_empty());
+        assert_eq!(store.get_raw(BUCKET, "small").await.unwrap(), [1]);
+    }
+
+    #[tokio::test]
+    async fn concurrent_writes_of_same_content() {
+        let store = Arc::new(create_store());
+        let value = vec![1; 100];
+        let keys: Vec<_> = (0..10).map(|i| format!("key{i}")).collect();
+        let mut tasks = JoinSet::new();
+        for key in keys.clone() {
+            let store = store.clone();
+            let value = value.clone();
+            tasks.spawn(async move { store.put_raw(BUCKET, &key, value).await });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result.unwrap().unwrap();
+        }
+
+        for key in &keys {
+            assert_eq!(store.get_raw(BUCKET, key).await.unwrap(), value);
+        }
+        // Removing all keys but one must not affect the remaining key.
+        for key in &keys[1..] {
+            store.remove_raw(BUCKET, key).await.unwrap();
+        }
+        assert_eq!(store.collect_garbage(BUCKET).await.unwrap(), 0);
+        assert_eq!(store.get_raw(BUCKET, &keys[0]).await.unwrap(), value);
+    }
+
+    #[tokio::test]
+    async fn content_is_removed_after_last_key_is_removed() {
+        let store = Arc::new(create_store());
+        let value = vec![1; 100];
+        store.put_raw(BUCKET, "first", value.clone()).await.unwrap();
+        store
+            .put_raw(BUCKET, "second", value.clone())
+            .await
+            .unwrap();
+        let gc_task = store.spawn_garbage_collection(Duration::from_millis(10));
+
+        store.remove_raw(BUCKET, "first").await.unwrap();
+        // Wait for several garbage collection runs.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(internal_keys(&store).await.len(), 1);
+        assert_eq!(store.get_raw(BUCKET, "second").await.unwrap(), value);
+
+        store.remove_raw(BUCKET, "second").await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !internal_keys(&store).await.is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("content was not removed");
+
+        drop(store);
+        tokio::time::timeout(Duration::from_secs(5), gc_task)
+            .await
+            .expect("garbage collection did not stop")
+            .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn garbage_collection_concurrent_with_writes() {
+        let store = Arc::new(create_store());
+        let mut writers = JoinSet::new();
+        for i in 0..10 {
+            let store = store.clone();
+            writers.spawn(async move {
+                let key = format!("key{i}");
+                for j in 0_u8..50 {
+                    let value = vec![j % 3; 100];
+                    store.put_raw(BUCKET, &key, value.clone()).await.unwrap();
+                    assert_eq!(store.get_raw(BUCKET, &key).await.unwrap(), value);
+                    if j % 5 == 0 {
+                        store.remove_raw(BUCKET, &key).await.unwrap();
+                    }
+                    tokio::task::yield_now().await;
+                }
+            });
+        }
+
+        let gc_store = store.clone();
+        let gc_task = tokio::spawn(async move {
+            loop {
+                gc_store.collect_garbage(BUCKET).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+        while let Some(result) = writers.join_next().await {
+            result.unwrap();
+        }
+        gc_task.abort();
+        // Check that garbage collection didn't panic.
+        assert!(gc_task.await.unwrap_err().is_cancelled());
+
+        for i in 0..10 {
+            let key = format!("key{i}");
+            assert_eq!(store.get_raw(BUCKET, &key).await.unwrap(), [49 % 3; 100]);
+        }
+        store.collect_garbage(BUCKET).await.unwrap();
+        assert_eq!(internal_keys(&store).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn overwriting_deduplicated_object() {
+        let store = create_store();
+        let old_value = vec![1; 100];
+        let new_value = vec![2; 100];
+        store
+            .put_raw(BUCKET, "test", old_value.clone())
+            .await
+            .unwrap();
+        // Putting the same value again must not lead to the content being collected.
+        store
+            .put_raw(BUCKET, "test", old_value.clone())
+            .await
+            .unwrap();
+        assert_eq!(store.get_raw(BUCKET, "test").await.unwrap(), old_value);
+        assert_eq!(store.collect_garbage(BUCKET).await.unwrap(), 0);
+        assert_eq!(internal_keys(&store).await.len(), 1);
+
+        store
+            .put_raw(BUCKET, "test", new_value.clone())
+            .await
+            .unwrap();
+        assert_eq!(store.get_raw(BUCKET, "test").await.unwrap(), new_value);
+        assert_eq!(store.collect_garbage(BUCKET).await.unwrap(), 1);
+        let new_hash: ContentHash = Sha256::digest(&new_value).into();
+        assert_eq!(internal_keys(&store).await, [content_key(&new_hash)]);
+
+        store.put_raw(BUCKET, "test", vec![3]).await.unwrap();
+        assert_eq!(store.get_raw(BUCKET, "test").await.unwrap(), [3]);
+        assert_eq!(store.collect_garbage(BUCKET).await.unwrap(), 1);
+        assert!(internal_keys(&store).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reading_legacy_objects() {
+        let store = create_store();
+        store
+            .inner
+            .put_raw(BUCKET, "legacy", vec![5; 100])
+            .await
+            .unwrap();
+        assert_eq!(store.get_raw(BUCKET, "legacy").await.unwrap(), [5; 100]);
+        store.remove_raw(BUCKET, "legacy").await.unwrap();
+        assert!(!store.exists_raw(BUCKET, "legacy").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn missing_content_is_reported_as_corruption() {
+        let store = create_store();
+        let value = vec![1; 100];
+        store.put_raw(BUCKET, "test", value.clone()).await.unwrap();
+        let hash: ContentHash = Sha256::digest(&value).into();
+        store
+            .inner
+            .remove_raw(BUCKET, &content_key(&hash))
+            .await
+            .unwrap();
+
+        let err = store.get_raw(BUCKET, "test").await.unwrap_err();
+        assert_matches!(err, ObjectStoreError::Corrupted(_));
+    }
+}
@@ -1,17 +1,22 @@
-use std::sync::Arc;
+use std::{collections::HashSet, future, sync::Arc};
 
 use anyhow::Context as _;
 use tokio::sync::OnceCell;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
 use crate::{
+    compression::CompressingObjectStore,
+    dedup::DeduplicatingObjectStore,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
-    mirror::MirroringObjectStore,
-    raw::{ObjectStore, ObjectStoreError},
+    mirror::{MirrorOptions, MirroringObjectStore},
+    raw::{Bucket, ObjectStore, ObjectStoreError},
     retries::StoreWithRetries,
+    s3::{S3AddressingStyle, S3BucketLocation, S3Store, S3StoreAuthMode},
 };
 
+const BYTES_IN_MEGABYTE: u64 = 1_024 * 1_024;
+
 /// Factory of [`ObjectStore`]s that caches the store instance once it's created. Used mainly for legacy reasons.
 ///
 /// Please do not use this factory in dependency injection; rely on `Arc<dyn ObjectStore>` instead. This allows to
@@ -56,6 +61,31 @@ impl ObjectStoreFactory {
         config: &ObjectStoreConfig,
     ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         tracing::trace!("Initializing object store with configuration {config:?}");
+        let store = Self::create_base_store(config).await?;
+        let store: Arc<dyn ObjectStore> = if config.compress_objects {
+            tracing::info!("Enabling compression and integrity checks for object store {store:?}");
+            Arc::new(CompressingObjectStore::new(store))
+        } else {
+            store
+        };
+        Ok(if config.deduplicate_objects {
+            tracing::info!("Enabling content-based deduplication for object store {store:?}");
+            let store = Arc::new(DeduplicatingObjectStore::new(store));
+            if let Some(interval) = config.deduplication_gc_interval() {
+                tracing::info!(
+                    "Enabling garbage collection of deduplicated content with interval {interval:?}"
+                );
+                store.spawn_garbage_collection(interval);
+            }
+            store
+        } else {
+            store
+        })
+    }
+
+    async fn create_base_store(
+        config: &ObjectStoreConfig,
+    ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         match &config.mode {
             ObjectStoreMode::GCS { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
@@ -65,7 +95,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+                Self::wrap_mirroring(store, config).await
             }
             ObjectStoreMode::GCSWithCredentialFile {
                 bucket_base_url,
@@ -80,7 +110,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+                Self::wrap_mirroring(store, config).await
             }
             ObjectStoreMode::GCSAnonymousReadOnly { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
@@ -90,7 +120,41 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+                Self::wrap_mirroring(store, config).await
+            }
+            ObjectStoreMode::S3 {
+                bucket_name,
+                region,
+                endpoint,
+                path_style,
+            } => {
+                let location =
+                    Self::s3_location(bucket_name, region, endpoint.as_ref(), *path_style);
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    future::ready(S3Store::new(S3StoreAuthMode::Environment, location.clone()))
+                })
+                .await?;
+                Self::wrap_mirroring(store, config).await
+            }
+            ObjectStoreMode::S3WithStaticCredentials {
+                bucket_name,
+                region,
+                endpoint,
+                path_style,
+                access_key_id,
+                secret_access_key,
+            } => {
+                let location =
+                    Self::s3_location(bucket_name, region, endpoint.as_ref(), *path_style);
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    let auth_mode = S3StoreAuthMode::StaticCredentials {
+                        access_key_id: access_key_id.clone(),
+                        secret_access_key: secret_access_key.clone(),
+                    };
+                    future::ready(S3Store::new(auth_mode, location.clone()))
+                })
+                .await?;
+                Self::wrap_mirroring(store, config).await
             }
 
             ObjectStoreMode::FileBacked {
@@ -109,14 +173,54 @@ impl ObjectStoreFactory {
         }
     }
 
+    fn s3_location(
+        bucket_name: &str,
+        region: &str,
+        endpoint: Option<&String>,
+        path_style: bool,
+    ) -> S3BucketLocation {
+        S3BucketLocation {
+            bucket_name: bucket_name.to_owned(),
+            region: region.to_owned(),
+            endpoint: endpoint.cloned(),
+            addressing_style: if path_style {
+                S3AddressingStyle::Path
+            } else {
+                S3AddressingStyle::VirtualHost
+            },
+        }
+    }
+
     async fn wrap_mirroring(
         store: impl ObjectStore,
-        mirror_path: Option<&String>,
+        config: &ObjectStoreConfig,
     ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
-        Ok(if let Some(mirror_path) = mirror_path {
-            Arc::new(MirroringObjectStore::new(store, mirror_path.clone()).await?)
+        let Some(mirror_path) = &config.local_mirror_path else {
+            return Ok(Arc::new(store));
+        };
+
+        let buckets = if config.local_mirror_buckets.is_empty() {
+            None
         } else {
-            Arc::new(store)
-        })
+            let buckets = config
+                .local_mirror_buckets
+                .iter()
+                .map(|bucket| bucket.parse::<Bucket>())
+                .collect::<Result<HashSet<_>, _>>()
+                .map_err(|err| ObjectStoreError::Initialization {
+                    source: err.into(),
+                    is_transient: false,
+                })?;
+            Some(buckets)
+        };
+        let options = MirrorOptions {
+            max_size_bytes: config
+                .local_mirror_max_size_mb
+                .map(|size_mb| size_mb * BYTES_IN_MEGABYTE),
+            buckets,
+        };
+        Ok(Arc::new(
+            MirroringObjectStore::new(store, mirror_path.clone(), options).await?,
+        ))
     }
 }
@@ -1,9 +1,9 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, time::SystemTime};
 
 use async_trait::async_trait;
 use tokio::{fs, io};
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, KeysPage, ObjectStore, ObjectStoreError};
 
 impl From<io::Error> for ObjectStoreError {
     fn from(err: io::Error) -> Self {
@@ -30,20 +30,7 @@ impl FileBackedObjectStore {
     ///
     /// Propagates I/O errors.
     pub async fn new(base_dir: String) -> Result<Self, ObjectStoreError> {
-        for bucket in &[
-            Bucket::ProverJobs,
-            Bucket::WitnessInput,
-            Bucket::LeafAggregationWitnessJobs,
-            Bucket::NodeAggregationWitnessJobs,
-            Bucket::SchedulerWitnessJobs,
-            Bucket::ProverJobsFri,
-            Bucket::LeafAggregationWitnessJobsFri,
-            Bucket::NodeAggregationWitnessJobsFri,
-            Bucket::SchedulerWitnessJobsFri,
-            Bucket::ProofsFri,
-            Bucket::StorageSnapshot,
-            Bucket::TeeVerifierInput,
-        ] {
+        for bucket in Bucket::ALL {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path).await?;
         }
@@ -53,6 +40,17 @@ impl FileBackedObjectStore {
     fn filename(&self, bucket: Bucket, key: &str) -> String {
         format!("{}/{bucket}/{key}", self.base_dir)
     }
+
+    /// Sets the modification time of the specified object to the current time.
+    pub(crate) async fn touch(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let filename = self.filename(bucket, key);
+        let file = fs::OpenOptions::new().write(true).open(filename).await?;
+        let file = file.into_std().await;
+        tokio::task::spawn_blocking(move || file.set_modified(SystemTime::now()))
+            .await
+            .expect("updating file modification time panicked")
+            .map_err(From::from)
+    }
 }
 
 #[async_trait]
@@ -77,6 +75,37 @@ impl ObjectStore for FileBackedObjectStore {
         fs::remove_file(filename).await.map_err(From::from)
     }
 
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let filename = self.filename(bucket, key);
+        fs::try_exists(filename).await.map_err(From::from)
+    }
+
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        let mut entries = fs::read_dir(self.storage_prefix_raw(bucket)).await?;
+        let mut keys = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_file() {
+                continue;
+            }
+            // Keys are always valid UTF-8 strings, so we skip files with other names.
+            if let Ok(key) = entry.file_name().into_string() {
+                if key.starts_with(prefix) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys.sort_unstable();
+        Ok(KeysPage::from_sorted_keys(
+            keys.iter().map(String::as_str),
+            page_token,
+        ))
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!("{}/{}", self.base_dir, bucket)
     }
@@ -117,6 +146,63 @@ mod test {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_exists() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        object_store
+            .put_raw(Bucket::ProverJobs, "test-key.bin", vec![0, 1])
+            .await
+            .unwrap();
+        assert!(object_store
+            .exists_raw(Bucket::ProverJobs, "test-key.bin")
+            .await
+            .unwrap());
+        assert!(!object_store
+            .exists_raw(Bucket::ProverJobs, "missing.bin")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_list_keys() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        for key in ["b-2.bin", "a-1.bin", "b-1.bin"] {
+            object_store
+                .put_raw(Bucket::ProverJobs, key, vec![0, 1])
+                .await
+                .unwrap();
+        }
+
+        let page = object_store
+            .list_keys_raw(Bucket::ProverJobs, "b-", None)
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["b-1.bin", "b-2.bin"]);
+        assert_eq!(page.next_page_token, None);
+        let page = object_store
+            .list_keys_raw(Bucket::ProverJobs, "", Some("a-1.bin"))
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["b-1.bin", "b-2.bin"]);
+
+        object_store
+            .remove_batch_raw(
+                Bucket::ProverJobs,
+                &["a-1.bin".to_owned(), "missing.bin".to_owned()],
+            )
+            .await
+            .unwrap();
+        let page = object_store
+            .list_keys_raw(Bucket::ProverJobs, "", None)
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["b-1.bin", "b-2.bin"]);
+    }
+
     #[tokio::test]
     async fn test_remove() {
         let dir = TempDir::new().unwrap();
@@ -1,6 +1,6 @@
 //! GCS-based [`ObjectStore`] implementation.
 
-use std::{error::Error as StdError, fmt, io};
+use std::fmt;
 
 use async_trait::async_trait;
 use google_cloud_auth::{credentials::CredentialsFile, error::Error as AuthError};
@@ -11,6 +11,7 @@ use google_cloud_storage::{
             delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
         },
         Error as HttpError,
@@ -18,7 +19,7 @@ use google_cloud_storage::{
 };
 use http::StatusCode;
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{has_transient_io_source, Bucket, KeysPage, ObjectStore, ObjectStoreError};
 
 /// [`ObjectStore`] implementation based on GCS.
 pub struct GoogleCloudStore {
@@ -96,21 +97,6 @@ impl From<AuthError> for ObjectStoreError {
     }
 }
 
-fn has_transient_io_source(mut err: &(dyn StdError + 'static)) -> bool {
-    loop {
-        if err.is::<io::Error>() {
-            // We treat any I/O errors as transient. This isn't always true, but frequently occurring I/O errors
-            // (e.g., "connection reset by peer") *are* transient, and treating an error as transient is a safer option,
-            // even if it can lead to unnecessary retries.
-            return true;
-        }
-        err = match err.source() {
-            Some(source) => source,
-            None => return false,
-        };
-    }
-}
-
 impl From<HttpError> for ObjectStoreError {
     fn from(err: HttpError) -> Self {
         let is_not_found = match &err {
@@ -195,6 +181,58 @@ impl ObjectStore for GoogleCloudStore {
         Ok(())
     }
 
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Fetching metadata from GCS for key {filename} from bucket {}",
+            self.bucket_prefix
+        );
+
+        let request = GetObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            object: filename,
+            ..GetObjectRequest::default()
+        };
+        match self.client.get_object(&request).await {
+            Ok(_) => Ok(true),
+            Err(err) => match ObjectStoreError::from(err) {
+                ObjectStoreError::KeyNotFound(_) => Ok(false),
+                err => Err(err),
+            },
+        }
+    }
+
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        tracing::trace!(
+            "Listing keys in GCS with prefix {bucket_prefix}{prefix} from bucket {}",
+            self.bucket_prefix
+        );
+
+        let request = ListObjectsRequest {
+            bucket: self.bucket_prefix.clone(),
+            prefix: Some(format!("{bucket_prefix}{prefix}")),
+            page_token: page_token.map(str::to_owned),
+            ..ListObjectsRequest::default()
+        };
+        let response = self.client.list_objects(&request).await?;
+        let keys = response
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|object| Some(object.name.strip_prefix(&bucket_prefix)?.to_owned()))
+            .collect();
+        Ok(KeysPage {
+            keys,
+            next_page_token: response.next_page_token,
+        })
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "https://storage.googleapis.com/{}/{}",
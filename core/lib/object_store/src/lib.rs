@@ -1,10 +1,15 @@
 //! This crate provides the [object storage abstraction](ObjectStore) that allows to get,
-//! put and remove binary blobs. The following implementations are available:
+//! put, list and remove binary blobs. The following implementations are available:
 //!
 //! - [File-backed store](FileBackedObjectStore) saving blobs as separate files in the local filesystem
 //! - [GCS-based store](GoogleCloudStore)
+//! - [S3-compatible store](S3Store) (AWS S3, MinIO etc.)
 //! - [Mock in-memory store](MockObjectStore)
 //!
+//! Stores can be wrapped in the [compressing middleware](CompressingObjectStore), which compresses objects
+//! and verifies their integrity, and in the [deduplicating middleware](DeduplicatingObjectStore), which stores
+//! objects with identical contents once.
+//!
 //! Normally, these implementations are not used directly. Instead, a store trait object (`Arc<dyn ObjectStore>`)
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
 //! This trait object is what should be used for dependency injection.
@@ -23,6 +28,8 @@
     clippy::doc_markdown
 )]
 
+mod compression;
+mod dedup;
 mod factory;
 mod file;
 mod gcs;
@@ -32,6 +39,7 @@ mod mock;
 mod objects;
 mod raw;
 mod retries;
+mod s3;
 
 // Re-export `bincode` crate so that client binaries can conveniently use it.
 pub use bincode;
@@ -42,10 +50,13 @@ pub mod _reexports {
 }
 
 pub use self::{
+    compression::CompressingObjectStore,
+    dedup::DeduplicatingObjectStore,
     factory::ObjectStoreFactory,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mock::MockObjectStore,
     objects::StoredObject,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    raw::{Bucket, KeysPage, ObjectStore, ObjectStoreError},
+    s3::{S3AddressingStyle, S3BucketLocation, S3Store, S3StoreAuthMode},
 };
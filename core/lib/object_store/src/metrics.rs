@@ -2,10 +2,20 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, LabeledFamily, LatencyObserver, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelValue, Gauge, Histogram, LabeledFamily, LatencyObserver, Metrics,
+    Unit,
+};
 
 use crate::Bucket;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum MirrorLookupOutcome {
+    Hit,
+    Miss,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_object_store")]
 pub(crate) struct ObjectStoreMetrics {
@@ -15,6 +25,15 @@ pub(crate) struct ObjectStoreMetrics {
     /// Latency to store an object in the store (accounting for retries).
     #[metrics(buckets = Buckets::LATENCIES, labels = ["bucket"])]
     storing_time: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Number of object lookups in the local mirror, split by the lookup outcome.
+    #[metrics(labels = ["bucket", "outcome"])]
+    mirror_lookups: LabeledFamily<(&'static str, MirrorLookupOutcome), Counter, 2>,
+    /// Number of objects evicted from the local mirror.
+    #[metrics(labels = ["bucket"])]
+    mirror_evictions: LabeledFamily<&'static str, Counter>,
+    /// Total size of objects in the local mirror. Only reported if the mirror size is bounded.
+    #[metrics(unit = Unit::Bytes)]
+    mirror_size: Gauge<u64>,
 }
 
 impl ObjectStoreMetrics {
@@ -25,6 +44,23 @@ impl ObjectStoreMetrics {
     pub fn start_store(&self, bucket: Bucket) -> LatencyObserver<'_> {
         self.storing_time[&bucket.as_str()].start()
     }
+
+    pub fn report_mirror_lookup(&self, bucket: Bucket, is_hit: bool) {
+        let outcome = if is_hit {
+            MirrorLookupOutcome::Hit
+        } else {
+            MirrorLookupOutcome::Miss
+        };
+        self.mirror_lookups[&(bucket.as_str(), outcome)].inc();
+    }
+
+    pub fn report_mirror_eviction(&self, bucket: Bucket) {
+        self.mirror_evictions[&bucket.as_str()].inc();
+    }
+
+    pub fn report_mirror_size(&self, size: u64) {
+        self.mirror_size.set(size);
+    }
 }
 
 #[vise::register]
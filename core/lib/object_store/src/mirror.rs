@@ -1,23 +1,218 @@
 //! Mirroring object store.
 
+use std::{collections::HashSet, time::SystemTime};
+
 use async_trait::async_trait;
+use lru::LruCache;
+use tokio::{
+    fs,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{
+    file::FileBackedObjectStore,
+    metrics::OBJECT_STORE_METRICS,
+    raw::{KeysPage, ObjectStore},
+    Bucket, ObjectStoreError,
+};
+
+/// Options for [`MirroringObjectStore`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MirrorOptions {
+    /// Maximum total size of mirrored objects in bytes. If not specified, the mirror size is unbounded.
+    pub max_size_bytes: Option<u64>,
+    /// Buckets to mirror. If not specified, all buckets are mirrored.
+    pub buckets: Option<HashSet<Bucket>>,
+}
+
+/// Index of mirrored objects used to evict least recently used objects once the mirror exceeds its size budget.
+#[derive(Debug)]
+struct LruIndex {
+    entries: LruCache<(Bucket, String), u64>,
+    total_size: u64,
+    max_size: u64,
+}
+
+impl LruIndex {
+    fn new(max_size: u64) -> Self {
+        Self {
+            entries: LruCache::unbounded(),
+            total_size: 0,
+            max_size,
+        }
+    }
+
+    fn touch(&mut self, bucket: Bucket, key: &str) {
+        self.entries.get(&(bucket, key.to_owned()));
+    }
 
-use crate::{file::FileBackedObjectStore, raw::ObjectStore, Bucket, ObjectStoreError};
+    /// Inserts an object into the index and returns objects that should be evicted from the mirror.
+    fn insert(&mut self, bucket: Bucket, key: &str, size: u64) -> Vec<(Bucket, String)> {
+        if let Some(prev_size) = self.entries.put((bucket, key.to_owned()), size) {
+            self.total_size -= prev_size;
+        }
+        self.total_size += size;
+
+        let mut evicted = vec![];
+        while self.total_size > self.max_size {
+            let Some((evicted_key, evicted_size)) = self.entries.pop_lru() else {
+                break;
+            };
+            self.total_size -= evicted_size;
+            evicted.push(evicted_key);
+        }
+        evicted
+    }
+
+    fn remove(&mut self, bucket: Bucket, key: &str) {
+        if let Some(size) = self.entries.pop(&(bucket, key.to_owned())) {
+            self.total_size -= size;
+        }
+    }
+}
 
 #[derive(Debug)]
 pub(crate) struct MirroringObjectStore<S> {
     inner: S,
     mirror_store: FileBackedObjectStore,
+    buckets: Option<HashSet<Bucket>>,
+    lru_index: Option<Mutex<LruIndex>>,
 }
 
 impl<S: ObjectStore> MirroringObjectStore<S> {
-    pub async fn new(inner: S, mirror_path: String) -> Result<Self, ObjectStoreError> {
-        tracing::info!("Initializing mirroring for store {inner:?} at `{mirror_path}`");
-        let mirror_store = FileBackedObjectStore::new(mirror_path).await?;
-        Ok(Self {
+    pub async fn new(
+        inner: S,
+        mirror_path: String,
+        options: MirrorOptions,
+    ) -> Result<Self, ObjectStoreError> {
+        tracing::info!(
+            "Initializing mirroring for store {inner:?} at `{mirror_path}` with options {options:?}"
+        );
+        let mirror_store = FileBackedObjectStore::new(mirror_path.clone()).await?;
+        let mut this = Self {
             inner,
             mirror_store,
-        })
+            buckets: options.buckets,
+            lru_index: None,
+        };
+        if let Some(max_size) = options.max_size_bytes {
+            let index = this.load_lru_index(&mirror_path, max_size).await?;
+            this.lru_index = Some(Mutex::new(index));
+        }
+        Ok(this)
+    }
+
+    /// Loads the LRU index from the mirror directory, using file modification times as a proxy for access times
+    /// (modification times are updated on each mirror hit).
+    /// Evicts objects if the mirror exceeds the size budget (e.g., because the budget was decreased).
+    async fn load_lru_index(
+        &self,
+        mirror_path: &str,
+        max_size: u64,
+    ) -> Result<LruIndex, ObjectStoreError> {
+        let mut objects = vec![];
+        for bucket in Bucket::ALL {
+            let mut entries = fs::read_dir(format!("{mirror_path}/{bucket}")).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if !metadata.is_file() {
+                    continue;
+                }
+                let Ok(key) = entry.file_name().into_string() else {
+                    continue;
+                };
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                objects.push((modified, bucket, key, metadata.len()));
+            }
+        }
+        objects.sort_unstable_by(|(time, _, key, _), (other_time, _, other_key, _)| {
+            time.cmp(other_time).then_with(|| key.cmp(other_key))
+        });
+
+        let object_count = objects.len();
+        let mut index = LruIndex::new(max_size);
+        let mut evicted = vec![];
+        for (_, bucket, key, size) in objects {
+            evicted.extend(index.insert(bucket, &key, size));
+        }
+        tracing::info!(
+            "Loaded {object_count} mirrored objects with total size {} bytes; evicting {} objects to fit into {max_size} bytes",
+            index.total_size,
+            evicted.len()
+        );
+        self.evict(evicted).await;
+        OBJECT_STORE_METRICS.report_mirror_size(index.total_size);
+        Ok(index)
+    }
+
+    fn is_mirrored(&self, bucket: Bucket) -> bool {
+        self.buckets
+            .as_ref()
+            .map_or(true, |buckets| buckets.contains(&bucket))
+    }
+
+    async fn lock_lru_index(&self) -> Option<MutexGuard<'_, LruIndex>> {
+        Some(self.lru_index.as_ref()?.lock().await)
+    }
+
+    /// Marks a mirrored object as recently used.
+    async fn touch(&self, bucket: Bucket, key: &str) {
+        let Some(mut index) = self.lock_lru_index().await else {
+            return;
+        };
+        index.touch(bucket, key);
+        drop(index);
+
+        // Persist the access order so that it's restored after a restart; see `load_lru_index()`.
+        match self.mirror_store.touch(bucket, key).await {
+            // The object may have been evicted concurrently.
+            Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => {}
+            Err(err) => {
+                tracing::warn!(
+                    "failed updating access time for mirrored object: {:#}",
+                    anyhow::Error::from(err)
+                );
+            }
+        }
+    }
+
+    async fn mirror_object(&self, bucket: Bucket, key: &str, value: Vec<u8>) {
+        let size = value.len() as u64;
+        // The index lock is held until evictions are completed, so that the size accounting always matches
+        // the mirror contents, and concurrent calls cannot overshoot the size budget or evict the same objects.
+        let mut index = self.lock_lru_index().await;
+        if index.as_ref().map_or(false, |index| size > index.max_size) {
+            tracing::trace!("object is larger than mirror capacity; not mirroring it");
+            return;
+        }
+
+        if let Err(err) = self.mirror_store.put_raw(bucket, key, value).await {
+            tracing::warn!("failed mirroring object: {:#}", anyhow::Error::from(err));
+            return;
+        }
+        tracing::trace!("mirrored object");
+        if let Some(index) = &mut index {
+            let evicted = index.insert(bucket, key, size);
+            self.evict(evicted).await;
+            OBJECT_STORE_METRICS.report_mirror_size(index.total_size);
+        }
+    }
+
+    async fn evict(&self, objects: Vec<(Bucket, String)>) {
+        for (bucket, key) in objects {
+            OBJECT_STORE_METRICS.report_mirror_eviction(bucket);
+            match self.mirror_store.remove_raw(bucket, &key).await {
+                Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => {
+                    tracing::trace!("evicted object {bucket}/{key} from mirror");
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        "failed evicting object {bucket}/{key} from mirror: {:#}",
+                        anyhow::Error::from(err)
+                    );
+                }
+            }
+        }
     }
 }
 
@@ -25,9 +220,15 @@ impl<S: ObjectStore> MirroringObjectStore<S> {
 impl<S: ObjectStore> ObjectStore for MirroringObjectStore<S> {
     #[tracing::instrument(skip(self))]
     async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        if !self.is_mirrored(bucket) {
+            return self.inner.get_raw(bucket, key).await;
+        }
+
         match self.mirror_store.get_raw(bucket, key).await {
             Ok(object) => {
                 tracing::trace!("obtained object from mirror");
+                OBJECT_STORE_METRICS.report_mirror_lookup(bucket, true);
+                self.touch(bucket, key).await;
                 return Ok(object);
             }
             Err(err) => {
@@ -37,13 +238,10 @@ impl<S: ObjectStore> ObjectStore for MirroringObjectStore<S> {
                         anyhow::Error::from(err)
                     );
                 }
+                OBJECT_STORE_METRICS.report_mirror_lookup(bucket, false);
                 let object = self.inner.get_raw(bucket, key).await?;
                 tracing::trace!("obtained object from underlying store");
-                if let Err(err) = self.mirror_store.put_raw(bucket, key, object.clone()).await {
-                    tracing::warn!("failed mirroring object: {:#}", anyhow::Error::from(err));
-                } else {
-                    tracing::trace!("mirrored object");
-                }
+                self.mirror_object(bucket, key, object.clone()).await;
                 Ok(object)
             }
         }
@@ -56,20 +254,28 @@ impl<S: ObjectStore> ObjectStore for MirroringObjectStore<S> {
         key: &str,
         value: Vec<u8>,
     ) -> Result<(), ObjectStoreError> {
+        if !self.is_mirrored(bucket) {
+            return self.inner.put_raw(bucket, key, value).await;
+        }
+
         self.inner.put_raw(bucket, key, value.clone()).await?;
         // Only put the value into the mirror once it has been put in the underlying store
-        if let Err(err) = self.mirror_store.put_raw(bucket, key, value).await {
-            tracing::warn!("failed mirroring object: {:#}", anyhow::Error::from(err));
-        } else {
-            tracing::trace!("mirrored object");
-        }
+        self.mirror_object(bucket, key, value).await;
         Ok(())
     }
 
     #[tracing::instrument(skip(self))]
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
         self.inner.remove_raw(bucket, key).await?;
+        if !self.is_mirrored(bucket) {
+            return Ok(());
+        }
         // Only remove the value from the mirror once it has been removed in the underlying store
+        let mut index = self.lock_lru_index().await;
+        if let Some(index) = &mut index {
+            index.remove(bucket, key);
+            OBJECT_STORE_METRICS.report_mirror_size(index.total_size);
+        }
         if let Err(err) = self.mirror_store.remove_raw(bucket, key).await {
             tracing::warn!(
                 "failed removing object from mirror: {:#}",
@@ -81,6 +287,65 @@ impl<S: ObjectStore> ObjectStore for MirroringObjectStore<S> {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self))]
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        if !self.is_mirrored(bucket) {
+            return self.inner.exists_raw(bucket, key).await;
+        }
+        match self.mirror_store.exists_raw(bucket, key).await {
+            Ok(true) => {
+                tracing::trace!("object exists in mirror");
+                return Ok(true);
+            }
+            Ok(false) => { /* fall through to the underlying store */ }
+            Err(err) => {
+                tracing::warn!(
+                    "unexpected error calling local mirror store: {:#}",
+                    anyhow::Error::from(err)
+                );
+            }
+        }
+        self.inner.exists_raw(bucket, key).await
+    }
+
+    // The mirror may contain only a subset of objects, so listing always uses the underlying store.
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        self.inner.list_keys_raw(bucket, prefix, page_token).await
+    }
+
+    #[tracing::instrument(skip(self, keys), fields(keys.len = keys.len()))]
+    async fn remove_batch_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        self.inner.remove_batch_raw(bucket, keys).await?;
+        if !self.is_mirrored(bucket) {
+            return Ok(());
+        }
+        let mut index = self.lock_lru_index().await;
+        if let Some(index) = &mut index {
+            for key in keys {
+                index.remove(bucket, key);
+            }
+            OBJECT_STORE_METRICS.report_mirror_size(index.total_size);
+        }
+        if let Err(err) = self.mirror_store.remove_batch_raw(bucket, keys).await {
+            tracing::warn!(
+                "failed removing objects from mirror: {:#}",
+                anyhow::Error::from(err)
+            );
+        } else {
+            tracing::trace!("removed objects from mirror");
+        }
+        Ok(())
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
@@ -88,8 +353,11 @@ impl<S: ObjectStore> ObjectStore for MirroringObjectStore<S> {
 
 #[cfg(test)]
 mod tests {
+    use std::{sync::Arc, time::Duration};
+
     use assert_matches::assert_matches;
     use tempfile::TempDir;
+    use tokio::task::JoinSet;
 
     use super::*;
     use crate::MockObjectStore;
@@ -104,7 +372,9 @@ mod tests {
             .put_raw(Bucket::StorageSnapshot, "test", vec![1, 2, 3])
             .await
             .unwrap();
-        let mirroring_store = MirroringObjectStore::new(mock_store, path).await.unwrap();
+        let mirroring_store = MirroringObjectStore::new(mock_store, path, MirrorOptions::default())
+            .await
+            .unwrap();
 
         let object = mirroring_store
             .get_raw(Bucket::StorageSnapshot, "test")
@@ -146,5 +416,235 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(object, [3, 2, 1]);
+
+        assert!(mirroring_store
+            .exists_raw(Bucket::StorageSnapshot, "test")
+            .await
+            .unwrap());
+        assert!(!mirroring_store
+            .exists_raw(Bucket::StorageSnapshot, "missing")
+            .await
+            .unwrap());
+        let page = mirroring_store
+            .list_keys_raw(Bucket::StorageSnapshot, "", None)
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["other", "test"]);
+
+        mirroring_store
+            .remove_batch_raw(Bucket::StorageSnapshot, &["other".to_owned()])
+            .await
+            .unwrap();
+        let err = mirroring_store
+            .mirror_store
+            .get_raw(Bucket::StorageSnapshot, "other")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+        assert!(!mirroring_store
+            .exists_raw(Bucket::StorageSnapshot, "other")
+            .await
+            .unwrap());
+    }
+
+    async fn mirrored_keys(
+        store: &MirroringObjectStore<MockObjectStore>,
+        bucket: Bucket,
+    ) -> Vec<String> {
+        store
+            .mirror_store
+            .list_keys_raw(bucket, "", None)
+            .await
+            .unwrap()
+            .keys
+    }
+
+    #[tokio::test]
+    async fn mirroring_with_lru_eviction() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_owned();
+
+        let mock_store = MockObjectStore::default();
+        for key in ["a", "b", "c"] {
+            mock_store
+                .put_raw(Bucket::StorageSnapshot, key, vec![0; 4])
+                .await
+                .unwrap();
+        }
+        mock_store
+            .put_raw(Bucket::StorageSnapshot, "large", vec![0; 100])
+            .await
+            .unwrap();
+        let options = MirrorOptions {
+            max_size_bytes: Some(10),
+            buckets: None,
+        };
+        let mirroring_store = MirroringObjectStore::new(mock_store, path.clone(), options.clone())
+            .await
+            .unwrap();
+
+        for key in ["a", "b", "a", "c"] {
+            let object = mirroring_store
+                .get_raw(Bucket::StorageSnapshot, key)
+                .await
+                .unwrap();
+            assert_eq!(object, [0; 4]);
+        }
+        // "b" is the least recently used object, so it should be evicted.
+        assert_eq!(
+            mirrored_keys(&mirroring_store, Bucket::StorageSnapshot).await,
+            ["a", "c"]
+        );
+
+        // Objects exceeding the mirror capacity should not be mirrored.
+        let object = mirroring_store
+            .get_raw(Bucket::StorageSnapshot, "large")
+            .await
+            .unwrap();
+        assert_eq!(object.len(), 100);
+        assert_eq!(
+            mirrored_keys(&mirroring_store, Bucket::StorageSnapshot).await,
+            ["a", "c"]
+        );
+
+        mirroring_store
+            .remove_raw(Bucket::StorageSnapshot, "a")
+            .await
+            .unwrap();
+        {
+            let index = mirroring_store.lock_lru_index().await.unwrap();
+            assert_eq!(index.total_size, 4);
+        }
+
+        // Check that the index is restored after restarting the store with a decreased budget.
+        let options = MirrorOptions {
+            max_size_bytes: Some(3),
+            buckets: None,
+        };
+        let mirroring_store = MirroringObjectStore::new(mirroring_store.inner, path, options)
+            .await
+            .unwrap();
+        assert!(mirrored_keys(&mirroring_store, Bucket::StorageSnapshot)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn lru_order_is_restored_after_restart() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_owned();
+
+        let mock_store = MockObjectStore::default();
+        for key in ["a", "b", "c"] {
+            mock_store
+                .put_raw(Bucket::StorageSnapshot, key, vec![0; 4])
+                .await
+                .unwrap();
+        }
+        let options = MirrorOptions {
+            max_size_bytes: Some(8),
+            buckets: None,
+        };
+        let mirroring_store = MirroringObjectStore::new(mock_store, path.clone(), options.clone())
+            .await
+            .unwrap();
+        // Sleep between accesses so that file modification times are distinguishable.
+        for key in ["a", "b", "a"] {
+            mirroring_store
+                .get_raw(Bucket::StorageSnapshot, key)
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let mirroring_store = MirroringObjectStore::new(mirroring_store.inner, path, options)
+            .await
+            .unwrap();
+        mirroring_store
+            .get_raw(Bucket::StorageSnapshot, "c")
+            .await
+            .unwrap();
+        // "b" was accessed less recently than "a" before the restart, so it should be evicted.
+        assert_eq!(
+            mirrored_keys(&mirroring_store, Bucket::StorageSnapshot).await,
+            ["a", "c"]
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_mirroring_respects_size_budget() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_owned();
+
+        let options = MirrorOptions {
+            max_size_bytes: Some(10),
+            buckets: None,
+        };
+        let mirroring_store = MirroringObjectStore::new(MockObjectStore::default(), path, options)
+            .await
+            .unwrap();
+        let mirroring_store = Arc::new(mirroring_store);
+        let mut tasks = JoinSet::new();
+        for i in 0..20 {
+            let store = mirroring_store.clone();
+            tasks.spawn(async move {
+                store
+                    .put_raw(Bucket::StorageSnapshot, &i.to_string(), vec![0; 4])
+                    .await
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result.unwrap().unwrap();
+        }
+
+        let index = mirroring_store.lock_lru_index().await.unwrap();
+        assert_eq!(index.total_size, 8);
+        assert_eq!(index.entries.len(), 2);
+        drop(index);
+        assert_eq!(
+            mirrored_keys(&mirroring_store, Bucket::StorageSnapshot)
+                .await
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn mirroring_selected_buckets() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_owned();
+
+        let mock_store = MockObjectStore::default();
+        let options = MirrorOptions {
+            max_size_bytes: None,
+            buckets: Some(HashSet::from([Bucket::StorageSnapshot])),
+        };
+        let mirroring_store = MirroringObjectStore::new(mock_store, path, options)
+            .await
+            .unwrap();
+        mirroring_store
+            .put_raw(Bucket::StorageSnapshot, "test", vec![1, 2, 3])
+            .await
+            .unwrap();
+        mirroring_store
+            .put_raw(Bucket::ProofsFri, "test", vec![3, 2, 1])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mirrored_keys(&mirroring_store, Bucket::StorageSnapshot).await,
+            ["test"]
+        );
+        assert!(mirrored_keys(&mirroring_store, Bucket::ProofsFri)
+            .await
+            .is_empty());
+        let object = mirroring_store
+            .get_raw(Bucket::ProofsFri, "test")
+            .await
+            .unwrap();
+        assert_eq!(object, [3, 2, 1]);
+        assert!(mirrored_keys(&mirroring_store, Bucket::ProofsFri)
+            .await
+            .is_empty());
     }
 }
@@ -5,7 +5,7 @@ use std::{collections::HashMap, sync::Arc};
 use async_trait::async_trait;
 use tokio::sync::Mutex;
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, KeysPage, ObjectStore, ObjectStoreError};
 
 type BucketMap = HashMap<String, Vec<u8>>;
 
@@ -54,6 +54,32 @@ impl ObjectStore for MockObjectStore {
         Ok(())
     }
 
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        Ok(lock
+            .get(&bucket)
+            .map_or(false, |bucket_map| bucket_map.contains_key(key)))
+    }
+
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        let Some(bucket_map) = lock.get(&bucket) else {
+            return Ok(KeysPage::default());
+        };
+        let mut keys: Vec<_> = bucket_map
+            .keys()
+            .map(String::as_str)
+            .filter(|key| key.starts_with(prefix))
+            .collect();
+        keys.sort_unstable();
+        Ok(KeysPage::from_sorted_keys(keys.into_iter(), page_token))
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         bucket.to_string()
     }
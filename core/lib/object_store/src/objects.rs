@@ -171,6 +171,41 @@ impl dyn ObjectStore + '_ {
         self.remove_raw(V::BUCKET, &key).await
     }
 
+    /// Checks whether a value associated with the key exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors specific to the storage.
+    pub async fn exists<V: StoredObject>(&self, key: V::Key<'_>) -> Result<bool, ObjectStoreError> {
+        let key = V::encode_key(key);
+        self.exists_raw(V::BUCKET, &key).await
+    }
+
+    /// Lists all keys in the bucket starting with the specified prefix, fetching all pages of keys
+    /// from the underlying store.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors specific to the storage.
+    pub async fn list_keys(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let mut keys = vec![];
+        let mut page_token = None;
+        loop {
+            let page = self
+                .list_keys_raw(bucket, prefix, page_token.as_deref())
+                .await?;
+            keys.extend(page.keys);
+            page_token = page.next_page_token;
+            if page_token.is_none() {
+                return Ok(keys);
+            }
+        }
+    }
+
     pub fn get_storage_prefix<V: StoredObject>(&self) -> String {
         self.storage_prefix_raw(V::BUCKET)
     }
@@ -261,4 +296,55 @@ mod tests {
         let reconstructed_factory_deps = store.get(key).await.unwrap();
         assert_eq!(factory_deps, reconstructed_factory_deps);
     }
+
+    #[tokio::test]
+    async fn listing_keys_across_pages() {
+        let store = MockObjectStore::arc();
+        let factory_deps = SnapshotFactoryDependencies {
+            factory_deps: vec![],
+        };
+        let batch_count = 1_010_u32;
+        assert!(batch_count as usize > crate::raw::MAX_KEYS_PER_PAGE);
+        for l1_batch_number in 0..batch_count {
+            store
+                .put(L1BatchNumber(l1_batch_number), &factory_deps)
+                .await
+                .unwrap();
+        }
+        assert!(store
+            .exists::<SnapshotFactoryDependencies>(L1BatchNumber(0))
+            .await
+            .unwrap());
+        assert!(!store
+            .exists::<SnapshotFactoryDependencies>(L1BatchNumber(batch_count))
+            .await
+            .unwrap());
+
+        let first_page = store
+            .list_keys_raw(Bucket::StorageSnapshot, "snapshot_l1_batch_", None)
+            .await
+            .unwrap();
+        assert_eq!(first_page.keys.len(), crate::raw::MAX_KEYS_PER_PAGE);
+        assert!(first_page.next_page_token.is_some());
+
+        let keys = store
+            .list_keys(Bucket::StorageSnapshot, "snapshot_l1_batch_")
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), batch_count as usize);
+        let keys = store
+            .list_keys(Bucket::StorageSnapshot, "snapshot_l1_batch_100_")
+            .await
+            .unwrap();
+        assert_eq!(keys, ["snapshot_l1_batch_100_factory_deps.proto.gzip"]);
+
+        store
+            .remove_batch_raw(Bucket::StorageSnapshot, &keys)
+            .await
+            .unwrap();
+        assert!(!store
+            .exists::<SnapshotFactoryDependencies>(L1BatchNumber(100))
+            .await
+            .unwrap());
+    }
 }
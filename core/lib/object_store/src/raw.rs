@@ -1,4 +1,4 @@
-use std::{error, fmt};
+use std::{error, fmt, io, str, sync::Arc};
 
 use async_trait::async_trait;
 
@@ -21,6 +21,22 @@ pub enum Bucket {
 }
 
 impl Bucket {
+    /// All supported buckets.
+    pub const ALL: [Self; 12] = [
+        Self::ProverJobs,
+        Self::WitnessInput,
+        Self::LeafAggregationWitnessJobs,
+        Self::NodeAggregationWitnessJobs,
+        Self::SchedulerWitnessJobs,
+        Self::ProverJobsFri,
+        Self::LeafAggregationWitnessJobsFri,
+        Self::NodeAggregationWitnessJobsFri,
+        Self::SchedulerWitnessJobsFri,
+        Self::ProofsFri,
+        Self::StorageSnapshot,
+        Self::TeeVerifierInput,
+    ];
+
     pub(crate) fn as_str(self) -> &'static str {
         match self {
             Self::ProverJobs => "prover_jobs",
@@ -45,9 +61,35 @@ impl fmt::Display for Bucket {
     }
 }
 
+impl str::FromStr for Bucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|bucket| bucket.as_str() == s)
+            .ok_or_else(|| format!("unknown bucket: `{s}`"))
+    }
+}
+
 /// Thread-safe boxed error.
 pub type BoxedError = Box<dyn error::Error + Send + Sync>;
 
+pub(crate) fn has_transient_io_source(mut err: &(dyn error::Error + 'static)) -> bool {
+    loop {
+        if err.is::<io::Error>() {
+            // We treat any I/O errors as transient. This isn't always true, but frequently occurring I/O errors
+            // (e.g., "connection reset by peer") *are* transient, and treating an error as transient is a safer option,
+            // even if it can lead to unnecessary retries.
+            return true;
+        }
+        err = match err.source() {
+            Some(source) => source,
+            None => return false,
+        };
+    }
+}
+
 /// Errors during [`ObjectStore`] operations.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -61,6 +103,8 @@ pub enum ObjectStoreError {
     KeyNotFound(BoxedError),
     /// Object (de)serialization failed.
     Serialization(BoxedError),
+    /// Object is corrupted (e.g., its checksum doesn't match its contents).
+    Corrupted(BoxedError),
     /// Other error has occurred when accessing the store (e.g., a network error).
     Other {
         source: BoxedError,
@@ -75,7 +119,7 @@ impl ObjectStoreError {
             Self::Initialization { is_transient, .. } | Self::Other { is_transient, .. } => {
                 *is_transient
             }
-            Self::KeyNotFound(_) | Self::Serialization(_) => false,
+            Self::KeyNotFound(_) | Self::Serialization(_) | Self::Corrupted(_) => false,
         }
     }
 }
@@ -95,6 +139,7 @@ impl fmt::Display for ObjectStoreError {
             }
             Self::KeyNotFound(err) => write!(formatter, "key not found: {err}"),
             Self::Serialization(err) => write!(formatter, "serialization error: {err}"),
+            Self::Corrupted(err) => write!(formatter, "corrupted object: {err}"),
             Self::Other {
                 source,
                 is_transient,
@@ -112,7 +157,47 @@ impl error::Error for ObjectStoreError {
             Self::Initialization { source, .. } | Self::Other { source, .. } => {
                 Some(source.as_ref())
             }
-            Self::KeyNotFound(err) | Self::Serialization(err) => Some(err.as_ref()),
+            Self::KeyNotFound(err) | Self::Serialization(err) | Self::Corrupted(err) => {
+                Some(err.as_ref())
+            }
+        }
+    }
+}
+
+/// Maximum number of keys in a [`KeysPage`] returned by stores that do not have a natural page size
+/// (e.g., file-backed and mock stores).
+pub(crate) const MAX_KEYS_PER_PAGE: usize = 1_000;
+
+/// Page of keys returned by [`ObjectStore::list_keys_raw()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeysPage {
+    /// Keys in the page. Keys are relative to the bucket, i.e. can be used directly in other [`ObjectStore`] methods.
+    pub keys: Vec<String>,
+    /// Opaque token allowing to fetch the next page of keys. `None` if this page is the last one.
+    pub next_page_token: Option<String>,
+}
+
+impl KeysPage {
+    /// Creates a page from keys sorted in the lexicographic order, skipping keys up to and including `page_token`
+    /// (which, for such pages, is the last key in the previous page).
+    pub(crate) fn from_sorted_keys<'a>(
+        keys: impl Iterator<Item = &'a str>,
+        page_token: Option<&str>,
+    ) -> Self {
+        let mut keys: Vec<_> = keys
+            .filter(|key| page_token.map_or(true, |token| *key > token))
+            .take(MAX_KEYS_PER_PAGE + 1)
+            .map(str::to_owned)
+            .collect();
+        let next_page_token = if keys.len() > MAX_KEYS_PER_PAGE {
+            keys.truncate(MAX_KEYS_PER_PAGE);
+            keys.last().cloned()
+        } else {
+            None
+        };
+        Self {
+            keys,
+            next_page_token,
         }
     }
 }
@@ -153,5 +238,102 @@ pub trait ObjectStore: 'static + fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Checks whether an object with the specified key exists in the given bucket, without fetching the object.
+    ///
+    /// The default implementation fetches the object; implementations should override it with a cheaper check
+    /// if possible.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be accessed.
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        match self.get_raw(bucket, key).await {
+            Ok(_) => Ok(true),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Lists a page of keys in the given bucket that start with the specified `prefix`. Keys are returned
+    /// in the lexicographic order. To get the first page, `page_token` should be set to `None`; to get subsequent pages,
+    /// it should be set to [`KeysPage::next_page_token`] from the previous page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing fails.
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError>;
+
+    /// Removes values associated with the specified keys from the given bucket. Missing keys are ignored.
+    ///
+    /// The default implementation removes keys one by one; implementations should override it if the store
+    /// supports bulk removal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if removal fails. In this case, some of the keys may be removed.
+    async fn remove_batch_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        for key in keys {
+            match self.remove_raw(bucket, key).await {
+                Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => { /* continue */ }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
+
+#[async_trait]
+impl<S: ObjectStore + ?Sized> ObjectStore for Arc<S> {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        (**self).get_raw(bucket, key).await
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        (**self).put_raw(bucket, key, value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        (**self).remove_raw(bucket, key).await
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        (**self).exists_raw(bucket, key).await
+    }
+
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        (**self).list_keys_raw(bucket, prefix, page_token).await
+    }
+
+    async fn remove_batch_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        (**self).remove_batch_raw(bucket, keys).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        (**self).storage_prefix_raw(bucket)
+    }
+}
@@ -5,7 +5,7 @@ use rand::Rng;
 
 use crate::{
     metrics::OBJECT_STORE_METRICS,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    raw::{Bucket, KeysPage, ObjectStore, ObjectStoreError},
 };
 
 /// Information about request added to logs.
@@ -16,6 +16,9 @@ enum Request<'a> {
     Get(Bucket, &'a str),
     Put(Bucket, &'a str),
     Remove(Bucket, &'a str),
+    Exists(Bucket, &'a str),
+    List(Bucket, &'a str),
+    RemoveBatch(Bucket, usize),
 }
 
 impl Request<'_> {
@@ -121,6 +124,40 @@ impl<S: ObjectStore> ObjectStore for StoreWithRetries<S> {
             .await
     }
 
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        Request::Exists(bucket, key)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.exists_raw(bucket, key)
+            })
+            .await
+    }
+
+    async fn list_keys_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        Request::List(bucket, prefix)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.list_keys_raw(bucket, prefix, page_token)
+            })
+            .await
+    }
+
+    async fn remove_batch_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        // Removals are idempotent, so it's safe to retry the entire batch.
+        Request::RemoveBatch(bucket, keys.len())
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.remove_batch_raw(bucket, keys)
+            })
+            .await
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
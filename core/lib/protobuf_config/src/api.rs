@@ -1,4 +1,4 @@
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroUsize};
 
 use anyhow::Context as _;
 use zksync_config::configs::{api, ApiConfig};
@@ -69,6 +69,42 @@ impl ProtoRepr for proto::Web3JsonRpc {
             })
             .collect::<anyhow::Result<_>>()
             .context("max_response_body_size_overrides")?;
+        let method_weights = self
+            .method_weights
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let weight = required(&entry.weight).with_context(|| format!("[{i}].weight"))?;
+                Ok((
+                    entry
+                        .method
+                        .clone()
+                        .with_context(|| format!("[{i}].method"))?,
+                    NonZeroU32::new(*weight).with_context(|| format!("[{i}].weight is zero"))?,
+                ))
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("method_weights")?;
+        let api_keys = self
+            .api_keys
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let quota = required(&entry.quota).with_context(|| format!("[{i}].quota"))?;
+                Ok((
+                    entry.key.clone().with_context(|| format!("[{i}].key"))?,
+                    NonZeroU32::new(*quota).with_context(|| format!("[{i}].quota is zero"))?,
+                ))
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("api_keys")?;
+        let trusted_proxies = self
+            .trusted_proxies
+            .iter()
+            .enumerate()
+            .map(|(i, ip)| ip.parse().with_context(|| format!("[{i}]")))
+            .collect::<anyhow::Result<_>>()
+            .context("trusted_proxies")?;
 
         Ok(Self::Type {
             http_port: required(&self.http_port)
@@ -81,10 +117,12 @@ impl ProtoRepr for proto::Web3JsonRpc {
             ws_url: required(&self.ws_url).context("ws_url")?.clone(),
             req_entities_limit: self.req_entities_limit,
             filters_disabled: self.filters_disabled.unwrap_or(false),
+            txpool_namespace_enabled: self.txpool_namespace_enabled.unwrap_or(false),
             filters_limit: self.filters_limit,
             subscriptions_limit: self.subscriptions_limit,
             pubsub_polling_interval: self.pubsub_polling_interval,
             max_nonce_ahead: *required(&self.max_nonce_ahead).context("max_nonce_ahead")?,
+            replacement_fee_bump_percent: self.replacement_fee_bump_percent,
             gas_price_scale_factor: *required(&self.gas_price_scale_factor)
                 .context("gas_price_scale_factor")?,
             request_timeout: self.request_timeout,
@@ -140,6 +178,15 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("websocket_requests_per_minute_limit")?,
+            client_requests_per_minute_limit: self
+                .client_requests_per_minute_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("client_requests_per_minute_limit")?,
+            api_key_header: self.api_key_header.clone(),
+            api_keys,
+            trusted_proxies,
+            method_weights,
             tree_api_url: self.tree_api_url.clone(),
             mempool_cache_update_interval: self.mempool_cache_update_interval,
             mempool_cache_size: self
@@ -147,6 +194,16 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("mempool_cache_size")?,
+            response_cache_size_mb: self
+                .response_cache_size_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("response_cache_size_mb")?,
+            graphql_port: self
+                .graphql_port
+                .map(|x| x.try_into())
+                .transpose()
+                .context("graphql_port")?,
             whitelisted_tokens_for_aa: self
                 .whitelisted_tokens_for_aa
                 .iter()
@@ -165,12 +222,16 @@ impl ProtoRepr for proto::Web3JsonRpc {
             ws_url: Some(this.ws_url.clone()),
             req_entities_limit: this.req_entities_limit,
             filters_disabled: Some(this.filters_disabled),
+            txpool_namespace_enabled: Some(this.txpool_namespace_enabled),
             mempool_cache_update_interval: this.mempool_cache_update_interval,
             mempool_cache_size: this.mempool_cache_size.map(|x| x.try_into().unwrap()),
+            response_cache_size_mb: this.response_cache_size_mb.map(|x| x.try_into().unwrap()),
+            graphql_port: this.graphql_port.map(Into::into),
             filters_limit: this.filters_limit,
             subscriptions_limit: this.subscriptions_limit,
             pubsub_polling_interval: this.pubsub_polling_interval,
             max_nonce_ahead: Some(this.max_nonce_ahead),
+            replacement_fee_bump_percent: this.replacement_fee_bump_percent,
             gas_price_scale_factor: Some(this.gas_price_scale_factor),
             request_timeout: this.request_timeout,
             account_pks: this
@@ -216,6 +277,31 @@ impl ProtoRepr for proto::Web3JsonRpc {
             websocket_requests_per_minute_limit: this
                 .websocket_requests_per_minute_limit
                 .map(|x| x.into()),
+            client_requests_per_minute_limit: this
+                .client_requests_per_minute_limit
+                .map(|x| x.into()),
+            api_key_header: this.api_key_header.clone(),
+            api_keys: this
+                .api_keys
+                .iter()
+                .map(|(key, quota)| proto::RpcApiKey {
+                    key: Some(key.to_owned()),
+                    quota: Some(quota.get()),
+                })
+                .collect(),
+            trusted_proxies: this
+                .trusted_proxies
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            method_weights: this
+                .method_weights
+                .iter()
+                .map(|(method, weight)| proto::RpcMethodWeight {
+                    method: Some(method.to_owned()),
+                    weight: Some(weight.get()),
+                })
+                .collect(),
             tree_api_url: this.tree_api_url.clone(),
             whitelisted_tokens_for_aa: this
                 .whitelisted_tokens_for_aa
@@ -146,6 +146,13 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: *required(&self.stuck_tx_timeout).context("stuck_tx_timeout")?,
             remove_stuck_txs: *required(&self.remove_stuck_txs).context("remove_stuck_txs")?,
             delay_interval: *required(&self.delay_interval).context("delay_interval")?,
+            max_txs_per_account: self
+                .max_txs_per_account
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_txs_per_account")?,
+            max_nonce_gap: self.max_nonce_gap,
+            evict_lowest_fee: self.evict_lowest_fee.unwrap_or(false),
         })
     }
 
@@ -157,6 +164,9 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: Some(this.stuck_tx_timeout),
             remove_stuck_txs: Some(this.remove_stuck_txs),
             delay_interval: Some(this.delay_interval),
+            max_txs_per_account: this.max_txs_per_account.map(|x| x.try_into().unwrap()),
+            max_nonce_gap: this.max_nonce_gap,
+            evict_lowest_fee: Some(this.evict_lowest_fee),
         }
     }
 }
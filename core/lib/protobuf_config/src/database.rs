@@ -1,3 +1,5 @@
+use std::num::NonZeroU32;
+
 use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::{
@@ -48,6 +50,11 @@ impl ProtoRepr for proto::MerkleTree {
             max_l1_batches_per_iter: required(&self.max_l1_batches_per_iter)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_l1_batches_per_iter")?,
+            consistency_check_interval_sec: self
+                .consistency_check_interval_sec
+                .map(|interval| NonZeroU32::new(interval).context("cannot be 0"))
+                .transpose()
+                .context("consistency_check_interval_sec")?,
         })
     }
 
@@ -60,6 +67,9 @@ impl ProtoRepr for proto::MerkleTree {
             memtable_capacity_mb: Some(this.memtable_capacity_mb.try_into().unwrap()),
             stalled_writes_timeout_sec: Some(this.stalled_writes_timeout_sec),
             max_l1_batches_per_iter: Some(this.max_l1_batches_per_iter.try_into().unwrap()),
+            consistency_check_interval_sec: this
+                .consistency_check_interval_sec
+                .map(NonZeroU32::get),
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::num::NonZeroU32;
+
 use anyhow::Context as _;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 use zksync_protobuf::{repr::ProtoRepr, required};
@@ -37,6 +39,26 @@ impl ProtoRepr for proto::ObjectStore {
                     .context("file_backed_base_path")?
                     .clone(),
             },
+            proto::object_store::Mode::S3(mode) => ObjectStoreMode::S3 {
+                bucket_name: required(&mode.bucket_name).context("bucket_name")?.clone(),
+                region: required(&mode.region).context("region")?.clone(),
+                endpoint: mode.endpoint.clone(),
+                path_style: mode.path_style.unwrap_or(false),
+            },
+            proto::object_store::Mode::S3WithStaticCredentials(mode) => {
+                ObjectStoreMode::S3WithStaticCredentials {
+                    bucket_name: required(&mode.bucket_name).context("bucket_name")?.clone(),
+                    region: required(&mode.region).context("region")?.clone(),
+                    endpoint: mode.endpoint.clone(),
+                    path_style: mode.path_style.unwrap_or(false),
+                    access_key_id: required(&mode.access_key_id)
+                        .context("access_key_id")?
+                        .clone(),
+                    secret_access_key: required(&mode.secret_access_key)
+                        .context("secret_access_key")?
+                        .clone(),
+                }
+            }
         };
 
         Ok(Self::Type {
@@ -45,6 +67,15 @@ impl ProtoRepr for proto::ObjectStore {
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_retries")?,
             local_mirror_path: self.local_mirror_path.clone(),
+            local_mirror_max_size_mb: self.local_mirror_max_size_mb,
+            local_mirror_buckets: self.local_mirror_buckets.clone(),
+            compress_objects: self.compress_objects.unwrap_or(false),
+            deduplicate_objects: self.deduplicate_objects.unwrap_or(false),
+            deduplication_gc_interval_sec: self
+                .deduplication_gc_interval_sec
+                .map(|interval| NonZeroU32::new(interval).context("cannot be 0"))
+                .transpose()
+                .context("deduplication_gc_interval_sec")?,
         })
     }
 
@@ -76,12 +107,45 @@ impl ProtoRepr for proto::ObjectStore {
             } => proto::object_store::Mode::FileBacked(proto::object_store::FileBacked {
                 file_backed_base_path: Some(file_backed_base_path.clone()),
             }),
+            ObjectStoreMode::S3 {
+                bucket_name,
+                region,
+                endpoint,
+                path_style,
+            } => proto::object_store::Mode::S3(proto::object_store::S3 {
+                bucket_name: Some(bucket_name.clone()),
+                region: Some(region.clone()),
+                endpoint: endpoint.clone(),
+                path_style: Some(*path_style),
+            }),
+            ObjectStoreMode::S3WithStaticCredentials {
+                bucket_name,
+                region,
+                endpoint,
+                path_style,
+                access_key_id,
+                secret_access_key,
+            } => proto::object_store::Mode::S3WithStaticCredentials(
+                proto::object_store::S3WithStaticCredentials {
+                    bucket_name: Some(bucket_name.clone()),
+                    region: Some(region.clone()),
+                    endpoint: endpoint.clone(),
+                    path_style: Some(*path_style),
+                    access_key_id: Some(access_key_id.clone()),
+                    secret_access_key: Some(secret_access_key.clone()),
+                },
+            ),
         };
 
         Self {
             mode: Some(mode),
             max_retries: Some(this.max_retries.into()),
             local_mirror_path: this.local_mirror_path.clone(),
+            local_mirror_max_size_mb: this.local_mirror_max_size_mb,
+            local_mirror_buckets: this.local_mirror_buckets.clone(),
+            compress_objects: Some(this.compress_objects),
+            deduplicate_objects: Some(this.deduplicate_objects),
+            deduplication_gc_interval_sec: this.deduplication_gc_interval_sec.map(NonZeroU32::get),
         }
     }
 }
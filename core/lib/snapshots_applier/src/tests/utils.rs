@@ -4,7 +4,7 @@ use std::{collections::HashMap, fmt, future, sync::Arc};
 
 use async_trait::async_trait;
 use tokio::sync::watch;
-use zksync_object_store::{Bucket, MockObjectStore, ObjectStore, ObjectStoreError};
+use zksync_object_store::{Bucket, KeysPage, MockObjectStore, ObjectStore, ObjectStoreError};
 use zksync_types::{
     api,
     block::L2BlockHeader,
@@ -118,6 +118,15 @@ impl ObjectStore for ObjectStoreWithErrors {
         unreachable!("Should not be used in snapshot applier")
     }
 
+    async fn list_keys_raw(
+        &self,
+        _bucket: Bucket,
+        _prefix: &str,
+        _page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
@@ -357,6 +366,15 @@ impl ObjectStore for HangingObjectStore {
         unreachable!("Should not be used in snapshot applier")
     }
 
+    async fn list_keys_raw(
+        &self,
+        _bucket: Bucket,
+        _prefix: &str,
+        _page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
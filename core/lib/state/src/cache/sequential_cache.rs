@@ -92,6 +92,17 @@ impl<K: Ord + Copy, V: Clone> SequentialCache<K, V> {
         result
     }
 
+    /// Removes all entries for which `predicate` returns `false`, preserving the order of other entries.
+    pub fn retain(&mut self, mut predicate: impl FnMut(&K, &V) -> bool) {
+        self.data.retain(|(key, value)| predicate(key, value));
+        self.report_size();
+    }
+
+    /// Returns all values in the cache in the order they were inserted.
+    pub fn values(&self) -> Vec<V> {
+        self.data.iter().map(|(_, value)| value.clone()).collect()
+    }
+
     /// Returns the last key in the cache.
     pub fn get_last_key(&self) -> Option<K> {
         self.data.back().map(|&(key, _)| key)
@@ -157,6 +168,19 @@ mod tests {
         assert_eq!(cache.query(2), Some(vec![(3, 3), (4, 4)]));
     }
 
+    #[test]
+    fn retaining_entries() {
+        let mut cache = SequentialCache::<u32, u32>::new("retaining_entries", 100);
+        cache.insert(vec![(1, 1), (2, 2), (2, 5), (3, 6)]).unwrap();
+        cache.retain(|_, &value| value != 5);
+        assert_eq!(cache.query(1), Some(vec![(2, 2), (3, 6)]));
+        assert_eq!(cache.values(), [1, 2, 6]);
+        cache.retain(|&key, _| key > 1);
+        // The oldest entry was removed, so the cache cannot be used for the key anymore.
+        assert_eq!(cache.query(1), None);
+        assert_eq!(cache.query(2), Some(vec![(3, 6)]));
+    }
+
     #[test]
     fn insertion_at_capacity_limit() {
         let mut cache = SequentialCache::<u32, String>::new("insertion_at_capacity_limit", 2);
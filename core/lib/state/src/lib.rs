@@ -29,7 +29,7 @@ mod storage_view;
 mod test_utils;
 
 pub use self::{
-    cache::sequential_cache::SequentialCache,
+    cache::{lru_cache::LruCache, sequential_cache::SequentialCache, CacheValue},
     catchup::AsyncCatchupTask,
     in_memory::InMemoryStorage,
     // Note, that `test_infra` of the bootloader tests relies on this value to be exposed
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use strum::Display;
@@ -6,14 +8,18 @@ use zksync_basic_types::{
     L1BatchNumber, H160, H2048, H256, H64, U256, U64,
 };
 use zksync_contracts::BaseSystemContractsHashes;
+use zksync_utils::u256_to_h256;
 
 pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    debug_flat_call::DebugCallFlat,
+    fee::Fee,
     protocol_version::L1VerifierConfig,
-    vm_trace::{Call, CallType},
-    Address, L2BlockNumber, ProtocolVersionId,
+    transaction_request::CallRequest,
+    vm_trace::{Call, CallType, ViolatedValidationRule},
+    Address, L2BlockNumber, Nonce, ProtocolVersionId,
 };
 
 pub mod en;
@@ -586,6 +592,24 @@ pub struct GetLogsFilter {
     pub topics: Vec<(u32, Vec<H256>)>,
 }
 
+/// Position of a log in the chain used to continue log queries: the block number and the log index in the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsCursor {
+    pub block_number: U64,
+    pub log_index: U64,
+}
+
+/// Page of logs returned by `zks_getLogsPaginated`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPage {
+    pub logs: Vec<Log>,
+    /// Position of the first log not included into this page. `None` if the page contains all remaining logs
+    /// satisfying the filter.
+    pub cursor: Option<LogsCursor>,
+}
+
 /// Result of debugging block
 /// For some reasons geth returns result as {result: DebugCall}
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -594,6 +618,210 @@ pub struct ResultDebugCall {
     pub result: DebugCall,
 }
 
+/// Result of tracing a block with an arbitrary tracer. Like [`ResultDebugCall`], wraps the trace
+/// for each transaction into `{result: ...}`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDebugTrace {
+    pub result: DebugTraceResult,
+}
+
+/// Transaction trace produced by one of [`SupportedTracers`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum DebugTraceResult {
+    /// Output of `callTracer`.
+    CallTrace(DebugCall),
+    /// Output of `flatCallTracer`.
+    FlatCallTrace(Vec<DebugCallFlat>),
+    /// Output of `structLogger`.
+    StructLogs(StructLogTrace),
+    /// Output of `prestateTracer`.
+    PrestateTrace(PrestateTrace),
+}
+
+impl DebugTraceResult {
+    /// Returns the call trace if this is the `callTracer` output.
+    pub fn into_call_trace(self) -> Option<DebugCall> {
+        match self {
+            Self::CallTrace(call) => Some(call),
+            _ => None,
+        }
+    }
+}
+
+/// Output of `prestateTracer` in the Geth format.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// Output in the diff mode: states of the modified accounts before and after the transaction.
+    Diff {
+        pre: HashMap<Address, PrestateAccount>,
+        post: HashMap<Address, PrestateAccount>,
+    },
+    /// Output in the default mode: states of the touched accounts before the transaction.
+    Prestate(HashMap<Address, PrestateAccount>),
+}
+
+/// Output of `structLogger`, an opcode-level tracer similar to the default Geth tracer.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogTrace {
+    /// Gas used by the transaction.
+    pub gas: U256,
+    pub failed: bool,
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Single EraVM instruction recorded by `structLogger`. Instructions executed by the bootloader are not recorded.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u16,
+    /// Human-readable opcode name, e.g. `Add(Add)`.
+    pub op: String,
+    /// Ergs remaining before the instruction is executed.
+    pub gas: u64,
+    /// Ergs spent on the instruction. Not computed for instructions changing the call frame (e.g., far calls).
+    pub gas_cost: u64,
+    /// Depth of the call stack, including near call frames.
+    pub depth: usize,
+    /// Address of the contract executing the instruction.
+    pub address: Address,
+    /// Values of the general-purpose registers `r1`–`r15` before the instruction is executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registers: Option<Vec<U256>>,
+    /// Memory write performed by the instruction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_write: Option<StructLogMemoryWrite>,
+    /// Storage slot accessed by the instruction, with its value after the instruction is executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<HashMap<H256, H256>>,
+}
+
+/// Heap write recorded in [`StructLog`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogMemoryWrite {
+    pub region: MemoryRegion,
+    /// Byte offset of the written word.
+    pub offset: u32,
+    pub value: U256,
+}
+
+/// Memory region of the current call frame.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MemoryRegion {
+    Heap,
+    AuxHeap,
+}
+
+/// Output of `debug_traceValidation`: outcome of the account validation step of a transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationTrace {
+    /// Reason the validation step halted with (e.g., a revert in the account or paymaster validation method).
+    /// Validation may halt regardless of whether any validation rules are violated.
+    pub error: Option<String>,
+    /// Violated validation rules in the order they were encountered. Each distinct violation is reported once.
+    pub violations: Vec<ValidationViolation>,
+}
+
+impl ValidationTrace {
+    /// Checks whether the validation step has succeeded without violating any rules.
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none() && self.violations.is_empty()
+    }
+}
+
+/// Validation rule violation together with the call frame it happened in.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationViolation {
+    #[serde(flatten)]
+    pub rule: ValidationRule,
+    /// Human-readable description of the violation.
+    pub message: String,
+    /// Call frame executing when the rule was violated. Not recorded for older VM versions.
+    pub context: Option<ValidationCallContext>,
+}
+
+impl ValidationViolation {
+    pub fn new(rule: &ViolatedValidationRule, context: Option<ValidationCallContext>) -> Self {
+        Self {
+            rule: rule.into(),
+            message: rule.to_string(),
+            context,
+        }
+    }
+}
+
+/// Validation rule violated by a transaction, as reported by `debug_traceValidation`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "rule", rename_all = "camelCase")]
+pub enum ValidationRule {
+    /// Validation read a storage slot not belonging to the account and not otherwise trusted.
+    TouchedUnallowedStorageSlot { address: Address, key: H256 },
+    /// Validation called a contract without deployed code.
+    CalledContractWithNoCode { address: Address },
+    /// Validation used a forbidden context opcode (e.g., to read block metadata).
+    TouchedUnallowedContext,
+    /// Validation spent more computational gas than allowed.
+    TookTooManyComputationalGas { limit: u32 },
+}
+
+impl From<&ViolatedValidationRule> for ValidationRule {
+    fn from(rule: &ViolatedValidationRule) -> Self {
+        match rule {
+            ViolatedValidationRule::TouchedUnallowedStorageSlots(address, key) => {
+                Self::TouchedUnallowedStorageSlot {
+                    address: *address,
+                    key: u256_to_h256(*key),
+                }
+            }
+            ViolatedValidationRule::CalledContractWithNoCode(address) => {
+                Self::CalledContractWithNoCode { address: *address }
+            }
+            ViolatedValidationRule::TouchedUnallowedContext => Self::TouchedUnallowedContext,
+            ViolatedValidationRule::TookTooManyComputationalGas(limit) => {
+                Self::TookTooManyComputationalGas { limit: *limit }
+            }
+        }
+    }
+}
+
+/// Call frame in which a validation rule was violated.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationCallContext {
+    /// Address of the contract executing the violating instruction.
+    pub address: Address,
+    /// Address of the executed code. Differs from `address` for delegate calls.
+    pub code_address: Address,
+    /// Caller of the contract.
+    pub msg_sender: Address,
+    pub pc: u16,
+    /// Depth of the call stack, including near call frames.
+    pub depth: usize,
+}
+
+/// Account state reported by `prestateTracer`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Transaction nonce of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<HashMap<H256, H256>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum DebugCallType {
     Call,
@@ -717,24 +945,54 @@ impl ProtocolVersion {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
+    #[default]
     CallTracer,
+    FlatCallTracer,
+    PrestateTracer,
+    StructLogger,
 }
 
+/// Tracer-specific configuration. Fields irrelevant for the requested tracer are ignored.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerConfig {
+    /// Only return the top-level call (`callTracer` and `flatCallTracer`).
+    #[serde(default)]
     pub only_top_call: bool,
+    /// Return the state difference caused by the transaction instead of the touched prestate (`prestateTracer`).
+    #[serde(default)]
+    pub diff_mode: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Options for `structLogger`. Like in Geth, these options are specified at the top level of [`TracerConfig`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLoggerConfig {
+    /// Do not record accessed storage slots.
+    #[serde(default)]
+    pub disable_storage: bool,
+    /// Do not record register values.
+    #[serde(default)]
+    pub disable_stack: bool,
+    /// Do not record memory writes.
+    #[serde(default)]
+    pub disable_memory: bool,
+    /// Maximum number of recorded instructions; 0 means no limit.
+    #[serde(default)]
+    pub limit: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct TracerConfig {
     pub tracer: SupportedTracers,
     #[serde(default)]
     pub tracer_config: CallTracerConfig,
+    #[serde(flatten)]
+    pub struct_logger_config: StructLoggerConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -815,10 +1073,362 @@ pub struct ApiStorageLog {
     pub written_value: U256,
 }
 
+/// Geth-style state override applied on top of the VM state in `eth_call` and `eth_estimateGas`.
+/// Maps addresses of overridden accounts to the overrides.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StateOverride(HashMap<Address, OverrideAccount>);
+
+impl StateOverride {
+    pub fn new(accounts: HashMap<Address, OverrideAccount>) -> Self {
+        Self(accounts)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over all overridden accounts.
+    pub fn iter(&self) -> impl Iterator<Item = (&Address, &OverrideAccount)> + '_ {
+        self.0.iter()
+    }
+
+    /// Gets the override for the specified account.
+    pub fn get(&self, address: &Address) -> Option<&OverrideAccount> {
+        self.0.get(address)
+    }
+}
+
+/// Override for a single account in [`StateOverride`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideAccount {
+    /// Overridden account balance (in the base token).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Overridden account nonce. Only the transaction nonce of the account is overridden; the deployment nonce
+    /// is retained.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    /// Overridden account bytecode. Must be a valid zkEVM bytecode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Full replacement of the account storage: storage slots not mentioned in the map are set to zero.
+    /// Cannot be specified together with `state_diff`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<H256, H256>>,
+    /// Partial override of the account storage: only the mentioned storage slots are changed.
+    /// Cannot be specified together with `state`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<H256, H256>>,
+}
+
+/// Overrides for the environment of the block simulated by `eth_simulateV1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+    /// Number of the simulated L2 block. Must not be less than the number the block would have without the override;
+    /// if it is greater, all L2 blocks in between are assumed to be empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<U64>,
+    /// Timestamp of the simulated L2 block. Must be greater than the timestamp of the preceding L2 block,
+    /// and not less than the timestamp of the L1 batch the block belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+    /// Enforced base fee per gas.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U64>,
+    /// L1 gas price used in the batch fee input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_gas_price: Option<U64>,
+    /// Fair L2 gas price used in the batch fee input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_l2_gas_price: Option<U64>,
+    /// Fair pubdata price used in the batch fee input. Ignored for protocol versions without
+    /// the separate pubdata price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_pubdata_price: Option<U64>,
+}
+
+/// Payload of `eth_simulateV1`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Simulated blocks. Currently, at most one block is supported.
+    pub block_state_calls: Vec<SimulatedBlockCalls>,
+}
+
+/// Calls executed in a single simulated block, together with the overrides applied before executing them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlockCalls {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// Calls executed in order; each call observes the state changes made by the previous calls.
+    pub calls: Vec<CallRequest>,
+}
+
+/// Result of simulating a block in `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    pub number: U64,
+    pub timestamp: U64,
+    pub base_fee_per_gas: U256,
+    /// Total gas used by all calls in the block.
+    pub gas_used: U256,
+    pub calls: Vec<SimulatedCallResult>,
+}
+
+/// Result of a single call in a [`SimulatedBlock`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallResult {
+    /// 1 if the call has succeeded, 0 otherwise.
+    pub status: U64,
+    pub return_data: Bytes,
+    pub gas_used: U256,
+    /// Events emitted by the call.
+    pub logs: Vec<Log>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+/// Error of a failed call in [`SimulatedCallResult`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallError {
+    /// Error code: 3 for reverts (same as for `eth_call`), -32015 for other VM execution errors.
+    pub code: i64,
+    pub message: String,
+    /// ABI-encoded revert reason, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+/// Result of `eth_createAccessList`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    /// Storage slots accessed during the call, grouped by the contract address.
+    pub access_list: AccessList,
+    pub gas_used: U256,
+    /// Error message if the call has reverted or halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Contents of the transaction pool returned by `txpool_content` (with `T = Transaction`)
+/// and `txpool_inspect` (with `T = String`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolContent<T = Transaction> {
+    /// L2 transactions ready for execution grouped by the initiator address and nonce.
+    pub pending: BTreeMap<Address, BTreeMap<Nonce, T>>,
+    /// L2 transactions that cannot be executed until a nonce gap for the initiator is filled.
+    pub queued: BTreeMap<Address, BTreeMap<Nonce, T>>,
+    /// Priority operations queued from L1.
+    pub priority: Vec<T>,
+}
+
+impl<T> Default for TxpoolContent<T> {
+    fn default() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            queued: BTreeMap::new(),
+            priority: Vec::new(),
+        }
+    }
+}
+
+/// Transaction summaries returned by `txpool_inspect`.
+pub type TxpoolInspect = TxpoolContent<String>;
+
+/// Transaction pool status returned by `txpool_status`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolStatus {
+    pub pending: U64,
+    pub queued: U64,
+    pub priority: U64,
+}
+
+/// Fee estimate returned by `zks_estimateFeeWithPaymaster`, together with a preview
+/// of how the fee is split between the transaction initiator and its paymaster.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymasterFeeEstimate {
+    pub fee: Fee,
+    /// Paymaster covering the transaction fee; `None` if the fee is paid by the initiator.
+    pub paymaster: Option<Address>,
+    /// Amount of the base token (in wei) charged to the paymaster, net of the refund.
+    pub paymaster_charged: U256,
+    /// Amount of the base token (in wei) charged to the initiator, net of the refund.
+    pub initiator_charged: U256,
+    /// Token amounts for the `approvalBased` paymaster flow; `None` for other flows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_based: Option<ApprovalBasedFlowPreview>,
+}
+
+/// Token amounts for a transaction using the `approvalBased` paymaster flow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalBasedFlowPreview {
+    /// Token in which the initiator pays the paymaster.
+    pub token: Address,
+    /// Allowance for the paymaster specified in the paymaster input.
+    pub min_allowance: U256,
+    /// Amount of `token` transferred from the initiator to the paymaster, net of any refunds.
+    pub token_charged: U256,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn deserializing_state_override() {
+        let json = serde_json::json!({
+            "0x0000000000000000000000000000000000000123": {
+                "balance": "0x100",
+                "nonce": "0x5",
+                "code": "0x0000",
+                "stateDiff": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001":
+                        "0x0000000000000000000000000000000000000000000000000000000000000002",
+                },
+            },
+            "0x0000000000000000000000000000000000000456": {
+                "state": {},
+            },
+        });
+        let state_override: StateOverride = serde_json::from_value(json.clone()).unwrap();
+
+        let account = &state_override.0[&Address::from_low_u64_be(0x123)];
+        assert_eq!(account.balance, Some(U256::from(0x100)));
+        assert_eq!(account.nonce, Some(U256::from(5)));
+        assert_eq!(account.code, Some(Bytes(vec![0; 2])));
+        assert_eq!(account.state, None);
+        let state_diff = account.state_diff.as_ref().unwrap();
+        assert_eq!(
+            state_diff[&H256::from_low_u64_be(1)],
+            H256::from_low_u64_be(2)
+        );
+
+        let account = &state_override.0[&Address::from_low_u64_be(0x456)];
+        assert_eq!(account.state, Some(HashMap::new()));
+        assert_eq!(account.balance, None);
+
+        assert_eq!(serde_json::to_value(&state_override).unwrap(), json);
+    }
+
+    #[test]
+    fn deserializing_simulate_payload() {
+        let json = serde_json::json!({
+            "blockStateCalls": [{
+                "blockOverrides": { "number": "0x10", "time": "0x1000", "l1GasPrice": "0x100" },
+                "stateOverrides": {
+                    "0x0000000000000000000000000000000000000123": { "balance": "0x100" },
+                },
+                "calls": [
+                    { "to": "0x0000000000000000000000000000000000000123", "data": "0x01" },
+                    { "to": "0x0000000000000000000000000000000000000456" },
+                ],
+            }],
+            "validation": false,
+        });
+        let payload: SimulatePayload = serde_json::from_value(json).unwrap();
+
+        let [block] = payload.block_state_calls.as_slice() else {
+            panic!("Unexpected payload: {payload:?}");
+        };
+        let block_overrides = block.block_overrides.unwrap();
+        assert_eq!(block_overrides.number, Some(U64::from(0x10)));
+        assert_eq!(block_overrides.time, Some(U64::from(0x1000)));
+        assert_eq!(block_overrides.l1_gas_price, Some(U64::from(0x100)));
+        assert_eq!(block_overrides.fair_l2_gas_price, None);
+        let state_overrides = block.state_overrides.as_ref().unwrap();
+        assert!(state_overrides
+            .get(&Address::from_low_u64_be(0x123))
+            .is_some());
+        assert_eq!(block.calls.len(), 2);
+        assert_eq!(block.calls[0].data, Some(Bytes(vec![1])));
+    }
+
+    #[test]
+    fn deserializing_tracer_config() {
+        let json = serde_json::json!({
+            "tracer": "prestateTracer",
+            "tracerConfig": { "diffMode": true },
+        });
+        let config: TracerConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.tracer, SupportedTracers::PrestateTracer);
+        assert!(config.tracer_config.diff_mode);
+        assert!(!config.tracer_config.only_top_call);
+
+        let json = serde_json::json!({ "tracer": "flatCallTracer" });
+        let config: TracerConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.tracer, SupportedTracers::FlatCallTracer);
+        assert!(!config.tracer_config.diff_mode);
+
+        let json = serde_json::json!({
+            "tracer": "structLogger",
+            "disableStorage": true,
+            "limit": 100,
+        });
+        let config: TracerConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.tracer, SupportedTracers::StructLogger);
+        assert_eq!(
+            config.struct_logger_config,
+            StructLoggerConfig {
+                disable_storage: true,
+                limit: 100,
+                ..StructLoggerConfig::default()
+            }
+        );
+    }
+
+    #[test]
+    fn prestate_trace_serialization() {
+        let account = PrestateAccount {
+            balance: Some(100.into()),
+            nonce: Some(1.into()),
+            code: None,
+            storage: Some(HashMap::from([(
+                H256::from_low_u64_be(1),
+                H256::from_low_u64_be(2),
+            )])),
+        };
+        let address = Address::from_low_u64_be(0x123);
+
+        let trace = PrestateTrace::Prestate(HashMap::from([(address, account.clone())]));
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "0x0000000000000000000000000000000000000123": {
+                    "balance": "0x64",
+                    "nonce": "0x1",
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000001":
+                            "0x0000000000000000000000000000000000000000000000000000000000000002",
+                    },
+                },
+            })
+        );
+        let restored: DebugTraceResult = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, DebugTraceResult::PrestateTrace(trace));
+
+        let trace = PrestateTrace::Diff {
+            pre: HashMap::from([(address, account)]),
+            post: HashMap::new(),
+        };
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(json["post"], serde_json::json!({}));
+        let restored: DebugTraceResult = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, DebugTraceResult::PrestateTrace(trace));
+    }
+
     // TODO (PLA-965): remove test after removing deprecating fields.
     #[allow(deprecated)]
     #[test]
@@ -848,4 +1458,52 @@ mod tests {
         serde_json::from_str::<OldProtocolVersion>(&serde_json::to_string(&new_version).unwrap())
             .unwrap();
     }
+
+    #[test]
+    fn serializing_txpool_inspect() {
+        let account = Address::from_low_u64_be(0x123);
+        let inspect = TxpoolInspect {
+            pending: BTreeMap::from([(
+                account,
+                BTreeMap::from([(Nonce(1), "summary".to_owned())]),
+            )]),
+            queued: BTreeMap::new(),
+            priority: vec!["priority summary".to_owned()],
+        };
+        let json = serde_json::json!({
+            "pending": {
+                "0x0000000000000000000000000000000000000123": { "1": "summary" },
+            },
+            "queued": {},
+            "priority": ["priority summary"],
+        });
+        assert_eq!(serde_json::to_value(&inspect).unwrap(), json);
+        let restored: TxpoolInspect = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, inspect);
+    }
+
+    #[test]
+    fn serializing_validation_trace() {
+        let rule = ViolatedValidationRule::TouchedUnallowedStorageSlots(
+            Address::from_low_u64_be(0x123),
+            U256::from(1),
+        );
+        let trace = ValidationTrace {
+            error: None,
+            violations: vec![ValidationViolation::new(&rule, None)],
+        };
+        assert!(!trace.is_valid());
+
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(
+            json["violations"][0]["rule"],
+            serde_json::json!("touchedUnallowedStorageSlot")
+        );
+        assert_eq!(
+            json["violations"][0]["address"],
+            serde_json::json!("0x0000000000000000000000000000000000000123")
+        );
+        let restored: ValidationTrace = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, trace);
+    }
 }
@@ -77,6 +77,31 @@ impl Fee {
         // For now, we charge only for base fee.
         block_base_fee_per_gas
     }
+
+    /// Checks whether a transaction with this fee can replace a pending transaction with the `replaced` fee
+    /// (i.e., a transaction from the same account with the same nonce). Both `max_fee_per_gas` and `max_priority_fee_per_gas`
+    /// must be bumped by at least `min_bump_percent` percent, similarly to replace-by-fee rules in Ethereum clients.
+    pub fn is_replacement_for(&self, replaced: &Self, min_bump_percent: u32) -> bool {
+        self.max_fee_per_gas >= Self::bump(replaced.max_fee_per_gas, min_bump_percent)
+            && self.max_priority_fee_per_gas
+                >= Self::bump(replaced.max_priority_fee_per_gas, min_bump_percent)
+    }
+
+    /// Returns the minimum `max_fee_per_gas` for a transaction replacing a transaction with this fee.
+    pub fn min_replacement_fee_per_gas(&self, min_bump_percent: u32) -> U256 {
+        Self::bump(self.max_fee_per_gas, min_bump_percent)
+    }
+
+    fn bump(value: U256, percent: u32) -> U256 {
+        let product = value.saturating_mul(percent.into());
+        let (increase, remainder) = product.div_mod(100.into());
+        let increase = if remainder.is_zero() {
+            increase
+        } else {
+            increase + 1
+        };
+        value.saturating_add(increase)
+    }
 }
 
 /// Returns how many slots would ABI-encoding of the transaction with such parameters take
@@ -101,3 +126,39 @@ pub fn encoding_len(
 
     BASE_LEN + dynamic_len as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee(max_fee_per_gas: u64, max_priority_fee_per_gas: u64) -> Fee {
+        Fee {
+            max_fee_per_gas: max_fee_per_gas.into(),
+            max_priority_fee_per_gas: max_priority_fee_per_gas.into(),
+            ..Fee::default()
+        }
+    }
+
+    #[test]
+    fn checking_replacement_fees() {
+        let replaced = fee(1_000, 100);
+        assert_eq!(replaced.min_replacement_fee_per_gas(10), 1_100.into());
+        assert!(fee(1_100, 110).is_replacement_for(&replaced, 10));
+        assert!(fee(2_000, 200).is_replacement_for(&replaced, 10));
+        assert!(!fee(1_099, 110).is_replacement_for(&replaced, 10));
+        assert!(!fee(1_100, 109).is_replacement_for(&replaced, 10));
+        assert!(fee(1_000, 100).is_replacement_for(&replaced, 0));
+
+        // Bumped values are rounded up.
+        let replaced = fee(15, 0);
+        assert_eq!(replaced.min_replacement_fee_per_gas(10), 17.into());
+        assert!(fee(17, 0).is_replacement_for(&replaced, 10));
+        assert!(!fee(16, 0).is_replacement_for(&replaced, 10));
+
+        let replaced = Fee {
+            max_fee_per_gas: U256::MAX,
+            ..Fee::default()
+        };
+        assert_eq!(replaced.min_replacement_fee_per_gas(10), U256::MAX);
+    }
+}
@@ -12,6 +12,7 @@ use zksync_utils::{
 
 use super::{EIP_1559_TX_TYPE, EIP_2930_TX_TYPE, EIP_712_TX_TYPE};
 use crate::{
+    api::StateOverride,
     fee::Fee,
     l1::L1Tx,
     l2::{L2Tx, TransactionType},
@@ -70,6 +71,8 @@ pub struct CallRequest {
 /// sometimes users may want to override those.
 pub struct CallOverrides {
     pub enforced_base_fee: Option<u64>,
+    /// State override applied on top of the VM state before executing the call.
+    pub state_override: Option<StateOverride>,
 }
 
 impl CallRequest {
@@ -90,7 +93,10 @@ impl CallRequest {
             None
         };
 
-        Ok(CallOverrides { enforced_base_fee })
+        Ok(CallOverrides {
+            enforced_base_fee,
+            state_override: None,
+        })
     }
 }
 
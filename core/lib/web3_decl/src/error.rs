@@ -14,7 +14,10 @@ use std::{
 use jsonrpsee::{core::ClientError, types::error::ErrorCode};
 use pin_project_lite::pin_project;
 use thiserror::Error;
-use zksync_types::{api::SerializationTransactionError, L1BatchNumber, L2BlockNumber};
+use zksync_types::{
+    api::{LogsCursor, SerializationTransactionError},
+    L1BatchNumber, L2BlockNumber,
+};
 
 /// Server-side representation of the RPC error.
 #[derive(Debug, Error)]
@@ -35,10 +38,17 @@ pub enum Web3Error {
     TooManyTopics,
     #[error("Filter not found")]
     FilterNotFound,
+    /// The last field is the cursor pointing to the start of the block following the suggested block range; it's returned
+    /// to the client as the error data and can be used to continue the query with `zks_getLogsPaginated` after fetching
+    /// logs in the suggested range.
     #[error("Query returned more than {0} results. Try with this block range [{1:#x}, {2:#x}].")]
-    LogsLimitExceeded(usize, u32, u32),
+    LogsLimitExceeded(usize, u32, u32, LogsCursor),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("Invalid state override: {0}")]
+    InvalidStateOverride(String),
+    #[error("Invalid simulation request: {0}")]
+    InvalidSimulationRequest(String),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
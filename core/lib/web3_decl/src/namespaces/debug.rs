@@ -2,14 +2,16 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugTraceResult, ResultDebugTrace, TracerConfig, ValidationTrace,
+    },
     debug_flat_call::DebugCallFlat,
     transaction_request::CallRequest,
 };
 
 use crate::{
     client::{ForNetwork, L2},
-    types::H256,
+    types::{Bytes, H256},
 };
 
 #[cfg_attr(
@@ -26,7 +28,7 @@ pub trait DebugNamespace {
         &self,
         block: BlockNumber,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Vec<ResultDebugCall>>;
+    ) -> RpcResult<Vec<ResultDebugTrace>>;
 
     #[method(name = "traceBlockByNumber.callFlatTracer")]
     async fn trace_block_by_number_flat(
@@ -40,7 +42,7 @@ pub trait DebugNamespace {
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Vec<ResultDebugCall>>;
+    ) -> RpcResult<Vec<ResultDebugTrace>>;
 
     #[method(name = "traceCall")]
     async fn trace_call(
@@ -48,12 +50,16 @@ pub trait DebugNamespace {
         request: CallRequest,
         block: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> RpcResult<DebugCall>;
+    ) -> RpcResult<DebugTraceResult>;
 
     #[method(name = "traceTransaction")]
     async fn trace_transaction(
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Option<DebugCall>>;
+    ) -> RpcResult<Option<DebugTraceResult>>;
+
+    /// Runs the account validation step for a signed transaction and returns all violated validation rules.
+    #[method(name = "traceValidation")]
+    async fn trace_validation(&self, tx_bytes: Bytes) -> RpcResult<ValidationTrace>;
 }
@@ -2,7 +2,10 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    api::{BlockId, BlockIdVariant, BlockNumber, Transaction, TransactionVariant},
+    api::{
+        AccessListResult, BlockId, BlockIdVariant, BlockNumber, SimulatePayload, SimulatedBlock,
+        StateOverride, Transaction, TransactionVariant,
+    },
     transaction_request::CallRequest,
     Address, H256,
 };
@@ -31,10 +34,34 @@ pub trait EthNamespace {
     async fn chain_id(&self) -> RpcResult<U64>;
 
     #[method(name = "call")]
-    async fn call(&self, req: CallRequest, block: Option<BlockIdVariant>) -> RpcResult<Bytes>;
+    async fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Bytes>;
 
     #[method(name = "estimateGas")]
-    async fn estimate_gas(&self, req: CallRequest, _block: Option<BlockNumber>) -> RpcResult<U256>;
+    async fn estimate_gas(
+        &self,
+        req: CallRequest,
+        _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<U256>;
+
+    #[method(name = "createAccessList")]
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<AccessListResult>;
+
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
 
     #[method(name = "gasPrice")]
     async fn gas_price(&self) -> RpcResult<U256>;
@@ -178,15 +205,15 @@ pub trait EthNamespace {
 mod pub_sub {
     use jsonrpsee::{core::SubscriptionResult, proc_macros::rpc};
 
-    use crate::types::PubSubFilter;
-
     #[rpc(server, namespace = "eth")]
     pub trait EthPubSub {
+        /// Creates a subscription. Parameters depend on the subscription type (e.g., a [`PubSubFilter`](crate::types::PubSubFilter)
+        /// for `logs` subscriptions), so they are parsed by the implementation.
         #[subscription(name = "subscribe" => "subscription", unsubscribe = "unsubscribe", item = PubSubResult)]
         async fn subscribe(
             &self,
             sub_type: String,
-            filter: Option<PubSubFilter>,
+            params: Option<serde_json::Value>,
         ) -> SubscriptionResult;
     }
 }
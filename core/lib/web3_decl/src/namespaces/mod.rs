@@ -1,13 +1,13 @@
 pub use self::{
     debug::DebugNamespaceClient, en::EnNamespaceClient, eth::EthNamespaceClient,
-    net::NetNamespaceClient, snapshots::SnapshotsNamespaceClient, web3::Web3NamespaceClient,
-    zks::ZksNamespaceClient,
+    net::NetNamespaceClient, snapshots::SnapshotsNamespaceClient, txpool::TxpoolNamespaceClient,
+    web3::Web3NamespaceClient, zks::ZksNamespaceClient,
 };
 #[cfg(feature = "server")]
 pub use self::{
     debug::DebugNamespaceServer, en::EnNamespaceServer, eth::EthNamespaceServer,
     eth::EthPubSubServer, net::NetNamespaceServer, snapshots::SnapshotsNamespaceServer,
-    web3::Web3NamespaceServer, zks::ZksNamespaceServer,
+    txpool::TxpoolNamespaceServer, web3::Web3NamespaceServer, zks::ZksNamespaceServer,
 };
 
 mod debug;
@@ -15,5 +15,6 @@ mod en;
 mod eth;
 mod net;
 mod snapshots;
+mod txpool;
 mod web3;
 mod zks;
@@ -5,8 +5,9 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetailedResult, TransactionDetails,
+        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, LogsCursor, LogsPage,
+        PaymasterFeeEstimate, Proof, ProtocolVersion, TransactionDetailedResult,
+        TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -16,7 +17,7 @@ use zksync_types::{
 
 use crate::{
     client::{ForNetwork, L2},
-    types::{Bytes, Token},
+    types::{Bytes, Filter, Token},
 };
 
 #[cfg_attr(
@@ -31,6 +32,12 @@ pub trait ZksNamespace {
     #[method(name = "estimateFee")]
     async fn estimate_fee(&self, req: CallRequest) -> RpcResult<Fee>;
 
+    #[method(name = "estimateFeeWithPaymaster")]
+    async fn estimate_fee_with_paymaster(
+        &self,
+        req: CallRequest,
+    ) -> RpcResult<PaymasterFeeEstimate>;
+
     #[method(name = "estimateGasL1ToL2")]
     async fn estimate_gas_l1_to_l2(&self, req: CallRequest) -> RpcResult<U256>;
 
@@ -131,4 +138,19 @@ pub trait ZksNamespace {
         &self,
         tx_bytes: Bytes,
     ) -> RpcResult<TransactionDetailedResult>;
+
+    /// Returns a page of logs satisfying the filter, starting from the `cursor` position (inclusive) if it's provided.
+    /// The returned page contains a cursor to request the next page if not all logs fit into it.
+    #[method(name = "getLogsPaginated")]
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogsCursor>,
+    ) -> RpcResult<LogsPage>;
+
+    /// Submits a signed transaction cancelling a pending transaction with the same nonce. The cancelling transaction
+    /// must be a zero-value transfer from the account to itself without calldata, and must bump fees compared
+    /// to the cancelled transaction. Returns the hash of the cancelling transaction.
+    #[method(name = "cancelTransaction")]
+    async fn cancel_transaction(&self, tx_bytes: Bytes) -> RpcResult<H256>;
 }
@@ -13,6 +13,7 @@ use core::{
 
 use rlp::Rlp;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use zksync_types::{api, L1BatchNumber};
 pub use zksync_types::{
     api::{Block, BlockNumber, Log, TransactionReceipt, TransactionRequest},
     ethabi,
@@ -334,6 +335,86 @@ fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
     }
 }
 
+/// Filter for `newPendingTransactions` subscriptions.
+#[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransactionsFilter {
+    /// Transaction initiators to match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<ValueOrArray<Address>>,
+    /// Transaction recipients to match. Contract deployments are never matched if this filter is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<ValueOrArray<Address>>,
+    /// If set, full transactions are sent to the subscriber instead of transaction hashes.
+    #[serde(default)]
+    pub full_transactions: bool,
+}
+
+impl PendingTransactionsFilter {
+    /// Checks whether this filter has any effect compared to the plain `newPendingTransactions` subscription.
+    pub fn is_trivial(&self) -> bool {
+        self.from.is_none() && self.to.is_none() && !self.full_transactions
+    }
+
+    pub fn matches(&self, tx: &api::Transaction) -> bool {
+        if let Some(from) = &self.from {
+            if !tx.from.map_or(false, |address| from.0.contains(&address)) {
+                return false;
+            }
+        }
+        if let Some(to) = &self.to {
+            if !tx.to.map_or(false, |address| to.0.contains(&address)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Status of an L1 batch reported to `l1Batches` subscribers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum L1BatchStatus {
+    /// The batch is sealed by the state keeper.
+    Sealed,
+    /// The batch commitment is confirmed on L1.
+    Committed,
+    /// The batch proof is confirmed on L1.
+    Proven,
+    /// The batch execution is confirmed on L1.
+    Executed,
+}
+
+impl L1BatchStatus {
+    /// All statuses in the order an L1 batch goes through them.
+    pub const ALL: [Self; 4] = [Self::Sealed, Self::Committed, Self::Proven, Self::Executed];
+}
+
+/// Notification sent to `l1Batches` subscribers when an L1 batch transitions to a new status.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchStatusUpdate {
+    pub l1_batch_number: L1BatchNumber,
+    pub status: L1BatchStatus,
+}
+
+/// Filter for `l1Batches` subscriptions.
+#[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchStatusFilter {
+    /// Statuses to report. If not specified, all status transitions are reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statuses: Option<ValueOrArray<L1BatchStatus>>,
+}
+
+impl L1BatchStatusFilter {
+    pub fn matches(&self, update: &L1BatchStatusUpdate) -> bool {
+        self.statuses
+            .as_ref()
+            .map_or(true, |statuses| statuses.0.contains(&update.status))
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -341,7 +422,9 @@ pub enum PubSubResult {
     Header(BlockHeader),
     Log(Log),
     TxHash(H256),
-    Syncing(bool),
+    Transaction(api::Transaction),
+    L1BatchStatus(L1BatchStatusUpdate),
+    Syncing(SyncState),
 }
 
 #[cfg(test)]
@@ -438,4 +521,56 @@ mod tests {
         let restored_value: ValueOrArray<Address> = serde_json::from_value(json).unwrap();
         assert_eq!(restored_value, value);
     }
+
+    #[test]
+    fn pending_transactions_filter_basics() {
+        let json = serde_json::json!({
+            "from": "0x0101010101010101010101010101010101010101",
+            "to": [
+                "0x0202020202020202020202020202020202020202",
+                "0x0303030303030303030303030303030303030303",
+            ],
+        });
+        let filter: PendingTransactionsFilter = serde_json::from_value(json).unwrap();
+        assert!(!filter.is_trivial());
+        assert!(!filter.full_transactions);
+
+        let mut tx = api::Transaction {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Address::repeat_byte(3)),
+            ..api::Transaction::default()
+        };
+        assert!(filter.matches(&tx));
+        tx.to = None;
+        assert!(!filter.matches(&tx));
+        tx.to = Some(Address::repeat_byte(2));
+        tx.from = Some(Address::repeat_byte(2));
+        assert!(!filter.matches(&tx));
+
+        let filter: PendingTransactionsFilter =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(filter.is_trivial());
+        assert!(filter.matches(&tx));
+    }
+
+    #[test]
+    fn l1_batch_status_filter_basics() {
+        let update = L1BatchStatusUpdate {
+            l1_batch_number: L1BatchNumber(1),
+            status: L1BatchStatus::Proven,
+        };
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            serde_json::json!({ "l1BatchNumber": 1, "status": "proven" })
+        );
+
+        let filter: L1BatchStatusFilter =
+            serde_json::from_value(serde_json::json!({ "statuses": "committed" })).unwrap();
+        assert!(!filter.matches(&update));
+        let filter: L1BatchStatusFilter =
+            serde_json::from_value(serde_json::json!({ "statuses": ["committed", "proven"] }))
+                .unwrap();
+        assert!(filter.matches(&update));
+        assert!(L1BatchStatusFilter::default().matches(&update));
+    }
 }
@@ -55,9 +55,13 @@ use zksync_metadata_calculator::{
     api_server::TreeApiHttpClient, MetadataCalculator, MetadataCalculatorConfig,
 };
 use zksync_node_api_server::{
+    graphql::GraphQlServer,
     healthcheck::HealthCheckHandle,
     tx_sender::{build_tx_sender, TxSenderConfig},
-    web3::{self, mempool_cache::MempoolCache, state::InternalApiConfig, Namespace},
+    web3::{
+        self, mempool_cache::MempoolCache, response_cache::ResponseCache, state::InternalApiConfig,
+        Namespace,
+    },
 };
 use zksync_node_fee_model::{
     l1_gas_price::GasAdjusterSingleton, BatchFeeModelInputProvider, MainNodeFeeInputProvider,
@@ -130,6 +134,8 @@ pub enum Component {
     HttpApi,
     /// Public Web3 API (including PubSub) running on WebSocket server.
     WsApi,
+    /// Public GraphQL API over blocks, L1 batches, transactions and logs.
+    GraphQlApi,
     /// REST API for contract verification.
     ContractVerificationApi,
     /// Metadata calculator.
@@ -173,6 +179,7 @@ impl FromStr for Components {
             ])),
             "http_api" => Ok(Components(vec![Component::HttpApi])),
             "ws_api" => Ok(Components(vec![Component::WsApi])),
+            "graphql_api" => Ok(Components(vec![Component::GraphQlApi])),
             "contract_verification_api" => Ok(Components(vec![Component::ContractVerificationApi])),
             "tree" => Ok(Components(vec![Component::Tree])),
             "tree_api" => Ok(Components(vec![Component::TreeApi])),
@@ -326,9 +333,11 @@ pub async fn initialize_components(
             .state_keeper_config
             .clone()
             .context("state_keeper_config")?;
+        let mempool_config = configs.mempool_config.clone().context("mempool_config")?;
         let tx_sender_config = TxSenderConfig::new(
             &state_keeper_config,
             &api_config.web3_json_rpc,
+            &mempool_config,
             wallets
                 .state_keeper
                 .clone()
@@ -354,6 +363,12 @@ pub async fn initialize_components(
         task_futures.push(tokio::spawn(
             mempool_cache_update_task.run(stop_receiver.clone()),
         ));
+        let response_cache =
+            ResponseCache::new(api_config.web3_json_rpc.response_cache_size() as u64);
+        let response_cache_update_task = response_cache.update_task(connection_pool.clone());
+        task_futures.push(tokio::spawn(
+            response_cache_update_task.run(stop_receiver.clone()),
+        ));
 
         if components.contains(&Component::HttpApi) {
             storage_caches = Some(
@@ -391,6 +406,7 @@ pub async fn initialize_components(
                 state_keeper_config.save_call_traces,
                 storage_caches.clone().unwrap(),
                 mempool_cache.clone(),
+                response_cache.clone(),
             )
             .await
             .context("run_http_api")?;
@@ -439,6 +455,7 @@ pub async fn initialize_components(
                 stop_receiver.clone(),
                 storage_caches,
                 mempool_cache,
+                response_cache,
             )
             .await
             .context("run_ws_api")?;
@@ -471,6 +488,26 @@ pub async fn initialize_components(
         }
     }
 
+    if components.contains(&Component::GraphQlApi) {
+        let rpc_config = &configs
+            .api_config
+            .as_ref()
+            .context("api_config")?
+            .web3_json_rpc;
+        let port = rpc_config
+            .graphql_port
+            .context("GraphQL server port is not specified")?;
+        let server = GraphQlServer::new(
+            (Ipv4Addr::UNSPECIFIED, port).into(),
+            replica_connection_pool.clone(),
+            l2_chain_id,
+        )
+        .with_max_page_size(rpc_config.req_entities_limit())
+        .with_client_rate_limits(rpc_config.into());
+        task_futures.push(tokio::spawn(server.run(stop_receiver.clone())));
+        tracing::info!("Initialized GraphQL API on port {port}");
+    }
+
     let object_store_config = configs
         .core_object_store
         .clone()
@@ -817,7 +854,7 @@ async fn add_state_keeper_to_task_futures(
             .connection()
             .await
             .context("Access storage to build mempool")?;
-        let mempool = MempoolGuard::from_storage(&mut storage, mempool_config.capacity).await;
+        let mempool = MempoolGuard::from_storage(&mut storage, mempool_config).await;
         mempool.register_metrics();
         mempool
     };
@@ -1026,6 +1063,12 @@ async fn run_tree(
         }));
     }
 
+    if let Some(check_interval) = merkle_tree_config.consistency_check_interval() {
+        let consistency_checker = metadata_calculator.consistency_checker(check_interval);
+        app_health.insert_component(consistency_checker.health_check())?;
+        task_futures.push(tokio::spawn(consistency_checker.run(stop_receiver.clone())));
+    }
+
     let tree_health_check = metadata_calculator.tree_health_check();
     app_health.insert_custom_component(Arc::new(tree_health_check))?;
     let tree_task = tokio::spawn(metadata_calculator.run(stop_receiver));
@@ -1243,6 +1286,7 @@ async fn run_http_api(
     with_debug_namespace: bool,
     storage_caches: PostgresStorageCaches,
     mempool_cache: MempoolCache,
+    response_cache: ResponseCache,
 ) -> anyhow::Result<()> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1260,6 +1304,9 @@ async fn run_http_api(
         namespaces.push(Namespace::Debug)
     }
     namespaces.push(Namespace::Snapshots);
+    if api_config.web3_json_rpc.txpool_namespace_enabled {
+        namespaces.push(Namespace::Txpool);
+    }
 
     let updaters_pool = ConnectionPool::<Core>::builder(database_secrets.replica_url()?, 2)
         .build()
@@ -1276,7 +1323,11 @@ async fn run_http_api(
             .with_tx_sender(tx_sender)
             .with_vm_barrier(vm_barrier)
             .with_mempool_cache(mempool_cache)
+            .with_response_cache(response_cache)
             .enable_api_namespaces(namespaces);
+    api_builder = api_builder
+        .with_client_rate_limits((&api_config.web3_json_rpc).into())
+        .with_method_weights(api_config.web3_json_rpc.method_weights.clone());
     if let Some(tree_api_url) = api_config.web3_json_rpc.tree_api_url() {
         let tree_api = Arc::new(TreeApiHttpClient::new(tree_api_url));
         api_builder = api_builder.with_tree_api(tree_api.clone());
@@ -1308,6 +1359,7 @@ async fn run_ws_api(
     stop_receiver: watch::Receiver<bool>,
     storage_caches: PostgresStorageCaches,
     mempool_cache: MempoolCache,
+    response_cache: ResponseCache,
 ) -> anyhow::Result<()> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1326,6 +1378,9 @@ async fn run_ws_api(
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
     namespaces.push(Namespace::Snapshots);
+    if api_config.web3_json_rpc.txpool_namespace_enabled {
+        namespaces.push(Namespace::Txpool);
+    }
 
     let mut api_builder =
         web3::ApiBuilder::jsonrpsee_backend(internal_api.clone(), replica_connection_pool)
@@ -1344,7 +1399,11 @@ async fn run_ws_api(
             .with_tx_sender(tx_sender)
             .with_vm_barrier(vm_barrier)
             .with_mempool_cache(mempool_cache)
+            .with_response_cache(response_cache)
             .enable_api_namespaces(namespaces);
+    api_builder = api_builder
+        .with_client_rate_limits((&api_config.web3_json_rpc).into())
+        .with_method_weights(api_config.web3_json_rpc.method_weights.clone());
     if let Some(tree_api_url) = api_config.web3_json_rpc.tree_api_url() {
         let tree_api = Arc::new(TreeApiHttpClient::new(tree_api_url));
         api_builder = api_builder.with_tree_api(tree_api.clone());
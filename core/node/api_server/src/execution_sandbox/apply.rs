@@ -19,11 +19,12 @@ use tokio::runtime::Handle;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_state::{PostgresStorage, ReadStorage, StoragePtr, StorageView, WriteStorage};
 use zksync_system_constants::{
-    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
-    SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION, ZKPORTER_IS_AVAILABLE,
+    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_HASHES_POSITION,
+    SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION, SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+    SYSTEM_CONTEXT_STORED_L2_BLOCK_HASHES, ZKPORTER_IS_AVAILABLE,
 };
 use zksync_types::{
-    api,
+    api::{self, BlockOverrides},
     block::{pack_block_info, unpack_block_info, L2BlockHasher},
     fee_model::BatchFeeInput,
     get_nonce_key,
@@ -34,11 +35,13 @@ use zksync_types::{
 use zksync_utils::{h256_to_u256, time::seconds_since_epoch, u256_to_h256};
 
 use super::{
+    storage::StorageWithOverrides,
     vm_metrics::{self, SandboxStage, SANDBOX_METRICS},
-    BlockArgs, TxExecutionArgs, TxSharedArgs, VmPermit,
+    BlockArgs, InvalidBlockOverrides, TxExecutionArgs, TxSharedArgs, VmPermit,
 };
 
-type BoxedVm<'a> = Box<VmInstance<StorageView<PostgresStorage<'a>>, HistoryDisabled>>;
+type SandboxStorage<'a> = StorageView<StorageWithOverrides<PostgresStorage<'a>>>;
+type BoxedVm<'a> = Box<VmInstance<SandboxStorage<'a>, HistoryDisabled>>;
 
 #[derive(Debug)]
 struct Sandbox<'a> {
@@ -46,7 +49,7 @@ struct Sandbox<'a> {
     l1_batch_env: L1BatchEnv,
     execution_args: &'a TxExecutionArgs,
     l2_block_info_to_reset: Option<StoredL2BlockInfo>,
-    storage_view: StorageView<PostgresStorage<'a>>,
+    storage_view: SandboxStorage<'a>,
 }
 
 impl<'a> Sandbox<'a> {
@@ -90,13 +93,20 @@ impl<'a> Sandbox<'a> {
         .context("cannot create `PostgresStorage`")?
         .with_caches(shared_args.caches.clone());
 
+        let mut storage = StorageWithOverrides::new(storage);
+        if let Some(state_override) = &execution_args.state_override {
+            storage.apply_state_override(state_override);
+        }
         let storage_view = StorageView::new(storage);
-        let (system_env, l1_batch_env) = Self::prepare_env(
+        let (system_env, mut l1_batch_env) = Self::prepare_env(
             shared_args,
             execution_args,
             &resolved_block_info,
             next_l2_block_info,
         );
+        if let Some(block_overrides) = &execution_args.block_overrides {
+            Self::apply_fee_overrides(&mut l1_batch_env, system_env.version, block_overrides);
+        }
 
         Ok(Self {
             system_env,
@@ -254,13 +264,119 @@ impl<'a> Sandbox<'a> {
         (system_env, l1_batch_env)
     }
 
+    fn apply_fee_overrides(
+        l1_batch_env: &mut L1BatchEnv,
+        protocol_version: ProtocolVersionId,
+        overrides: &BlockOverrides,
+    ) {
+        let fee_input = l1_batch_env.fee_input;
+        let l1_gas_price = overrides
+            .l1_gas_price
+            .map_or(fee_input.l1_gas_price(), |price| price.as_u64());
+        let fair_l2_gas_price = overrides
+            .fair_l2_gas_price
+            .map_or(fee_input.fair_l2_gas_price(), |price| price.as_u64());
+        let fair_pubdata_price = overrides
+            .fair_pubdata_price
+            .map_or(fee_input.fair_pubdata_price(), |price| price.as_u64());
+        l1_batch_env.fee_input = BatchFeeInput::for_protocol_version(
+            protocol_version,
+            fair_l2_gas_price,
+            Some(fair_pubdata_price),
+            l1_gas_price,
+        );
+        if let Some(base_fee) = overrides.base_fee_per_gas {
+            l1_batch_env.enforced_base_fee = Some(base_fee.as_u64());
+        }
+    }
+
+    /// Applies L2 block number and timestamp overrides. Since these overrides influence the state of
+    /// the system context contract, this must be called after [`Self::setup_storage_view()`].
+    ///
+    /// This method is blocking.
+    fn apply_l2_block_overrides(
+        &mut self,
+        overrides: &BlockOverrides,
+    ) -> Result<(), InvalidBlockOverrides> {
+        let l2_block_info_key = StorageKey::new(
+            AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+            SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+        );
+        let l2_block_info = self.storage_view.read_value(&l2_block_info_key);
+        let (mut prev_l2_block_number, prev_l2_block_timestamp) =
+            unpack_block_info(h256_to_u256(l2_block_info));
+        let next_l2_block = &mut self.l1_batch_env.first_l2_block;
+
+        if let Some(number) = overrides.number {
+            let number = u32::try_from(number.as_u64()).map_err(|_| {
+                InvalidBlockOverrides(format!("block number {number} is out of range"))
+            })?;
+            if number < next_l2_block.number {
+                return Err(InvalidBlockOverrides(format!(
+                    "block number {number} is less than the number of the simulated block ({})",
+                    next_l2_block.number
+                )));
+            }
+
+            if number > next_l2_block.number {
+                // Pretend that all L2 blocks before the simulated one are empty. The hash of the previous L2 block
+                // is computed in the same way as in the system context contract.
+                prev_l2_block_number = (number - 1).into();
+                let l2_block_info = pack_block_info(prev_l2_block_number, prev_l2_block_timestamp);
+                self.storage_view
+                    .set_value(l2_block_info_key, u256_to_h256(l2_block_info));
+
+                let hash_position = h256_to_u256(SYSTEM_CONTEXT_CURRENT_L2_BLOCK_HASHES_POSITION)
+                    + U256::from((number - 2) % SYSTEM_CONTEXT_STORED_L2_BLOCK_HASHES);
+                let hash_key = StorageKey::new(
+                    AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+                    u256_to_h256(hash_position),
+                );
+                let rolling_hash_key = StorageKey::new(
+                    AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+                    SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+                );
+                next_l2_block.prev_block_hash = L2BlockHasher::hash(
+                    L2BlockNumber(number - 1),
+                    prev_l2_block_timestamp,
+                    self.storage_view.read_value(&hash_key),
+                    self.storage_view.read_value(&rolling_hash_key),
+                    self.system_env.version,
+                );
+                next_l2_block.number = number;
+            }
+        }
+
+        if let Some(timestamp) = overrides.time {
+            next_l2_block.timestamp = timestamp.as_u64();
+        }
+        // Mirrors checks performed by the VM on instantiation.
+        let is_after_prev_block = if prev_l2_block_number == 0 {
+            next_l2_block.timestamp >= prev_l2_block_timestamp
+        } else {
+            next_l2_block.timestamp > prev_l2_block_timestamp
+        };
+        if !is_after_prev_block || next_l2_block.timestamp < self.l1_batch_env.timestamp {
+            return Err(InvalidBlockOverrides(format!(
+                "block timestamp {} must be greater than the timestamp of the previous L2 block ({prev_l2_block_timestamp}) \
+                 and not less than the L1 batch timestamp ({})",
+                next_l2_block.timestamp, self.l1_batch_env.timestamp
+            )));
+        }
+        Ok(())
+    }
+
     /// This method is blocking.
     fn into_vm(
         mut self,
         tx: &Transaction,
         adjust_pubdata_price: bool,
-    ) -> (BoxedVm<'a>, StoragePtr<StorageView<PostgresStorage<'a>>>) {
+    ) -> anyhow::Result<(BoxedVm<'a>, StoragePtr<SandboxStorage<'a>>, L1BatchEnv)> {
         self.setup_storage_view(tx);
+        let execution_args = self.execution_args;
+        if let Some(block_overrides) = &execution_args.block_overrides {
+            self.apply_l2_block_overrides(block_overrides)?;
+        }
         let protocol_version = self.system_env.version;
         if adjust_pubdata_price {
             self.l1_batch_env.fee_input = adjust_pubdata_price_for_tx(
@@ -271,6 +387,7 @@ impl<'a> Sandbox<'a> {
             );
         };
 
+        let l1_batch_env = self.l1_batch_env.clone();
         let storage_view = self.storage_view.to_rc_ptr();
         let vm = Box::new(VmInstance::new_with_specific_version(
             self.l1_batch_env,
@@ -279,7 +396,7 @@ impl<'a> Sandbox<'a> {
             protocol_version.into_api_vm_version(),
         ));
 
-        (vm, storage_view)
+        Ok((vm, storage_view, l1_batch_env))
     }
 }
 
@@ -296,9 +413,10 @@ pub(super) fn apply_vm_in_sandbox<T>(
     tx: Transaction,
     block_args: BlockArgs,
     apply: impl FnOnce(
-        &mut VmInstance<StorageView<PostgresStorage<'_>>, HistoryDisabled>,
+        &mut VmInstance<SandboxStorage<'_>, HistoryDisabled>,
         Transaction,
         ProtocolVersionId,
+        &L1BatchEnv,
     ) -> T,
 ) -> anyhow::Result<T> {
     let stage_started_at = Instant::now();
@@ -321,7 +439,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
         block_args,
     ))?;
     let protocol_version = sandbox.system_env.version;
-    let (mut vm, storage_view) = sandbox.into_vm(&tx, adjust_pubdata_price);
+    let (mut vm, storage_view, l1_batch_env) = sandbox.into_vm(&tx, adjust_pubdata_price)?;
 
     SANDBOX_METRICS.sandbox[&SandboxStage::Initialization].observe(stage_started_at.elapsed());
     span.exit();
@@ -332,7 +450,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
         tx.nonce().unwrap_or(Nonce(0))
     );
     let execution_latency = SANDBOX_METRICS.sandbox[&SandboxStage::Execution].start();
-    let result = apply(&mut vm, tx, protocol_version);
+    let result = apply(&mut vm, tx, protocol_version, &l1_batch_env);
     let vm_execution_took = execution_latency.observe();
 
     let memory_metrics = vm.record_vm_memory_metrics();
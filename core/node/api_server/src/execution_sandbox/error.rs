@@ -1,6 +1,11 @@
 use multivm::interface::{Halt, TxRevertReason};
 use thiserror::Error;
 
+/// Error caused by [`BlockOverrides`](zksync_types::api::BlockOverrides) incompatible with the simulated block.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub(crate) struct InvalidBlockOverrides(pub String);
+
 #[derive(Debug, Error)]
 pub(crate) enum SandboxExecutionError {
     #[error("Account validation failed: {0}")]
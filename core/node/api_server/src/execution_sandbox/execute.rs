@@ -1,15 +1,21 @@
 //! Implementation of "executing" methods, e.g. `eth_call`.
 
+use std::iter;
+
 use anyhow::Context as _;
 use multivm::{
     interface::{TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
     tracers::StorageInvocations,
+    utils::get_batch_base_fee,
     MultiVMTracer,
 };
 use tracing::{span, Level};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, transaction_request::CallOverrides,
+    api::{BlockOverrides, StateOverride},
+    fee::TransactionExecutionMetrics,
+    l2::L2Tx,
+    transaction_request::CallOverrides,
     ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
 };
 
@@ -25,6 +31,10 @@ pub(crate) struct TxExecutionArgs {
     pub added_balance: U256,
     pub enforced_base_fee: Option<u64>,
     pub missed_storage_invocation_limit: usize,
+    /// State override applied on top of the VM state before execution.
+    pub state_override: Option<StateOverride>,
+    /// Overrides for the environment of the L2 block the execution is performed in.
+    pub block_overrides: Option<BlockOverrides>,
 }
 
 impl TxExecutionArgs {
@@ -35,10 +45,12 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             enforced_base_fee: Some(tx.common_data.fee.max_fee_per_gas.as_u64()),
             missed_storage_invocation_limit: usize::MAX,
+            state_override: None,
+            block_overrides: None,
         }
     }
 
-    fn for_eth_call(
+    pub fn for_eth_call(
         enforced_base_fee: Option<u64>,
         vm_execution_cache_misses_limit: Option<usize>,
     ) -> Self {
@@ -49,6 +61,8 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             enforced_base_fee,
             missed_storage_invocation_limit,
+            state_override: None,
+            block_overrides: None,
         }
     }
 
@@ -72,8 +86,20 @@ impl TxExecutionArgs {
             enforced_nonce: tx.nonce(),
             added_balance,
             enforced_base_fee: Some(base_fee),
+            state_override: None,
+            block_overrides: None,
         }
     }
+
+    pub fn with_state_override(mut self, state_override: Option<StateOverride>) -> Self {
+        self.state_override = state_override;
+        self
+    }
+
+    pub fn with_block_overrides(mut self, block_overrides: Option<BlockOverrides>) -> Self {
+        self.block_overrides = block_overrides;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +112,19 @@ pub(crate) struct TransactionExecutionOutput {
     pub are_published_bytecodes_ok: bool,
 }
 
+/// Output of executing a bundle of transactions in a single L2 block.
+#[derive(Debug, Clone)]
+pub(crate) struct BundleExecutionOutput {
+    /// Number of the L2 block the transactions were executed in.
+    pub l2_block_number: u32,
+    /// Timestamp of the L2 block the transactions were executed in.
+    pub l2_block_timestamp: u64,
+    /// Base fee used during execution.
+    pub base_fee: u64,
+    /// Outputs of the VM for each transaction, in the execution order.
+    pub results: Vec<VmExecutionResultAndLogs>,
+}
+
 /// Executor of transactions.
 #[derive(Debug)]
 pub(crate) enum TransactionExecutor {
@@ -114,7 +153,7 @@ impl TransactionExecutor {
         custom_tracers: Vec<ApiTracer>,
     ) -> anyhow::Result<TransactionExecutionOutput> {
         if let Self::Mock(mock_executor) = self {
-            return mock_executor.execute_tx(&tx, &block_args);
+            return mock_executor.execute_tx(&tx, &block_args, &custom_tracers);
         }
 
         let total_factory_deps = tx
@@ -133,7 +172,7 @@ impl TransactionExecutor {
                 &connection_pool,
                 tx,
                 block_args,
-                |vm, tx, _| {
+                |vm, tx, _, _| {
                     let storage_invocation_tracer =
                         StorageInvocations::new(execution_args.missed_storage_invocation_limit);
                     let custom_tracers: Vec<_> = custom_tracers
@@ -178,7 +217,8 @@ impl TransactionExecutor {
         let execution_args = TxExecutionArgs::for_eth_call(
             call_overrides.enforced_base_fee,
             vm_execution_cache_misses_limit,
-        );
+        )
+        .with_state_override(call_overrides.state_override);
 
         if tx.common_data.signature.is_empty() {
             tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
@@ -198,4 +238,90 @@ impl TransactionExecutor {
             .await?;
         Ok(output.vm)
     }
+
+    /// Executes a bundle of L2 transactions in the `eth_call` mode in a single L2 block. Each transaction
+    /// observes state changes made by the preceding transactions. `custom_tracers` are only attached
+    /// to the last transaction in the bundle.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute_bundle_eth_call(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        execution_args: TxExecutionArgs,
+        txs: Vec<L2Tx>,
+        block_args: BlockArgs,
+        custom_tracers: Vec<ApiTracer>,
+    ) -> anyhow::Result<BundleExecutionOutput> {
+        let txs: Vec<Transaction> = txs
+            .into_iter()
+            .map(|mut tx| {
+                if tx.common_data.signature.is_empty() {
+                    tx.common_data.signature =
+                        PackedEthSignature::default().serialize_packed().into();
+                }
+                tx.into()
+            })
+            .collect();
+        if let Self::Mock(mock_executor) = self {
+            return mock_executor.execute_bundle(&txs, &block_args);
+        }
+
+        let tx_count = txs.len();
+        let mut txs = txs.into_iter();
+        let first_tx = txs.next().context("transaction bundle is empty")?;
+        let output = tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "execute_bundle_in_sandbox").entered();
+            let result = apply::apply_vm_in_sandbox(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx,
+                block_args,
+                |vm, first_tx, protocol_version, l1_batch_env| {
+                    let mut custom_tracers = Some(custom_tracers);
+                    let results = iter::once(first_tx)
+                        .chain(txs)
+                        .enumerate()
+                        .map(|(i, tx)| {
+                            let storage_invocation_tracer = StorageInvocations::new(
+                                execution_args.missed_storage_invocation_limit,
+                            );
+                            let custom_tracers = if i + 1 == tx_count {
+                                custom_tracers.take().unwrap_or_default()
+                            } else {
+                                vec![]
+                            };
+                            let tracers: Vec<_> = custom_tracers
+                                .into_iter()
+                                .map(|tracer| tracer.into_boxed())
+                                .chain([storage_invocation_tracer.into_tracer_pointer()])
+                                .collect();
+                            let (_, result) = vm.inspect_transaction_with_bytecode_compression(
+                                tracers.into(),
+                                tx,
+                                true,
+                            );
+                            result
+                        })
+                        .collect();
+
+                    let vm_version = protocol_version.into_api_vm_version();
+                    BundleExecutionOutput {
+                        l2_block_number: l1_batch_env.first_l2_block.number,
+                        l2_block_timestamp: l1_batch_env.first_l2_block.timestamp,
+                        base_fee: get_batch_base_fee(l1_batch_env, vm_version),
+                        results,
+                    }
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("bundle execution panicked")??;
+        Ok(output)
+    }
 }
@@ -0,0 +1,192 @@
+//! VM storage functionality specifically used in the VM sandbox.
+
+use std::collections::{HashMap, HashSet};
+
+use zksync_state::ReadStorage;
+use zksync_types::{
+    api::StateOverride,
+    get_code_key, get_known_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
+    AccountTreeId, StorageKey, StorageValue, H256, U256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
+
+/// [`ReadStorage`] implementation applying a [`StateOverride`] on top of the wrapped storage.
+///
+/// Overrides are applied on the storage level (rather than on the level of [`StorageView`](zksync_state::StorageView)),
+/// so that full storage replacement (`state` in the override) can be implemented; from the VM perspective,
+/// overridden values are indistinguishable from the original storage values.
+#[derive(Debug)]
+pub(super) struct StorageWithOverrides<S> {
+    storage_handle: S,
+    overridden_slots: HashMap<StorageKey, H256>,
+    /// Overridden transaction nonces keyed by the nonce storage key. Deployment nonces are not overridden,
+    /// so the full nonce is computed lazily.
+    overridden_nonces: HashMap<StorageKey, U256>,
+    overridden_factory_deps: HashMap<H256, Vec<u8>>,
+    /// Accounts with the fully replaced storage; their storage slots not present in `overridden_slots` are zero.
+    empty_accounts: HashSet<AccountTreeId>,
+}
+
+impl<S: ReadStorage> StorageWithOverrides<S> {
+    /// Creates a storage without overrides.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage_handle: storage,
+            overridden_slots: HashMap::new(),
+            overridden_nonces: HashMap::new(),
+            overridden_factory_deps: HashMap::new(),
+            empty_accounts: HashSet::new(),
+        }
+    }
+
+    /// Applies the provided state override. The override is assumed to be validated beforehand; in particular,
+    /// all overridden bytecodes must be valid.
+    pub fn apply_state_override(&mut self, state_override: &StateOverride) {
+        for (address, account) in state_override.iter() {
+            if let Some(balance) = account.balance {
+                let balance_key = storage_key_for_eth_balance(address);
+                self.overridden_slots
+                    .insert(balance_key, u256_to_h256(balance));
+            }
+
+            if let Some(nonce) = account.nonce {
+                self.overridden_nonces.insert(get_nonce_key(address), nonce);
+            }
+
+            if let Some(code) = &account.code {
+                let code_hash = hash_bytecode(&code.0);
+                self.overridden_slots
+                    .insert(get_code_key(address), code_hash);
+                self.overridden_slots
+                    .insert(get_known_code_key(&code_hash), H256::from_low_u64_be(1));
+                self.overridden_factory_deps
+                    .insert(code_hash, code.0.clone());
+            }
+
+            let account_id = AccountTreeId::new(*address);
+            if let Some(state) = &account.state {
+                self.empty_accounts.insert(account_id);
+                self.override_slots(account_id, state);
+            }
+            if let Some(state_diff) = &account.state_diff {
+                self.override_slots(account_id, state_diff);
+            }
+        }
+    }
+
+    fn override_slots(&mut self, account: AccountTreeId, slots: &HashMap<H256, H256>) {
+        let slots = slots
+            .iter()
+            .map(|(&key, &value)| (StorageKey::new(account, key), value));
+        self.overridden_slots.extend(slots);
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for StorageWithOverrides<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(value) = self.overridden_slots.get(key) {
+            return *value;
+        }
+        if let Some(&nonce) = self.overridden_nonces.get(key) {
+            let full_nonce = self.storage_handle.read_value(key);
+            let (_, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+            return u256_to_h256(nonces_to_full_nonce(nonce, deployment_nonce));
+        }
+        if self.empty_accounts.contains(key.account()) {
+            return H256::zero();
+        }
+        self.storage_handle.read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.storage_handle.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.overridden_factory_deps
+            .get(&hash)
+            .cloned()
+            .or_else(|| self.storage_handle.load_factory_dep(hash))
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.storage_handle.get_enumeration_index(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_state::InMemoryStorage;
+    use zksync_types::{api::OverrideAccount, Address};
+
+    use super::*;
+
+    #[test]
+    fn override_basics() {
+        let address = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+        let mut inner = InMemoryStorage::with_system_contracts(hash_bytecode);
+        let slot_key = StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(1));
+        inner.set_value(slot_key, H256::repeat_byte(0xff));
+        let other_slot_key = StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(2));
+        inner.set_value(other_slot_key, H256::repeat_byte(0xfe));
+        let nonce_key = get_nonce_key(&address);
+        inner.set_value(
+            nonce_key,
+            u256_to_h256(nonces_to_full_nonce(3.into(), 5.into())),
+        );
+
+        let code = vec![1_u8; 32];
+        let code_hash = hash_bytecode(&code);
+        let state_override = StateOverride::new(HashMap::from([
+            (
+                address,
+                OverrideAccount {
+                    balance: Some(100.into()),
+                    nonce: Some(10.into()),
+                    code: Some(code.clone().into()),
+                    state: Some(HashMap::from([(
+                        H256::from_low_u64_be(1),
+                        H256::repeat_byte(1),
+                    )])),
+                    state_diff: None,
+                },
+            ),
+            (
+                other_address,
+                OverrideAccount {
+                    state_diff: Some(HashMap::from([(
+                        H256::from_low_u64_be(1),
+                        H256::repeat_byte(2),
+                    )])),
+                    ..OverrideAccount::default()
+                },
+            ),
+        ]));
+
+        let mut storage = StorageWithOverrides::new(inner);
+        storage.apply_state_override(&state_override);
+
+        let balance = storage.read_value(&storage_key_for_eth_balance(&address));
+        assert_eq!(h256_to_u256(balance), 100.into());
+        let full_nonce = h256_to_u256(storage.read_value(&nonce_key));
+        assert_eq!(decompose_full_nonce(full_nonce), (10.into(), 5.into()));
+        assert_eq!(storage.read_value(&get_code_key(&address)), code_hash);
+        assert!(storage.is_bytecode_known(&code_hash));
+        assert_eq!(storage.load_factory_dep(code_hash), Some(code));
+
+        // `state` override fully replaces the account storage.
+        assert_eq!(storage.read_value(&slot_key), H256::repeat_byte(1));
+        assert_eq!(storage.read_value(&other_slot_key), H256::zero());
+
+        // `stateDiff` override only changes the specified slots.
+        let other_account = AccountTreeId::new(other_address);
+        let key = StorageKey::new(other_account, H256::from_low_u64_be(1));
+        assert_eq!(storage.read_value(&key), H256::repeat_byte(2));
+
+        // Accounts not mentioned in the override are not affected.
+        let other_balance = storage.read_value(&storage_key_for_eth_balance(&other_address));
+        assert_eq!(other_balance, H256::zero());
+    }
+}
@@ -2,20 +2,23 @@ use std::fmt;
 
 use multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, ExecuteTransactionCommon, Transaction,
+    api::ValidationTrace, fee::TransactionExecutionMetrics, l2::L2Tx, vm_trace::Call,
+    ExecuteTransactionCommon, Transaction,
 };
 
 use super::{
-    execute::{TransactionExecutionOutput, TransactionExecutor},
+    execute::{BundleExecutionOutput, TransactionExecutionOutput, TransactionExecutor},
     validate::ValidationError,
-    BlockArgs,
+    ApiTracer, BlockArgs,
 };
 
 type TxResponseFn = dyn Fn(&Transaction, &BlockArgs) -> VmExecutionResultAndLogs + Send + Sync;
+type CallTracesFn = dyn Fn(&Transaction) -> Vec<Call> + Send + Sync;
 
 pub struct MockTransactionExecutor {
     call_responses: Box<TxResponseFn>,
     tx_responses: Box<TxResponseFn>,
+    call_traces: Box<CallTracesFn>,
 }
 
 impl fmt::Debug for MockTransactionExecutor {
@@ -38,6 +41,7 @@ impl Default for MockTransactionExecutor {
             tx_responses: Box::new(|tx, _| {
                 panic!("Unexpect transaction call: {tx:?}");
             }),
+            call_traces: Box::new(|_| vec![]),
         }
     }
 }
@@ -51,6 +55,23 @@ impl MockTransactionExecutor {
         self.call_responses = self.wrap_responses(responses);
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_call_responses_with_logs<F>(&mut self, responses: F)
+    where
+        F: Fn(&Transaction, &BlockArgs) -> VmExecutionResultAndLogs + 'static + Send + Sync,
+    {
+        self.call_responses = Box::new(responses);
+    }
+
+    /// Sets call traces returned to the call tracer (if one is attached to the execution).
+    #[cfg(test)]
+    pub(crate) fn set_call_traces<F>(&mut self, call_traces: F)
+    where
+        F: Fn(&Transaction) -> Vec<Call> + 'static + Send + Sync,
+    {
+        self.call_traces = Box::new(call_traces);
+    }
+
     #[cfg(test)]
     pub(crate) fn set_tx_responses<F>(&mut self, responses: F)
     where
@@ -98,12 +119,30 @@ impl MockTransactionExecutor {
         }
     }
 
+    pub(crate) fn trace_validation(&self, tx: L2Tx, block_args: &BlockArgs) -> ValidationTrace {
+        let result = (self.tx_responses)(&tx.into(), block_args);
+        let error = match result.result {
+            ExecutionResult::Halt { reason } => Some(reason.to_string()),
+            _ => None,
+        };
+        ValidationTrace {
+            error,
+            violations: vec![],
+        }
+    }
+
     pub(crate) fn execute_tx(
         &self,
         tx: &Transaction,
         block_args: &BlockArgs,
+        custom_tracers: &[ApiTracer],
     ) -> anyhow::Result<TransactionExecutionOutput> {
         let result = self.get_execution_result(tx, block_args);
+        for tracer in custom_tracers {
+            if let ApiTracer::CallTracer(call_traces) = tracer {
+                call_traces.set((self.call_traces)(tx)).ok();
+            }
+        }
         let output = TransactionExecutionOutput {
             vm: result,
             metrics: TransactionExecutionMetrics::default(),
@@ -113,6 +152,23 @@ impl MockTransactionExecutor {
         Ok(output)
     }
 
+    pub(crate) fn execute_bundle(
+        &self,
+        txs: &[Transaction],
+        block_args: &BlockArgs,
+    ) -> anyhow::Result<BundleExecutionOutput> {
+        let results = txs
+            .iter()
+            .map(|tx| self.get_execution_result(tx, block_args))
+            .collect();
+        Ok(BundleExecutionOutput {
+            l2_block_number: block_args.resolved_block_number().0,
+            l2_block_timestamp: 0,
+            base_fee: 0,
+            results,
+        })
+    }
+
     fn get_execution_result(
         &self,
         tx: &Transaction,
@@ -1,9 +1,11 @@
 //! Tests for the VM execution sandbox.
 
 use assert_matches::assert_matches;
+use multivm::interface::L1BatchEnv;
 use zksync_dal::ConnectionPool;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l2_block, create_l2_transaction, prepare_recovery_snapshot};
+use zksync_types::Transaction;
 
 use super::*;
 use crate::{execution_sandbox::apply::apply_vm_in_sandbox, tx_sender::ApiContracts};
@@ -195,7 +197,7 @@ async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs
             &pool,
             transaction.clone(),
             block_args,
-            |_, received_tx, _| {
+            |_, received_tx, _, _| {
                 assert_eq!(received_tx, transaction);
             },
         )
@@ -204,3 +206,67 @@ async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs
     .expect("VM instantiation panicked")
     .expect("VM instantiation errored");
 }
+
+#[tokio::test]
+async fn instantiating_vm_with_block_overrides() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let block_args = BlockArgs::pending(&mut storage).await.unwrap();
+    drop(storage);
+
+    let block_overrides = api::BlockOverrides {
+        number: Some(10.into()),
+        time: Some(u64::from(u32::MAX).into()),
+        l1_gas_price: Some(1_000.into()),
+        ..api::BlockOverrides::default()
+    };
+    let l1_batch_env =
+        instantiate_vm_with_block_overrides(pool.clone(), block_args, block_overrides)
+            .await
+            .unwrap();
+    assert_eq!(l1_batch_env.first_l2_block.number, 10);
+    assert_eq!(l1_batch_env.first_l2_block.timestamp, u64::from(u32::MAX));
+    assert_eq!(l1_batch_env.fee_input.l1_gas_price(), 1_000);
+
+    let block_overrides = api::BlockOverrides {
+        number: Some(0.into()),
+        ..api::BlockOverrides::default()
+    };
+    let err = instantiate_vm_with_block_overrides(pool.clone(), block_args, block_overrides)
+        .await
+        .unwrap_err();
+    assert!(
+        err.downcast_ref::<InvalidBlockOverrides>().is_some(),
+        "{err:?}"
+    );
+}
+
+async fn instantiate_vm_with_block_overrides(
+    pool: ConnectionPool<Core>,
+    block_args: BlockArgs,
+    block_overrides: api::BlockOverrides,
+) -> anyhow::Result<L1BatchEnv> {
+    let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
+    let vm_permit = vm_concurrency_limiter.acquire().await.unwrap();
+    let transaction: Transaction = create_l2_transaction(10, 100).into();
+
+    tokio::task::spawn_blocking(move || {
+        let execution_args = TxExecutionArgs::for_gas_estimate(None, &transaction, 123)
+            .with_block_overrides(Some(block_overrides));
+        apply_vm_in_sandbox(
+            vm_permit,
+            TxSharedArgs::mock(ApiContracts::load_from_disk().estimate_gas),
+            false,
+            &execution_args,
+            &pool,
+            transaction,
+            block_args,
+            |_, _, _, l1_batch_env| l1_batch_env.clone(),
+        )
+    })
+    .await
+    .expect("VM instantiation panicked")
+}
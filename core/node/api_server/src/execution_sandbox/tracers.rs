@@ -1,14 +1,29 @@
 use std::sync::Arc;
 
-use multivm::{tracers::CallTracer, vm_latest::HistoryMode, MultiVMTracer, MultiVmTracerPointer};
+use multivm::{
+    tracers::{prestate_tracer::PrestateTracerResult, CallTracer, PrestateTracer, StructLogTracer},
+    vm_latest::HistoryMode,
+    MultiVMTracer, MultiVmTracerPointer,
+};
 use once_cell::sync::OnceCell;
 use zksync_state::WriteStorage;
-use zksync_types::vm_trace::Call;
+use zksync_types::{
+    api::{StructLog, StructLoggerConfig},
+    vm_trace::Call,
+};
 
 /// Custom tracers supported by our API
 #[derive(Debug)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer {
+        diff_mode: bool,
+        result: Arc<OnceCell<PrestateTracerResult>>,
+    },
+    StructLogTracer {
+        config: StructLoggerConfig,
+        result: Arc<OnceCell<Vec<StructLog>>>,
+    },
 }
 
 impl ApiTracer {
@@ -20,6 +35,12 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer { diff_mode, result } => {
+                PrestateTracer::new(diff_mode, result).into_tracer_pointer()
+            }
+            ApiTracer::StructLogTracer { config, result } => {
+                StructLogTracer::new(config, result).into_tracer_pointer()
+            }
         }
     }
 }
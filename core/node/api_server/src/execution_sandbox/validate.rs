@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 use anyhow::Context as _;
 use multivm::{
@@ -10,8 +10,14 @@ use multivm::{
     vm_latest::HistoryDisabled,
     MultiVMTracer,
 };
+use once_cell::sync::OnceCell;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_types::{l2::L2Tx, Address, Transaction, TRUSTED_ADDRESS_SLOTS, TRUSTED_TOKEN_SLOTS};
+use zksync_types::{
+    api::{ValidationTrace, ValidationViolation},
+    l2::L2Tx,
+    vm_trace::ViolatedValidationRule,
+    Address, Transaction, TRUSTED_ADDRESS_SLOTS, TRUSTED_TOKEN_SLOTS,
+};
 
 use super::{
     apply,
@@ -30,6 +36,16 @@ pub(crate) enum ValidationError {
     Internal(#[from] anyhow::Error),
 }
 
+/// Outcome of running the validation step of a transaction in the sandbox.
+#[derive(Debug)]
+struct ValidationOutcome {
+    result: ExecutionResult,
+    /// First violated validation rule, if any.
+    violated_rule: Option<ViolatedValidationRule>,
+    /// All distinct violations; only collected if requested.
+    violations: Vec<ValidationViolation>,
+}
+
 impl TransactionExecutor {
     pub(crate) async fn validate_tx_in_sandbox(
         &self,
@@ -44,6 +60,81 @@ impl TransactionExecutor {
             return mock.validate_tx(tx, &block_args);
         }
 
+        let outcome = self
+            .run_validation(
+                connection_pool,
+                vm_permit,
+                tx,
+                shared_args,
+                block_args,
+                computational_gas_limit,
+                false,
+            )
+            .await?;
+        match (outcome.result, outcome.violated_rule) {
+            (_, Some(err)) => Err(ValidationError::Vm(
+                validator::ValidationError::ViolatedRule(err),
+            )),
+            (ExecutionResult::Halt { reason }, _) => Err(ValidationError::Vm(
+                validator::ValidationError::FailedTx(reason),
+            )),
+            (_, None) => Ok(()),
+        }
+    }
+
+    /// Runs the validation step of a transaction in the sandbox. Unlike [`Self::validate_tx_in_sandbox()`],
+    /// doesn't stop on the first violated validation rule and reports all distinct violations.
+    pub(crate) async fn trace_validation_in_sandbox(
+        &self,
+        connection_pool: ConnectionPool<Core>,
+        vm_permit: VmPermit,
+        tx: L2Tx,
+        shared_args: TxSharedArgs,
+        block_args: BlockArgs,
+        computational_gas_limit: u32,
+    ) -> anyhow::Result<ValidationTrace> {
+        if let Self::Mock(mock) = self {
+            return Ok(mock.trace_validation(tx, &block_args));
+        }
+
+        let outcome = self
+            .run_validation(
+                connection_pool,
+                vm_permit,
+                tx,
+                shared_args,
+                block_args,
+                computational_gas_limit,
+                true,
+            )
+            .await?;
+        let mut violations = outcome.violations;
+        if violations.is_empty() {
+            // Older VM versions don't support collecting violations and stop on the first one.
+            violations.extend(
+                outcome
+                    .violated_rule
+                    .map(|rule| ValidationViolation::new(&rule, None)),
+            );
+        }
+        let error = match outcome.result {
+            ExecutionResult::Halt { reason } => Some(reason.to_string()),
+            _ => None,
+        };
+        Ok(ValidationTrace { error, violations })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run_validation(
+        &self,
+        connection_pool: ConnectionPool<Core>,
+        vm_permit: VmPermit,
+        tx: L2Tx,
+        shared_args: TxSharedArgs,
+        block_args: BlockArgs,
+        computational_gas_limit: u32,
+        collect_violations: bool,
+    ) -> anyhow::Result<ValidationOutcome> {
         let stage_latency = SANDBOX_METRICS.sandbox[&SandboxStage::ValidateInSandbox].start();
         let mut connection = connection_pool
             .connection_tagged("api")
@@ -62,7 +153,7 @@ impl TransactionExecutor {
         let execution_args = TxExecutionArgs::for_validation(&tx);
         let tx: Transaction = tx.into();
 
-        let validation_result = tokio::task::spawn_blocking(move || {
+        let outcome = tokio::task::spawn_blocking(move || {
             let span = tracing::debug_span!("validate_in_sandbox").entered();
             let result = apply::apply_vm_in_sandbox(
                 vm_permit,
@@ -72,15 +163,19 @@ impl TransactionExecutor {
                 &connection_pool,
                 tx,
                 block_args,
-                |vm, tx, protocol_version| {
+                |vm, tx, protocol_version, _| {
                     let stage_latency = SANDBOX_METRICS.sandbox[&SandboxStage::Validation].start();
                     let span = tracing::debug_span!("validation").entered();
                     vm.push_transaction(tx);
 
-                    let (tracer, validation_result) = ValidationTracer::<HistoryDisabled>::new(
+                    let (mut tracer, validation_result) = ValidationTracer::<HistoryDisabled>::new(
                         validation_params,
                         protocol_version.into(),
                     );
+                    let violations = Arc::new(OnceCell::new());
+                    if collect_violations {
+                        tracer = tracer.collecting_violations(violations.clone());
+                    }
 
                     let result = vm.inspect(
                         vec![
@@ -91,20 +186,15 @@ impl TransactionExecutor {
                         .into(),
                         VmExecutionMode::OneTx,
                     );
-
-                    let result = match (result.result, validation_result.get()) {
-                        (_, Some(err)) => {
-                            Err(validator::ValidationError::ViolatedRule(err.clone()))
-                        }
-                        (ExecutionResult::Halt { reason }, _) => {
-                            Err(validator::ValidationError::FailedTx(reason))
-                        }
-                        (_, None) => Ok(()),
+                    let outcome = ValidationOutcome {
+                        result: result.result,
+                        violated_rule: validation_result.get().cloned(),
+                        violations: violations.get().cloned().unwrap_or_default(),
                     };
 
                     stage_latency.observe();
                     span.exit();
-                    result
+                    outcome
                 },
             );
             span.exit();
@@ -114,7 +204,7 @@ impl TransactionExecutor {
         .context("transaction validation panicked")??;
 
         stage_latency.observe();
-        validation_result.map_err(ValidationError::Vm)
+        Ok(outcome)
     }
 }
 
@@ -0,0 +1,115 @@
+//! Batched entity loaders. Resolvers for nested fields (e.g., `transaction { block }` for each transaction
+//! in a list) request entities via these loaders, which coalesce concurrent requests into a single DB query
+//! instead of issuing a query per parent object.
+
+use std::collections::HashMap;
+
+use async_graphql::dataloader::Loader;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_types::{api, L1BatchNumber, L2BlockNumber, L2ChainId, H256};
+
+use super::schema::{internal_error, Transaction};
+
+async fn connection(pool: &ConnectionPool<Core>) -> async_graphql::Result<Connection<'_, Core>> {
+    pool.connection_tagged("api").await.map_err(internal_error)
+}
+
+/// Loads L2 blocks (with transaction hashes) by their numbers.
+#[derive(Debug)]
+pub(super) struct BlockLoader {
+    pub pool: ConnectionPool<Core>,
+}
+
+#[async_trait::async_trait]
+impl Loader<L2BlockNumber> for BlockLoader {
+    type Value = api::Block<H256>;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        keys: &[L2BlockNumber],
+    ) -> Result<HashMap<L2BlockNumber, Self::Value>, Self::Error> {
+        let mut storage = connection(&self.pool).await?;
+        let blocks = storage
+            .blocks_web3_dal()
+            .get_api_blocks(keys)
+            .await
+            .map_err(internal_error)?;
+        Ok(blocks
+            .into_iter()
+            .map(|block| (L2BlockNumber(block.number.as_u32()), block))
+            .collect())
+    }
+}
+
+/// Loads transactions together with their receipts by transaction hashes.
+#[derive(Debug)]
+pub(super) struct TransactionLoader {
+    pub pool: ConnectionPool<Core>,
+    pub chain_id: L2ChainId,
+}
+
+#[async_trait::async_trait]
+impl Loader<H256> for TransactionLoader {
+    type Value = Transaction;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[H256]) -> Result<HashMap<H256, Self::Value>, Self::Error> {
+        let mut storage = connection(&self.pool).await?;
+        let transactions = storage
+            .transactions_web3_dal()
+            .get_transactions(keys, self.chain_id)
+            .await
+            .map_err(internal_error)?;
+        let receipts = storage
+            .transactions_web3_dal()
+            .get_transaction_receipts(keys)
+            .await
+            .map_err(internal_error)?;
+        let mut receipts: HashMap<_, _> = receipts
+            .into_iter()
+            .map(|receipt| (receipt.transaction_hash, receipt))
+            .collect();
+
+        Ok(transactions
+            .into_iter()
+            .map(|tx| {
+                let receipt = receipts.remove(&tx.hash);
+                (tx.hash, Transaction::new(tx, receipt))
+            })
+            .collect())
+    }
+}
+
+/// Loads L1 batch details by batch numbers. The DAL doesn't provide a batched query for batch details,
+/// so batches are loaded one by one, but using a single connection. Since keys are deduplicated and a single
+/// query page references few batches, this is still much cheaper than loading batches independently.
+#[derive(Debug)]
+pub(super) struct L1BatchLoader {
+    pub pool: ConnectionPool<Core>,
+}
+
+#[async_trait::async_trait]
+impl Loader<L1BatchNumber> for L1BatchLoader {
+    type Value = api::L1BatchDetails;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        keys: &[L1BatchNumber],
+    ) -> Result<HashMap<L1BatchNumber, Self::Value>, Self::Error> {
+        let mut storage = connection(&self.pool).await?;
+        let mut batches = HashMap::with_capacity(keys.len());
+        for &number in keys {
+            let details = storage
+                .blocks_web3_dal()
+                .get_l1_batch_details(number)
+                .await
+                .map_err(internal_error)?;
+            if let Some(details) = details {
+                batches.insert(number, details);
+            }
+        }
+        Ok(batches)
+    }
+}
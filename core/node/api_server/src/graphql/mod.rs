@@ -0,0 +1,153 @@
+//! Optional GraphQL API server.
+//!
+//! The schema is modelled on the [Geth GraphQL schema](https://eips.ethereum.org/EIPS/eip-1767) and extends it
+//! with zkSync-specific concepts, such as L1 batches, priority operations and L2-to-L1 logs. Unlike JSON-RPC,
+//! GraphQL allows fetching related entities in a single request (e.g., an L1 batch with all its transactions
+//! and their receipts). All data is read via `*_web3_dal` DAL modules.
+
+use std::{net::SocketAddr, num::NonZeroU32};
+
+use anyhow::Context as _;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{
+    extract::State,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use http::{header, HeaderValue, StatusCode};
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core};
+use zksync_types::L2ChainId;
+
+use self::schema::{build_schema, GraphQlSchema, GraphQlState};
+use crate::web3::backend_jsonrpsee::{
+    ClientIdLayer, ClientRateLimits, PeerAddrMakeService, RateLimitError,
+};
+
+mod loaders;
+mod scalars;
+mod schema;
+#[cfg(test)]
+mod tests;
+
+/// Default maximum number of entities (e.g., blocks or logs) returned by a single list query.
+const DEFAULT_MAX_PAGE_SIZE: usize = 1_024;
+
+/// GraphQL API server. Serves GraphQL queries at the `/graphql` path.
+#[derive(Debug)]
+pub struct GraphQlServer {
+    bind_address: SocketAddr,
+    pool: ConnectionPool<Core>,
+    chain_id: L2ChainId,
+    max_page_size: usize,
+    client_rate_limits: ClientRateLimits,
+}
+
+impl GraphQlServer {
+    pub fn new(bind_address: SocketAddr, pool: ConnectionPool<Core>, chain_id: L2ChainId) -> Self {
+        Self {
+            bind_address,
+            pool,
+            chain_id,
+            max_page_size: DEFAULT_MAX_PAGE_SIZE,
+            client_rate_limits: ClientRateLimits::default(),
+        }
+    }
+
+    /// Sets the maximum number of entities (e.g., blocks or logs) returned by a single list query.
+    /// Queries exceeding the limit return an error.
+    pub fn with_max_page_size(mut self, max_page_size: usize) -> Self {
+        self.max_page_size = max_page_size;
+        self
+    }
+
+    /// Sets per-client rate limits. Each GraphQL request has the unit weight; the amount of work per request
+    /// is bounded by query depth and complexity limits.
+    pub fn with_client_rate_limits(mut self, client_rate_limits: ClientRateLimits) -> Self {
+        self.client_rate_limits = client_rate_limits;
+        self
+    }
+
+    fn schema(&self) -> GraphQlSchema {
+        build_schema(GraphQlState {
+            pool: self.pool.clone(),
+            chain_id: self.chain_id,
+            max_page_size: self.max_page_size,
+        })
+    }
+
+    fn router(&self) -> anyhow::Result<Router> {
+        let mut router = Router::new()
+            .route("/graphql", post(handle_request))
+            .with_state(self.schema());
+        if let Some(client_id_layer) = self.client_rate_limits.build_layer("GraphQL")? {
+            router = router.layer(middleware::from_fn_with_state(
+                client_id_layer,
+                limit_client_requests,
+            ));
+        }
+        Ok(router)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let app = self.router()?;
+        // Peer addresses are required to identify clients for rate limiting.
+        let make_service = PeerAddrMakeService::new(move || app.clone());
+
+        tracing::info!("Starting GraphQL server on {}", self.bind_address);
+        axum::Server::try_bind(&self.bind_address)
+            .with_context(|| format!("Failed binding GraphQL server to {}", self.bind_address))?
+            .serve(make_service)
+            .with_graceful_shutdown(async move {
+                if stop_receiver.changed().await.is_err() {
+                    tracing::warn!(
+                        "Stop signal sender for GraphQL server was dropped without sending a signal"
+                    );
+                }
+                tracing::info!("Stop signal received, GraphQL server is shutting down");
+            })
+            .await
+            .context("GraphQL server failed")?;
+        tracing::info!("GraphQL server shut down");
+        Ok(())
+    }
+}
+
+async fn handle_request(
+    State(schema): State<GraphQlSchema>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute(request.into_inner()).await.into()
+}
+
+async fn limit_client_requests<B>(
+    State(client_id_layer): State<ClientIdLayer>,
+    request: http::Request<B>,
+    next: Next<B>,
+) -> Response {
+    match client_id_layer.check_request(&request, NonZeroU32::MIN) {
+        Ok(()) => next.run(request).await,
+        Err(err) => rate_limited_response(err),
+    }
+}
+
+/// Converts a rate limit error into an HTTP 429 response with a GraphQL-formatted error body.
+fn rate_limited_response(err: RateLimitError) -> Response {
+    let (message, retry_after) = match err {
+        RateLimitError::Exceeded { retry_after } => ("Too many requests", Some(retry_after)),
+        RateLimitError::InsufficientCapacity => ("Request weight exceeds rate limit quota", None),
+    };
+    let body = Json(serde_json::json!({ "errors": [{ "message": message }] }));
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, body).into_response();
+    if let Some(retry_after) = retry_after {
+        // `Retry-After` is measured in whole seconds. Round the delay up so that a client retrying
+        // after the hinted delay is not rate-limited again.
+        let retry_after_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+    }
+    response
+}
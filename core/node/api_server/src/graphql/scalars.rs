@@ -0,0 +1,45 @@
+//! Custom GraphQL scalars. Their representation matches the one used in the Web3 JSON-RPC API.
+
+use async_graphql::scalar;
+use serde::{Deserialize, Serialize};
+use zksync_types::{web3, H256, U256};
+
+/// 20-byte Ethereum address encoded as a `0x`-prefixed hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(super) struct Address(pub zksync_types::Address);
+
+scalar!(
+    Address,
+    "Address",
+    "20-byte address encoded as a 0x-prefixed hex string"
+);
+
+/// 32-byte value (e.g., a hash or a log topic) encoded as a `0x`-prefixed hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(super) struct Bytes32(pub H256);
+
+scalar!(
+    Bytes32,
+    "Bytes32",
+    "32-byte value encoded as a 0x-prefixed hex string"
+);
+
+/// Arbitrary-length byte string encoded as a `0x`-prefixed hex string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct Bytes(pub web3::Bytes);
+
+scalar!(
+    Bytes,
+    "Bytes",
+    "Arbitrary-length byte string encoded as a 0x-prefixed hex string"
+);
+
+/// 256-bit unsigned integer encoded as a `0x`-prefixed hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(super) struct BigInt(pub U256);
+
+scalar!(
+    BigInt,
+    "BigInt",
+    "256-bit unsigned integer encoded as a 0x-prefixed hex string"
+);
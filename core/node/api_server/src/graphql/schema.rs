@@ -0,0 +1,739 @@
+//! GraphQL schema: query root and object types.
+
+use std::fmt;
+
+use async_graphql::{
+    dataloader::DataLoader, Context, EmptyMutation, EmptySubscription, Enum, InputObject, Object,
+    Result, Schema, SimpleObject,
+};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_types::{
+    api, l2_to_l1_log, L1BatchNumber, L2BlockNumber, L2ChainId, H256, PRIORITY_OPERATION_L2_TX_TYPE,
+};
+
+use super::{
+    loaders::{BlockLoader, L1BatchLoader, TransactionLoader},
+    scalars::{Address, BigInt, Bytes, Bytes32},
+};
+
+/// Maximum number of topics in a log filter.
+const MAX_TOPIC_COUNT: usize = 4;
+/// Maximum nesting depth of GraphQL queries. Limits the amount of data that can be requested in a single query,
+/// e.g. via `block { parent { parent { ... } } }`.
+const MAX_QUERY_DEPTH: usize = 16;
+/// Maximum complexity of GraphQL queries. Each scalar or object field costs 1; the cost of a list field
+/// is the cost of its item selection multiplied by [`LIST_COMPLEXITY_MULTIPLIER`]. Unlike the depth limit,
+/// this bounds queries that nest several list fields, e.g. `blocks { transactions { logs { ... } } }`.
+const MAX_QUERY_COMPLEXITY: usize = 2_000;
+/// Estimated number of items in a list field used when computing query complexity.
+const LIST_COMPLEXITY_MULTIPLIER: usize = 10;
+
+pub(super) type GraphQlSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub(super) fn build_schema(state: GraphQlState) -> GraphQlSchema {
+    let block_loader = BlockLoader {
+        pool: state.pool.clone(),
+    };
+    let transaction_loader = TransactionLoader {
+        pool: state.pool.clone(),
+        chain_id: state.chain_id,
+    };
+    let l1_batch_loader = L1BatchLoader {
+        pool: state.pool.clone(),
+    };
+
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .data(DataLoader::new(block_loader, tokio::spawn))
+        .data(DataLoader::new(transaction_loader, tokio::spawn))
+        .data(DataLoader::new(l1_batch_loader, tokio::spawn))
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// State shared by all GraphQL resolvers.
+#[derive(Debug)]
+pub(super) struct GraphQlState {
+    pub pool: ConnectionPool<Core>,
+    pub chain_id: L2ChainId,
+    pub max_page_size: usize,
+}
+
+impl GraphQlState {
+    fn get<'a>(ctx: &Context<'a>) -> &'a Self {
+        ctx.data_unchecked()
+    }
+
+    async fn connection(&self) -> Result<Connection<'_, Core>> {
+        self.pool
+            .connection_tagged("api")
+            .await
+            .map_err(internal_error)
+    }
+}
+
+fn loader<'a, T: Send + Sync + 'static>(ctx: &Context<'a>) -> &'a DataLoader<T> {
+    ctx.data_unchecked()
+}
+
+/// Converts an internal error into a GraphQL error without exposing error details to the client.
+pub(super) fn internal_error(err: impl fmt::Display) -> async_graphql::Error {
+    tracing::warn!("Internal error in GraphQL resolver: {err}");
+    async_graphql::Error::new("Internal error")
+}
+
+async fn load_block(ctx: &Context<'_>, number: L2BlockNumber) -> Result<Option<Block>> {
+    let block = loader::<BlockLoader>(ctx).load_one(number).await?;
+    Ok(block.map(Block))
+}
+
+/// Loads blocks with the specified numbers. Missing blocks are skipped; the returned blocks are ordered by number.
+async fn load_blocks(
+    ctx: &Context<'_>,
+    numbers: impl IntoIterator<Item = L2BlockNumber>,
+) -> Result<Vec<Block>> {
+    let blocks = loader::<BlockLoader>(ctx).load_many(numbers).await?;
+    let mut blocks: Vec<_> = blocks.into_values().map(Block).collect();
+    blocks.sort_unstable_by_key(|block| block.0.number);
+    Ok(blocks)
+}
+
+async fn load_transaction(ctx: &Context<'_>, hash: H256) -> Result<Option<Transaction>> {
+    loader::<TransactionLoader>(ctx).load_one(hash).await
+}
+
+/// Loads transactions together with their receipts. Transactions are ordered by their position in the chain.
+async fn load_transactions(
+    ctx: &Context<'_>,
+    hashes: impl IntoIterator<Item = H256>,
+) -> Result<Vec<Transaction>> {
+    let transactions = loader::<TransactionLoader>(ctx).load_many(hashes).await?;
+    let mut transactions: Vec<_> = transactions.into_values().collect();
+    transactions.sort_unstable_by_key(|tx| (tx.tx.block_number, tx.tx.transaction_index));
+    Ok(transactions)
+}
+
+async fn load_logs(state: &GraphQlState, filter: api::GetLogsFilter) -> Result<Vec<api::Log>> {
+    let limit = state.max_page_size;
+    let mut storage = state.connection().await?;
+    let logs = storage
+        .events_web3_dal()
+        .get_logs(filter, limit.saturating_add(1))
+        .await
+        .map_err(internal_error)?;
+    if logs.len() > limit {
+        let message = format!("Query returned more than {limit} logs; narrow down the filter");
+        return Err(async_graphql::Error::new(message));
+    }
+    Ok(logs)
+}
+
+fn topics_filter(topics: Option<Vec<Vec<Bytes32>>>) -> Result<Vec<(u32, Vec<H256>)>> {
+    let topics = topics.unwrap_or_default();
+    if topics.len() > MAX_TOPIC_COUNT {
+        return Err(async_graphql::Error::new("More than four topics in filter"));
+    }
+    // Empty alternatives for a topic position match any topic, so they are skipped.
+    Ok(topics
+        .into_iter()
+        .enumerate()
+        .filter(|(_, alternatives)| !alternatives.is_empty())
+        .map(|(idx, alternatives)| {
+            let alternatives = alternatives.into_iter().map(|topic| topic.0).collect();
+            (idx as u32 + 1, alternatives)
+        })
+        .collect())
+}
+
+fn block_number(number: u64) -> L2BlockNumber {
+    L2BlockNumber(u32::try_from(number).unwrap_or(u32::MAX))
+}
+
+/// Log filter for the top-level `logs` query.
+#[derive(Debug, InputObject)]
+pub(super) struct FilterCriteria {
+    /// First block to return logs from (inclusive). Defaults to the latest sealed block.
+    from_block: Option<u64>,
+    /// Last block to return logs from (inclusive). Defaults to the latest sealed block.
+    to_block: Option<u64>,
+    /// Addresses of the contracts emitting logs. If not specified, logs emitted by any contract are returned.
+    addresses: Option<Vec<Address>>,
+    /// Topic alternatives for each topic position. An empty list for a position matches any topic.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// Log filter for logs in a specific block.
+#[derive(Debug, InputObject)]
+pub(super) struct BlockFilterCriteria {
+    /// Addresses of the contracts emitting logs. If not specified, logs emitted by any contract are returned.
+    addresses: Option<Vec<Address>>,
+    /// Topic alternatives for each topic position. An empty list for a position matches any topic.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+#[derive(Debug)]
+pub(super) struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Returns an L2 block by its number or hash. If neither is specified, returns the latest sealed block.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<u64>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block>> {
+        let block_id = match (number, hash) {
+            (Some(_), Some(_)) => {
+                return Err(async_graphql::Error::new(
+                    "Only one of `number` and `hash` may be specified",
+                ));
+            }
+            (Some(number), None) => api::BlockId::Number(api::BlockNumber::Number(number.into())),
+            (None, Some(hash)) => api::BlockId::Hash(hash.0),
+            (None, None) => api::BlockId::Number(api::BlockNumber::Latest),
+        };
+
+        let state = GraphQlState::get(ctx);
+        let mut storage = state.connection().await?;
+        let number = storage
+            .blocks_web3_dal()
+            .resolve_block_id(block_id)
+            .await
+            .map_err(internal_error)?;
+        drop(storage);
+        let Some(number) = number else {
+            return Ok(None);
+        };
+        load_block(ctx, number).await
+    }
+
+    /// Returns L2 blocks in the `[from, to]` range. If `to` is not specified, it defaults to the latest sealed block.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn blocks(&self, ctx: &Context<'_>, from: u64, to: Option<u64>) -> Result<Vec<Block>> {
+        let state = GraphQlState::get(ctx);
+        let mut storage = state.connection().await?;
+        let from = block_number(from);
+        let to = if let Some(to) = to {
+            block_number(to)
+        } else {
+            let latest = storage
+                .blocks_web3_dal()
+                .resolve_block_id(api::BlockId::Number(api::BlockNumber::Latest))
+                .await
+                .map_err(internal_error)?;
+            let Some(latest) = latest else {
+                return Ok(vec![]);
+            };
+            latest
+        };
+        drop(storage);
+        if to < from {
+            return Ok(vec![]);
+        }
+        let block_count = (to.0 - from.0) as usize + 1;
+        if block_count > state.max_page_size {
+            let message = format!(
+                "Requested {block_count} blocks; at most {} blocks can be requested at once",
+                state.max_page_size
+            );
+            return Err(async_graphql::Error::new(message));
+        }
+        load_blocks(ctx, (from.0..=to.0).map(L2BlockNumber)).await
+    }
+
+    /// Returns a transaction by its hash.
+    async fn transaction(&self, ctx: &Context<'_>, hash: Bytes32) -> Result<Option<Transaction>> {
+        load_transaction(ctx, hash.0).await
+    }
+
+    /// Returns logs matching the specified filter.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn logs(&self, ctx: &Context<'_>, filter: FilterCriteria) -> Result<Vec<Log>> {
+        let state = GraphQlState::get(ctx);
+        let mut storage = state.connection().await?;
+        let latest = storage
+            .blocks_web3_dal()
+            .resolve_block_id(api::BlockId::Number(api::BlockNumber::Latest))
+            .await
+            .map_err(internal_error)?;
+        drop(storage);
+        let Some(latest) = latest else {
+            return Ok(vec![]);
+        };
+
+        let get_logs_filter = api::GetLogsFilter {
+            from_block: filter.from_block.map_or(latest, block_number),
+            to_block: filter.to_block.map_or(latest, block_number).min(latest),
+            addresses: filter
+                .addresses
+                .unwrap_or_default()
+                .into_iter()
+                .map(|address| address.0)
+                .collect(),
+            topics: topics_filter(filter.topics)?,
+        };
+        let logs = load_logs(state, get_logs_filter).await?;
+        Ok(logs.into_iter().map(Log).collect())
+    }
+
+    /// Returns an L1 batch by its number. If the number is not specified, returns the latest sealed L1 batch.
+    async fn l1_batch(&self, ctx: &Context<'_>, number: Option<u64>) -> Result<Option<L1Batch>> {
+        let state = GraphQlState::get(ctx);
+        let mut storage = state.connection().await?;
+        let number = if let Some(number) = number {
+            L1BatchNumber(u32::try_from(number).unwrap_or(u32::MAX))
+        } else {
+            let sealed = storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .map_err(internal_error)?;
+            let Some(sealed) = sealed else {
+                return Ok(None);
+            };
+            sealed
+        };
+        let details = storage
+            .blocks_web3_dal()
+            .get_l1_batch_details(number)
+            .await
+            .map_err(internal_error)?;
+        Ok(details.map(L1Batch))
+    }
+
+    /// Returns the L2 chain ID.
+    async fn chain_id(&self, ctx: &Context<'_>) -> BigInt {
+        BigInt(GraphQlState::get(ctx).chain_id.as_u64().into())
+    }
+}
+
+/// L2 block.
+#[derive(Debug)]
+pub(super) struct Block(api::Block<H256>);
+
+#[Object]
+impl Block {
+    async fn number(&self) -> u64 {
+        self.0.number.as_u64()
+    }
+
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.0.hash)
+    }
+
+    /// Parent block, or `null` for the genesis block.
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let Some(parent_number) = self.0.number.as_u32().checked_sub(1) else {
+            return Ok(None);
+        };
+        load_block(ctx, L2BlockNumber(parent_number)).await
+    }
+
+    async fn timestamp(&self) -> u64 {
+        self.0.timestamp.as_u64()
+    }
+
+    async fn gas_limit(&self) -> BigInt {
+        BigInt(self.0.gas_limit)
+    }
+
+    async fn gas_used(&self) -> BigInt {
+        BigInt(self.0.gas_used)
+    }
+
+    async fn base_fee_per_gas(&self) -> BigInt {
+        BigInt(self.0.base_fee_per_gas)
+    }
+
+    async fn transaction_count(&self) -> u64 {
+        self.0.transactions.len() as u64
+    }
+
+    /// Transactions in this block together with their receipts.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn transactions(&self, ctx: &Context<'_>) -> Result<Vec<Transaction>> {
+        load_transactions(ctx, self.0.transactions.iter().copied()).await
+    }
+
+    /// Logs emitted in this block and matching the optional filter.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn logs(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<BlockFilterCriteria>,
+    ) -> Result<Vec<Log>> {
+        let (addresses, topics) =
+            filter.map_or((None, None), |filter| (filter.addresses, filter.topics));
+        let number = L2BlockNumber(self.0.number.as_u32());
+        let get_logs_filter = api::GetLogsFilter {
+            from_block: number,
+            to_block: number,
+            addresses: addresses
+                .unwrap_or_default()
+                .into_iter()
+                .map(|address| address.0)
+                .collect(),
+            topics: topics_filter(topics)?,
+        };
+        let logs = load_logs(GraphQlState::get(ctx), get_logs_filter).await?;
+        Ok(logs.into_iter().map(Log).collect())
+    }
+
+    /// Number of the L1 batch this block belongs to, or `null` if the batch is not sealed yet.
+    async fn l1_batch_number(&self) -> Option<u64> {
+        self.0.l1_batch_number.map(|number| number.as_u64())
+    }
+
+    /// L1 batch this block belongs to, or `null` if the batch is not sealed yet.
+    async fn l1_batch(&self, ctx: &Context<'_>) -> Result<Option<L1Batch>> {
+        let Some(number) = self.0.l1_batch_number else {
+            return Ok(None);
+        };
+        let details = loader::<L1BatchLoader>(ctx)
+            .load_one(L1BatchNumber(number.as_u32()))
+            .await?;
+        Ok(details.map(L1Batch))
+    }
+}
+
+/// Transaction together with its receipt (the receipt is missing for pending transactions).
+#[derive(Debug, Clone)]
+pub(super) struct Transaction {
+    tx: api::Transaction,
+    receipt: Option<api::TransactionReceipt>,
+}
+
+impl Transaction {
+    pub(super) fn new(tx: api::Transaction, receipt: Option<api::TransactionReceipt>) -> Self {
+        Self { tx, receipt }
+    }
+
+    fn is_priority_op(&self) -> bool {
+        self.tx.transaction_type == Some(PRIORITY_OPERATION_L2_TX_TYPE.into())
+    }
+}
+
+#[Object]
+impl Transaction {
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.tx.hash)
+    }
+
+    async fn nonce(&self) -> BigInt {
+        BigInt(self.tx.nonce)
+    }
+
+    /// Index of the transaction in its block, or `null` for pending transactions.
+    async fn index(&self) -> Option<u64> {
+        self.tx.transaction_index.map(|index| index.as_u64())
+    }
+
+    async fn from(&self) -> Option<Address> {
+        self.tx.from.map(Address)
+    }
+
+    async fn to(&self) -> Option<Address> {
+        self.tx.to.map(Address)
+    }
+
+    async fn value(&self) -> BigInt {
+        BigInt(self.tx.value)
+    }
+
+    async fn gas_price(&self) -> Option<BigInt> {
+        self.tx.gas_price.map(BigInt)
+    }
+
+    async fn max_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx.max_fee_per_gas.map(BigInt)
+    }
+
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx.max_priority_fee_per_gas.map(BigInt)
+    }
+
+    async fn gas(&self) -> BigInt {
+        BigInt(self.tx.gas)
+    }
+
+    async fn input_data(&self) -> Bytes {
+        Bytes(self.tx.input.clone())
+    }
+
+    #[graphql(name = "type")]
+    async fn tx_type(&self) -> Option<u64> {
+        self.tx.transaction_type.map(|tx_type| tx_type.as_u64())
+    }
+
+    /// Whether this transaction is a priority operation, i.e., was submitted via L1.
+    async fn is_priority_operation(&self) -> bool {
+        self.is_priority_op()
+    }
+
+    /// Block containing the transaction, or `null` for pending transactions.
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let Some(number) = self.tx.block_number else {
+            return Ok(None);
+        };
+        load_block(ctx, L2BlockNumber(number.as_u32())).await
+    }
+
+    /// Execution status: 1 for success, 0 for failure, or `null` for pending transactions.
+    async fn status(&self) -> Option<u64> {
+        Some(self.receipt.as_ref()?.status.as_u64())
+    }
+
+    async fn gas_used(&self) -> Option<BigInt> {
+        self.receipt.as_ref()?.gas_used.map(BigInt)
+    }
+
+    async fn cumulative_gas_used(&self) -> Option<BigInt> {
+        Some(BigInt(self.receipt.as_ref()?.cumulative_gas_used))
+    }
+
+    async fn effective_gas_price(&self) -> Option<BigInt> {
+        self.receipt.as_ref()?.effective_gas_price.map(BigInt)
+    }
+
+    /// Address of the contract deployed by the transaction, if any.
+    async fn created_contract(&self) -> Option<Address> {
+        self.receipt.as_ref()?.contract_address.map(Address)
+    }
+
+    /// Logs emitted by the transaction, or `null` for pending transactions.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn logs(&self) -> Option<Vec<Log>> {
+        let receipt = self.receipt.as_ref()?;
+        Some(receipt.logs.iter().cloned().map(Log).collect())
+    }
+
+    /// Number of the L1 batch containing the transaction, or `null` if the batch is not sealed yet.
+    async fn l1_batch_number(&self) -> Option<u64> {
+        let number = self.receipt.as_ref()?.l1_batch_number?;
+        Some(number.as_u64())
+    }
+
+    /// Index of the transaction in its L1 batch, or `null` if the batch is not sealed yet.
+    async fn l1_batch_index(&self) -> Option<u64> {
+        let index = self.receipt.as_ref()?.l1_batch_tx_index?;
+        Some(index.as_u64())
+    }
+
+    /// L2-to-L1 logs emitted by the transaction, or `null` for pending transactions.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn l2_to_l1_logs(&self) -> Option<Vec<L2ToL1Log>> {
+        let receipt = self.receipt.as_ref()?;
+        Some(receipt.l2_to_l1_logs.iter().map(L2ToL1Log::from).collect())
+    }
+}
+
+/// Log (aka event) emitted by a transaction.
+#[derive(Debug)]
+pub(super) struct Log(api::Log);
+
+#[Object]
+impl Log {
+    /// Index of the log in its block.
+    async fn index(&self) -> Option<u64> {
+        self.0.log_index.map(|index| index.as_u64())
+    }
+
+    /// Address of the contract that emitted the log.
+    async fn address(&self) -> Address {
+        Address(self.0.address)
+    }
+
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.0.topics.iter().copied().map(Bytes32).collect()
+    }
+
+    async fn data(&self) -> Bytes {
+        Bytes(self.0.data.clone())
+    }
+
+    async fn block_number(&self) -> Option<u64> {
+        self.0.block_number.map(|number| number.as_u64())
+    }
+
+    async fn l1_batch_number(&self) -> Option<u64> {
+        self.0.l1_batch_number.map(|number| number.as_u64())
+    }
+
+    /// Transaction that emitted the log.
+    async fn transaction(&self, ctx: &Context<'_>) -> Result<Option<Transaction>> {
+        let Some(hash) = self.0.transaction_hash else {
+            return Ok(None);
+        };
+        load_transaction(ctx, hash).await
+    }
+}
+
+/// Status of an L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub(super) enum L1BatchStatus {
+    /// The batch is sealed, but its proof is not verified on L1 yet.
+    Sealed,
+    /// The batch proof is verified on L1.
+    Verified,
+}
+
+impl From<&api::BlockStatus> for L1BatchStatus {
+    fn from(status: &api::BlockStatus) -> Self {
+        match status {
+            api::BlockStatus::Sealed => Self::Sealed,
+            api::BlockStatus::Verified => Self::Verified,
+        }
+    }
+}
+
+/// L1 batch: a group of consecutive L2 blocks committed, proven and executed on L1 together.
+#[derive(Debug)]
+pub(super) struct L1Batch(api::L1BatchDetails);
+
+impl L1Batch {
+    async fn block_numbers(&self, ctx: &Context<'_>) -> Result<Vec<L2BlockNumber>> {
+        let mut storage = GraphQlState::get(ctx).connection().await?;
+        let range = storage
+            .blocks_web3_dal()
+            .get_l2_block_range_of_l1_batch(self.0.number)
+            .await
+            .map_err(internal_error)?;
+        Ok(range.map_or_else(Vec::new, |(first, last)| {
+            (first.0..=last.0).map(L2BlockNumber).collect()
+        }))
+    }
+
+    async fn all_transactions(&self, ctx: &Context<'_>) -> Result<Vec<Transaction>> {
+        let max_page_size = GraphQlState::get(ctx).max_page_size;
+        let tx_count = self.0.base.l1_tx_count + self.0.base.l2_tx_count;
+        if tx_count > max_page_size {
+            let message = format!(
+                "L1 batch #{} contains {tx_count} transactions; at most {max_page_size} transactions \
+                 can be requested at once",
+                self.0.number
+            );
+            return Err(async_graphql::Error::new(message));
+        }
+
+        let numbers = self.block_numbers(ctx).await?;
+        let blocks = load_blocks(ctx, numbers).await?;
+        let hashes = blocks.into_iter().flat_map(|block| block.0.transactions);
+        load_transactions(ctx, hashes).await
+    }
+}
+
+#[Object]
+impl L1Batch {
+    async fn number(&self) -> u64 {
+        self.0.number.0.into()
+    }
+
+    async fn timestamp(&self) -> u64 {
+        self.0.base.timestamp
+    }
+
+    /// Number of priority operations in the batch.
+    async fn l1_tx_count(&self) -> u64 {
+        self.0.base.l1_tx_count as u64
+    }
+
+    async fn l2_tx_count(&self) -> u64 {
+        self.0.base.l2_tx_count as u64
+    }
+
+    /// State root hash after the batch, or `null` if it's not computed yet.
+    async fn root_hash(&self) -> Option<Bytes32> {
+        self.0.base.root_hash.map(Bytes32)
+    }
+
+    async fn status(&self) -> L1BatchStatus {
+        L1BatchStatus::from(&self.0.base.status)
+    }
+
+    async fn commit_tx_hash(&self) -> Option<Bytes32> {
+        self.0.base.commit_tx_hash.map(Bytes32)
+    }
+
+    async fn prove_tx_hash(&self) -> Option<Bytes32> {
+        self.0.base.prove_tx_hash.map(Bytes32)
+    }
+
+    async fn execute_tx_hash(&self) -> Option<Bytes32> {
+        self.0.base.execute_tx_hash.map(Bytes32)
+    }
+
+    /// L2 blocks in this batch.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn blocks(&self, ctx: &Context<'_>) -> Result<Vec<Block>> {
+        let numbers = self.block_numbers(ctx).await?;
+        load_blocks(ctx, numbers).await
+    }
+
+    /// All transactions in this batch together with their receipts.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn transactions(&self, ctx: &Context<'_>) -> Result<Vec<Transaction>> {
+        self.all_transactions(ctx).await
+    }
+
+    /// Priority operations (i.e., transactions submitted via L1) in this batch.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn priority_operations(&self, ctx: &Context<'_>) -> Result<Vec<Transaction>> {
+        let transactions = self.all_transactions(ctx).await?;
+        Ok(transactions
+            .into_iter()
+            .filter(Transaction::is_priority_op)
+            .collect())
+    }
+
+    /// L2-to-L1 logs emitted in this batch.
+    #[graphql(complexity = "LIST_COMPLEXITY_MULTIPLIER * child_complexity")]
+    async fn l2_to_l1_logs(&self, ctx: &Context<'_>) -> Result<Vec<L2ToL1Log>> {
+        let mut storage = GraphQlState::get(ctx).connection().await?;
+        let logs = storage
+            .blocks_web3_dal()
+            .get_l2_to_l1_logs(self.0.number)
+            .await
+            .map_err(internal_error)?;
+        Ok(logs.iter().map(L2ToL1Log::from).collect())
+    }
+}
+
+/// L2-to-L1 log, i.e. a message sent from L2 to L1 as a part of an L1 batch.
+#[derive(Debug, SimpleObject)]
+pub(super) struct L2ToL1Log {
+    shard_id: u32,
+    is_service: bool,
+    /// Index of the transaction emitting the log in its L1 batch.
+    tx_index_in_l1_batch: Option<u64>,
+    sender: Address,
+    key: Bytes32,
+    value: Bytes32,
+}
+
+impl From<&api::L2ToL1Log> for L2ToL1Log {
+    fn from(log: &api::L2ToL1Log) -> Self {
+        Self {
+            shard_id: log.shard_id.as_u32(),
+            is_service: log.is_service,
+            tx_index_in_l1_batch: log.tx_index_in_l1_batch.map(|index| index.as_u64()),
+            sender: Address(log.sender),
+            key: Bytes32(log.key),
+            value: Bytes32(log.value),
+        }
+    }
+}
+
+impl From<&l2_to_l1_log::L2ToL1Log> for L2ToL1Log {
+    fn from(log: &l2_to_l1_log::L2ToL1Log) -> Self {
+        Self {
+            shard_id: log.shard_id.into(),
+            is_service: log.is_service,
+            tx_index_in_l1_batch: Some(log.tx_number_in_block.into()),
+            sender: Address(log.sender),
+            key: Bytes32(log.key),
+            value: Bytes32(log.value),
+        }
+    }
+}
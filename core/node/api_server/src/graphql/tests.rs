@@ -0,0 +1,271 @@
+//! Tests for the GraphQL server.
+
+use std::{net::Ipv4Addr, num::NonZeroU32};
+
+use assert_matches::assert_matches;
+use serde_json::json;
+use zksync_dal::{transactions_dal::L2TxSubmissionResult, Connection, CoreDal};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::{
+    create_l1_batch, create_l2_block, create_l2_transaction, execute_l2_transaction,
+};
+use zksync_types::{
+    fee::TransactionExecutionMetrics, L1BatchNumber, L2BlockNumber, ProtocolVersionId, H256,
+};
+
+use super::*;
+use crate::web3::backend_jsonrpsee::PeerAddr;
+
+async fn prepare_storage(storage: &mut Connection<'_, Core>) -> H256 {
+    insert_genesis_batch(storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let tx = create_l2_transaction(10, 100);
+    let tx_hash = tx.hash();
+    let tx_submission_result = storage
+        .transactions_dal()
+        .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+        .await
+        .unwrap();
+    assert_matches!(tx_submission_result, L2TxSubmissionResult::Added);
+
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(1))
+        .await
+        .unwrap();
+    storage
+        .transactions_dal()
+        .mark_txs_as_executed_in_l2_block(
+            L2BlockNumber(1),
+            &[execute_l2_transaction(tx)],
+            1.into(),
+            ProtocolVersionId::latest(),
+            false,
+        )
+        .await
+        .unwrap();
+    let mut l1_batch = create_l1_batch(1);
+    l1_batch.l2_tx_count = 1;
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&l1_batch)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    tx_hash
+}
+
+fn test_server(pool: ConnectionPool<Core>) -> GraphQlServer {
+    GraphQlServer::new(([127, 0, 0, 1], 0).into(), pool, L2ChainId::default())
+        .with_max_page_size(10)
+}
+
+async fn execute_query(schema: &GraphQlSchema, query: &str) -> serde_json::Value {
+    let response = schema.execute(query).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()
+}
+
+#[tokio::test]
+async fn querying_blocks() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let tx_hash = prepare_storage(&mut pool.connection().await.unwrap()).await;
+    let schema = test_server(pool).schema();
+
+    let data = execute_query(
+        &schema,
+        "{ block(number: 0) { number parent { number } l1BatchNumber } }",
+    )
+    .await;
+    assert_eq!(
+        data,
+        json!({ "block": { "number": 0, "parent": null, "l1BatchNumber": 0 } })
+    );
+
+    let data = execute_query(
+        &schema,
+        "{ block { number transactionCount transactions { hash status l1BatchNumber } } }",
+    )
+    .await;
+    assert_eq!(
+        data,
+        json!({
+            "block": {
+                "number": 1,
+                "transactionCount": 1,
+                "transactions": [{ "hash": tx_hash, "status": 1, "l1BatchNumber": 1 }],
+            }
+        })
+    );
+
+    let data = execute_query(&schema, "{ blocks(from: 0) { number } }").await;
+    assert_eq!(
+        data,
+        json!({ "blocks": [{ "number": 0 }, { "number": 1 }] })
+    );
+    let data = execute_query(&schema, "{ block(number: 100) { number } }").await;
+    assert_eq!(data, json!({ "block": null }));
+}
+
+#[tokio::test]
+async fn querying_l1_batch_with_transactions() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let tx_hash = prepare_storage(&mut pool.connection().await.unwrap()).await;
+    let schema = test_server(pool).schema();
+
+    let query = r#"{
+        l1Batch {
+            number
+            status
+            blocks { number }
+            transactions { hash isPriorityOperation block { number } }
+            priorityOperations { hash }
+        }
+    }"#;
+    let data = execute_query(&schema, query).await;
+    assert_eq!(
+        data,
+        json!({
+            "l1Batch": {
+                "number": 1,
+                "status": "SEALED",
+                "blocks": [{ "number": 1 }],
+                "transactions": [{
+                    "hash": tx_hash,
+                    "isPriorityOperation": false,
+                    "block": { "number": 1 },
+                }],
+                "priorityOperations": [],
+            }
+        })
+    );
+
+    let query = format!(r#"{{ transaction(hash: "{tx_hash:?}") {{ index l1BatchNumber }} }}"#);
+    let data = execute_query(&schema, &query).await;
+    assert_eq!(
+        data,
+        json!({ "transaction": { "index": 0, "l1BatchNumber": 1 } })
+    );
+}
+
+#[tokio::test]
+async fn query_limits_are_enforced() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&mut pool.connection().await.unwrap()).await;
+    let schema = test_server(pool).schema();
+
+    let response = schema
+        .execute("{ blocks(from: 0, to: 100) { number } }")
+        .await;
+    assert_eq!(response.errors.len(), 1, "{:?}", response.errors);
+    assert!(
+        response.errors[0].message.contains("at most 10 blocks"),
+        "{:?}",
+        response.errors
+    );
+
+    let response = schema
+        .execute("{ logs(filter: { topics: [[], [], [], [], []] }) { index } }")
+        .await;
+    assert_eq!(response.errors.len(), 1, "{:?}", response.errors);
+    assert_eq!(
+        response.errors[0].message,
+        "More than four topics in filter"
+    );
+}
+
+#[tokio::test]
+async fn l1_batch_transactions_are_limited_by_page_size() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&mut pool.connection().await.unwrap()).await;
+    let schema = test_server(pool).with_max_page_size(0).schema();
+
+    let response = schema
+        .execute("{ l1Batch(number: 1) { transactions { hash } } }")
+        .await;
+    assert_eq!(response.errors.len(), 1, "{:?}", response.errors);
+    assert!(
+        response.errors[0]
+            .message
+            .contains("at most 0 transactions"),
+        "{:?}",
+        response.errors
+    );
+}
+
+#[tokio::test]
+async fn overly_complex_queries_are_rejected() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&mut pool.connection().await.unwrap()).await;
+    let schema = test_server(pool).schema();
+
+    let query = r#"{
+        blocks(from: 0) {
+            transactions {
+                logs { address topics data }
+                l2ToL1Logs { sender }
+            }
+        }
+    }"#;
+    let response = schema.execute(query).await;
+    assert_eq!(response.errors.len(), 1, "{:?}", response.errors);
+    assert!(
+        response.errors[0].message.contains("too complex"),
+        "{:?}",
+        response.errors
+    );
+}
+
+fn graphql_request(peer_ip: Ipv4Addr, query: &str) -> http::Request<axum::body::Body> {
+    let body = serde_json::to_vec(&json!({ "query": query })).unwrap();
+    let mut request = http::Request::post("/graphql")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(axum::body::Body::from(body))
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(PeerAddr((peer_ip, 12345).into()));
+    request
+}
+
+#[tokio::test]
+async fn client_requests_are_rate_limited() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&mut pool.connection().await.unwrap()).await;
+    let client_rate_limits =
+        ClientRateLimits::default().with_requests_per_minute_limit(NonZeroU32::new(1).unwrap());
+    let mut router = test_server(pool)
+        .with_client_rate_limits(client_rate_limits)
+        .router()
+        .unwrap();
+
+    let client_ip = Ipv4Addr::new(10, 0, 0, 1);
+    let response = tower::Service::call(&mut router, graphql_request(client_ip, "{ chainId }"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = tower::Service::call(&mut router, graphql_request(client_ip, "{ chainId }"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after > 0 && retry_after <= 60, "{retry_after}");
+
+    // Other clients are not affected.
+    let other_ip = Ipv4Addr::new(10, 0, 0, 2);
+    let response = tower::Service::call(&mut router, graphql_request(other_ip, "{ chainId }"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
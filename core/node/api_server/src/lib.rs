@@ -3,6 +3,7 @@
 #[macro_use]
 mod utils;
 pub mod execution_sandbox;
+pub mod graphql;
 pub mod healthcheck;
 pub mod tx_sender;
 pub mod web3;
@@ -1,7 +1,7 @@
 use std::collections::hash_map::{Entry, HashMap};
 
 use tokio::sync::Mutex;
-use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal};
+use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal, DalError};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
 use zksync_types::{fee::TransactionExecutionMetrics, l2::L2Tx, Address, Nonce, H256};
 
@@ -9,19 +9,58 @@ use super::{tx_sink::TxSink, SubmitTxError};
 use crate::web3::metrics::API_METRICS;
 
 /// Wrapper for the master DB pool that allows to submit transactions to the mempool.
+///
+/// Pending transactions with the same initiator and nonce are replaced only if the new transaction bumps fees
+/// by at least `replacement_fee_bump_percent`; this is checked atomically with the replacement.
 #[derive(Debug)]
 pub struct MasterPoolSink {
     master_pool: ConnectionPool<Core>,
+    replacement_fee_bump_percent: u32,
     inflight_requests: Mutex<HashMap<(Address, Nonce), H256>>,
 }
 
 impl MasterPoolSink {
-    pub fn new(master_pool: ConnectionPool<Core>) -> Self {
+    pub fn new(master_pool: ConnectionPool<Core>, replacement_fee_bump_percent: u32) -> Self {
         Self {
             master_pool,
+            replacement_fee_bump_percent,
             inflight_requests: Mutex::new(HashMap::new()),
         }
     }
+
+    async fn insert_tx(
+        &self,
+        tx: &L2Tx,
+        execution_metrics: TransactionExecutionMetrics,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        let mut connection = self
+            .master_pool
+            .connection_tagged("api")
+            .await
+            .map_err(DalError::generalize)?;
+        let min_bump_percent = self.replacement_fee_bump_percent;
+        let submission_res_handle = connection
+            .transactions_dal()
+            .insert_transaction_l2_with_fee_bump(tx, execution_metrics, min_bump_percent)
+            .await
+            .map_err(DalError::generalize)?;
+        APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
+
+        if submission_res_handle == L2TxSubmissionResult::ReplacementUnderpriced {
+            let replaced_fee = connection
+                .transactions_web3_dal()
+                .get_pending_l2_tx_fee(tx.initiator_account(), tx.nonce())
+                .await
+                .map_err(DalError::generalize)?
+                .map(|(_, fee)| fee)
+                .unwrap_or_default();
+            return Err(SubmitTxError::ReplacementUnderpriced(
+                replaced_fee.min_replacement_fee_per_gas(min_bump_percent),
+                min_bump_percent,
+            ));
+        }
+        Ok(submission_res_handle)
+    }
 }
 
 #[async_trait::async_trait]
@@ -51,18 +90,7 @@ impl TxSink for MasterPoolSink {
         };
         drop(lock);
 
-        let result = match self.master_pool.connection_tagged("api").await {
-            Ok(mut connection) => connection
-                .transactions_dal()
-                .insert_transaction_l2(tx, execution_metrics)
-                .await
-                .map(|submission_res_handle| {
-                    APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
-                    submission_res_handle
-                })
-                .map_err(|err| err.generalize().into()),
-            Err(err) => Err(err.generalize().into()),
-        };
+        let result = self.insert_tx(tx, execution_metrics).await;
 
         self.inflight_requests
             .lock()
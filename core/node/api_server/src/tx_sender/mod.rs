@@ -12,18 +12,24 @@ use multivm::{
     vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
 use tokio::sync::RwLock;
-use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
+use zksync_config::configs::{
+    api::Web3JsonRpcConfig,
+    chain::{MempoolConfig, StateKeeperConfig},
+};
 use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{
     transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal,
 };
+use zksync_mempool::EvictionPolicy;
 use zksync_node_fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider};
 use zksync_state::PostgresStorageCaches;
 use zksync_state_keeper::{
+    mempool_eviction_policy,
     seal_criteria::{ConditionalSealer, NoopSealer, SealData},
     SequencerSealer,
 };
 use zksync_types::{
+    api::{BlockOverrides, PaymasterFeeEstimate, StateOverride, ValidationTrace},
     fee::{Fee, TransactionExecutionMetrics},
     fee_model::BatchFeeInput,
     get_code_key, get_intrinsic_constants,
@@ -34,19 +40,21 @@ use zksync_types::{
     ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
     MAX_NEW_FACTORY_DEPS, U256,
 };
-use zksync_utils::h256_to_u256;
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256};
 
 pub(super) use self::result::SubmitTxError;
 use self::{master_pool_sink::MasterPoolSink, tx_sink::TxSink};
 use crate::{
     execution_sandbox::{
-        BlockArgs, SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs,
-        VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+        ApiTracer, BlockArgs, BundleExecutionOutput, SubmitTxStage, TransactionExecutor,
+        TxExecutionArgs, TxSharedArgs, VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit,
+        SANDBOX_METRICS,
     },
     tx_sender::result::ApiCallResult,
 };
 
 pub mod master_pool_sink;
+mod paymaster;
 pub mod proxy;
 mod result;
 #[cfg(test)]
@@ -63,7 +71,8 @@ pub async fn build_tx_sender(
     storage_caches: PostgresStorageCaches,
 ) -> (TxSender, VmConcurrencyBarrier) {
     let sequencer_sealer = SequencerSealer::new(state_keeper_config.clone());
-    let master_pool_sink = MasterPoolSink::new(master_pool);
+    let master_pool_sink =
+        MasterPoolSink::new(master_pool, web3_json_config.replacement_fee_bump_percent());
     let tx_sender_builder = TxSenderBuilder::new(
         tx_sender_config.clone(),
         replica_pool.clone(),
@@ -271,6 +280,10 @@ pub struct TxSenderConfig {
     pub fee_account_addr: Address,
     pub gas_price_scale_factor: f64,
     pub max_nonce_ahead: u32,
+    /// Minimum fee bump (in percent) for a transaction replacing a pending transaction with the same nonce.
+    pub replacement_fee_bump_percent: u32,
+    /// Mempool eviction policy. Per-account limits from the policy are checked before accepting a transaction.
+    pub mempool_eviction_policy: EvictionPolicy,
     pub max_allowed_l2_tx_gas_limit: u64,
     pub vm_execution_cache_misses_limit: Option<usize>,
     pub validation_computational_gas_limit: u32,
@@ -282,6 +295,7 @@ impl TxSenderConfig {
     pub fn new(
         state_keeper_config: &StateKeeperConfig,
         web3_json_config: &Web3JsonRpcConfig,
+        mempool_config: &MempoolConfig,
         fee_account_addr: Address,
         chain_id: L2ChainId,
     ) -> Self {
@@ -289,6 +303,8 @@ impl TxSenderConfig {
             fee_account_addr,
             gas_price_scale_factor: web3_json_config.gas_price_scale_factor,
             max_nonce_ahead: web3_json_config.max_nonce_ahead,
+            replacement_fee_bump_percent: web3_json_config.replacement_fee_bump_percent(),
+            mempool_eviction_policy: mempool_eviction_policy(mempool_config),
             max_allowed_l2_tx_gas_limit: state_keeper_config.max_allowed_l2_tx_gas_limit,
             vm_execution_cache_misses_limit: web3_json_config.vm_execution_cache_misses_limit,
             validation_computational_gas_limit: state_keeper_config
@@ -545,14 +561,17 @@ impl TxSender {
 
         // We still double-check the nonce manually
         // to make sure that only the correct nonce is submitted and the transaction's hashes never repeat
-        self.validate_account_nonce(tx).await?;
+        let expected_nonce = self.validate_account_nonce(tx).await?;
+        self.validate_replacement(tx).await?;
+        self.validate_mempool_admission(tx, expected_nonce).await?;
         // Even though without enough balance the tx will not pass anyway
         // we check the user for enough balance explicitly here for better DevEx.
         self.validate_enough_balance(tx).await?;
         Ok(())
     }
 
-    async fn validate_account_nonce(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+    /// Checks the transaction nonce; returns the expected nonce for the transaction initiator.
+    async fn validate_account_nonce(&self, tx: &L2Tx) -> Result<Nonce, SubmitTxError> {
         let Nonce(expected_nonce) = self
             .get_expected_nonce(tx.initiator_account())
             .await
@@ -578,11 +597,127 @@ impl TxSender {
                     tx.nonce().0,
                 ))
             } else {
-                Ok(())
+                Ok(Nonce(expected_nonce))
             }
         }
     }
 
+    /// Checks per-account limits of the mempool eviction policy, so that transactions which would be evicted
+    /// from the mempool immediately are rejected early.
+    async fn validate_mempool_admission(
+        &self,
+        tx: &L2Tx,
+        expected_nonce: Nonce,
+    ) -> Result<(), SubmitTxError> {
+        let policy = &self.0.sender_config.mempool_eviction_policy;
+        let pending_txs = if policy.max_txs_per_account.is_some() {
+            let mut storage = self.acquire_replica_connection().await?;
+            storage
+                .transactions_web3_dal()
+                .get_pending_l2_txs_count(tx.initiator_account(), tx.nonce())
+                .await
+                .context("failed getting number of pending transactions")?
+        } else {
+            0
+        };
+        let nonce_gap = tx.nonce().0.saturating_sub(expected_nonce.0);
+        policy
+            .check_admission(pending_txs, nonce_gap)
+            .map_err(SubmitTxError::MempoolPolicy)
+    }
+
+    /// Checks that the transaction bumps fees sufficiently if it replaces a pending transaction with the same nonce.
+    /// This check uses the replica pool and only allows to reject underpriced transactions early; the check
+    /// is enforced atomically with the replacement by [`MasterPoolSink`].
+    async fn validate_replacement(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        let Some((replaced_hash, replaced_fee)) = self.get_pending_tx_fee(tx).await? else {
+            return Ok(());
+        };
+        if replaced_hash == tx.hash() {
+            // Will be handled as a duplicate transaction
+            return Ok(());
+        }
+
+        let min_bump_percent = self.0.sender_config.replacement_fee_bump_percent;
+        if tx
+            .common_data
+            .fee
+            .is_replacement_for(&replaced_fee, min_bump_percent)
+        {
+            tracing::debug!(
+                "Transaction {:?} replaces pending transaction {replaced_hash:?}",
+                tx.hash()
+            );
+            Ok(())
+        } else {
+            Err(SubmitTxError::ReplacementUnderpriced(
+                replaced_fee.min_replacement_fee_per_gas(min_bump_percent),
+                min_bump_percent,
+            ))
+        }
+    }
+
+    async fn get_pending_tx_fee(&self, tx: &L2Tx) -> anyhow::Result<Option<(H256, Fee)>> {
+        let mut storage = self.acquire_replica_connection().await?;
+        let pending_tx = storage
+            .transactions_web3_dal()
+            .get_pending_l2_tx_fee(tx.initiator_account(), tx.nonce())
+            .await?;
+        Ok(pending_tx)
+    }
+
+    /// Submits a transaction cancelling a pending transaction with the same nonce. The cancelling transaction
+    /// must be a zero-value transfer from the account to itself without calldata; it is subject to the same
+    /// replacement rules as other transactions.
+    pub async fn cancel_tx(&self, tx: L2Tx) -> Result<(), SubmitTxError> {
+        let is_self_transfer = tx.execute.contract_address == tx.initiator_account()
+            && tx.execute.value.is_zero()
+            && tx.execute.calldata.is_empty()
+            && tx.execute.factory_deps_length() == 0;
+        if !is_self_transfer {
+            return Err(SubmitTxError::InvalidCancellationTx);
+        }
+
+        let tx_hash = tx.hash();
+        if self.0.tx_sink.persists_pending_txs() {
+            let Some((cancelled_hash, _)) = self.get_pending_tx_fee(&tx).await? else {
+                return Err(SubmitTxError::NoTransactionToCancel(tx.nonce().0));
+            };
+            tracing::info!(
+                "Cancelling transaction {cancelled_hash:?} with transaction {tx_hash:?}"
+            );
+        }
+        self.submit_tx(tx).await?;
+        Ok(())
+    }
+
+    /// Runs the account validation step of a transaction in the sandbox on top of the pending block and returns
+    /// all violated validation rules. Unlike [`Self::submit_tx()`], doesn't perform any other checks
+    /// and doesn't submit the transaction.
+    pub async fn trace_validation(&self, tx: L2Tx) -> Result<ValidationTrace, SubmitTxError> {
+        let shared_args = self.shared_args().await?;
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+        let mut connection = self.acquire_replica_connection().await?;
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        drop(connection);
+
+        let computational_gas_limit = self.0.sender_config.validation_computational_gas_limit;
+        let trace = self
+            .0
+            .executor
+            .trace_validation_in_sandbox(
+                self.0.replica_connection_pool.clone(),
+                vm_permit,
+                tx,
+                shared_args,
+                block_args,
+                computational_gas_limit,
+            )
+            .await?;
+        Ok(trace)
+    }
+
     async fn get_expected_nonce(&self, initiator_account: Address) -> anyhow::Result<Nonce> {
         let mut storage = self.acquire_replica_connection().await?;
         let latest_block_number = storage
@@ -651,6 +786,7 @@ impl TxSender {
         block_args: BlockArgs,
         base_fee: u64,
         vm_version: VmVersion,
+        state_override: Option<&StateOverride>,
     ) -> anyhow::Result<(VmExecutionResultAndLogs, TransactionExecutionMetrics)> {
         let gas_limit_with_overhead = tx_gas_limit
             + derive_overhead(
@@ -683,10 +819,32 @@ impl TxSender {
             }
         }
 
+        self.execute_for_gas_estimate(
+            vm_permit,
+            tx,
+            fee_model_params,
+            block_args,
+            base_fee,
+            state_override,
+        )
+        .await
+    }
+
+    /// Executes the transaction with its fee params as is, using the same sandbox settings as gas estimation.
+    async fn execute_for_gas_estimate(
+        &self,
+        vm_permit: VmPermit,
+        tx: Transaction,
+        fee_model_params: BatchFeeInput,
+        block_args: BlockArgs,
+        base_fee: u64,
+        state_override: Option<&StateOverride>,
+    ) -> anyhow::Result<(VmExecutionResultAndLogs, TransactionExecutionMetrics)> {
         let shared_args = self.shared_args_for_gas_estimate(fee_model_params).await;
         let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
         let execution_args =
-            TxExecutionArgs::for_gas_estimate(vm_execution_cache_misses_limit, &tx, base_fee);
+            TxExecutionArgs::for_gas_estimate(vm_execution_cache_misses_limit, &tx, base_fee)
+                .with_state_override(state_override.cloned());
         let execution_output = self
             .0
             .executor
@@ -696,7 +854,7 @@ impl TxSender {
                 true,
                 execution_args,
                 self.0.replica_connection_pool.clone(),
-                tx.clone(),
+                tx,
                 block_args,
                 vec![],
             )
@@ -719,16 +877,67 @@ impl TxSender {
         }
     }
 
+    pub async fn get_txs_fee_in_wei(
+        &self,
+        tx: Transaction,
+        estimated_fee_scale_factor: f64,
+        acceptable_overestimation: u64,
+        state_override: Option<StateOverride>,
+    ) -> Result<Fee, SubmitTxError> {
+        let (fee, _) = self
+            .estimate_fee(
+                tx,
+                estimated_fee_scale_factor,
+                acceptable_overestimation,
+                state_override,
+                false,
+            )
+            .await?;
+        Ok(fee)
+    }
+
+    /// Estimates the fee for an L2 transaction and previews how it will be split between the initiator
+    /// and the paymaster (if any). The preview is based on the execution of the transaction with the returned fee
+    /// (i.e., with the same gas limit and max fee per gas that the paymaster would observe), which includes
+    /// the paymaster validation and post-op.
+    pub async fn estimate_paymaster_fee(
+        &self,
+        tx: L2Tx,
+        estimated_fee_scale_factor: f64,
+        acceptable_overestimation: u64,
+    ) -> Result<PaymasterFeeEstimate, SubmitTxError> {
+        let (fee, execution_result) = self
+            .estimate_fee(
+                tx.clone().into(),
+                estimated_fee_scale_factor,
+                acceptable_overestimation,
+                None,
+                true,
+            )
+            .await?;
+        let execution_result =
+            execution_result.context("transaction was not executed with the estimated fee")?;
+        Ok(paymaster::preview_paymaster_fee(
+            &tx,
+            fee,
+            &execution_result,
+        ))
+    }
+
+    /// Returns the estimated fee. If `execute_with_fee` is set, the L2 transaction is additionally executed
+    /// with the estimated fee, and the execution result is returned as well.
     #[tracing::instrument(level = "debug", skip_all, fields(
         initiator = ?tx.initiator_account(),
         nonce = ?tx.nonce(),
     ))]
-    pub async fn get_txs_fee_in_wei(
+    async fn estimate_fee(
         &self,
         mut tx: Transaction,
         estimated_fee_scale_factor: f64,
         acceptable_overestimation: u64,
-    ) -> Result<Fee, SubmitTxError> {
+        state_override: Option<StateOverride>,
+        execute_with_fee: bool,
+    ) -> Result<(Fee, Option<VmExecutionResultAndLogs>), SubmitTxError> {
         let estimation_started_at = Instant::now();
 
         let mut connection = self.acquire_replica_connection().await?;
@@ -765,33 +974,42 @@ impl TxSender {
             }
         }
 
+        let initiator_override = state_override
+            .as_ref()
+            .and_then(|state_override| state_override.get(&tx.initiator_account()));
         let hashed_key = get_code_key(&tx.initiator_account());
         // If the default account does not have enough funds for transferring `tx.value`, without taking into account the fee,
         // there is no sense to estimate the fee.
-        let account_code_hash = self
-            .acquire_replica_connection()
-            .await?
-            .storage_web3_dal()
-            .get_value(&hashed_key)
-            .await
-            .with_context(|| {
-                format!(
-                    "failed getting code hash for account {:?}",
-                    tx.initiator_account()
-                )
-            })?;
-
-        if !tx.is_l1()
-            && account_code_hash == H256::zero()
-            && tx.execute.value > self.get_balance(&tx.initiator_account()).await?
-        {
-            tracing::info!(
-                "fee estimation failed on validation step.
-                account: {} does not have enough funds for for transferring tx.value: {}.",
-                &tx.initiator_account(),
-                tx.execute.value
-            );
-            return Err(SubmitTxError::InsufficientFundsForTransfer);
+        let account_code_hash =
+            if let Some(code) = initiator_override.and_then(|account| account.code.as_ref()) {
+                hash_bytecode(&code.0)
+            } else {
+                self.acquire_replica_connection()
+                    .await?
+                    .storage_web3_dal()
+                    .get_value(&hashed_key)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed getting code hash for account {:?}",
+                            tx.initiator_account()
+                        )
+                    })?
+            };
+        if !tx.is_l1() && account_code_hash == H256::zero() {
+            let balance = match initiator_override.and_then(|account| account.balance) {
+                Some(balance) => balance,
+                None => self.get_balance(&tx.initiator_account()).await?,
+            };
+            if tx.execute.value > balance {
+                tracing::info!(
+                    "fee estimation failed on validation step.
+                    account: {} does not have enough funds for for transferring tx.value: {}.",
+                    &tx.initiator_account(),
+                    tx.execute.value
+                );
+                return Err(SubmitTxError::InsufficientFundsForTransfer);
+            }
         }
 
         // For L2 transactions we need a properly formatted signature
@@ -831,6 +1049,7 @@ impl TxSender {
                     block_args,
                     base_fee,
                     protocol_version.into(),
+                    state_override.as_ref(),
                 )
                 .await
                 .context("estimate_gas step failed")?;
@@ -866,6 +1085,7 @@ impl TxSender {
                     block_args,
                     base_fee,
                     protocol_version.into(),
+                    state_override.as_ref(),
                 )
                 .await
                 .context("estimate_gas step failed")?;
@@ -890,7 +1110,7 @@ impl TxSender {
             ((upper_bound + additional_gas_for_pubdata) as f64 * estimated_fee_scale_factor) as u64;
         let (result, tx_metrics) = self
             .estimate_gas_step(
-                vm_permit,
+                vm_permit.clone(),
                 tx.clone(),
                 suggested_gas_limit,
                 gas_per_pubdata_byte as u32,
@@ -898,11 +1118,12 @@ impl TxSender {
                 block_args,
                 base_fee,
                 protocol_version.into(),
+                state_override.as_ref(),
             )
             .await
             .context("final estimate_gas step failed")?;
 
-        result.into_api_call_result()?;
+        result.clone().into_api_call_result()?;
         self.ensure_tx_executable(&tx, &tx_metrics, false)?;
 
         // Now, we need to calculate the final overhead for the transaction.
@@ -944,12 +1165,33 @@ impl TxSender {
             suggested_gas_limit - estimated_gas_for_pubdata,
         );
 
-        Ok(Fee {
+        let fee = Fee {
             max_fee_per_gas: base_fee.into(),
             max_priority_fee_per_gas: 0u32.into(),
             gas_limit: full_gas_limit.into(),
             gas_per_pubdata_limit: gas_per_pubdata_byte.into(),
-        })
+        };
+
+        let mut execution_result = None;
+        if execute_with_fee {
+            if let ExecuteTransactionCommon::L2(common_data) = &mut tx.common_data {
+                common_data.fee = fee.clone();
+                let (result, _) = self
+                    .execute_for_gas_estimate(
+                        vm_permit,
+                        tx,
+                        fee_input,
+                        block_args,
+                        base_fee,
+                        state_override.as_ref(),
+                    )
+                    .await
+                    .context("executing transaction with estimated fee failed")?;
+                result.clone().into_api_call_result()?;
+                execution_result = Some(result);
+            }
+        }
+        Ok((fee, execution_result))
     }
 
     // For now, both L1 gas price and pubdata price are scaled with the same coefficient
@@ -969,11 +1211,26 @@ impl TxSender {
         call_overrides: CallOverrides,
         tx: L2Tx,
     ) -> Result<Vec<u8>, SubmitTxError> {
+        self.execute_eth_call(block_args, call_overrides, tx, vec![])
+            .await?
+            .into_api_call_result()
+    }
+
+    /// Same as [`Self::eth_call()`], but returns the full VM output (e.g., including storage logs)
+    /// instead of the call output. `custom_tracers` are attached to the execution.
+    pub(super) async fn execute_eth_call(
+        &self,
+        block_args: BlockArgs,
+        call_overrides: CallOverrides,
+        tx: L2Tx,
+        custom_tracers: Vec<ApiTracer>,
+    ) -> Result<VmExecutionResultAndLogs, SubmitTxError> {
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
 
         let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
-        self.0
+        let output = self
+            .0
             .executor
             .execute_tx_eth_call(
                 vm_permit,
@@ -983,10 +1240,43 @@ impl TxSender {
                 tx,
                 block_args,
                 vm_execution_cache_misses_limit,
+                custom_tracers,
+            )
+            .await?;
+        Ok(output)
+    }
+
+    /// Executes a bundle of calls in a single L2 block. Each call observes state changes made by the preceding calls.
+    pub(super) async fn eth_simulate(
+        &self,
+        block_args: BlockArgs,
+        block_overrides: Option<BlockOverrides>,
+        state_override: Option<StateOverride>,
+        txs: Vec<L2Tx>,
+    ) -> Result<BundleExecutionOutput, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let execution_args = TxExecutionArgs::for_eth_call(
+            None,
+            self.0.sender_config.vm_execution_cache_misses_limit,
+        )
+        .with_state_override(state_override)
+        .with_block_overrides(block_overrides);
+        let output = self
+            .0
+            .executor
+            .execute_bundle_eth_call(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                execution_args,
+                txs,
+                block_args,
                 vec![],
             )
-            .await?
-            .into_api_call_result()
+            .await?;
+        Ok(output)
     }
 
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
@@ -0,0 +1,322 @@
+//! Preview of transaction fees paid by paymasters.
+
+use multivm::interface::VmExecutionResultAndLogs;
+use once_cell::sync::Lazy;
+use zksync_types::{
+    api::{ApprovalBasedFlowPreview, PaymasterFeeEstimate},
+    ethabi,
+    fee::Fee,
+    l2::L2Tx,
+    Address, VmEvent, BOOTLOADER_ADDRESS, H256, L2_BASE_TOKEN_ADDRESS, U256,
+};
+use zksync_utils::h256_to_account_address;
+
+/// Signature of the `Transfer(address,address,uint256)` event emitted both by ERC-20 tokens and the base token contract.
+static TRANSFER_EVENT_SIGNATURE: Lazy<H256> = Lazy::new(|| {
+    ethabi::long_signature(
+        "Transfer",
+        &[
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Uint(256),
+        ],
+    )
+});
+
+const APPROVAL_BASED_FLOW_PARAMS: [ethabi::ParamType; 3] = [
+    ethabi::ParamType::Address,
+    ethabi::ParamType::Uint(256),
+    ethabi::ParamType::Bytes,
+];
+
+/// Selector of `IPaymasterFlow.approvalBased(address,uint256,bytes)`.
+static APPROVAL_BASED_FLOW_SELECTOR: Lazy<[u8; 4]> =
+    Lazy::new(|| ethabi::short_signature("approvalBased", &APPROVAL_BASED_FLOW_PARAMS));
+
+/// Decoded paymaster input for the `approvalBased` flow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ApprovalBasedInput {
+    token: Address,
+    min_allowance: U256,
+}
+
+impl ApprovalBasedInput {
+    fn parse(paymaster_input: &[u8]) -> Option<Self> {
+        if paymaster_input.len() < 4 || paymaster_input[..4] != *APPROVAL_BASED_FLOW_SELECTOR {
+            return None;
+        }
+        let tokens = ethabi::decode(&APPROVAL_BASED_FLOW_PARAMS, &paymaster_input[4..]).ok()?;
+        let [token, min_allowance, _] = <[ethabi::Token; 3]>::try_from(tokens).ok()?;
+        Some(Self {
+            token: token.into_address()?,
+            min_allowance: min_allowance.into_uint()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Transfer {
+    token: Address,
+    from: Address,
+    to: Address,
+    amount: U256,
+}
+
+impl Transfer {
+    fn parse(event: &VmEvent) -> Option<Self> {
+        let [signature, from, to] = event.indexed_topics.as_slice() else {
+            return None;
+        };
+        if *signature != *TRANSFER_EVENT_SIGNATURE || event.value.len() != 32 {
+            return None;
+        }
+        Some(Self {
+            token: event.address,
+            from: h256_to_account_address(from),
+            to: h256_to_account_address(to),
+            amount: U256::from_big_endian(&event.value),
+        })
+    }
+}
+
+/// Returns the amount of `token` transferred from `from` to `to` minus the amount transferred back.
+fn net_transferred(transfers: &[Transfer], token: Address, from: Address, to: Address) -> U256 {
+    let (sent, received) = transfers
+        .iter()
+        .filter(|transfer| transfer.token == token)
+        .fold(
+            (U256::zero(), U256::zero()),
+            |(sent, received), transfer| {
+                if transfer.from == from && transfer.to == to {
+                    (sent.saturating_add(transfer.amount), received)
+                } else if transfer.from == to && transfer.to == from {
+                    (sent, received.saturating_add(transfer.amount))
+                } else {
+                    (sent, received)
+                }
+            },
+        );
+    sent.saturating_sub(received)
+}
+
+/// Splits the fee of a transaction between its initiator and paymaster based on the token transfers
+/// emitted when executing the transaction with the estimated `fee`. The fee is paid to the bootloader during validation,
+/// and the unused part is refunded by the bootloader after the paymaster post-op.
+pub(super) fn preview_paymaster_fee(
+    tx: &L2Tx,
+    fee: Fee,
+    execution_result: &VmExecutionResultAndLogs,
+) -> PaymasterFeeEstimate {
+    let transfers: Vec<_> = execution_result
+        .logs
+        .events
+        .iter()
+        .filter_map(Transfer::parse)
+        .collect();
+    let initiator = tx.initiator_account();
+    let paymaster_params = &tx.common_data.paymaster_params;
+    let paymaster =
+        (paymaster_params.paymaster != Address::zero()).then_some(paymaster_params.paymaster);
+
+    let charged = |account| {
+        net_transferred(
+            &transfers,
+            L2_BASE_TOKEN_ADDRESS,
+            account,
+            BOOTLOADER_ADDRESS,
+        )
+    };
+    let approval_based = paymaster.and_then(|paymaster| {
+        let input = ApprovalBasedInput::parse(&paymaster_params.paymaster_input)?;
+        Some(ApprovalBasedFlowPreview {
+            token: input.token,
+            min_allowance: input.min_allowance,
+            token_charged: net_transferred(&transfers, input.token, initiator, paymaster),
+        })
+    });
+
+    PaymasterFeeEstimate {
+        fee,
+        paymaster,
+        paymaster_charged: paymaster.map_or_else(U256::zero, charged),
+        initiator_charged: charged(initiator),
+        approval_based,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use multivm::interface::{ExecutionResult, VmExecutionLogs};
+    use zksync_dal::{ConnectionPool, Core};
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+    use zksync_node_test_utils::create_l2_transaction;
+    use zksync_types::{
+        transaction_request::PaymasterParams, ExecuteTransactionCommon, L1BatchNumber, L2ChainId,
+        Transaction,
+    };
+    use zksync_utils::{address_to_h256, u256_to_h256};
+
+    use super::*;
+    use crate::{
+        execution_sandbox::{testonly::MockTransactionExecutor, BlockArgs},
+        web3::testonly::create_test_tx_sender,
+    };
+
+    fn transfer_event(token: Address, from: Address, to: Address, amount: u64) -> VmEvent {
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: token,
+            indexed_topics: vec![
+                *TRANSFER_EVENT_SIGNATURE,
+                address_to_h256(&from),
+                address_to_h256(&to),
+            ],
+            value: u256_to_h256(U256::from(amount)).as_bytes().to_vec(),
+        }
+    }
+
+    fn execution_result(events: Vec<VmEvent>) -> VmExecutionResultAndLogs {
+        VmExecutionResultAndLogs {
+            result: ExecutionResult::Success { output: vec![] },
+            logs: VmExecutionLogs {
+                events,
+                ..VmExecutionLogs::default()
+            },
+            statistics: Default::default(),
+            refunds: Default::default(),
+        }
+    }
+
+    #[test]
+    fn parsing_approval_based_input() {
+        let token = Address::repeat_byte(0x11);
+        let encoded = ethabi::encode(&[
+            ethabi::Token::Address(token),
+            ethabi::Token::Uint(1_000.into()),
+            ethabi::Token::Bytes(vec![1, 2, 3]),
+        ]);
+        let input = [APPROVAL_BASED_FLOW_SELECTOR.as_slice(), &encoded].concat();
+        assert_eq!(
+            ApprovalBasedInput::parse(&input),
+            Some(ApprovalBasedInput {
+                token,
+                min_allowance: 1_000.into(),
+            })
+        );
+
+        assert_eq!(ApprovalBasedInput::parse(&input[..4]), None);
+        assert_eq!(ApprovalBasedInput::parse(&encoded), None);
+        let general_flow_selector = ethabi::short_signature("general", &[ethabi::ParamType::Bytes]);
+        let input = [general_flow_selector.as_slice(), &encoded].concat();
+        assert_eq!(ApprovalBasedInput::parse(&input), None);
+    }
+
+    #[test]
+    fn previewing_fee_without_paymaster() {
+        let tx = create_l2_transaction(10, 100);
+        let initiator = tx.initiator_account();
+        let events = vec![
+            transfer_event(L2_BASE_TOKEN_ADDRESS, initiator, BOOTLOADER_ADDRESS, 1_000),
+            transfer_event(L2_BASE_TOKEN_ADDRESS, BOOTLOADER_ADDRESS, initiator, 300),
+        ];
+
+        let preview = preview_paymaster_fee(&tx, Fee::default(), &execution_result(events));
+        assert_eq!(preview.paymaster, None);
+        assert_eq!(preview.initiator_charged, 700.into());
+        assert_eq!(preview.paymaster_charged, 0.into());
+        assert_eq!(preview.approval_based, None);
+    }
+
+    #[test]
+    fn previewing_fee_with_approval_based_paymaster() {
+        let token = Address::repeat_byte(0x11);
+        let paymaster = Address::repeat_byte(0x22);
+        let other_token = Address::repeat_byte(0x33);
+        let mut tx = create_l2_transaction(10, 100);
+        let initiator = tx.initiator_account();
+        let encoded = ethabi::encode(&[
+            ethabi::Token::Address(token),
+            ethabi::Token::Uint(5_000.into()),
+            ethabi::Token::Bytes(vec![]),
+        ]);
+        tx.common_data.paymaster_params = PaymasterParams {
+            paymaster,
+            paymaster_input: [APPROVAL_BASED_FLOW_SELECTOR.as_slice(), &encoded].concat(),
+        };
+
+        let events = vec![
+            transfer_event(token, initiator, paymaster, 4_000),
+            transfer_event(other_token, initiator, paymaster, 100),
+            transfer_event(L2_BASE_TOKEN_ADDRESS, paymaster, BOOTLOADER_ADDRESS, 1_000),
+            // Value transfers must not be counted as a part of the fee.
+            transfer_event(L2_BASE_TOKEN_ADDRESS, initiator, paymaster, 50),
+            transfer_event(L2_BASE_TOKEN_ADDRESS, BOOTLOADER_ADDRESS, paymaster, 400),
+        ];
+        let preview = preview_paymaster_fee(&tx, Fee::default(), &execution_result(events));
+        assert_eq!(preview.paymaster, Some(paymaster));
+        assert_eq!(preview.paymaster_charged, 600.into());
+        assert_eq!(preview.initiator_charged, 0.into());
+        assert_eq!(
+            preview.approval_based,
+            Some(ApprovalBasedFlowPreview {
+                token,
+                min_allowance: 5_000.into(),
+                token_charged: 4_000.into(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn preview_is_based_on_execution_with_estimated_fee() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let paymaster = Address::repeat_byte(0x22);
+        // Emulates a paymaster paying the maximum fee during validation and receiving a half of it back as a refund.
+        let execute = move |tx: &Transaction, _: &BlockArgs| {
+            let ExecuteTransactionCommon::L2(common_data) = &tx.common_data else {
+                panic!("unexpected transaction: {tx:?}");
+            };
+            let max_fee = common_data.fee.gas_limit * common_data.fee.max_fee_per_gas;
+            let max_fee = max_fee.as_u64();
+            execution_result(vec![
+                transfer_event(
+                    L2_BASE_TOKEN_ADDRESS,
+                    paymaster,
+                    BOOTLOADER_ADDRESS,
+                    max_fee,
+                ),
+                transfer_event(
+                    L2_BASE_TOKEN_ADDRESS,
+                    BOOTLOADER_ADDRESS,
+                    paymaster,
+                    max_fee / 2,
+                ),
+            ])
+        };
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_tx_responses_with_logs(execute);
+        tx_executor.set_call_responses_with_logs(execute);
+        let (tx_sender, _) =
+            create_test_tx_sender(pool, L2ChainId::default(), tx_executor.into()).await;
+
+        let mut tx = create_l2_transaction(10, 100);
+        tx.common_data.paymaster_params = PaymasterParams {
+            paymaster,
+            paymaster_input: vec![],
+        };
+        let estimate = tx_sender
+            .estimate_paymaster_fee(tx, 1.2, 1_000)
+            .await
+            .unwrap();
+
+        let max_fee = estimate.fee.gas_limit * estimate.fee.max_fee_per_gas;
+        assert_eq!(estimate.paymaster, Some(paymaster));
+        assert_eq!(estimate.paymaster_charged, max_fee - max_fee / 2);
+        assert_eq!(estimate.initiator_charged, 0.into());
+    }
+}
@@ -254,6 +254,11 @@ impl TxSink for TxProxy {
         Ok(L2TxSubmissionResult::Proxied)
     }
 
+    fn persists_pending_txs(&self) -> bool {
+        // Pending transactions are stored on the main node, which checks transaction replacements.
+        false
+    }
+
     async fn lookup_pending_nonce(
         &self,
         account_address: Address,
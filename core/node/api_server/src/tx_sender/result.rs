@@ -1,5 +1,6 @@
 use multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
 use thiserror::Error;
+use zksync_mempool::EvictionReason;
 use zksync_types::{l2::error::TxCheckError, U256};
 use zksync_web3_decl::error::EnrichedClientError;
 
@@ -14,6 +15,17 @@ pub enum SubmitTxError {
     NonceIsTooLow(u32, u32, u32),
     #[error("insertion of another transaction with the same nonce is in progress")]
     InsertionInProgress,
+    #[error(
+        "replacement transaction underpriced. max fee per gas must be at least {0}; \
+        max fee per gas and max priority fee per gas must be bumped by at least {1}%"
+    )]
+    ReplacementUnderpriced(U256, u32),
+    #[error("no pending transaction with nonce {0} to cancel")]
+    NoTransactionToCancel(u32),
+    #[error("cancelling transaction must be a zero-value transfer to the sender without calldata")]
+    InvalidCancellationTx,
+    #[error("transaction rejected by mempool policy: {0}")]
+    MempoolPolicy(EvictionReason),
     #[error("{0}")]
     IncorrectTx(#[from] TxCheckError),
     #[error("insufficient funds for gas + value. balance: {0}, fee: {1}, value: {2}")]
@@ -83,6 +95,15 @@ impl SubmitTxError {
             Self::NonceIsTooHigh(_, _, _) => "nonce-is-too-high",
             Self::NonceIsTooLow(_, _, _) => "nonce-is-too-low",
             Self::InsertionInProgress => "insertion-in-progress",
+            Self::ReplacementUnderpriced(_, _) => "replacement-underpriced",
+            Self::NoTransactionToCancel(_) => "no-transaction-to-cancel",
+            Self::InvalidCancellationTx => "invalid-cancellation-tx",
+            Self::MempoolPolicy(reason) => match reason {
+                EvictionReason::TooManyAccountTxs => "too-many-account-txs",
+                EvictionReason::NonceGapTooLarge => "nonce-gap-too-large",
+                EvictionReason::Expired => "mempool-tx-expired",
+                EvictionReason::LowFee => "mempool-low-fee",
+            },
             Self::IncorrectTx(_) => "incorrect-tx",
             Self::NotEnoughBalanceForFeeValue(_, _, _) => "not-enough-balance-for-fee",
             Self::ExecutionReverted(_, _) => "execution-reverted",
@@ -2,6 +2,7 @@
 
 use assert_matches::assert_matches;
 use multivm::interface::ExecutionResult;
+use zksync_mempool::EvictionReason;
 use zksync_node_fee_model::MockBatchFeeParamsProvider;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l2_block, create_l2_transaction, prepare_recovery_snapshot};
@@ -158,3 +159,177 @@ async fn submitting_tx_requires_one_connection() {
         .unwrap()
         .expect("transaction is not persisted");
 }
+
+fn modify_tx(tx: &L2Tx, modify: impl FnOnce(&mut L2Tx)) -> L2Tx {
+    let mut tx = tx.clone();
+    modify(&mut tx);
+    // Changing transaction fields invalidates its signature, but it's OK since the transaction executor is mocked.
+    tx.set_input(H256::random().0.to_vec(), H256::random());
+    tx
+}
+
+#[tokio::test]
+async fn replacing_and_cancelling_tx() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l2_chain_id = L2ChainId::default();
+    let fee_input = MockBatchFeeParamsProvider::default()
+        .get_batch_fee_input_scaled(1.0, 1.0)
+        .await
+        .unwrap();
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let tx = create_l2_transaction(base_fee, gas_per_pubdata);
+    let initiator = tx.initiator_account();
+
+    let balance_key = storage_key_for_eth_balance(&initiator);
+    let storage_log = StorageLog::new_write_log(balance_key, u256_to_h256(U256::one() << 64));
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(L2BlockNumber(0), &[(H256::zero(), vec![storage_log])])
+        .await
+        .unwrap();
+    drop(storage);
+
+    let mut tx_executor = MockTransactionExecutor::default();
+    tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+    let (tx_sender, _) = create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor.into()).await;
+    let min_bump_percent = tx_sender.0.sender_config.replacement_fee_bump_percent;
+    assert_eq!(min_bump_percent, 10);
+
+    let submission_result = tx_sender.submit_tx(tx.clone()).await.unwrap();
+    assert_matches!(submission_result.0, L2TxSubmissionResult::Added);
+
+    let underpriced_tx = modify_tx(&tx, |tx| {
+        tx.common_data.fee.max_fee_per_gas = (base_fee * 105 / 100).into();
+    });
+    let err = tx_sender.submit_tx(underpriced_tx).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::ReplacementUnderpriced(min_fee, 10)
+            if min_fee == tx.common_data.fee.min_replacement_fee_per_gas(10)
+    );
+
+    let replacement_tx = modify_tx(&tx, |tx| {
+        tx.common_data.fee.max_fee_per_gas = (base_fee * 11 / 10 + 1).into();
+    });
+    let submission_result = tx_sender.submit_tx(replacement_tx.clone()).await.unwrap();
+    assert_matches!(submission_result.0, L2TxSubmissionResult::Replaced);
+
+    let invalid_cancellation_tx = modify_tx(&replacement_tx, |tx| {
+        tx.common_data.fee.max_fee_per_gas *= 2;
+        tx.execute.value = 1.into();
+    });
+    let err = tx_sender
+        .cancel_tx(invalid_cancellation_tx)
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::InvalidCancellationTx);
+
+    let cancellation_tx = modify_tx(&replacement_tx, |tx| {
+        tx.common_data.fee.max_fee_per_gas *= 2;
+        tx.execute.contract_address = initiator;
+    });
+    let missing_tx_cancellation = modify_tx(&cancellation_tx, |tx| {
+        tx.common_data.nonce = Nonce(1);
+    });
+    let err = tx_sender
+        .cancel_tx(missing_tx_cancellation)
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::NoTransactionToCancel(1));
+
+    tx_sender.cancel_tx(cancellation_tx.clone()).await.unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    for replaced_tx in [&tx, &replacement_tx] {
+        let replaced_tx = storage
+            .transactions_web3_dal()
+            .get_transaction_by_hash(replaced_tx.hash(), l2_chain_id)
+            .await
+            .unwrap();
+        assert!(replaced_tx.is_none(), "{replaced_tx:?}");
+    }
+    let pending_tx = storage
+        .transactions_web3_dal()
+        .get_pending_l2_tx_fee(initiator, Nonce(0))
+        .await
+        .unwrap();
+    assert_eq!(pending_tx.unwrap().0, cancellation_tx.hash());
+}
+
+#[tokio::test]
+async fn enforcing_mempool_limits_for_account() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l2_chain_id = L2ChainId::default();
+    let fee_input = MockBatchFeeParamsProvider::default()
+        .get_batch_fee_input_scaled(1.0, 1.0)
+        .await
+        .unwrap();
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let tx = create_l2_transaction(base_fee, gas_per_pubdata);
+    let initiator = tx.initiator_account();
+
+    let balance_key = storage_key_for_eth_balance(&initiator);
+    let storage_log = StorageLog::new_write_log(balance_key, u256_to_h256(U256::one() << 64));
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(L2BlockNumber(0), &[(H256::zero(), vec![storage_log])])
+        .await
+        .unwrap();
+    drop(storage);
+
+    let mut tx_executor = MockTransactionExecutor::default();
+    tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+    let (mut tx_sender, _) =
+        create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor.into()).await;
+    Arc::get_mut(&mut tx_sender.0)
+        .unwrap()
+        .sender_config
+        .mempool_eviction_policy = EvictionPolicy {
+        max_txs_per_account: Some(3),
+        max_nonce_gap: Some(2),
+        ..EvictionPolicy::default()
+    };
+
+    let tx_with_nonce = |nonce| {
+        modify_tx(&tx, |tx| {
+            tx.common_data.nonce = Nonce(nonce);
+        })
+    };
+    for nonce in 0..2 {
+        let submission_result = tx_sender.submit_tx(tx_with_nonce(nonce)).await.unwrap();
+        assert_matches!(submission_result.0, L2TxSubmissionResult::Added);
+    }
+    let err = tx_sender.submit_tx(tx_with_nonce(3)).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::MempoolPolicy(EvictionReason::NonceGapTooLarge)
+    );
+    let pending_tx = tx_with_nonce(2);
+    let submission_result = tx_sender.submit_tx(pending_tx.clone()).await.unwrap();
+    assert_matches!(submission_result.0, L2TxSubmissionResult::Added);
+
+    let err = tx_sender.submit_tx(tx_with_nonce(3)).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::MempoolPolicy(EvictionReason::TooManyAccountTxs)
+    );
+    // Replacing a pending transaction is still allowed.
+    let replacement_tx = modify_tx(&pending_tx, |tx| {
+        tx.common_data.fee.max_fee_per_gas *= 2;
+        tx.common_data.fee.max_priority_fee_per_gas *= 2;
+    });
+    let submission_result = tx_sender.submit_tx(replacement_tx).await.unwrap();
+    assert_matches!(submission_result.0, L2TxSubmissionResult::Replaced);
+}
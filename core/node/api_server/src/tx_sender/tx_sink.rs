@@ -30,6 +30,13 @@ pub trait TxSink: std::fmt::Debug + Send + Sync + 'static {
         execution_metrics: TransactionExecutionMetrics,
     ) -> Result<L2TxSubmissionResult, SubmitTxError>;
 
+    /// Returns whether submitted transactions are persisted as pending transactions visible via the replica pool.
+    /// If not (e.g., if transactions are proxied to the main node), checks involving pending transactions
+    /// are left to the node receiving the transactions. By default, returns `true`.
+    fn persists_pending_txs(&self) -> bool {
+        true
+    }
+
     /// Attempts to look up the pending nonce for the account in the sink-specific storage.
     /// By default, returns `Ok(None)`.
     async fn lookup_pending_nonce(
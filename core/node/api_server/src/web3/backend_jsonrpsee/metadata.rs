@@ -3,7 +3,7 @@
 use std::{cell::RefCell, mem, sync::Arc, time::Instant};
 
 use thread_local::ThreadLocal;
-use zksync_types::api;
+use zksync_types::{api, L1BatchNumber};
 use zksync_web3_decl::{
     error::Web3Error,
     jsonrpsee::{helpers::MethodResponseResult, MethodResponse},
@@ -22,6 +22,9 @@ pub(crate) struct MethodMetadata {
     pub block_id: Option<api::BlockId>,
     /// Difference between the latest block number and the requested block ID.
     pub block_diff: Option<u32>,
+    /// L1 batch containing all data returned by the call. Set only for calls returning data that cannot change
+    /// once the batch is executed; used for response caching.
+    pub response_l1_batch: Option<L1BatchNumber>,
     /// Did this call return an app-level error?
     pub has_app_error: bool,
 }
@@ -33,6 +36,7 @@ impl MethodMetadata {
             started_at: Instant::now(),
             block_id: None,
             block_diff: None,
+            response_l1_batch: None,
             has_app_error: false,
         }
     }
@@ -88,6 +92,24 @@ impl MethodTracer {
         }
     }
 
+    /// Sets the L1 batch containing all data returned by the current JSON-RPC method call. Once this batch is executed,
+    /// the response may be cached (provided that the method is cacheable).
+    ///
+    /// This should be called inside JSON-RPC method handlers; otherwise, this method is a no-op.
+    pub fn set_response_l1_batch(&self, l1_batch_number: L1BatchNumber) {
+        let cell = self.inner.get_or_default();
+        if let Some(metadata) = &mut *cell.borrow_mut() {
+            metadata.response_l1_batch = Some(l1_batch_number);
+        }
+    }
+
+    /// Returns the L1 batch set via [`Self::set_response_l1_batch()`] for the current JSON-RPC method call.
+    pub(super) fn response_l1_batch(&self) -> Option<L1BatchNumber> {
+        let cell = self.inner.get_or_default();
+        let metadata = cell.borrow();
+        metadata.as_ref()?.response_l1_batch
+    }
+
     pub(super) fn new_call<'a>(
         self: &Arc<Self>,
         name: &'static str,
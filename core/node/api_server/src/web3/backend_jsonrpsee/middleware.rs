@@ -1,40 +1,53 @@
 use std::{
     cell::RefCell,
-    collections::HashSet,
-    future::Future,
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    fmt,
+    future::{self, Future},
+    net::{IpAddr, SocketAddr},
     num::NonZeroU32,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Weak},
     task::{Context, Poll},
     time::{Duration, Instant},
 };
 
+use anyhow::Context as _;
+use axum::extract::connect_info::Connected;
 use governor::{
-    clock::DefaultClock,
+    clock::{Clock, DefaultClock, QuantaInstant},
     middleware::NoOpMiddleware,
-    state::{InMemoryState, NotKeyed},
-    Quota, RateLimiter,
+    state::{keyed::DashMapStateStore, InMemoryState, NotKeyed},
+    NegativeMultiDecision, NotUntil, Quota, RateLimiter,
 };
+use http::header::HeaderName;
 use once_cell::sync::OnceCell;
 use pin_project_lite::pin_project;
 use rand::{rngs::SmallRng, RngCore, SeedableRng};
 use tokio::sync::watch;
 use tracing::instrument::{Instrument, Instrumented};
 use vise::{
-    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram, Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, GaugeGuard, Histogram,
+    Metrics,
 };
+use zksync_config::configs::api::{RpcApiKeys, RpcMethodWeights, Web3JsonRpcConfig};
 use zksync_web3_decl::jsonrpsee::{
+    helpers::MethodResponseResult,
     server::middleware::rpc::{layer::ResponseFuture, RpcServiceT},
-    types::{error::ErrorCode, ErrorObject, Request},
+    types::{error::ErrorCode, ErrorObject, Id, Request, ResponsePayload},
     MethodResponse,
 };
 
 use super::metadata::{MethodCall, MethodTracer};
-use crate::web3::metrics::{ObservedRpcParams, API_METRICS};
+use crate::web3::{
+    metrics::{ObservedRpcParams, API_METRICS},
+    response_cache::{ResponseCache, ResponseCacheKey},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "transport", rename_all = "snake_case")]
 pub(crate) enum Transport {
+    Http,
     Ws,
 }
 
@@ -43,38 +56,552 @@ pub(crate) enum Transport {
 struct LimitMiddlewareMetrics {
     /// Number of rate-limited requests.
     rate_limited: Family<Transport, Counter>,
+    /// Number of requests rejected by per-client rate limits.
+    client_rate_limited: Family<Transport, Counter>,
     /// Size of batch requests.
     #[metrics(buckets = Buckets::exponential(1.0..=512.0, 2.0))]
     size: Family<Transport, Histogram<usize>>,
     /// Number of requests rejected by the limiter.
     rejected: Family<Transport, Counter>,
+    /// Number of clients currently tracked by per-client rate limits.
+    tracked_clients: Gauge<usize>,
 }
 
 #[vise::register]
 static METRICS: vise::Global<LimitMiddlewareMetrics> = vise::Global::new();
 
-/// A rate-limiting middleware.
+tokio::task_local! {
+    /// Client that has sent the HTTP request currently being handled. Set by [`ClientIdService`].
+    static CURRENT_CLIENT_ID: Option<ClientId>;
+}
+
+/// Address of the peer that has sent an HTTP request. Inserted into request extensions by [`PeerAddrService`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PeerAddr(pub SocketAddr);
+
+/// [`hyper`]-compatible "make service" creating a [`PeerAddrService`] for each accepted connection.
 ///
-/// `jsonrpsee` will allocate the instance of this struct once per session.
-pub(crate) struct LimitMiddleware<S> {
+/// `jsonrpsee` doesn't expose peer addresses to HTTP middleware, so servers requiring them are served via this wrapper.
+#[derive(Clone)]
+pub(crate) struct PeerAddrMakeService<F> {
+    make_service: F,
+}
+
+impl<F> fmt::Debug for PeerAddrMakeService<F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("PeerAddrMakeService")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> PeerAddrMakeService<F> {
+    pub fn new(make_service: F) -> Self {
+        Self { make_service }
+    }
+}
+
+impl<'a, T, F, S> tower::Service<&'a T> for PeerAddrMakeService<F>
+where
+    SocketAddr: Connected<&'a T>,
+    F: Fn() -> S,
+{
+    type Response = PeerAddrService<S>;
+    type Error = Infallible;
+    type Future = future::Ready<Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, target: &'a T) -> Self::Future {
+        future::ready(Ok(PeerAddrService {
+            inner: (self.make_service)(),
+            peer_addr: SocketAddr::connect_info(target),
+        }))
+    }
+}
+
+/// HTTP service inserting [`PeerAddr`] into request extensions.
+#[derive(Debug, Clone)]
+pub(crate) struct PeerAddrService<S> {
     inner: S,
-    rate_limiter: Option<RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>,
+    peer_addr: SocketAddr,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for PeerAddrService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        request.extensions_mut().insert(PeerAddr(self.peer_addr));
+        self.inner.call(request)
+    }
+}
+
+/// Interval between removing stale client state from per-client rate limits.
+const CLIENT_RATE_LIMITER_PRUNING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Per-client rate limiting settings shared by the JSON-RPC and GraphQL API servers.
+///
+/// Clients are identified by the header set with [`Self::with_api_key_header()`] if it contains a known API key
+/// (see [`Self::with_api_keys()`]), or by their IP address otherwise.
+#[derive(Debug, Clone)]
+pub struct ClientRateLimits {
+    requests_per_minute_limit: Option<NonZeroU32>,
+    api_key_header: Option<String>,
+    api_keys: RpcApiKeys,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl Default for ClientRateLimits {
+    fn default() -> Self {
+        Self {
+            requests_per_minute_limit: None,
+            api_key_header: None,
+            api_keys: RpcApiKeys::empty(),
+            trusted_proxies: vec![],
+        }
+    }
+}
+
+impl From<&Web3JsonRpcConfig> for ClientRateLimits {
+    fn from(config: &Web3JsonRpcConfig) -> Self {
+        Self {
+            requests_per_minute_limit: config.client_requests_per_minute_limit,
+            api_key_header: config.api_key_header.clone(),
+            api_keys: config.api_keys.clone(),
+            trusted_proxies: config.trusted_proxies.clone(),
+        }
+    }
+}
+
+impl ClientRateLimits {
+    /// Sets the limit on the total weight of requests per minute for a single client. If not set,
+    /// per-client rate limiting is disabled.
+    pub fn with_requests_per_minute_limit(mut self, requests_per_minute_limit: NonZeroU32) -> Self {
+        self.requests_per_minute_limit = Some(requests_per_minute_limit);
+        self
+    }
+
+    /// Sets the name of the HTTP header containing client API keys.
+    pub fn with_api_key_header(mut self, api_key_header: String) -> Self {
+        self.api_key_header = Some(api_key_header);
+        self
+    }
+
+    /// Sets known client API keys together with their per-client quotas. Requests with unknown API keys
+    /// are attributed to clients by their IP address.
+    pub fn with_api_keys(mut self, api_keys: RpcApiKeys) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    /// Sets IP addresses of reverse proxies trusted to report client IP addresses in `X-Forwarded-For` / `X-Real-IP`
+    /// headers. Without trusted proxies, clients are identified by the peer IP address.
+    pub fn with_trusted_proxies(mut self, trusted_proxies: Vec<IpAddr>) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    /// Creates a layer identifying clients and spawns a task periodically pruning the state of the created rate limiter.
+    /// Returns `None` if per-client rate limiting is disabled. `server_name` is only used for logging.
+    pub(crate) fn build_layer(&self, server_name: &str) -> anyhow::Result<Option<ClientIdLayer>> {
+        let Some(limit) = self.requests_per_minute_limit else {
+            if !self.api_keys.is_empty() {
+                tracing::warn!(
+                    "API keys are configured for {server_name} server, but per-client rate limiting is disabled"
+                );
+            }
+            return Ok(None);
+        };
+        let api_key_header = self
+            .api_key_header
+            .as_deref()
+            .map(HeaderName::try_from)
+            .transpose()
+            .context("invalid API key header name")?;
+        let trusted_proxies = self
+            .trusted_proxies
+            .iter()
+            .map(IpAddr::to_canonical)
+            .collect();
+
+        let limiter = Arc::new(ClientRateLimiter::new(limit, &self.api_keys));
+        tokio::spawn(ClientRateLimiter::prune_periodically(
+            Arc::downgrade(&limiter),
+            CLIENT_RATE_LIMITER_PRUNING_INTERVAL,
+        ));
+        Ok(Some(ClientIdLayer::new(
+            limiter,
+            api_key_header,
+            trusted_proxies,
+        )))
+    }
+}
+
+/// Identifier of a client used for per-client rate limiting.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ClientId {
+    /// One of the API keys known to [`ClientRateLimiter`].
+    ApiKey(Arc<str>),
+    Ip(IpAddr),
+}
+
+/// HTTP-level middleware identifying clients for per-client rate limiting.
+///
+/// `jsonrpsee` instantiates RPC-level middleware synchronously when handling an HTTP request (for WebSocket connections,
+/// this is the upgrade request), so the client identified by this middleware is available to [`LimitLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct ClientIdLayer {
+    inner: Arc<ClientIdentifier>,
+}
+
+impl ClientIdLayer {
+    fn new(
+        limiter: Arc<ClientRateLimiter>,
+        api_key_header: Option<HeaderName>,
+        trusted_proxies: HashSet<IpAddr>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(ClientIdentifier {
+                limiter,
+                api_key_header,
+                trusted_proxies,
+            }),
+        }
+    }
+
+    pub fn limiter(&self) -> &Arc<ClientRateLimiter> {
+        &self.inner.limiter
+    }
+
+    /// Identifies the client sending an HTTP request and charges the request against the client's rate limit.
+    /// Used by HTTP servers not based on `jsonrpsee` (e.g., the GraphQL server); requests must be tagged
+    /// with [`PeerAddr`], e.g. by serving them via [`PeerAddrMakeService`].
+    pub fn check_request<B>(
+        &self,
+        request: &http::Request<B>,
+        weight: NonZeroU32,
+    ) -> Result<(), RateLimitError> {
+        let Some(client_id) = self.inner.identify(request) else {
+            return Ok(());
+        };
+        let result = self.inner.limiter.check(&client_id, weight);
+        if result.is_err() {
+            METRICS.client_rate_limited[&Transport::Http].inc();
+        }
+        result
+    }
+}
+
+impl<S> tower::Layer<S> for ClientIdLayer {
+    type Service = ClientIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientIdService {
+            inner,
+            identifier: self.inner.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ClientIdentifier {
+    limiter: Arc<ClientRateLimiter>,
+    api_key_header: Option<HeaderName>,
+    trusted_proxies: HashSet<IpAddr>,
+}
+
+impl ClientIdentifier {
+    const FORWARDED_FOR_HEADER: &'static str = "x-forwarded-for";
+    const REAL_IP_HEADER: &'static str = "x-real-ip";
+
+    /// Identifies the client by a known API key, or by its IP address. Unknown API keys are ignored, so that clients
+    /// cannot obtain fresh quotas by rotating keys. Forwarding headers are only trusted if the peer is a trusted proxy.
+    fn identify<B>(&self, request: &http::Request<B>) -> Option<ClientId> {
+        let headers = request.headers();
+        let api_key = self
+            .api_key_header
+            .as_ref()
+            .and_then(|header| headers.get(header)?.to_str().ok())
+            .and_then(|key| self.limiter.known_api_key(key.trim()));
+        if let Some(api_key) = api_key {
+            return Some(ClientId::ApiKey(api_key));
+        }
+
+        let peer_ip = request
+            .extensions()
+            .get::<PeerAddr>()?
+            .0
+            .ip()
+            .to_canonical();
+        if !self.trusted_proxies.contains(&peer_ip) {
+            return Some(ClientId::Ip(peer_ip));
+        }
+        // The rightmost `X-Forwarded-For` entry is the one appended by the trusted proxy; other entries
+        // are supplied by the client and cannot be trusted.
+        let forwarded_ip = headers
+            .get_all(Self::FORWARDED_FOR_HEADER)
+            .iter()
+            .last()
+            .and_then(|value| value.to_str().ok()?.rsplit(',').next()?.trim().parse().ok());
+        let ip = forwarded_ip.or_else(|| {
+            let value = headers.get(Self::REAL_IP_HEADER)?.to_str().ok()?;
+            value.trim().parse().ok()
+        });
+        Some(ClientId::Ip(ip.unwrap_or(peer_ip)))
+    }
+}
+
+/// HTTP service produced by [`ClientIdLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct ClientIdService<S> {
+    inner: S,
+    identifier: Arc<ClientIdentifier>,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for ClientIdService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let client_id = self.identifier.identify(&request);
+        CURRENT_CLIENT_ID.sync_scope(client_id, || self.inner.call(request))
+    }
+}
+
+type DirectRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>;
+
+/// Error returned by a rate limit check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RateLimitError {
+    /// The request can be retried after the specified delay.
+    Exceeded { retry_after: Duration },
+    /// The request weight exceeds the quota, so the request will never be allowed.
+    InsufficientCapacity,
+}
+
+impl RateLimitError {
+    fn new(err: NegativeMultiDecision<NotUntil<QuantaInstant>>, clock: &DefaultClock) -> Self {
+        match err {
+            NegativeMultiDecision::BatchNonConforming(_, not_until) => Self::Exceeded {
+                retry_after: not_until.wait_time_from(clock.now()),
+            },
+            NegativeMultiDecision::InsufficientCapacity(_) => Self::InsufficientCapacity,
+        }
+    }
+
+    fn into_response(self, id: Id<'_>) -> MethodResponse {
+        let code =
+            ErrorCode::ServerError(http::StatusCode::TOO_MANY_REQUESTS.as_u16().into()).code();
+        let err = match self {
+            Self::Exceeded { retry_after } => {
+                // Round the delay up so that a client retrying after the hinted delay is not rate-limited again.
+                let retry_after_ms =
+                    u64::try_from(retry_after.as_micros().div_ceil(1_000)).unwrap_or(u64::MAX);
+                ErrorObject::owned(
+                    code,
+                    "Too many requests",
+                    Some(serde_json::json!({ "retryAfterMs": retry_after_ms })),
+                )
+            }
+            Self::InsufficientCapacity => {
+                ErrorObject::owned(code, "Request weight exceeds rate limit quota", None::<()>)
+            }
+        };
+        MethodResponse::error(id, err)
+    }
+}
+
+/// Rate limits shared among all requests from the same client, regardless of the transport or connection.
+pub(crate) struct ClientRateLimiter {
+    ip_limiter: RateLimiter<IpAddr, DashMapStateStore<IpAddr>, DefaultClock, NoOpMiddleware>,
+    api_key_limiters: HashMap<Arc<str>, DirectRateLimiter>,
+    clock: DefaultClock,
+}
+
+impl fmt::Debug for ClientRateLimiter {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ClientRateLimiter")
+            .field("tracked_ips", &self.ip_limiter.len())
+            .field("api_keys", &self.api_key_limiters.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ClientRateLimiter {
+    /// Creates a limiter with the specified quota for clients identified by IP address, and quotas for known API keys.
+    pub fn new(requests_per_minute_limit: NonZeroU32, api_keys: &RpcApiKeys) -> Self {
+        let clock = DefaultClock::default();
+        let api_key_limiters = api_keys
+            .iter()
+            .map(|(key, quota)| {
+                let limiter = RateLimiter::direct_with_clock(Quota::per_minute(quota), &clock);
+                (Arc::from(key), limiter)
+            })
+            .collect();
+        Self {
+            ip_limiter: RateLimiter::dashmap_with_clock(
+                Quota::per_minute(requests_per_minute_limit),
+                &clock,
+            ),
+            api_key_limiters,
+            clock,
+        }
+    }
+
+    fn known_api_key(&self, key: &str) -> Option<Arc<str>> {
+        let (key, _) = self.api_key_limiters.get_key_value(key)?;
+        Some(key.clone())
+    }
+
+    fn check(&self, client_id: &ClientId, weight: NonZeroU32) -> Result<(), RateLimitError> {
+        let result = match client_id {
+            ClientId::ApiKey(key) => {
+                let Some(limiter) = self.api_key_limiters.get(key) else {
+                    // Shouldn't happen; API keys are checked by `ClientIdentifier`.
+                    return Ok(());
+                };
+                limiter.check_n(weight)
+            }
+            ClientId::Ip(ip) => self.ip_limiter.check_key_n(ip, weight),
+        };
+        result.map_err(|err| RateLimitError::new(err, &self.clock))
+    }
+
+    /// Periodically removes state for IP addresses that have their quotas fully replenished, so that memory consumption
+    /// doesn't grow unboundedly. Terminates once the limiter is dropped.
+    pub async fn prune_periodically(this: Weak<Self>, interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+            let Some(this) = this.upgrade() else {
+                return;
+            };
+            this.ip_limiter.retain_recent();
+            this.ip_limiter.shrink_to_fit();
+            METRICS.tracked_clients.set(this.ip_limiter.len());
+        }
+    }
+}
+
+/// [`tower`] middleware layer that wraps services into [`LimitMiddleware`].
+#[derive(Debug, Clone)]
+pub(crate) struct LimitLayer {
     transport: Transport,
-    _guard: GaugeGuard,
+    connection_requests_per_minute_limit: Option<NonZeroU32>,
+    client_rate_limiter: Option<Arc<ClientRateLimiter>>,
+    method_weights: Arc<RpcMethodWeights>,
+    clock: DefaultClock,
 }
 
-impl<S> LimitMiddleware<S> {
-    pub(crate) fn new(inner: S, requests_per_minute_limit: Option<NonZeroU32>) -> Self {
+impl LimitLayer {
+    pub fn http(method_weights: Arc<RpcMethodWeights>) -> Self {
+        Self::new(Transport::Http, method_weights)
+    }
+
+    pub fn ws(method_weights: Arc<RpcMethodWeights>) -> Self {
+        Self::new(Transport::Ws, method_weights)
+    }
+
+    fn new(transport: Transport, method_weights: Arc<RpcMethodWeights>) -> Self {
         Self {
+            transport,
+            connection_requests_per_minute_limit: None,
+            client_rate_limiter: None,
+            method_weights,
+            clock: DefaultClock::default(),
+        }
+    }
+
+    /// Sets the limit on the total weight of requests per minute for a single WebSocket connection.
+    /// Ignored for HTTP, which doesn't have long-lived sessions.
+    pub fn with_connection_limit(mut self, requests_per_minute_limit: Option<NonZeroU32>) -> Self {
+        self.connection_requests_per_minute_limit = requests_per_minute_limit;
+        self
+    }
+
+    pub fn with_client_limiter(mut self, limiter: Option<Arc<ClientRateLimiter>>) -> Self {
+        self.client_rate_limiter = limiter;
+        self
+    }
+}
+
+impl<S> tower::Layer<S> for LimitLayer {
+    type Service = LimitMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        let is_ws = matches!(self.transport, Transport::Ws);
+        let rate_limiter = self
+            .connection_requests_per_minute_limit
+            .filter(|_| is_ws)
+            .map(|limit| RateLimiter::direct_with_clock(Quota::per_minute(limit), &self.clock));
+        // Clients can only be unidentified if the server isn't wrapped in `PeerAddrMakeService`.
+        let client_id = CURRENT_CLIENT_ID.try_with(Clone::clone).ok().flatten();
+        let client_limit = self.client_rate_limiter.clone().zip(client_id);
+
+        LimitMiddleware {
             inner,
-            rate_limiter: requests_per_minute_limit
-                .map(|limit| RateLimiter::direct(Quota::per_minute(limit))),
-            transport: Transport::Ws,
-            _guard: API_METRICS.ws_open_sessions.inc_guard(1),
+            rate_limiter,
+            client_limit,
+            method_weights: self.method_weights.clone(),
+            clock: self.clock.clone(),
+            transport: self.transport,
+            _guard: is_ws.then(|| API_METRICS.ws_open_sessions.inc_guard(1)),
         }
     }
 }
 
+/// A rate-limiting middleware. Each request is charged with the weight of the called method
+/// against the per-connection limit (only for WebSocket connections) and the per-client limit.
+///
+/// `jsonrpsee` will allocate the instance of this struct once per session for WebSocket connections,
+/// and once per request for HTTP.
+pub(crate) struct LimitMiddleware<S> {
+    inner: S,
+    rate_limiter: Option<DirectRateLimiter>,
+    client_limit: Option<(Arc<ClientRateLimiter>, ClientId)>,
+    method_weights: Arc<RpcMethodWeights>,
+    clock: DefaultClock,
+    transport: Transport,
+    _guard: Option<GaugeGuard>,
+}
+
+impl<S> LimitMiddleware<S> {
+    fn check(&self, weight: NonZeroU32) -> Result<(), RateLimitError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            if let Err(err) = rate_limiter.check_n(weight) {
+                METRICS.rate_limited[&self.transport].inc();
+                return Err(RateLimitError::new(err, &self.clock));
+            }
+        }
+        if let Some((client_limiter, client_id)) = &self.client_limit {
+            if let Err(err) = client_limiter.check(client_id, weight) {
+                METRICS.client_rate_limited[&self.transport].inc();
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<'a, S> RpcServiceT<'a> for LimitMiddleware<S>
 where
     S: Send + Sync + RpcServiceT<'a>,
@@ -82,26 +609,133 @@ where
     type Future = ResponseFuture<S::Future>;
 
     fn call(&self, request: Request<'a>) -> Self::Future {
-        if let Some(rate_limiter) = &self.rate_limiter {
-            let num_requests = NonZeroU32::MIN; // 1 request, no batches possible
+        // Batches are split into separate requests by `jsonrpsee`, so each request is charged separately.
+        let weight = self.method_weights.get(request.method_name());
+        match self.check(weight) {
+            Ok(()) => ResponseFuture::future(self.inner.call(request)),
+            Err(err) => ResponseFuture::ready(err.into_response(request.id)),
+        }
+    }
+}
 
-            // Note: if required, we can extract data on rate limiting from the error.
-            if rate_limiter.check_n(num_requests).is_err() {
-                METRICS.rate_limited[&self.transport].inc();
+/// [`tower`] middleware layer that wraps services into [`CacheMiddleware`].
+#[derive(Debug, Clone)]
+pub(crate) struct CacheLayer {
+    cache: ResponseCache,
+    method_tracer: Arc<MethodTracer>,
+}
 
-                let rp = MethodResponse::error(
-                    request.id,
-                    ErrorObject::borrowed(
-                        ErrorCode::ServerError(http::StatusCode::TOO_MANY_REQUESTS.as_u16().into())
-                            .code(),
-                        "Too many requests",
-                        None,
-                    ),
+impl CacheLayer {
+    pub fn new(cache: ResponseCache, method_tracer: Arc<MethodTracer>) -> Self {
+        Self {
+            cache,
+            method_tracer,
+        }
+    }
+}
+
+impl<S> tower::Layer<S> for CacheLayer {
+    type Service = CacheMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CacheMiddleware {
+            inner,
+            cache: self.cache.clone(),
+            method_tracer: self.method_tracer.clone(),
+        }
+    }
+}
+
+/// RPC-level middleware serving responses for cacheable methods from [`ResponseCache`]. On a cache miss, the response
+/// is inserted into the cache if the method handler has reported the L1 batch of the returned data via [`MethodTracer`].
+/// Consequently, this middleware must be placed after [`MetadataMiddleware`].
+#[derive(Debug)]
+pub(crate) struct CacheMiddleware<S> {
+    inner: S,
+    cache: ResponseCache,
+    method_tracer: Arc<MethodTracer>,
+}
+
+impl<'a, S> RpcServiceT<'a> for CacheMiddleware<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<WithResponseCaching<S::Future>>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let key = ResponseCacheKey::new(request.method_name(), request.params.as_deref());
+        let Some(key) = key else {
+            return ResponseFuture::future(WithResponseCaching::new(
+                self.inner.call(request),
+                None,
+            ));
+        };
+
+        if let Some(cached) = self.cache.get(&key) {
+            let payload = ResponsePayload::result(cached.as_raw());
+            // The response size was checked when the response was initially computed.
+            return ResponseFuture::ready(MethodResponse::response(
+                request.id,
+                payload,
+                usize::MAX,
+            ));
+        }
+        let insertion = PendingInsertion {
+            cache: self.cache.clone(),
+            generation: self.cache.generation(),
+            key,
+            method_tracer: self.method_tracer.clone(),
+        };
+        ResponseFuture::future(WithResponseCaching::new(
+            self.inner.call(request),
+            Some(insertion),
+        ))
+    }
+}
+
+#[derive(Debug)]
+struct PendingInsertion {
+    cache: ResponseCache,
+    generation: u64,
+    key: ResponseCacheKey,
+    method_tracer: Arc<MethodTracer>,
+}
+
+pin_project! {
+    #[derive(Debug)]
+    pub(crate) struct WithResponseCaching<F> {
+        #[pin]
+        inner: F,
+        insertion: Option<PendingInsertion>,
+    }
+}
+
+impl<F> WithResponseCaching<F> {
+    fn new(inner: F, insertion: Option<PendingInsertion>) -> Self {
+        Self { inner, insertion }
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for WithResponseCaching<F> {
+    type Output = MethodResponse;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let projection = self.project();
+        let response = futures::ready!(projection.inner.poll(cx));
+        // Method metadata is still set as current since this future is polled by `WithMethodCall`.
+        if let Some(insertion) = projection.insertion.take() {
+            let is_success = matches!(response.success_or_error, MethodResponseResult::Success);
+            let l1_batch_number = insertion.method_tracer.response_l1_batch();
+            if let Some(l1_batch_number) = l1_batch_number.filter(|_| is_success) {
+                insertion.cache.insert(
+                    insertion.key,
+                    insertion.generation,
+                    l1_batch_number,
+                    &response.result,
                 );
-                return ResponseFuture::ready(rp);
             }
         }
-        ResponseFuture::future(self.inner.call(request))
+        Poll::Ready(response)
     }
 }
 
@@ -334,6 +968,7 @@ where
 mod tests {
     use std::time::Duration;
 
+    use assert_matches::assert_matches;
     use rand::{thread_rng, Rng};
     use test_casing::{test_casing, Product};
     use zksync_types::api;
@@ -398,6 +1033,91 @@ mod tests {
         }
     }
 
+    fn test_identifier(api_key_header: Option<HeaderName>) -> ClientIdentifier {
+        let api_keys = RpcApiKeys::from_iter([("key", NonZeroU32::new(5).unwrap())]);
+        ClientIdentifier {
+            limiter: Arc::new(ClientRateLimiter::new(
+                NonZeroU32::new(5).unwrap(),
+                &api_keys,
+            )),
+            api_key_header,
+            trusted_proxies: HashSet::from([IpAddr::from([127, 0, 0, 1])]),
+        }
+    }
+
+    fn test_request(peer_ip: [u8; 4], headers: &[(&str, &str)]) -> http::Request<()> {
+        let mut request = http::Request::new(());
+        for &(name, value) in headers {
+            request
+                .headers_mut()
+                .append(HeaderName::from_static(name), value.parse().unwrap());
+        }
+        let peer_addr = SocketAddr::from((peer_ip, 12345));
+        request.extensions_mut().insert(PeerAddr(peer_addr));
+        request
+    }
+
+    #[test]
+    fn identifying_clients() {
+        let identifier = test_identifier(Some(HeaderName::from_static("x-api-key")));
+        let client_ip = IpAddr::from([10, 0, 0, 1]);
+        let request = test_request([10, 0, 0, 1], &[]);
+        assert_eq!(identifier.identify(&request), Some(ClientId::Ip(client_ip)));
+
+        // Forwarding headers are ignored if the peer is not a trusted proxy.
+        let request = test_request(
+            [10, 0, 0, 1],
+            &[("x-forwarded-for", "1.2.3.4"), ("x-real-ip", "1.2.3.4")],
+        );
+        assert_eq!(identifier.identify(&request), Some(ClientId::Ip(client_ip)));
+
+        // Only the rightmost `X-Forwarded-For` entry is trusted.
+        let request = test_request([127, 0, 0, 1], &[("x-forwarded-for", "1.2.3.4, 10.0.0.2")]);
+        let expected_ip = IpAddr::from([10, 0, 0, 2]);
+        assert_eq!(
+            identifier.identify(&request),
+            Some(ClientId::Ip(expected_ip))
+        );
+        let request = test_request([127, 0, 0, 1], &[("x-real-ip", "10.0.0.2")]);
+        assert_eq!(
+            identifier.identify(&request),
+            Some(ClientId::Ip(expected_ip))
+        );
+
+        // Unknown API keys are ignored.
+        let request = test_request([10, 0, 0, 1], &[("x-api-key", "unknown")]);
+        assert_eq!(identifier.identify(&request), Some(ClientId::Ip(client_ip)));
+        let request = test_request([10, 0, 0, 1], &[("x-api-key", "key")]);
+        assert_eq!(
+            identifier.identify(&request),
+            Some(ClientId::ApiKey("key".into()))
+        );
+
+        // The API key header is ignored if it's not configured.
+        let identifier = test_identifier(None);
+        assert_eq!(identifier.identify(&request), Some(ClientId::Ip(client_ip)));
+    }
+
+    #[test]
+    fn client_rate_limiter_basics() {
+        let api_keys = RpcApiKeys::from_iter([("test", NonZeroU32::new(5).unwrap())]);
+        let limiter = ClientRateLimiter::new(NonZeroU32::new(5).unwrap(), &api_keys);
+        let client = ClientId::ApiKey(limiter.known_api_key("test").unwrap());
+        assert_eq!(limiter.known_api_key("unknown"), None);
+        let other_client = ClientId::Ip(IpAddr::from([10, 0, 0, 1]));
+        let weight = NonZeroU32::new(3).unwrap();
+
+        limiter.check(&client, weight).unwrap();
+        let err = limiter.check(&client, weight).unwrap_err();
+        assert_matches!(err, RateLimitError::Exceeded { retry_after } if retry_after > Duration::ZERO);
+        limiter.check(&other_client, weight).unwrap();
+
+        let err = limiter
+            .check(&other_client, NonZeroU32::new(6).unwrap())
+            .unwrap_err();
+        assert_eq!(err, RateLimitError::InsufficientCapacity);
+    }
+
     #[tokio::test]
     async fn traffic_tracker_basics() {
         let traffic_tracker = TrafficTracker::default();
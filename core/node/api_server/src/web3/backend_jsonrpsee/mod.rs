@@ -7,10 +7,14 @@ use zksync_web3_decl::{
     jsonrpsee::types::{error::ErrorCode, ErrorObjectOwned},
 };
 
+pub use self::middleware::ClientRateLimits;
+#[cfg(test)]
+pub(crate) use self::middleware::PeerAddr;
 pub(crate) use self::{
     metadata::{MethodMetadata, MethodTracer},
     middleware::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, ShutdownMiddleware, TrafficTracker,
+        CacheLayer, ClientIdLayer, CorrelationMiddleware, LimitLayer, MetadataLayer,
+        PeerAddrMakeService, RateLimitError, ShutdownMiddleware, TrafficTracker,
     },
 };
 use crate::tx_sender::SubmitTxError;
@@ -25,9 +29,14 @@ impl MethodTracer {
     pub(crate) fn map_err(&self, err: Web3Error) -> ErrorObjectOwned {
         self.observe_error(&err);
 
-        let data = match &err {
-            Web3Error::SubmitTransactionError(_, data) => Some(format!("0x{}", hex::encode(data))),
-            Web3Error::ProxyError(_) => Some("0x".to_owned()),
+        let data: Option<serde_json::Value> = match &err {
+            Web3Error::SubmitTransactionError(_, data) => {
+                Some(format!("0x{}", hex::encode(data)).into())
+            }
+            Web3Error::ProxyError(_) => Some("0x".into()),
+            Web3Error::LogsLimitExceeded(.., cursor) => {
+                Some(serde_json::json!({ "cursor": cursor }))
+            }
             _ => None,
         };
         let code = match err {
@@ -39,7 +48,9 @@ impl MethodTracer {
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFilterBlockHash
-            | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
+            | Web3Error::InvalidStateOverride(_)
+            | Web3Error::InvalidSimulationRequest(_)
+            | Web3Error::LogsLimitExceeded(..) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _)
             | Web3Error::SerializationError(_)
             | Web3Error::ProxyError(_) => 3,
@@ -3,5 +3,6 @@ pub mod en;
 pub mod eth;
 pub mod net;
 pub mod snapshots;
+pub mod txpool;
 pub mod web3;
 pub mod zks;
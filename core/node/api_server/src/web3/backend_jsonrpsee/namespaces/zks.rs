@@ -3,8 +3,9 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use zksync_types::{
     api::{
-        ApiStorageLog, BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Log, Proof,
-        ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        ApiStorageLog, BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Log,
+        LogsCursor, LogsPage, PaymasterFeeEstimate, Proof, ProtocolVersion,
+        TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -15,7 +16,7 @@ use zksync_types::{
 use zksync_web3_decl::{
     jsonrpsee::core::{async_trait, RpcResult},
     namespaces::ZksNamespaceServer,
-    types::Token,
+    types::{Filter, Token},
 };
 
 use crate::web3::ZksNamespace;
@@ -28,6 +29,15 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn estimate_fee_with_paymaster(
+        &self,
+        req: CallRequest,
+    ) -> RpcResult<PaymasterFeeEstimate> {
+        self.estimate_fee_with_paymaster_impl(req)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn estimate_gas_l1_to_l2(&self, req: CallRequest) -> RpcResult<U256> {
         self.estimate_l1_to_l2_gas_impl(req)
             .await
@@ -215,4 +225,20 @@ impl ZksNamespaceServer for ZksNamespace {
             })
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogsCursor>,
+    ) -> RpcResult<LogsPage> {
+        self.get_logs_paginated_impl(filter, cursor)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn cancel_transaction(&self, tx_bytes: Bytes) -> RpcResult<H256> {
+        self.cancel_transaction_impl(tx_bytes)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
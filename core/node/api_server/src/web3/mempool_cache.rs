@@ -1,18 +1,24 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use chrono::NaiveDateTime;
 use tokio::sync::{watch, RwLock};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_state::SequentialCache;
-use zksync_types::H256;
+use zksync_types::{Address, Nonce, H256};
 
 use super::metrics::MEMPOOL_CACHE_METRICS;
 
+/// Cached transaction hash together with the initiator address and nonce for L2 transactions.
+type CachedTx = (H256, Option<(Address, Nonce)>);
+
 /// Used for `eth_newPendingTransactionFilter` requests on API servers
 /// Stores all transactions accepted by the mempool and provides a way to query all that are newer than a given timestamp.
 /// Updates the cache based on interval passed in the constructor
+///
+/// If an L2 transaction is replaced by another transaction with the same initiator and nonce, the replaced transaction
+/// is removed from the cache once the replacement is loaded.
 #[derive(Debug, Clone)]
-pub struct MempoolCache(Arc<RwLock<SequentialCache<NaiveDateTime, H256>>>);
+pub struct MempoolCache(Arc<RwLock<SequentialCache<NaiveDateTime, CachedTx>>>);
 
 /// `INITIAL_LOOKBEHIND` is the period of time for which the cache is initially populated.
 const INITIAL_LOOKBEHIND: Duration = Duration::from_secs(120);
@@ -44,19 +50,47 @@ impl MempoolCache {
         &self,
         after: NaiveDateTime,
     ) -> Option<Vec<(NaiveDateTime, H256)>> {
-        self.0.read().await.query(after)
+        let txs = self.0.read().await.query(after)?;
+        Some(
+            txs.into_iter()
+                .map(|(received_at, (hash, _))| (received_at, hash))
+                .collect(),
+        )
+    }
+
+    /// Returns all cached transactions in the order they were received, together with the initiator address
+    /// and nonce for L2 transactions. Some of the returned transactions may be already included into a block.
+    pub async fn get_txs(&self) -> Vec<CachedTx> {
+        self.0.read().await.values()
     }
 }
 
 /// Task updating [`MempoolCache`]. Should be spawned as a Tokio task (exactly one task for the cache).
 #[derive(Debug)]
 pub struct MempoolCacheUpdateTask {
-    cache: Arc<RwLock<SequentialCache<NaiveDateTime, H256>>>,
+    cache: Arc<RwLock<SequentialCache<NaiveDateTime, CachedTx>>>,
     connection_pool: ConnectionPool<Core>,
     update_interval: Duration,
 }
 
 impl MempoolCacheUpdateTask {
+    /// Inserts new transactions into the cache, removing transactions replaced by them.
+    fn insert_txs(
+        cache: &mut SequentialCache<NaiveDateTime, CachedTx>,
+        txs: Vec<(NaiveDateTime, CachedTx)>,
+    ) -> anyhow::Result<()> {
+        let replacing_txs: HashSet<_> = txs
+            .iter()
+            .filter_map(|(_, (_, initiator_and_nonce))| *initiator_and_nonce)
+            .collect();
+        if !replacing_txs.is_empty() {
+            cache.retain(|_, (_, initiator_and_nonce)| {
+                initiator_and_nonce.map_or(true, |key| !replacing_txs.contains(&key))
+            });
+        }
+        cache.insert(txs)
+    }
+
     pub async fn run(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         loop {
             if *stop_receiver.borrow() {
@@ -77,14 +111,67 @@ impl MempoolCacheUpdateTask {
             let mut connection = self.connection_pool.connection_tagged("api").await?;
             let txs = connection
                 .transactions_web3_dal()
-                .get_pending_txs_hashes_after(last_timestamp, None)
+                .get_pending_txs_after(last_timestamp, None)
                 .await?;
             drop(connection);
             latency.observe();
             MEMPOOL_CACHE_METRICS.tx_batch_size.observe(txs.len());
 
-            self.cache.write().await.insert(txs)?;
+            let txs: Vec<_> = txs
+                .into_iter()
+                .map(|(received_at, hash, initiator_and_nonce)| {
+                    (received_at, (hash, initiator_and_nonce))
+                })
+                .collect();
+            Self::insert_txs(&mut *self.cache.write().await, txs)?;
             tokio::time::sleep(self.update_interval).await;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(secs: i64) -> NaiveDateTime {
+        chrono::DateTime::from_timestamp(secs, 0)
+            .unwrap()
+            .naive_utc()
+    }
+
+    #[test]
+    fn replaced_txs_are_removed_from_cache() {
+        let mut cache = SequentialCache::new("replaced_txs_are_removed_from_cache", 100);
+        let account = Address::repeat_byte(1);
+        let l1_tx = (timestamp(1), (H256::repeat_byte(1), None));
+        let l2_tx = (
+            timestamp(2),
+            (H256::repeat_byte(2), Some((account, Nonce(0)))),
+        );
+        let other_l2_tx = (
+            timestamp(3),
+            (H256::repeat_byte(3), Some((account, Nonce(1)))),
+        );
+        MempoolCacheUpdateTask::insert_txs(&mut cache, vec![l1_tx, l2_tx, other_l2_tx]).unwrap();
+
+        let replacing_tx = (
+            timestamp(4),
+            (H256::repeat_byte(4), Some((account, Nonce(0)))),
+        );
+        MempoolCacheUpdateTask::insert_txs(&mut cache, vec![replacing_tx]).unwrap();
+        let hashes: Vec<_> = cache
+            .query(timestamp(0))
+            .unwrap()
+            .into_iter()
+            .map(|(_, (hash, _))| hash)
+            .collect();
+        assert_eq!(
+            hashes,
+            [
+                H256::repeat_byte(1),
+                H256::repeat_byte(3),
+                H256::repeat_byte(4)
+            ]
+        );
+    }
+}
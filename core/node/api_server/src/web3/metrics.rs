@@ -168,6 +168,8 @@ enum Web3ErrorKind {
     FilterNotFound,
     LogsLimitExceeded,
     InvalidFilterBlockHash,
+    InvalidStateOverride,
+    InvalidSimulationRequest,
     TreeApiUnavailable,
     Internal,
 }
@@ -184,6 +186,8 @@ impl Web3ErrorKind {
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::InvalidStateOverride(_) => Self::InvalidStateOverride,
+            Web3Error::InvalidSimulationRequest(_) => Self::InvalidSimulationRequest,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
             Web3Error::InternalError(_) | Web3Error::MethodNotImplemented => Self::Internal,
         }
@@ -223,6 +227,7 @@ struct Web3ConfigLabels {
     #[metrics(unit = Unit::Bytes)]
     response_body_size_limit: Option<usize>,
     websocket_requests_per_minute_limit: Option<u32>,
+    client_requests_per_minute_limit: Option<u32>,
 }
 
 /// Roughly exponential buckets for the `web3_call_block_diff` metric. The distribution should be skewed towards lower values.
@@ -291,6 +296,9 @@ impl ApiMetrics {
             websocket_requests_per_minute_limit: optional
                 .websocket_requests_per_minute_limit
                 .map(Into::into),
+            client_requests_per_minute_limit: optional
+                .client_requests_per_minute_limit
+                .map(Into::into),
         };
         tracing::info!("{transport:?} Web3 server is configured with options: {config_labels:?}");
         if self.web3_info[&transport].set(config_labels).is_err() {
@@ -418,7 +426,11 @@ pub(crate) static API_METRICS: vise::Global<ApiMetrics> = vise::Global::new();
 pub enum SubscriptionType {
     Blocks,
     Txs,
+    /// Pending transactions subscriptions requiring full transaction data (i.e., filtered or full-transaction ones).
+    FullTxs,
     Logs,
+    Syncing,
+    L1Batches,
 }
 
 #[derive(Debug, Metrics)]
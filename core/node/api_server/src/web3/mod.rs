@@ -9,38 +9,42 @@ use tokio::{
     task::JoinHandle,
 };
 use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
-use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides};
+use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides, RpcMethodWeights};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
+use zksync_state_keeper::MempoolGuard;
 use zksync_types::L2BlockNumber;
 use zksync_web3_decl::{
     jsonrpsee::{
         server::{
-            middleware::rpc::either::Either, BatchRequestConfig, RpcServiceBuilder, ServerBuilder,
+            middleware::rpc::either::Either, stop_channel, BatchRequestConfig, RpcServiceBuilder,
+            ServerBuilder,
         },
         MethodCallback, Methods, RpcModule,
     },
     namespaces::{
         DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
-        NetNamespaceServer, SnapshotsNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
+        NetNamespaceServer, SnapshotsNamespaceServer, TxpoolNamespaceServer, Web3NamespaceServer,
+        ZksNamespaceServer,
     },
     types::Filter,
 };
 
 use self::{
     backend_jsonrpsee::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
-        TrafficTracker,
+        CacheLayer, ClientRateLimits, CorrelationMiddleware, LimitLayer, MetadataLayer,
+        MethodTracer, PeerAddrMakeService, ShutdownMiddleware, TrafficTracker,
     },
     mempool_cache::MempoolCache,
     metrics::API_METRICS,
     namespaces::{
-        DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace, Web3Namespace,
-        ZksNamespace,
+        DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace,
+        TxpoolNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
+    response_cache::ResponseCache,
     state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
 };
 use crate::{
@@ -53,6 +57,7 @@ pub mod mempool_cache;
 pub(super) mod metrics;
 pub mod namespaces;
 mod pubsub;
+pub mod response_cache;
 pub mod state;
 pub mod testonly;
 #[cfg(test)]
@@ -97,6 +102,7 @@ pub enum Namespace {
     En,
     Pubsub,
     Snapshots,
+    Txpool,
 }
 
 impl Namespace {
@@ -129,8 +135,12 @@ struct OptionalApiParams {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<MaxResponseSize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    client_rate_limits: ClientRateLimits,
+    method_weights: Option<RpcMethodWeights>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
+    mempool: Option<MempoolGuard>,
+    response_cache: Option<ResponseCache>,
     extended_tracing: bool,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
@@ -246,6 +256,18 @@ impl ApiBuilder {
         self
     }
 
+    /// Sets per-client rate limits. Request weights are determined by [`Self::with_method_weights()`].
+    pub fn with_client_rate_limits(mut self, client_rate_limits: ClientRateLimits) -> Self {
+        self.optional.client_rate_limits = client_rate_limits;
+        self
+    }
+
+    /// Sets relative RPC method weights used for rate limiting.
+    pub fn with_method_weights(mut self, method_weights: RpcMethodWeights) -> Self {
+        self.optional.method_weights = Some(method_weights);
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.optional.sync_state = Some(sync_state);
         self
@@ -277,6 +299,17 @@ impl ApiBuilder {
         self
     }
 
+    /// Provides access to the state keeper mempool. Only available on the main node; used by the `txpool` namespace.
+    pub fn with_mempool(mut self, mempool: MempoolGuard) -> Self {
+        self.optional.mempool = Some(mempool);
+        self
+    }
+
+    pub fn with_response_cache(mut self, cache: ResponseCache) -> Self {
+        self.optional.response_cache = Some(cache);
+        self
+    }
+
     pub fn with_extended_tracing(mut self, extended_tracing: bool) -> Self {
         self.optional.extended_tracing = extended_tracing;
         self
@@ -360,6 +393,7 @@ impl ApiServer {
             api_config: self.config,
             start_info,
             mempool_cache: self.optional.mempool_cache,
+            mempool: self.optional.mempool,
             last_sealed_l2_block,
             tree_api: self.optional.tree_api,
         })
@@ -406,9 +440,13 @@ impl ApiServer {
                 .context("cannot merge en namespace")?;
         }
         if namespaces.contains(&Namespace::Snapshots) {
-            rpc.merge(SnapshotsNamespace::new(rpc_state).into_rpc())
+            rpc.merge(SnapshotsNamespace::new(rpc_state.clone()).into_rpc())
                 .context("cannot merge snapshots namespace")?;
         }
+        if namespaces.contains(&Namespace::Txpool) {
+            rpc.merge(TxpoolNamespace::new(rpc_state).into_rpc())
+                .context("cannot merge txpool namespace")?;
+        }
         Ok(rpc)
     }
 
@@ -484,10 +522,13 @@ impl ApiServer {
         let pub_sub = if matches!(transport, ApiTransport::WebSocket(_))
             && self.namespaces.contains(&Namespace::Pubsub)
         {
-            let mut pub_sub = EthSubscribe::new();
+            let mut pub_sub = EthSubscribe::new(self.config.l2_chain_id);
             if let Some(sender) = &self.optional.pub_sub_events_sender {
                 pub_sub.set_events_sender(sender.clone());
             }
+            if let Some(sync_state) = &self.optional.sync_state {
+                pub_sub.set_sync_state(sync_state.clone());
+            }
 
             tasks.extend(pub_sub.spawn_notifiers(
                 self.pool.clone(),
@@ -623,6 +664,20 @@ impl ApiServer {
                 (u32::MAX, MaxResponseSizeOverrides::empty())
             };
         let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let client_id_layer = self
+            .optional
+            .client_rate_limits
+            .build_layer(&format!("{transport_str} API"))?;
+        let client_rate_limiter = client_id_layer
+            .as_ref()
+            .map(|layer| layer.limiter().clone());
+        let method_weights = Arc::new(
+            self.optional
+                .method_weights
+                .clone()
+                .unwrap_or_else(RpcMethodWeights::empty),
+        );
+        let response_cache = self.optional.response_cache.clone();
         let subscriptions_limit = self.optional.subscriptions_limit;
         let vm_barrier = self.optional.vm_barrier.clone();
         let health_updater = self.health_updater.clone();
@@ -661,7 +716,9 @@ impl ApiServer {
         // Assemble server middleware.
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
-            .option_layer(cors);
+            .option_layer(cors)
+            // Must be the innermost layer; see `ClientIdLayer` docs for details.
+            .option_layer(client_id_layer);
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -669,12 +726,24 @@ impl ApiServer {
             .flatten()
             .unwrap_or(5_000);
 
+        let cache_layer = response_cache.map(|cache| CacheLayer::new(cache, method_tracer.clone()));
         let metadata_layer = MetadataLayer::new(registered_method_names, method_tracer);
         let metadata_layer = if extended_tracing {
             Either::Left(metadata_layer.with_param_tracing())
         } else {
             Either::Right(metadata_layer)
         };
+        let limit_layer = if is_http {
+            // HTTP has no per-connection limits, so the layer is only necessary for per-client limits.
+            client_rate_limiter
+                .is_some()
+                .then(|| LimitLayer::http(method_weights).with_client_limiter(client_rate_limiter))
+        } else {
+            let layer = LimitLayer::ws(method_weights)
+                .with_connection_limit(websocket_requests_per_minute_limit)
+                .with_client_limiter(client_rate_limiter);
+            Some(layer)
+        };
         let traffic_tracker = TrafficTracker::default();
         let traffic_tracker_for_middleware = traffic_tracker.clone();
 
@@ -689,12 +758,10 @@ impl ApiServer {
                 extended_tracing.then(|| tower::layer::layer_fn(CorrelationMiddleware::new)),
             )
             .layer(metadata_layer)
-            // We want to capture limit middleware errors with `metadata_layer`; hence, `LimitMiddleware` is placed after it.
-            .option_layer((!is_http).then(|| {
-                tower::layer::layer_fn(move |svc| {
-                    LimitMiddleware::new(svc, websocket_requests_per_minute_limit)
-                })
-            }));
+            // We want to capture limit middleware errors with `metadata_layer`; hence, `LimitLayer` is placed after it.
+            .option_layer(limit_layer)
+            // Cached responses are served after rate limiting; `CacheLayer` relies on method metadata, so it's placed after `metadata_layer`.
+            .option_layer(cache_layer);
 
         let server_builder = ServerBuilder::default()
             .max_connections(max_connections as u32)
@@ -703,7 +770,35 @@ impl ApiServer {
             .set_batch_request_config(batch_request_config)
             .set_rpc_middleware(rpc_middleware);
 
-        let (local_addr, server_handle) = if is_http {
+        let (local_addr, server_handle) = if client_rate_limiter.is_some() {
+            // Per-client rate limiting requires peer addresses, which are not exposed by the `jsonrpsee` server
+            // to HTTP middleware. Hence, we serve the `jsonrpsee` service ourselves, tagging requests with peer addresses.
+            let server_builder = if is_http {
+                server_builder.http_only()
+            } else {
+                server_builder.set_id_provider(EthSubscriptionIdProvider)
+            };
+            let service_builder = server_builder.to_service_builder();
+            let methods = Methods::from(rpc);
+            let (stop_handle, server_handle) = stop_channel();
+            let service_stop_handle = stop_handle.clone();
+            let make_service = PeerAddrMakeService::new(move || {
+                service_builder
+                    .clone()
+                    .build(methods.clone(), service_stop_handle.clone())
+            });
+            let server = axum::Server::try_bind(&addr)
+                .with_context(|| format!("Failed binding {transport_str} JSON-RPC server"))?
+                .serve(make_service);
+            let local_addr = server.local_addr();
+            let server = server.with_graceful_shutdown(stop_handle.shutdown());
+            tokio::spawn(async move {
+                if let Err(err) = server.await {
+                    tracing::error!("{transport_str} JSON-RPC server failed: {err}");
+                }
+            });
+            (Ok(local_addr), server_handle)
+        } else if is_http {
             // HTTP-specific settings
             let server = server_builder
                 .http_only()
@@ -1,23 +1,35 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Context as _;
-use multivm::{interface::ExecutionResult, vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT};
+use multivm::{
+    interface::ExecutionResult,
+    tracers::prestate_tracer::{Account, State},
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+};
 use once_cell::sync::OnceCell;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{storage_logs_dal::DbStorageLog, Connection, Core, CoreDal, DalError};
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, DebugTraceResult, PrestateAccount, PrestateTrace,
+        ResultDebugCall, ResultDebugTrace, StructLogTrace, StructLoggerConfig, SupportedTracers,
+        TracerConfig, ValidationTrace,
+    },
     debug_flat_call::{flatten_debug_calls, DebugCallFlat},
     fee_model::BatchFeeInput,
+    get_code_key, get_nonce_key,
     l2::L2Tx,
     transaction_request::CallRequest,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
     vm_trace::Call,
-    AccountTreeId, H256,
+    web3::Bytes,
+    AccountTreeId, Address, L2BlockNumber, StorageKey, H256,
 };
+use zksync_utils::{h256_to_u256, u256_to_h256};
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
-    execution_sandbox::{ApiTracer, TxSharedArgs},
+    execution_sandbox::{ApiTracer, BlockArgs, TxExecutionArgs, TxSharedArgs},
     tx_sender::{ApiContracts, TxSenderConfig},
     web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
 };
@@ -57,41 +69,108 @@ impl DebugNamespace {
         &self.state.current_method
     }
 
-    pub async fn debug_trace_block_impl(
+    /// Converts a stored call trace to the output of the call-based tracer.
+    fn map_call_trace(
+        mut call: DebugCall,
+        tracer: SupportedTracers,
+        only_top_call: bool,
+    ) -> DebugTraceResult {
+        if only_top_call {
+            call.calls = vec![];
+        }
+        if tracer == SupportedTracers::FlatCallTracer {
+            let calls = vec![ResultDebugCall { result: call }];
+            DebugTraceResult::FlatCallTrace(flatten_debug_calls(calls))
+        } else {
+            DebugTraceResult::CallTrace(call)
+        }
+    }
+
+    /// Resolves the traced block. Returns `None` for the pending block.
+    async fn resolve_traced_block(
         &self,
+        connection: &mut Connection<'_, Core>,
         block_id: BlockId,
-        options: Option<TracerConfig>,
-    ) -> Result<Vec<ResultDebugCall>, Web3Error> {
+    ) -> Result<Option<L2BlockNumber>, Web3Error> {
         self.current_method().set_block_id(block_id);
         if matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
             // See `EthNamespace::get_block_impl()` for an explanation why this check is needed.
-            return Ok(vec![]);
+            return Ok(None);
         }
 
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
-        let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = self.state.resolve_block(connection, block_id).await?;
         self.current_method()
             .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+        Ok(Some(block_number))
+    }
 
+    async fn load_block_call_traces(
+        connection: &mut Connection<'_, Core>,
+        block_number: L2BlockNumber,
+    ) -> Result<Vec<DebugCall>, Web3Error> {
         let call_traces = connection
             .blocks_web3_dal()
             .get_traces_for_l2_block(block_number)
             .await
             .map_err(DalError::generalize)?;
-        let call_trace = call_traces
-            .into_iter()
-            .map(|call_trace| {
-                let mut result: DebugCall = call_trace.into();
-                if only_top_call {
-                    result.calls = vec![];
+        Ok(call_traces.into_iter().map(DebugCall::from).collect())
+    }
+
+    pub async fn debug_trace_block_impl(
+        &self,
+        block_id: BlockId,
+        options: Option<TracerConfig>,
+    ) -> Result<Vec<ResultDebugTrace>, Web3Error> {
+        let TracerConfig {
+            tracer,
+            tracer_config,
+            ..
+        } = options.unwrap_or_default();
+        let mut connection = self.state.acquire_connection().await?;
+        let Some(block_number) = self.resolve_traced_block(&mut connection, block_id).await? else {
+            return Ok(vec![]);
+        };
+
+        let results = match tracer {
+            SupportedTracers::CallTracer | SupportedTracers::FlatCallTracer => {
+                Self::load_block_call_traces(&mut connection, block_number)
+                    .await?
+                    .into_iter()
+                    .map(|call| Self::map_call_trace(call, tracer, tracer_config.only_top_call))
+                    .collect()
+            }
+            SupportedTracers::PrestateTracer => {
+                let block_logs = connection
+                    .storage_logs_dal()
+                    .get_storage_logs_for_l2_block(block_number)
+                    .await
+                    .map_err(DalError::generalize)?;
+                let transactions = connection
+                    .transactions_web3_dal()
+                    .get_raw_l2_block_transactions(block_number)
+                    .await
+                    .map_err(DalError::generalize)?;
+
+                let mut results = Vec::with_capacity(transactions.len());
+                for tx in transactions {
+                    let trace = Self::stored_tx_prestate(
+                        &mut connection,
+                        &block_logs,
+                        tx.hash(),
+                        tracer_config.diff_mode,
+                    )
+                    .await?;
+                    results.push(DebugTraceResult::PrestateTrace(trace));
                 }
-                ResultDebugCall { result }
-            })
-            .collect();
-        Ok(call_trace)
+                results
+            }
+            // Re-executing all transactions in a block is prohibitively expensive with the opcode-level tracing.
+            SupportedTracers::StructLogger => return Err(Web3Error::MethodNotImplemented),
+        };
+        Ok(results
+            .into_iter()
+            .map(|result| ResultDebugTrace { result })
+            .collect())
     }
 
     pub async fn debug_trace_block_flat_impl(
@@ -99,7 +178,24 @@ impl DebugNamespace {
         block_id: BlockId,
         options: Option<TracerConfig>,
     ) -> Result<Vec<DebugCallFlat>, Web3Error> {
-        let call_trace = self.debug_trace_block_impl(block_id, options).await?;
+        let only_top_call = options
+            .map(|options| options.tracer_config.only_top_call)
+            .unwrap_or(false);
+        let mut connection = self.state.acquire_connection().await?;
+        let Some(block_number) = self.resolve_traced_block(&mut connection, block_id).await? else {
+            return Ok(vec![]);
+        };
+
+        let call_trace = Self::load_block_call_traces(&mut connection, block_number)
+            .await?
+            .into_iter()
+            .map(|mut result| {
+                if only_top_call {
+                    result.calls = vec![];
+                }
+                ResultDebugCall { result }
+            })
+            .collect();
         let call_trace_flat = flatten_debug_calls(call_trace);
         Ok(call_trace_flat)
     }
@@ -108,37 +204,340 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<Option<DebugCall>, Web3Error> {
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+    ) -> Result<Option<DebugTraceResult>, Web3Error> {
+        let TracerConfig {
+            tracer,
+            tracer_config,
+            struct_logger_config,
+        } = options.unwrap_or_default();
         let mut connection = self.state.acquire_connection().await?;
+
+        if tracer == SupportedTracers::StructLogger {
+            let trace = self
+                .trace_stored_tx_struct_logs(connection, tx_hash, struct_logger_config)
+                .await?;
+            return Ok(trace.map(DebugTraceResult::StructLogs));
+        }
+
+        if tracer == SupportedTracers::PrestateTracer {
+            let tx = connection
+                .transactions_web3_dal()
+                .get_transaction_by_hash(tx_hash, self.sender_config().chain_id)
+                .await
+                .map_err(DalError::generalize)?;
+            let Some(block_number) = tx.and_then(|tx| tx.block_number) else {
+                return Ok(None);
+            };
+            let block_number = L2BlockNumber(block_number.as_u32());
+            let block_logs = connection
+                .storage_logs_dal()
+                .get_storage_logs_for_l2_block(block_number)
+                .await
+                .map_err(DalError::generalize)?;
+            let trace = Self::stored_tx_prestate(
+                &mut connection,
+                &block_logs,
+                tx_hash,
+                tracer_config.diff_mode,
+            )
+            .await?;
+            return Ok(Some(DebugTraceResult::PrestateTrace(trace)));
+        }
+
         let call_trace = connection
             .transactions_dal()
             .get_call_trace(tx_hash)
             .await
             .map_err(DalError::generalize)?;
         Ok(call_trace.map(|call_trace| {
-            let mut result: DebugCall = call_trace.into();
-            if only_top_call {
-                result.calls = vec![];
-            }
-            result
+            Self::map_call_trace(call_trace.into(), tracer, tracer_config.only_top_call)
         }))
     }
 
+    /// Re-executes a stored L2 transaction with the `structLogger` tracer attached. The transaction is executed
+    /// on top of the state after the preceding L2 block, after replaying the preceding transactions
+    /// in the same L2 block.
+    async fn trace_stored_tx_struct_logs(
+        &self,
+        mut connection: Connection<'_, Core>,
+        tx_hash: H256,
+        config: StructLoggerConfig,
+    ) -> Result<Option<StructLogTrace>, Web3Error> {
+        let tx = connection
+            .transactions_web3_dal()
+            .get_transaction_by_hash(tx_hash, self.sender_config().chain_id)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(block_number) = tx.and_then(|tx| tx.block_number) else {
+            return Ok(None);
+        };
+        let block_number = L2BlockNumber(block_number.as_u32());
+        let mut block_transactions = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(tx_position) = block_transactions
+            .iter()
+            .position(|tx| tx.hash() == tx_hash)
+        else {
+            return Ok(None);
+        };
+        block_transactions.truncate(tx_position + 1);
+        // L1 and upgrade transactions cannot be executed in the `eth_call` mode, so transactions in L2 blocks
+        // containing them cannot be replayed.
+        let txs = block_transactions
+            .into_iter()
+            .map(L2Tx::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Web3Error::MethodNotImplemented)?;
+
+        let Some(parent_block) = block_number.0.checked_sub(1) else {
+            // The genesis block doesn't contain L2 transactions.
+            return Ok(None);
+        };
+        let block_header = connection
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .with_context(|| format!("L2 block #{block_number} disappeared from storage"))?;
+        let parent_block_id = BlockId::Number(BlockNumber::Number(parent_block.into()));
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, parent_block_id)
+            .await?;
+        drop(connection);
+
+        let execution_args = TxExecutionArgs::for_eth_call(
+            Some(block_header.base_fee_per_gas),
+            self.sender_config().vm_execution_cache_misses_limit,
+        );
+        let trace = self
+            .execute_with_struct_logger(txs, execution_args, block_args, config)
+            .await?;
+        Ok(Some(trace))
+    }
+
+    /// Executes transactions in the sandbox in a single L2 block with the `structLogger` tracer attached
+    /// to the last transaction.
+    async fn execute_with_struct_logger(
+        &self,
+        txs: Vec<L2Tx>,
+        execution_args: TxExecutionArgs,
+        block_args: BlockArgs,
+        config: StructLoggerConfig,
+    ) -> Result<StructLogTrace, Web3Error> {
+        let shared_args = self.shared_args().await;
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+
+        let struct_logs = Arc::new(OnceCell::default());
+        let custom_tracers = vec![ApiTracer::StructLogTracer {
+            config,
+            result: struct_logs.clone(),
+        }];
+        let executor = &self.state.tx_sender.0.executor;
+        let output = executor
+            .execute_bundle_eth_call(
+                vm_permit,
+                shared_args,
+                self.state.connection_pool.clone(),
+                execution_args,
+                txs,
+                block_args,
+                custom_tracers,
+            )
+            .await?;
+        let result = output
+            .results
+            .into_iter()
+            .last()
+            .context("no execution results for traced transaction")?;
+
+        let (failed, return_value) = match result.result {
+            ExecutionResult::Success { output } => (false, output),
+            ExecutionResult::Revert { output } => (true, output.encoded_data()),
+            ExecutionResult::Halt { .. } => (true, vec![]),
+        };
+        // We had only one copy of Arc this arc is already dropped it's safe to unwrap
+        let struct_logs = Arc::try_unwrap(struct_logs)
+            .unwrap()
+            .take()
+            .unwrap_or_default();
+        Ok(StructLogTrace {
+            gas: result.statistics.gas_used.into(),
+            failed,
+            return_value: return_value.into(),
+            struct_logs,
+        })
+    }
+
+    /// Reconstructs the `prestateTracer` output for a stored transaction from the storage logs of its L2 block.
+    /// Since storage reads are not persisted, only accounts and storage slots modified by the transaction
+    /// are reported, even if the diff mode is off.
+    async fn stored_tx_prestate(
+        connection: &mut Connection<'_, Core>,
+        block_logs: &[DbStorageLog],
+        tx_hash: H256,
+        diff_mode: bool,
+    ) -> Result<PrestateTrace, Web3Error> {
+        let tx_logs: Vec<_> = block_logs
+            .iter()
+            .filter(|log| log.tx_hash == tx_hash)
+            .collect();
+        let Some(first_log) = tx_logs.first() else {
+            // Storage logs may be missing if the L2 block was pruned.
+            return Ok(if diff_mode {
+                PrestateTrace::Diff {
+                    pre: HashMap::new(),
+                    post: HashMap::new(),
+                }
+            } else {
+                PrestateTrace::Prestate(HashMap::new())
+            });
+        };
+
+        let mut modified_slots = HashMap::<Address, Vec<H256>>::new();
+        for log in &tx_logs {
+            let slots = modified_slots.entry(log.address).or_default();
+            if !slots.contains(&log.key) {
+                slots.push(log.key);
+            }
+        }
+        let hashed_keys: Vec<_> = modified_slots
+            .iter()
+            .flat_map(|(address, slots)| {
+                let account = AccountTreeId::new(*address);
+                let slot_keys = slots
+                    .iter()
+                    .map(move |&slot| StorageKey::new(account, slot));
+                let account_keys = [
+                    storage_key_for_eth_balance(address),
+                    get_nonce_key(address),
+                    get_code_key(address),
+                ];
+                slot_keys.chain(account_keys)
+            })
+            .map(|key| key.hashed_key())
+            .collect();
+
+        let pre_values = connection
+            .storage_logs_dal()
+            .get_storage_values_before_operation(
+                &hashed_keys,
+                first_log.l2_block_number,
+                first_log.operation_number,
+            )
+            .await
+            .map_err(DalError::generalize)?;
+        let pre_values: HashMap<_, _> = pre_values
+            .into_iter()
+            .map(|(key, value)| (key, value.unwrap_or_default()))
+            .collect();
+        // Logs are ordered by `operation_number`, so the last write for each key wins.
+        let mut post_values = pre_values.clone();
+        post_values.extend(tx_logs.iter().map(|log| (log.hashed_key, log.value)));
+
+        let mut pre = Self::collect_account_states(&modified_slots, &pre_values);
+        if !diff_mode {
+            return Ok(PrestateTrace::Prestate(
+                Self::to_api_state(connection, pre).await?,
+            ));
+        }
+
+        let mut post = Self::collect_account_states(&modified_slots, &post_values);
+        pre.retain(|address, account| post.get(address) != Some(account));
+        post.retain(|address, _| pre.contains_key(address));
+        Ok(PrestateTrace::Diff {
+            pre: Self::to_api_state(connection, pre).await?,
+            post: Self::to_api_state(connection, post).await?,
+        })
+    }
+
+    fn collect_account_states(
+        modified_slots: &HashMap<Address, Vec<H256>>,
+        values: &HashMap<H256, H256>,
+    ) -> State {
+        let value = |key: StorageKey| values.get(&key.hashed_key()).copied().unwrap_or_default();
+        modified_slots
+            .iter()
+            .map(|(address, slots)| {
+                let account_id = AccountTreeId::new(*address);
+                let storage = slots
+                    .iter()
+                    .map(|&slot| (slot, value(StorageKey::new(account_id, slot))))
+                    .collect();
+                let account = Account {
+                    balance: Some(h256_to_u256(value(storage_key_for_eth_balance(address)))),
+                    code: Some(h256_to_u256(value(get_code_key(address)))),
+                    nonce: Some(h256_to_u256(value(get_nonce_key(address)))),
+                    storage: Some(storage),
+                };
+                (*address, account)
+            })
+            .collect()
+    }
+
+    /// Converts the VM-level account states to the API format, loading bytecodes by their hashes.
+    async fn to_api_state(
+        connection: &mut Connection<'_, Core>,
+        state: State,
+    ) -> Result<HashMap<Address, PrestateAccount>, Web3Error> {
+        let mut api_state = HashMap::with_capacity(state.len());
+        for (address, account) in state {
+            let code = match account.code {
+                Some(code_hash) if !code_hash.is_zero() => connection
+                    .factory_deps_dal()
+                    .get_sealed_factory_dep(u256_to_h256(code_hash))
+                    .await
+                    .map_err(DalError::generalize)?
+                    .map(Bytes::from),
+                _ => None,
+            };
+            let account = PrestateAccount {
+                balance: account.balance,
+                nonce: account.nonce.map(|nonce| decompose_full_nonce(nonce).0),
+                code,
+                storage: account.storage,
+            };
+            api_state.insert(address, account);
+        }
+        Ok(api_state)
+    }
+
+    pub async fn debug_trace_validation_impl(
+        &self,
+        tx_bytes: Bytes,
+    ) -> Result<ValidationTrace, Web3Error> {
+        let (mut tx, hash) = self.state.parse_transaction_bytes(&tx_bytes.0)?;
+        tx.set_input(tx_bytes.0, hash);
+        self.current_method()
+            .set_block_id(BlockId::Number(BlockNumber::Pending));
+
+        let trace = self.state.tx_sender.trace_validation(tx).await?;
+        Ok(trace)
+    }
+
     pub async fn debug_trace_call_impl(
         &self,
         mut request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<DebugTraceResult, Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let TracerConfig {
+            tracer,
+            tracer_config,
+            struct_logger_config,
+        } = options.unwrap_or_default();
 
         let mut connection = self.state.acquire_connection().await?;
         let block_args = self
@@ -167,6 +566,40 @@ impl DebugNamespace {
         let call_overrides = request.get_call_overrides()?;
         let tx = L2Tx::from_request(request.into(), MAX_ENCODED_TX_SIZE)?;
 
+        let call_tracer_result = Arc::new(OnceCell::default());
+        let prestate_tracer_result = Arc::new(OnceCell::default());
+        let custom_tracers = match tracer {
+            SupportedTracers::StructLogger => {
+                let execution_args = TxExecutionArgs::for_eth_call(
+                    call_overrides.enforced_base_fee,
+                    self.sender_config().vm_execution_cache_misses_limit,
+                )
+                .with_state_override(call_overrides.state_override);
+                let trace = self
+                    .execute_with_struct_logger(
+                        vec![tx],
+                        execution_args,
+                        block_args,
+                        struct_logger_config,
+                    )
+                    .await?;
+                return Ok(DebugTraceResult::StructLogs(trace));
+            }
+            // We don't need properly trace if we only need top call
+            SupportedTracers::CallTracer | SupportedTracers::FlatCallTracer
+                if tracer_config.only_top_call =>
+            {
+                vec![]
+            }
+            SupportedTracers::CallTracer | SupportedTracers::FlatCallTracer => {
+                vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+            }
+            SupportedTracers::PrestateTracer => vec![ApiTracer::PrestateTracer {
+                diff_mode: tracer_config.diff_mode,
+                result: prestate_tracer_result.clone(),
+            }],
+        };
+
         let shared_args = self.shared_args().await;
         let vm_permit = self
             .state
@@ -176,14 +609,6 @@ impl DebugNamespace {
             .await;
         let vm_permit = vm_permit.context("cannot acquire VM permit")?;
 
-        // We don't need properly trace if we only need top call
-        let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
-            vec![]
-        } else {
-            vec![ApiTracer::CallTracer(call_tracer_result.clone())]
-        };
-
         let executor = &self.state.tx_sender.0.executor;
         let result = executor
             .execute_tx_eth_call(
@@ -209,6 +634,26 @@ impl DebugNamespace {
             }
         };
 
+        if tracer == SupportedTracers::PrestateTracer {
+            // We had only one copy of Arc this arc is already dropped it's safe to unwrap
+            let (pre, post) = Arc::try_unwrap(prestate_tracer_result)
+                .unwrap()
+                .take()
+                .unwrap_or_default();
+            let mut connection = self.state.acquire_connection().await?;
+            let trace = if tracer_config.diff_mode {
+                PrestateTrace::Diff {
+                    pre: Self::to_api_state(&mut connection, pre).await?,
+                    post: Self::to_api_state(&mut connection, post).await?,
+                }
+            } else {
+                // Outside the diff mode, the tracer only populates `post` with the original state
+                // of all touched accounts.
+                PrestateTrace::Prestate(Self::to_api_state(&mut connection, post).await?)
+            };
+            return Ok(DebugTraceResult::PrestateTrace(trace));
+        }
+
         // We had only one copy of Arc this arc is already dropped it's safe to unwrap
         let trace = Arc::try_unwrap(call_tracer_result)
             .unwrap()
@@ -223,7 +668,7 @@ impl DebugNamespace {
             revert_reason,
             trace,
         );
-        Ok(call.into())
+        Ok(Self::map_call_trace(call.into(), tracer, false))
     }
 
     async fn shared_args(&self) -> TxSharedArgs {
@@ -1,25 +1,37 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
 use anyhow::Context as _;
+use multivm::interface::ExecutionResult;
+use once_cell::sync::OnceCell;
 use zksync_dal::{CoreDal, DalError};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, GetLogsFilter, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        AccessListResult, BlockId, BlockNumber, LogsCursor, SimulatePayload, SimulatedBlock,
+        SimulatedCallError, SimulatedCallResult, StateOverride, Transaction, TransactionId,
+        TransactionReceipt, TransactionVariant,
     },
     l2::{L2Tx, TransactionType},
-    transaction_request::CallRequest,
+    transaction_request::{CallOverrides, CallRequest},
     utils::decompose_full_nonce,
-    web3::{self, Bytes, FeeHistory, SyncInfo, SyncState},
-    AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    vm_trace::Call,
+    web3::{self, AccessList, AccessListItem, Bytes, FeeHistory, SyncInfo, SyncState},
+    AccountTreeId, L1BatchNumber, L2BlockNumber, StorageKey, StorageLogQuery, H256,
+    L2_BASE_TOKEN_ADDRESS, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{bytecode::validate_bytecode, u256_to_h256};
 use zksync_web3_decl::{
     error::Web3Error,
     types::{Address, Block, Filter, FilterChanges, Log, U64},
 };
 
-use crate::web3::{
-    backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter,
+use crate::{
+    execution_sandbox::{ApiTracer, BlockArgs, InvalidBlockOverrides},
+    tx_sender::SubmitTxError,
+    web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter},
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
@@ -50,11 +62,91 @@ impl EthNamespace {
         Ok(block_number.0.into())
     }
 
+    /// Checks that the state override is well-formed.
+    fn validate_state_override(state_override: &StateOverride) -> Result<(), Web3Error> {
+        for (address, account) in state_override.iter() {
+            if account.state.is_some() && account.state_diff.is_some() {
+                return Err(Web3Error::InvalidStateOverride(format!(
+                    "account {address:?} has both `state` and `stateDiff` overrides"
+                )));
+            }
+            if let Some(code) = &account.code {
+                validate_bytecode(&code.0).map_err(|err| {
+                    Web3Error::InvalidStateOverride(format!(
+                        "invalid bytecode for account {address:?}: {err}"
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn call_impl(
         &self,
-        mut request: CallRequest,
+        request: CallRequest,
         block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> Result<Bytes, Web3Error> {
+        if let Some(state_override) = &state_override {
+            Self::validate_state_override(state_override)?;
+        }
+        let (block_args, mut call_overrides, tx) =
+            self.prepare_call_request(request, block_id).await?;
+        call_overrides.state_override = state_override;
+
+        // It is assumed that the previous checks has already enforced that the `max_fee_per_gas` is at most u64.
+        let call_result: Vec<u8> = self
+            .state
+            .tx_sender
+            .eth_call(block_args, call_overrides, tx)
+            .await?;
+        Ok(call_result.into())
+    }
+
+    pub async fn create_access_list_impl(
+        &self,
+        request: CallRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<AccessListResult, Web3Error> {
+        let (block_args, call_overrides, tx) = self.prepare_call_request(request, block_id).await?;
+        // Like geth, we exclude the transaction sender and recipient from the access list unless
+        // their storage is accessed, since they are always warm.
+        let excluded_addresses = [tx.initiator_account(), tx.recipient_account()];
+        let call_traces = Arc::new(OnceCell::default());
+        let custom_tracers = vec![ApiTracer::CallTracer(call_traces.clone())];
+        let output = self
+            .state
+            .tx_sender
+            .execute_eth_call(block_args, call_overrides, tx, custom_tracers)
+            .await?;
+        // The tracer is dropped after execution, so it's safe to unwrap
+        let call_traces = Arc::try_unwrap(call_traces)
+            .unwrap()
+            .take()
+            .unwrap_or_default();
+
+        let error = match &output.result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output } => Some(output.to_string()),
+            ExecutionResult::Halt { reason } => Some(reason.to_string()),
+        };
+        Ok(AccessListResult {
+            access_list: build_access_list(
+                &output.logs.storage_logs,
+                &call_traces,
+                &excluded_addresses,
+            ),
+            gas_used: output.statistics.gas_used.into(),
+            error,
+        })
+    }
+
+    /// Resolves the block for a call request and converts the request into an L2 transaction.
+    async fn prepare_call_request(
+        &self,
+        mut request: CallRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<(BlockArgs, CallOverrides, L2Tx), Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
@@ -82,24 +174,150 @@ impl EthNamespace {
         }
         let call_overrides = request.get_call_overrides()?;
         let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
+        Ok((block_args, call_overrides, tx))
+    }
 
-        // It is assumed that the previous checks has already enforced that the `max_fee_per_gas` is at most u64.
-        let call_result: Vec<u8> = self
+    pub async fn simulate_v1_impl(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Web3Error> {
+        let mut blocks = payload.block_state_calls.into_iter();
+        let Some(block) = blocks.next() else {
+            return Ok(vec![]);
+        };
+        if blocks.next().is_some() {
+            return Err(Web3Error::InvalidSimulationRequest(
+                "simulating more than one block is not supported".to_owned(),
+            ));
+        }
+        if block.calls.is_empty() {
+            return Err(Web3Error::InvalidSimulationRequest(
+                "simulated block must contain at least one call".to_owned(),
+            ));
+        }
+        if let Some(state_override) = &block.state_overrides {
+            Self::validate_state_override(state_override)?;
+        }
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
             .state
-            .tx_sender
-            .eth_call(block_args, call_overrides, tx)
+            .resolve_block_args(&mut connection, block_id)
             .await?;
-        Ok(call_result.into())
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        drop(connection);
+
+        let default_gas = self
+            .state
+            .tx_sender
+            .get_default_eth_call_gas(block_args)
+            .await
+            .map_err(Web3Error::InternalError)?;
+        let mut txs = Vec::with_capacity(block.calls.len());
+        for mut request in block.calls {
+            request.gas = Some(request.gas.unwrap_or(default_gas.into()));
+            txs.push(L2Tx::from_request(
+                request.into(),
+                self.state.api_config.max_tx_size,
+            )?);
+        }
+
+        let output = self
+            .state
+            .tx_sender
+            .eth_simulate(
+                block_args,
+                block.block_overrides,
+                block.state_overrides,
+                txs,
+            )
+            .await
+            .map_err(|err| match err {
+                SubmitTxError::Internal(err) => match err.downcast::<InvalidBlockOverrides>() {
+                    Ok(err) => Web3Error::InvalidSimulationRequest(err.to_string()),
+                    Err(err) => Web3Error::InternalError(err),
+                },
+                err => err.into(),
+            })?;
+
+        let block_number = U64::from(output.l2_block_number);
+        let mut total_gas_used = U256::zero();
+        let calls = output
+            .results
+            .into_iter()
+            .map(|result| {
+                let gas_used = U256::from(result.statistics.gas_used);
+                total_gas_used += gas_used;
+                let logs = result
+                    .logs
+                    .events
+                    .iter()
+                    .map(|event| Log {
+                        block_number: Some(block_number),
+                        ..Log::from(event)
+                    })
+                    .collect();
+                let (return_data, error) = match result.result {
+                    ExecutionResult::Success { output } => (output, None),
+                    ExecutionResult::Revert { output } => {
+                        let data = output.encoded_data();
+                        let error = SimulatedCallError {
+                            code: 3,
+                            message: output.to_string(),
+                            data: Some(data.clone().into()),
+                        };
+                        (data, Some(error))
+                    }
+                    ExecutionResult::Halt { reason } => {
+                        let error = SimulatedCallError {
+                            code: -32015,
+                            message: reason.to_string(),
+                            data: None,
+                        };
+                        (vec![], Some(error))
+                    }
+                };
+                SimulatedCallResult {
+                    status: U64::from(u64::from(error.is_none())),
+                    return_data: return_data.into(),
+                    gas_used,
+                    logs,
+                    error,
+                }
+            })
+            .collect();
+
+        Ok(vec![SimulatedBlock {
+            number: block_number,
+            timestamp: output.l2_block_timestamp.into(),
+            base_fee_per_gas: output.base_fee.into(),
+            gas_used: total_gas_used,
+            calls,
+        }])
     }
 
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
         _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> Result<U256, Web3Error> {
+        if let Some(state_override) = &state_override {
+            Self::validate_state_override(state_override)?;
+        }
         let mut request_with_gas_per_pubdata_overridden = request;
         self.state
-            .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
+            .set_nonce_for_call_request(
+                &mut request_with_gas_per_pubdata_overridden,
+                state_override.as_ref(),
+            )
             .await?;
 
         if let Some(eip712_meta) = &mut request_with_gas_per_pubdata_overridden.eip712_meta {
@@ -137,7 +355,12 @@ impl EthNamespace {
         let fee = self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx.into(), scale_factor, acceptable_overestimation as u64)
+            .get_txs_fee_in_wei(
+                tx.into(),
+                scale_factor,
+                acceptable_overestimation as u64,
+                state_override,
+            )
             .await?;
         Ok(fee.gas_limit)
     }
@@ -177,6 +400,19 @@ impl EthNamespace {
         self.current_method().set_block_diff(diff);
     }
 
+    /// Reports the L1 batch containing the returned data, so that the response may be cached once the batch is executed.
+    /// Block tags (e.g., `latest`) resolve to different blocks over time, so responses for them are never cached.
+    fn set_response_l1_batch(&self, block_id: Option<BlockId>, l1_batch_number: Option<U64>) {
+        let is_block_tag = matches!(
+            block_id,
+            Some(BlockId::Number(number)) if !matches!(number, BlockNumber::Number(_))
+        );
+        if let (false, Some(number)) = (is_block_tag, l1_batch_number) {
+            self.current_method()
+                .set_response_l1_batch(L1BatchNumber(number.as_u32()));
+        }
+    }
+
     pub async fn get_logs_impl(&self, mut filter: Filter) -> Result<Vec<Log>, Web3Error> {
         self.state.resolve_filter_block_hash(&mut filter).await?;
         let (from_block, to_block) = self.state.resolve_filter_block_range(&filter).await?;
@@ -253,6 +489,7 @@ impl EthNamespace {
             return Ok(None);
         };
         self.set_block_diff(block_number);
+        self.set_response_l1_batch(Some(block_id), block.l1_batch_number);
 
         let transactions = if full_transactions {
             let mut transactions = storage
@@ -357,6 +594,7 @@ impl EthNamespace {
             return Ok(None);
         };
         self.set_block_diff(block_number); // only report block diff for existing L2 blocks
+        self.set_response_l1_batch(Some(block_id), block.l1_batch_number);
 
         let mut receipts = storage
             .transactions_web3_dal()
@@ -496,7 +734,13 @@ impl EthNamespace {
             }
         };
 
-        if transaction.is_none() {
+        if let Some(transaction) = &transaction {
+            let block_id = match id {
+                TransactionId::Hash(_) => None,
+                TransactionId::Block(block_id, _) => Some(block_id),
+            };
+            self.set_response_l1_batch(block_id, transaction.l1_batch_number);
+        } else {
             transaction = self.state.tx_sink().lookup_tx(id).await?;
         }
         Ok(transaction)
@@ -512,7 +756,11 @@ impl EthNamespace {
             .get_transaction_receipts(&[hash])
             .await
             .context("get_transaction_receipts")?;
-        Ok(receipts.into_iter().next())
+        let receipt = receipts.into_iter().next();
+        if let Some(receipt) = &receipt {
+            self.set_response_l1_batch(None, receipt.l1_batch_number);
+        }
+        Ok(receipt)
     }
 
     pub async fn new_block_filter_impl(&self) -> Result<U256, Web3Error> {
@@ -628,21 +876,7 @@ impl EthNamespace {
     }
 
     pub fn syncing_impl(&self) -> SyncState {
-        if let Some(state) = &self.state.sync_state {
-            // Node supports syncing process (i.e. not the main node).
-            if state.is_synced() {
-                SyncState::NotSyncing
-            } else {
-                SyncState::Syncing(SyncInfo {
-                    starting_block: 0u64.into(), // We always start syncing from genesis right now.
-                    current_block: state.get_local_block().0.into(),
-                    highest_block: state.get_main_node_block().0.into(),
-                })
-            }
-        } else {
-            // If there is no sync state, then the node is the main node and it's always synced.
-            SyncState::NotSyncing
-        }
+        api_sync_state(self.state.sync_state.as_ref())
     }
 
     pub async fn fee_history_impl(
@@ -749,61 +983,37 @@ impl EthNamespace {
             }
 
             TypedFilter::Events(filter, from_block) => {
-                let addresses = if let Some(addresses) = &filter.address {
-                    addresses.0.clone()
-                } else {
-                    vec![]
-                };
-                let topics = if let Some(topics) = &filter.topics {
-                    if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
-                        return Err(Web3Error::TooManyTopics);
-                    }
-                    let topics_by_idx = topics.iter().enumerate().filter_map(|(idx, topics)| {
-                        Some((idx as u32 + 1, topics.as_ref()?.0.clone()))
-                    });
-                    topics_by_idx.collect::<Vec<_>>()
-                } else {
-                    vec![]
-                };
-
-                let mut to_block = self
+                let get_logs_filter = self
                     .state
-                    .resolve_filter_block_number(filter.to_block)
+                    .resolve_get_logs_filter(filter, *from_block)
                     .await?;
-
-                if matches!(filter.to_block, Some(BlockNumber::Number(_))) {
-                    to_block = to_block.min(
-                        self.state
-                            .resolve_filter_block_number(Some(BlockNumber::Latest))
-                            .await?,
-                    );
-                }
-
-                let get_logs_filter = GetLogsFilter {
-                    from_block: *from_block,
-                    to_block,
-                    addresses,
-                    topics,
-                };
+                let to_block = get_logs_filter.to_block;
 
                 let mut storage = self.state.acquire_connection().await?;
 
                 // Check if there is more than one block in range and there are more than `req_entities_limit` logs that satisfies filter.
                 // In this case we should return error and suggest requesting logs with smaller block range.
                 if *from_block != to_block {
-                    if let Some(l2_block_number) = storage
+                    if let Some((l2_block_number, _)) = storage
                         .events_web3_dal()
-                        .get_log_block_number(
+                        .get_log_position(
                             &get_logs_filter,
                             self.state.api_config.req_entities_limit,
                         )
                         .await
                         .map_err(DalError::generalize)?
                     {
+                        // No logs are returned, so the cursor points to the start of the block following
+                        // the suggested block range rather than to the log exceeding the limit.
+                        let cursor = LogsCursor {
+                            block_number: l2_block_number.0.into(),
+                            log_index: 0.into(),
+                        };
                         return Err(Web3Error::LogsLimitExceeded(
                             self.state.api_config.req_entities_limit,
                             from_block.0,
                             from_block.0.max(l2_block_number.0 - 1),
+                            cursor,
                         ));
                     }
                 }
@@ -859,3 +1069,64 @@ impl EthNamespace {
     // - `compile_solidity`.
     // - `compile_serpent`.
 }
+
+/// Checks whether the address belongs to the kernel space (i.e., is below 2^16), where system contracts
+/// and precompiles are deployed.
+fn is_system_contract(address: Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
+
+/// Builds an access list from storage logs and call traces produced by the VM, similarly to the access list tracer in geth.
+/// The list contains accessed storage slots and all called addresses, except for system contracts and
+/// `excluded_addresses` without accessed slots. Addresses and storage keys are sorted and deduplicated.
+fn build_access_list(
+    storage_logs: &[StorageLogQuery],
+    call_traces: &[Call],
+    excluded_addresses: &[Address],
+) -> AccessList {
+    let mut accessed_slots = BTreeMap::<Address, BTreeSet<H256>>::new();
+    for log in storage_logs {
+        let log_query = &log.log_query;
+        if is_system_contract(log_query.address) {
+            continue;
+        }
+        accessed_slots
+            .entry(log_query.address)
+            .or_default()
+            .insert(u256_to_h256(log_query.key));
+    }
+
+    let mut pending_calls: Vec<_> = call_traces.iter().collect();
+    while let Some(call) = pending_calls.pop() {
+        if !is_system_contract(call.to) && !excluded_addresses.contains(&call.to) {
+            accessed_slots.entry(call.to).or_default();
+        }
+        pending_calls.extend(&call.calls);
+    }
+    accessed_slots
+        .into_iter()
+        .map(|(address, storage_keys)| AccessListItem {
+            address,
+            storage_keys: storage_keys.into_iter().collect(),
+        })
+        .collect()
+}
+
+/// Converts the node sync state to its API representation.
+pub(crate) fn api_sync_state(sync_state: Option<&zksync_node_sync::SyncState>) -> SyncState {
+    if let Some(state) = sync_state {
+        // Node supports syncing process (i.e. not the main node).
+        if state.is_synced() {
+            SyncState::NotSyncing
+        } else {
+            SyncState::Syncing(SyncInfo {
+                starting_block: 0u64.into(), // We always start syncing from genesis right now.
+                current_block: state.get_local_block().0.into(),
+                highest_block: state.get_main_node_block().0.into(),
+            })
+        }
+    } else {
+        // If there is no sync state, then the node is the main node and it's always synced.
+        SyncState::NotSyncing
+    }
+}
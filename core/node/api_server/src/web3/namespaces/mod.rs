@@ -6,10 +6,11 @@ mod en;
 pub(crate) mod eth;
 mod net;
 mod snapshots;
+mod txpool;
 mod web3;
 mod zks;
 
 pub(super) use self::{
     debug::DebugNamespace, en::EnNamespace, eth::EthNamespace, net::NetNamespace,
-    snapshots::SnapshotsNamespace, web3::Web3Namespace, zks::ZksNamespace,
+    snapshots::SnapshotsNamespace, txpool::TxpoolNamespace, web3::Web3Namespace, zks::ZksNamespace,
 };
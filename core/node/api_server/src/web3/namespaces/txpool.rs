@@ -0,0 +1,296 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_mempool::MempoolContents;
+use zksync_types::{
+    api::{Transaction, TxpoolContent, TxpoolInspect, TxpoolStatus},
+    get_nonce_key,
+    utils::decompose_full_nonce,
+    Address, Nonce, H256,
+};
+use zksync_utils::h256_to_u256;
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, state::RpcState};
+
+#[derive(Debug, Clone)]
+pub(crate) struct TxpoolNamespace {
+    state: RpcState,
+}
+
+impl TxpoolNamespace {
+    pub fn new(state: RpcState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    pub async fn content_impl(&self) -> Result<TxpoolContent, Web3Error> {
+        let contents = self.mempool_contents().await?;
+        let hashes = Self::limited_hashes(&contents, self.state.api_config.req_entities_limit);
+
+        let mut storage = self.state.acquire_connection().await?;
+        let transactions = storage
+            .transactions_web3_dal()
+            .get_transactions(&hashes, self.state.api_config.l2_chain_id)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(storage);
+
+        let transactions = transactions.into_iter().map(|tx| (tx.hash, tx)).collect();
+        Ok(Self::build_content(contents, transactions))
+    }
+
+    pub async fn inspect_impl(&self) -> Result<TxpoolInspect, Web3Error> {
+        let content = self.content_impl().await?;
+        let summarize_account_txs = |txs: BTreeMap<Nonce, Transaction>| {
+            txs.into_iter()
+                .map(|(nonce, tx)| (nonce, Self::summarize(&tx)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let summarize_txs = |txs: BTreeMap<Address, BTreeMap<Nonce, Transaction>>| {
+            txs.into_iter()
+                .map(|(account, txs)| (account, summarize_account_txs(txs)))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        Ok(TxpoolInspect {
+            pending: summarize_txs(content.pending),
+            queued: summarize_txs(content.queued),
+            priority: content.priority.iter().map(Self::summarize).collect(),
+        })
+    }
+
+    pub async fn status_impl(&self) -> Result<TxpoolStatus, Web3Error> {
+        let contents = self.mempool_contents().await?;
+        let count = |txs: &HashMap<Address, BTreeMap<Nonce, H256>>| {
+            txs.values().map(BTreeMap::len).sum::<usize>()
+        };
+        Ok(TxpoolStatus {
+            pending: count(&contents.pending).into(),
+            queued: count(&contents.queued).into(),
+            priority: contents.l1_transactions.len().into(),
+        })
+    }
+
+    /// Obtains mempool contents either from the state keeper mempool (on the main node),
+    /// or from the mempool cache (on other nodes).
+    async fn mempool_contents(&self) -> Result<MempoolContents, Web3Error> {
+        if let Some(mempool) = &self.state.mempool {
+            return Ok(mempool.contents());
+        }
+        let mempool_cache = self
+            .state
+            .mempool_cache
+            .as_ref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
+
+        // The cache contains all recently received transactions, so we need to filter out included
+        // and rejected ones.
+        let cached_txs = mempool_cache.get_txs().await;
+        let hashes: Vec<_> = cached_txs.iter().map(|(hash, _)| *hash).collect();
+        let mut storage = self.state.acquire_connection().await?;
+        let pending_hashes: HashSet<_> = storage
+            .transactions_web3_dal()
+            .filter_pending_tx_hashes(&hashes)
+            .await
+            .map_err(DalError::generalize)?
+            .into_iter()
+            .collect();
+
+        let mut l1_transactions = vec![];
+        let mut l2_transactions = HashMap::<_, BTreeMap<_, _>>::new();
+        for (hash, initiator_and_nonce) in cached_txs {
+            if !pending_hashes.contains(&hash) {
+                continue;
+            }
+            if let Some((initiator, nonce)) = initiator_and_nonce {
+                l2_transactions
+                    .entry(initiator)
+                    .or_default()
+                    .insert(nonce, hash);
+            } else {
+                l1_transactions.push(hash);
+            }
+        }
+
+        let accounts: Vec<_> = l2_transactions.keys().copied().collect();
+        let mut nonces = Self::account_nonces(&mut storage, &accounts).await?;
+        let l2_transactions = l2_transactions.into_iter().map(|(account, txs)| {
+            let nonce = nonces.remove(&account).unwrap_or(Nonce(0));
+            (account, nonce, txs)
+        });
+        Ok(MempoolContents::new(l1_transactions, l2_transactions))
+    }
+
+    async fn account_nonces(
+        storage: &mut Connection<'_, Core>,
+        accounts: &[Address],
+    ) -> Result<HashMap<Address, Nonce>, Web3Error> {
+        let (nonce_keys, account_by_nonce_key): (Vec<_>, HashMap<_, _>) = accounts
+            .iter()
+            .map(|&account| {
+                let nonce_key = get_nonce_key(&account).hashed_key();
+                (nonce_key, (nonce_key, account))
+            })
+            .unzip();
+        let nonce_values = storage
+            .storage_web3_dal()
+            .get_values(&nonce_keys)
+            .await
+            .map_err(DalError::generalize)?;
+
+        Ok(nonce_values
+            .into_iter()
+            .map(|(nonce_key, nonce_value)| {
+                let (account_nonce, _) = decompose_full_nonce(h256_to_u256(nonce_value));
+                let account = account_by_nonce_key[&nonce_key];
+                (account, Nonce(account_nonce.as_u32()))
+            })
+            .collect())
+    }
+
+    /// Returns hashes of transactions to load, with priority operations first, then pending and then queued
+    /// transactions, capped by `limit`.
+    fn limited_hashes(contents: &MempoolContents, limit: usize) -> Vec<H256> {
+        let l2_hashes = |txs: &HashMap<Address, BTreeMap<Nonce, H256>>| {
+            let mut txs: Vec<_> = txs.iter().collect();
+            // Sort accounts to make the output deterministic.
+            txs.sort_unstable_by_key(|(account, _)| **account);
+            txs.into_iter()
+                .flat_map(|(_, account_txs)| account_txs.values().copied())
+                .collect::<Vec<_>>()
+        };
+
+        contents
+            .l1_transactions
+            .iter()
+            .copied()
+            .chain(l2_hashes(&contents.pending))
+            .chain(l2_hashes(&contents.queued))
+            .take(limit)
+            .collect()
+    }
+
+    /// Arranges loaded transactions according to the mempool contents. Transactions that weren't loaded
+    /// (e.g., because of the entity limit, or because they were included into a block in the meantime)
+    /// are skipped.
+    fn build_content(
+        contents: MempoolContents,
+        mut transactions: HashMap<H256, Transaction>,
+    ) -> TxpoolContent {
+        let mut take_l2_txs = |txs: HashMap<Address, BTreeMap<Nonce, H256>>| {
+            txs.into_iter()
+                .filter_map(|(account, account_txs)| {
+                    let account_txs: BTreeMap<_, _> = account_txs
+                        .into_iter()
+                        .filter_map(|(nonce, hash)| Some((nonce, transactions.remove(&hash)?)))
+                        .collect();
+                    (!account_txs.is_empty()).then_some((account, account_txs))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let pending = take_l2_txs(contents.pending);
+        let queued = take_l2_txs(contents.queued);
+
+        TxpoolContent {
+            pending,
+            queued,
+            priority: contents
+                .l1_transactions
+                .iter()
+                .filter_map(|hash| transactions.remove(hash))
+                .collect(),
+        }
+    }
+
+    /// Summarizes a transaction in the format used by `txpool_inspect`.
+    fn summarize(tx: &Transaction) -> String {
+        let recipient = tx
+            .to
+            .map_or_else(|| "contract creation".to_owned(), |to| format!("{to:?}"));
+        let gas_price = tx.gas_price.unwrap_or_default();
+        format!(
+            "{recipient}: {} wei + {} gas × {gas_price} wei",
+            tx.value, tx.gas
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_tx(hash: H256) -> Transaction {
+        Transaction {
+            hash,
+            ..Transaction::default()
+        }
+    }
+
+    #[test]
+    fn building_content() {
+        let account = Address::repeat_byte(1);
+        let contents = MempoolContents::new(
+            vec![H256::repeat_byte(1)],
+            [(
+                account,
+                Nonce(0),
+                BTreeMap::from([
+                    (Nonce(0), H256::repeat_byte(2)),
+                    (Nonce(2), H256::repeat_byte(3)),
+                ]),
+            )],
+        );
+
+        let hashes = TxpoolNamespace::limited_hashes(&contents, 100);
+        assert_eq!(
+            hashes,
+            [
+                H256::repeat_byte(1),
+                H256::repeat_byte(2),
+                H256::repeat_byte(3)
+            ]
+        );
+        let hashes = TxpoolNamespace::limited_hashes(&contents, 2);
+        assert_eq!(hashes, [H256::repeat_byte(1), H256::repeat_byte(2)]);
+
+        let transactions = hashes
+            .into_iter()
+            .map(|hash| (hash, mock_tx(hash)))
+            .collect();
+        let content = TxpoolNamespace::build_content(contents, transactions);
+        assert_eq!(content.priority, [mock_tx(H256::repeat_byte(1))]);
+        assert_eq!(
+            content.pending,
+            BTreeMap::from([(
+                account,
+                BTreeMap::from([(Nonce(0), mock_tx(H256::repeat_byte(2)))])
+            )])
+        );
+        assert!(content.queued.is_empty());
+    }
+
+    #[test]
+    fn summarizing_tx() {
+        let tx = Transaction {
+            to: Some(Address::repeat_byte(0x11)),
+            value: 1_000.into(),
+            gas: 21_000.into(),
+            gas_price: Some(250_000_000.into()),
+            ..Transaction::default()
+        };
+        assert_eq!(
+            TxpoolNamespace::summarize(&tx),
+            "0x1111111111111111111111111111111111111111: 1000 wei + 21000 gas × 250000000 wei"
+        );
+
+        let tx = Transaction { to: None, ..tx };
+        assert!(
+            TxpoolNamespace::summarize(&tx).starts_with("contract creation: "),
+            "{tx:?}"
+        );
+    }
+}
@@ -8,8 +8,8 @@ use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, LogsCursor,
+        LogsPage, PaymasterFeeEstimate, Proof, ProtocolVersion, StorageProof, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -26,7 +26,7 @@ use zksync_types::{
 use zksync_utils::{address_to_h256, h256_to_u256};
 use zksync_web3_decl::{
     error::Web3Error,
-    types::{Address, Token, H256},
+    types::{Address, Filter, Token, H256},
 };
 
 use crate::web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, RpcState};
@@ -46,9 +46,30 @@ impl ZksNamespace {
     }
 
     pub async fn estimate_fee_impl(&self, request: CallRequest) -> Result<Fee, Web3Error> {
+        let tx = self.l2_tx_for_fee_estimation(request).await?;
+        self.estimate_fee(tx.into()).await
+    }
+
+    pub async fn estimate_fee_with_paymaster_impl(
+        &self,
+        request: CallRequest,
+    ) -> Result<PaymasterFeeEstimate, Web3Error> {
+        let tx = self.l2_tx_for_fee_estimation(request).await?;
+        let scale_factor = self.state.api_config.estimate_gas_scale_factor;
+        let acceptable_overestimation =
+            self.state.api_config.estimate_gas_acceptable_overestimation;
+
+        Ok(self
+            .state
+            .tx_sender
+            .estimate_paymaster_fee(tx, scale_factor, acceptable_overestimation as u64)
+            .await?)
+    }
+
+    async fn l2_tx_for_fee_estimation(&self, request: CallRequest) -> Result<L2Tx, Web3Error> {
         let mut request_with_gas_per_pubdata_overridden = request;
         self.state
-            .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
+            .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden, None)
             .await?;
 
         if let Some(ref mut eip712_meta) = request_with_gas_per_pubdata_overridden.eip712_meta {
@@ -64,7 +85,7 @@ impl ZksNamespace {
         // not consider provided ones.
         tx.common_data.fee.max_priority_fee_per_gas = 0u64.into();
         tx.common_data.fee.gas_per_pubdata_limit = U256::from(DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE);
-        self.estimate_fee(tx.into()).await
+        Ok(tx)
     }
 
     pub async fn estimate_l1_to_l2_gas_impl(
@@ -96,7 +117,7 @@ impl ZksNamespace {
         Ok(self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation as u64)
+            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation as u64, None)
             .await?)
     }
 
@@ -370,11 +391,16 @@ impl ZksNamespace {
             .ensure_not_pruned(block_number, &mut storage)
             .await?;
 
-        Ok(storage
+        let details = storage
             .blocks_web3_dal()
             .get_block_details(block_number)
             .await
-            .map_err(DalError::generalize)?)
+            .map_err(DalError::generalize)?;
+        if let Some(details) = &details {
+            self.current_method()
+                .set_response_l1_batch(details.l1_batch_number);
+        }
+        Ok(details)
     }
 
     pub async fn get_raw_block_transactions_impl(
@@ -422,11 +448,15 @@ impl ZksNamespace {
             .ensure_not_pruned(batch_number, &mut storage)
             .await?;
 
-        Ok(storage
+        let details = storage
             .blocks_web3_dal()
             .get_l1_batch_details(batch_number)
             .await
-            .map_err(DalError::generalize)?)
+            .map_err(DalError::generalize)?;
+        if details.is_some() {
+            self.current_method().set_response_l1_batch(batch_number);
+        }
+        Ok(details)
     }
 
     pub async fn get_bytecode_by_hash_impl(
@@ -480,10 +510,20 @@ impl ZksNamespace {
         l1_batch_number: L1BatchNumber,
     ) -> Result<Option<Proof>, Web3Error> {
         let mut storage = self.state.acquire_connection().await?;
-        self.state
+        // The Merkle tree may retain checkpoints for L1 batches pruned in Postgres, so we don't fail
+        // right away if the batch is pruned.
+        let pruning_error = match self
+            .state
             .start_info
             .ensure_not_pruned(l1_batch_number, &mut storage)
-            .await?;
+            .await
+        {
+            Ok(()) => None,
+            Err(err @ Web3Error::PrunedL1Batch(_)) => Some(err),
+            Err(err) => return Err(err),
+        };
+        drop(storage);
+
         let hashed_keys = keys
             .iter()
             .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
@@ -498,6 +538,9 @@ impl ZksNamespace {
             Ok(proofs) => proofs,
             Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
             Err(TreeApiError::NoVersion(err)) => {
+                if let Some(pruning_error) = pruning_error {
+                    return Err(pruning_error);
+                }
                 return if err.missing_version > err.version_count {
                     Ok(None)
                 } else {
@@ -568,4 +611,67 @@ impl ZksNamespace {
             err.into()
         })
     }
+
+    pub async fn cancel_transaction_impl(&self, tx_bytes: Bytes) -> Result<H256, Web3Error> {
+        let (mut tx, hash) = self.state.parse_transaction_bytes(&tx_bytes.0)?;
+        tx.set_input(tx_bytes.0, hash);
+
+        let cancel_result = self.state.tx_sender.cancel_tx(tx).await;
+        cancel_result.map(|()| hash).map_err(|err| {
+            tracing::debug!("Cancel transaction error: {err}");
+            API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
+            err.into()
+        })
+    }
+
+    pub async fn get_logs_paginated_impl(
+        &self,
+        mut filter: Filter,
+        cursor: Option<LogsCursor>,
+    ) -> Result<LogsPage, Web3Error> {
+        self.state.resolve_filter_block_hash(&mut filter).await?;
+        let mut from_block = self
+            .state
+            .resolve_filter_block_number(filter.from_block)
+            .await?;
+        let mut from_log_index = 0;
+        if let Some(cursor) = cursor {
+            let cursor_block = RpcState::u64_to_block_number(cursor.block_number);
+            // A cursor preceding the filtered block range is ignored.
+            if cursor_block >= from_block {
+                from_block = cursor_block;
+                from_log_index = u32::try_from(cursor.log_index.as_u64()).unwrap_or(u32::MAX);
+            }
+        }
+        let get_logs_filter = self
+            .state
+            .resolve_get_logs_filter(&filter, from_block)
+            .await?;
+
+        let limit = self.state.api_config.req_entities_limit;
+        let mut storage = self.state.acquire_connection().await?;
+        // Request an extra log to determine the cursor for the next page.
+        let mut logs = storage
+            .events_web3_dal()
+            .get_logs_from_position(&get_logs_filter, from_log_index, limit.saturating_add(1))
+            .await
+            .map_err(DalError::generalize)?;
+
+        let cursor = if logs.len() > limit {
+            let next_log = &logs[limit];
+            let cursor = LogsCursor {
+                block_number: next_log.block_number.context("missing log block number")?,
+                log_index: next_log
+                    .log_index
+                    .context("missing log index")?
+                    .as_u64()
+                    .into(),
+            };
+            logs.truncate(limit);
+            Some(cursor)
+        } else {
+            None
+        };
+        Ok(LogsPage { logs, cursor })
+    }
 }
@@ -1,7 +1,10 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
 
+use std::{collections::HashMap, sync::Arc};
+
 use chrono::NaiveDateTime;
 use futures::FutureExt;
+use serde::de::DeserializeOwned;
 use tokio::{
     sync::{broadcast, mpsc, watch},
     task::JoinHandle,
@@ -9,7 +12,8 @@ use tokio::{
 };
 use tracing::Instrument as _;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::{L2BlockNumber, H128, H256};
+use zksync_node_sync::SyncState;
+use zksync_types::{api, web3, L1BatchNumber, L2BlockNumber, L2ChainId, H128, H256};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::{server::SubscriptionMessage, SubscriptionResult},
@@ -18,12 +22,15 @@ use zksync_web3_decl::{
         PendingSubscriptionSink, SendTimeoutError, SubscriptionSink,
     },
     namespaces::EthPubSubServer,
-    types::{BlockHeader, Log, PubSubFilter, PubSubResult},
+    types::{
+        BlockHeader, L1BatchStatus, L1BatchStatusFilter, L1BatchStatusUpdate, Log,
+        PendingTransactionsFilter, PubSubFilter, PubSubResult,
+    },
 };
 
 use super::{
     metrics::{SubscriptionType, PUB_SUB_METRICS},
-    namespaces::eth::EVENT_TOPIC_NUMBER_LIMIT,
+    namespaces::eth::{api_sync_state, EVENT_TOPIC_NUMBER_LIMIT},
 };
 
 const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
@@ -145,7 +152,14 @@ impl PubSubNotifier {
             .map_err(Into::into)
     }
 
-    async fn notify_txs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    /// Notifies about new pending transactions. Besides sending transaction hashes via `self.sender`,
+    /// sends full transactions via `full_txs_sender` if it has any subscribers.
+    async fn notify_txs(
+        self,
+        full_txs_sender: broadcast::Sender<Vec<PubSubResult>>,
+        l2_chain_id: L2ChainId,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
         let mut last_time = chrono::Utc::now().naive_utc();
         let mut timer = interval(self.polling_interval);
         loop {
@@ -161,10 +175,24 @@ impl PubSubNotifier {
 
             if let Some((new_last_time, _)) = new_txs.last() {
                 last_time = *new_last_time;
-                let new_txs = new_txs
-                    .into_iter()
-                    .map(|(_, tx_hash)| PubSubResult::TxHash(tx_hash))
-                    .collect();
+                let tx_hashes: Vec<_> = new_txs.into_iter().map(|(_, tx_hash)| tx_hash).collect();
+                // Full transactions are relatively expensive to load, so we only load them if they are needed.
+                if full_txs_sender.receiver_count() > 0 {
+                    let db_latency =
+                        PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::FullTxs].start();
+                    let full_txs = self.full_txs(&tx_hashes, l2_chain_id).await?;
+                    db_latency.observe();
+
+                    let full_txs = full_txs
+                        .into_iter()
+                        .map(PubSubResult::Transaction)
+                        .collect();
+                    full_txs_sender.send(full_txs).ok();
+                    PUB_SUB_METRICS.broadcast_channel_len[&SubscriptionType::FullTxs]
+                        .set(full_txs_sender.len());
+                }
+
+                let new_txs = tx_hashes.into_iter().map(PubSubResult::TxHash).collect();
                 self.send_pub_sub_results(new_txs, SubscriptionType::Txs);
             }
             self.emit_event(PubSubEvent::NotifyIterationFinished(SubscriptionType::Txs));
@@ -185,6 +213,26 @@ impl PubSubNotifier {
             .map_err(Into::into)
     }
 
+    /// Loads full transactions with the specified hashes preserving the order of hashes.
+    async fn full_txs(
+        &self,
+        tx_hashes: &[H256],
+        l2_chain_id: L2ChainId,
+    ) -> anyhow::Result<Vec<api::Transaction>> {
+        let txs = self
+            .connection_pool
+            .connection_tagged("api")
+            .await?
+            .transactions_web3_dal()
+            .get_transactions(tx_hashes, l2_chain_id)
+            .await?;
+        let mut txs: HashMap<_, _> = txs.into_iter().map(|tx| (tx.hash, tx)).collect();
+        Ok(tx_hashes
+            .iter()
+            .filter_map(|hash| txs.remove(hash))
+            .collect())
+    }
+
     async fn notify_logs(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let Some(mut last_block_number) = self
             .get_starting_l2_block_number(&mut stop_receiver)
@@ -229,26 +277,202 @@ impl PubSubNotifier {
             .await
             .map_err(Into::into)
     }
+
+    async fn notify_l1_batches(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut last_numbers = None;
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, pubsub_l1_batches_notifier is shutting down");
+                break;
+            }
+            timer.tick().await;
+
+            if self.sender.receiver_count() == 0 {
+                // Nobody is subscribed to L1 batch updates, so there's no need to poll Postgres. The starting point
+                // is reset so that new subscribers don't receive updates accumulated while there were no subscribers.
+                last_numbers = None;
+                self.emit_event(PubSubEvent::NotifyIterationFinished(
+                    SubscriptionType::L1Batches,
+                ));
+                continue;
+            }
+
+            let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::L1Batches].start();
+            let numbers = self.latest_l1_batch_numbers().await?;
+            db_latency.observe();
+
+            if let Some(last_numbers) = &last_numbers {
+                let updates = l1_batch_status_updates(last_numbers, &numbers);
+                if !updates.is_empty() {
+                    let updates = updates
+                        .into_iter()
+                        .map(PubSubResult::L1BatchStatus)
+                        .collect();
+                    self.send_pub_sub_results(updates, SubscriptionType::L1Batches);
+                }
+            }
+            last_numbers = Some(numbers);
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::L1Batches,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the numbers of the latest L1 batches for each status in [`L1BatchStatus::ALL`].
+    async fn latest_l1_batch_numbers(&self) -> anyhow::Result<[Option<L1BatchNumber>; 4]> {
+        let mut storage = self.connection_pool.connection_tagged("api").await?;
+        let sealed = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+        let committed = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_committed_on_eth()
+            .await?;
+        let proven = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await?;
+        let executed = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?;
+        Ok([sealed, committed, proven, executed])
+    }
+}
+
+/// Computes L1 batch status transitions between two snapshots of latest L1 batch numbers returned by
+/// [`PubSubNotifier::latest_l1_batch_numbers()`]. If there was no L1 batch with a certain status in the previous snapshot,
+/// only the latest batch with this status is reported. Decreasing numbers (e.g., caused by a revert) are not reported.
+fn l1_batch_status_updates(
+    prev_numbers: &[Option<L1BatchNumber>; 4],
+    numbers: &[Option<L1BatchNumber>; 4],
+) -> Vec<L1BatchStatusUpdate> {
+    let mut updates = vec![];
+    for ((status, prev_number), number) in L1BatchStatus::ALL
+        .into_iter()
+        .zip(prev_numbers)
+        .zip(numbers)
+    {
+        let Some(number) = *number else {
+            continue;
+        };
+        let first_number = prev_number.map_or(number.0, |prev| prev.0 + 1);
+        let new_updates = (first_number..=number.0).map(|number| L1BatchStatusUpdate {
+            l1_batch_number: L1BatchNumber(number),
+            status,
+        });
+        updates.extend(new_updates);
+    }
+    updates
+}
+
+/// Updates the syncing status provided to `syncing` subscribers. Similar to Geth, only changes of the syncing flag
+/// are propagated (i.e., not every change of the sync progress).
+async fn notify_syncing(
+    sender: Arc<watch::Sender<web3::SyncState>>,
+    sync_state: SyncState,
+    polling_interval: Duration,
+    events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    let mut timer = interval(polling_interval);
+    loop {
+        if *stop_receiver.borrow() {
+            tracing::info!("Stop signal received, pubsub_syncing_notifier is shutting down");
+            break;
+        }
+        timer.tick().await;
+
+        let new_state = api_sync_state(Some(&sync_state));
+        sender.send_if_modified(|state| {
+            let is_modified = matches!(state, web3::SyncState::NotSyncing)
+                != matches!(new_state, web3::SyncState::NotSyncing);
+            if is_modified {
+                *state = new_state;
+            }
+            is_modified
+        });
+        if let Some(sender) = &events_sender {
+            sender
+                .send(PubSubEvent::NotifyIterationFinished(
+                    SubscriptionType::Syncing,
+                ))
+                .ok();
+        }
+    }
+    Ok(())
+}
+
+/// Filter applied to the items sent to a single subscriber.
+#[derive(Debug)]
+enum SubscriberFilter {
+    Logs(PubSubFilter),
+    PendingTransactions(PendingTransactionsFilter),
+    L1Batches(L1BatchStatusFilter),
+}
+
+impl SubscriberFilter {
+    /// Returns the item to send to the subscriber, or `None` if the item should be skipped.
+    fn apply(&self, item: PubSubResult) -> Option<PubSubResult> {
+        match (self, item) {
+            (Self::Logs(filter), PubSubResult::Log(log)) => {
+                filter.matches(&log).then_some(PubSubResult::Log(log))
+            }
+            (Self::PendingTransactions(filter), PubSubResult::Transaction(tx)) => {
+                if !filter.matches(&tx) {
+                    None
+                } else if filter.full_transactions {
+                    Some(PubSubResult::Transaction(tx))
+                } else {
+                    Some(PubSubResult::TxHash(tx.hash))
+                }
+            }
+            (Self::L1Batches(filter), PubSubResult::L1BatchStatus(update)) => filter
+                .matches(&update)
+                .then_some(PubSubResult::L1BatchStatus(update)),
+            (_, item) => Some(item),
+        }
+    }
+}
+
+/// Parses subscription parameters. Missing parameters are parsed as the default value.
+fn parse_params<T: DeserializeOwned + Default>(
+    params: Option<serde_json::Value>,
+) -> Result<T, serde_json::Error> {
+    params.map_or_else(|| Ok(T::default()), serde_json::from_value)
 }
 
 /// Subscription support for Web3 APIs.
 pub(super) struct EthSubscribe {
+    l2_chain_id: L2ChainId,
     blocks: broadcast::Sender<Vec<PubSubResult>>,
     transactions: broadcast::Sender<Vec<PubSubResult>>,
+    full_transactions: broadcast::Sender<Vec<PubSubResult>>,
     logs: broadcast::Sender<Vec<PubSubResult>>,
+    l1_batches: broadcast::Sender<Vec<PubSubResult>>,
+    syncing: Arc<watch::Sender<web3::SyncState>>,
+    sync_state: Option<SyncState>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
 impl EthSubscribe {
-    pub fn new() -> Self {
+    pub fn new(l2_chain_id: L2ChainId) -> Self {
         let (blocks, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (full_transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (l1_batches, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (syncing, _) = watch::channel(web3::SyncState::NotSyncing);
 
         Self {
+            l2_chain_id,
             blocks,
             transactions,
+            full_transactions,
             logs,
+            l1_batches,
+            syncing: Arc::new(syncing),
+            sync_state: None,
             events_sender: None,
         }
     }
@@ -257,6 +481,13 @@ impl EthSubscribe {
         self.events_sender = Some(sender);
     }
 
+    /// Sets the node sync state used for `syncing` subscriptions. If not set, the node is considered
+    /// to be always synced (which is the case for the main node).
+    pub fn set_sync_state(&mut self, sync_state: SyncState) {
+        self.syncing.send_replace(api_sync_state(Some(&sync_state)));
+        self.sync_state = Some(sync_state);
+    }
+
     async fn reject(sink: PendingSubscriptionSink) {
         sink.reject(ErrorObject::borrowed(
             ErrorCode::InvalidParams.code(),
@@ -270,7 +501,7 @@ impl EthSubscribe {
         sink: SubscriptionSink,
         subscription_type: SubscriptionType,
         mut receiver: broadcast::Receiver<Vec<PubSubResult>>,
-        filter: Option<PubSubFilter>,
+        filter: Option<SubscriberFilter>,
     ) {
         let _guard = PUB_SUB_METRICS.active_subscribers[&subscription_type].inc_guard(1);
         let lifetime_latency = PUB_SUB_METRICS.subscriber_lifetime[&subscription_type].start();
@@ -315,21 +546,60 @@ impl EthSubscribe {
         lifetime_latency.observe();
     }
 
+    /// Sends the current syncing status to the subscriber, and then sends every status change.
+    async fn run_syncing_subscriber(
+        sink: SubscriptionSink,
+        mut receiver: watch::Receiver<web3::SyncState>,
+    ) {
+        let subscription_type = SubscriptionType::Syncing;
+        let _guard = PUB_SUB_METRICS.active_subscribers[&subscription_type].inc_guard(1);
+        let lifetime_latency = PUB_SUB_METRICS.subscriber_lifetime[&subscription_type].start();
+        let closed = sink.closed().fuse();
+        tokio::pin!(closed);
+
+        loop {
+            let state = receiver.borrow_and_update().clone();
+            let handle_result = Self::handle_new_items(
+                &sink,
+                subscription_type,
+                vec![PubSubResult::Syncing(state)],
+                None,
+            )
+            .await;
+            if handle_result.is_err() {
+                PUB_SUB_METRICS.subscriber_send_timeouts[&subscription_type].inc();
+                break;
+            }
+
+            tokio::select! {
+                change_result = receiver.changed() => {
+                    if change_result.is_err() {
+                        break; // The sender is dropped, i.e. the server is shutting down.
+                    }
+                }
+                _ = &mut closed => {
+                    break;
+                }
+            }
+        }
+        lifetime_latency.observe();
+    }
+
     async fn handle_new_items(
         sink: &SubscriptionSink,
         subscription_type: SubscriptionType,
         new_items: Vec<PubSubResult>,
-        filter: Option<&PubSubFilter>,
+        filter: Option<&SubscriberFilter>,
     ) -> Result<(), SendTimeoutError> {
         let notify_latency = PUB_SUB_METRICS.notify_subscribers_latency[&subscription_type].start();
         for item in new_items {
-            if let PubSubResult::Log(log) = &item {
-                if let Some(filter) = &filter {
-                    if !filter.matches(log) {
-                        continue;
-                    }
-                }
-            }
+            let item = match filter {
+                Some(filter) => match filter.apply(item) {
+                    Some(item) => item,
+                    None => continue,
+                },
+                None => item,
+            };
 
             sink.send_timeout(
                 SubscriptionMessage::from_json(&item)
@@ -350,7 +620,7 @@ impl EthSubscribe {
         &self,
         pending_sink: PendingSubscriptionSink,
         sub_type: String,
-        params: Option<PubSubFilter>,
+        params: Option<serde_json::Value>,
     ) {
         let sub_type = match sub_type.as_str() {
             "newHeads" => {
@@ -366,48 +636,116 @@ impl EthSubscribe {
                 Some(SubscriptionType::Blocks)
             }
             "newPendingTransactions" => {
-                let Ok(sink) = pending_sink.accept().await else {
-                    return;
+                // Geth-compatible boolean parameter specifying whether full transactions should be returned.
+                let filter = match params {
+                    Some(serde_json::Value::Bool(full_transactions)) => {
+                        Ok(PendingTransactionsFilter {
+                            full_transactions,
+                            ..PendingTransactionsFilter::default()
+                        })
+                    }
+                    params => parse_params::<PendingTransactionsFilter>(params),
                 };
-                let transactions_rx = self.transactions.subscribe();
-                tokio::spawn(
-                    Self::run_subscriber(sink, SubscriptionType::Txs, transactions_rx, None)
-                        .in_current_span(),
-                );
-                Some(SubscriptionType::Txs)
+
+                match filter {
+                    Err(_) => {
+                        Self::reject(pending_sink).await;
+                        None
+                    }
+                    Ok(filter) if filter.is_trivial() => {
+                        let Ok(sink) = pending_sink.accept().await else {
+                            return;
+                        };
+                        let transactions_rx = self.transactions.subscribe();
+                        tokio::spawn(
+                            Self::run_subscriber(
+                                sink,
+                                SubscriptionType::Txs,
+                                transactions_rx,
+                                None,
+                            )
+                            .in_current_span(),
+                        );
+                        Some(SubscriptionType::Txs)
+                    }
+                    Ok(filter) => {
+                        let Ok(sink) = pending_sink.accept().await else {
+                            return;
+                        };
+                        let transactions_rx = self.full_transactions.subscribe();
+                        let filter = SubscriberFilter::PendingTransactions(filter);
+                        tokio::spawn(
+                            Self::run_subscriber(
+                                sink,
+                                SubscriptionType::FullTxs,
+                                transactions_rx,
+                                Some(filter),
+                            )
+                            .in_current_span(),
+                        );
+                        Some(SubscriptionType::FullTxs)
+                    }
+                }
             }
             "logs" => {
-                let filter = params.unwrap_or_default();
-                let topic_count = filter.topics.as_ref().map_or(0, Vec::len);
-
-                if topic_count > EVENT_TOPIC_NUMBER_LIMIT {
-                    Self::reject(pending_sink).await;
-                    None
-                } else {
+                let filter = parse_params::<PubSubFilter>(params);
+                let topic_count = filter
+                    .as_ref()
+                    .map_or(0, |filter| filter.topics.as_ref().map_or(0, Vec::len));
+
+                match filter {
+                    Ok(filter) if topic_count <= EVENT_TOPIC_NUMBER_LIMIT => {
+                        let Ok(sink) = pending_sink.accept().await else {
+                            return;
+                        };
+                        let logs_rx = self.logs.subscribe();
+                        let filter = SubscriberFilter::Logs(filter);
+                        tokio::spawn(
+                            Self::run_subscriber(
+                                sink,
+                                SubscriptionType::Logs,
+                                logs_rx,
+                                Some(filter),
+                            )
+                            .in_current_span(),
+                        );
+                        Some(SubscriptionType::Logs)
+                    }
+                    _ => {
+                        Self::reject(pending_sink).await;
+                        None
+                    }
+                }
+            }
+            "l1Batches" => {
+                if let Ok(filter) = parse_params::<L1BatchStatusFilter>(params) {
                     let Ok(sink) = pending_sink.accept().await else {
                         return;
                     };
-                    let logs_rx = self.logs.subscribe();
+                    let l1_batches_rx = self.l1_batches.subscribe();
+                    let filter = SubscriberFilter::L1Batches(filter);
                     tokio::spawn(
-                        Self::run_subscriber(sink, SubscriptionType::Logs, logs_rx, Some(filter))
-                            .in_current_span(),
+                        Self::run_subscriber(
+                            sink,
+                            SubscriptionType::L1Batches,
+                            l1_batches_rx,
+                            Some(filter),
+                        )
+                        .in_current_span(),
                     );
-                    Some(SubscriptionType::Logs)
+                    Some(SubscriptionType::L1Batches)
+                } else {
+                    Self::reject(pending_sink).await;
+                    None
                 }
             }
             "syncing" => {
                 let Ok(sink) = pending_sink.accept().await else {
                     return;
                 };
-
-                tokio::spawn(async move {
-                    sink.send_timeout(
-                        SubscriptionMessage::from_json(&PubSubResult::Syncing(false)).unwrap(),
-                        SUBSCRIPTION_SINK_SEND_TIMEOUT,
-                    )
-                    .await
-                });
-                None
+                let syncing_rx = self.syncing.subscribe();
+                tokio::spawn(Self::run_syncing_subscriber(sink, syncing_rx).in_current_span());
+                Some(SubscriptionType::Syncing)
             }
             _ => {
                 Self::reject(pending_sink).await;
@@ -429,7 +767,7 @@ impl EthSubscribe {
         polling_interval: Duration,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(5);
 
         let notifier = PubSubNotifier {
             sender: self.blocks.clone(),
@@ -446,18 +784,41 @@ impl EthSubscribe {
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_txs(stop_receiver.clone()));
+        let notifier_task = tokio::spawn(notifier.notify_txs(
+            self.full_transactions.clone(),
+            self.l2_chain_id,
+            stop_receiver.clone(),
+        ));
         notifier_tasks.push(notifier_task);
 
         let notifier = PubSubNotifier {
             sender: self.logs.clone(),
-            connection_pool,
+            connection_pool: connection_pool.clone(),
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
+        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
 
+        let notifier = PubSubNotifier {
+            sender: self.l1_batches.clone(),
+            connection_pool,
+            polling_interval,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_l1_batches(stop_receiver.clone()));
         notifier_tasks.push(notifier_task);
+
+        if let Some(sync_state) = self.sync_state.clone() {
+            let notifier_task = tokio::spawn(notify_syncing(
+                self.syncing.clone(),
+                sync_state,
+                polling_interval,
+                self.events_sender.clone(),
+                stop_receiver,
+            ));
+            notifier_tasks.push(notifier_task);
+        }
         notifier_tasks
     }
 }
@@ -468,9 +829,9 @@ impl EthPubSubServer for EthSubscribe {
         &self,
         pending: PendingSubscriptionSink,
         sub_type: String,
-        filter: Option<PubSubFilter>,
+        params: Option<serde_json::Value>,
     ) -> SubscriptionResult {
-        self.sub(pending, sub_type, filter).await;
+        self.sub(pending, sub_type, params).await;
         Ok(())
     }
 }
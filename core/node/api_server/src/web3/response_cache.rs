@@ -0,0 +1,339 @@
+//! Cache for responses of JSON-RPC methods returning immutable historical data.
+
+use std::{
+    mem,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_state::{CacheValue, LruCache};
+use zksync_types::L1BatchNumber;
+
+/// Interval between checking the last executed and the last pruned L1 batches.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Methods which responses may be cached. Handlers of these methods must report the L1 batch containing
+/// the returned data using [`MethodTracer::set_response_l1_batch()`](super::backend_jsonrpsee::MethodTracer::set_response_l1_batch());
+/// responses without this information are never cached.
+const CACHEABLE_METHODS: &[&str] = &[
+    "eth_getBlockByNumber",
+    "eth_getBlockByHash",
+    "eth_getBlockReceipts",
+    "eth_getTransactionByHash",
+    "eth_getTransactionReceipt",
+    "zks_getBlockDetails",
+    "zks_getL1BatchDetails",
+];
+
+/// Cache key consisting of the method name and its canonicalized params.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ResponseCacheKey {
+    method: &'static str,
+    params: Box<str>,
+}
+
+impl ResponseCacheKey {
+    /// Creates a key for the specified method call. Returns `None` if the method is not cacheable
+    /// or its params are malformed.
+    pub(crate) fn new(method: &str, params: Option<&RawValue>) -> Option<Self> {
+        let method = *CACHEABLE_METHODS.iter().find(|&&name| name == method)?;
+        // Round-tripping params via `serde_json::Value` removes insignificant whitespace and orders object keys.
+        let params = match params {
+            Some(params) => serde_json::from_str::<serde_json::Value>(params.get()).ok()?,
+            None => serde_json::Value::Array(vec![]),
+        };
+        Some(Self {
+            method,
+            params: params.to_string().into(),
+        })
+    }
+}
+
+/// Cached method result (i.e., the `result` field of a JSON-RPC response).
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResult {
+    result: Arc<RawValue>,
+    /// L1 batch containing the returned data.
+    l1_batch_number: L1BatchNumber,
+    weight: u32,
+}
+
+impl CachedResult {
+    pub(crate) fn as_raw(&self) -> &RawValue {
+        &self.result
+    }
+}
+
+impl CacheValue<ResponseCacheKey> for CachedResult {
+    fn cache_weight(&self) -> u32 {
+        self.weight
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct CacheState {
+    /// Last L1 batch executed on L1. Only data in this batch or earlier batches is cached.
+    last_executed_l1_batch: Option<L1BatchNumber>,
+    /// Last (soft-)pruned L1 batch. Responses with data in this batch or earlier batches are neither served nor cached,
+    /// so that the cache doesn't bypass pruning checks in method handlers.
+    last_pruned_l1_batch: Option<L1BatchNumber>,
+    /// Incremented on each rollback, so that responses computed before a rollback are not cached.
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct ResponseCacheInner {
+    responses: LruCache<ResponseCacheKey, CachedResult>,
+    state: Mutex<CacheState>,
+}
+
+/// Cache for responses of JSON-RPC methods returning immutable historical data, such as blocks or transaction receipts.
+/// A response is only cached if all returned data belongs to an L1 batch executed on L1, so that it cannot change afterwards.
+///
+/// Entries are keyed by the method name and canonicalized params. The cache is bounded by the total byte size of cached results;
+/// it's filled by the API server middleware and should be kept up to date by spawning [`ResponseCacheUpdateTask`].
+/// Responses containing data from pruned L1 batches are dropped once the update task observes pruning.
+/// On a node state rollback, [`Self::roll_back()`] must be called to invalidate potentially reverted data.
+#[derive(Debug, Clone)]
+pub struct ResponseCache(Arc<ResponseCacheInner>);
+
+impl ResponseCache {
+    /// Creates a cache with the specified capacity in bytes.
+    pub fn new(capacity: u64) -> Self {
+        Self(Arc::new(ResponseCacheInner {
+            responses: LruCache::new("api_responses", capacity),
+            state: Mutex::default(),
+        }))
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        self.0
+            .state
+            .lock()
+            .expect("response cache state is poisoned")
+    }
+
+    /// Returns a task that will update this cache in background.
+    pub fn update_task(&self, connection_pool: ConnectionPool<Core>) -> ResponseCacheUpdateTask {
+        ResponseCacheUpdateTask {
+            cache: self.clone(),
+            connection_pool,
+        }
+    }
+
+    /// Invalidates all cached responses after the node state is rolled back to the specified L1 batch.
+    ///
+    /// Only external nodes can roll back executed L1 batches; the main node block reverter refuses to do so,
+    /// so cached data is never affected by its reverts. Invalidation is still performed for consistency.
+    pub fn roll_back(&self, last_l1_batch_to_keep: L1BatchNumber) {
+        let mut state = self.state();
+        state.generation += 1;
+        state.last_executed_l1_batch = state
+            .last_executed_l1_batch
+            .map(|number| number.min(last_l1_batch_to_keep));
+        // Clearing the cache while holding the lock ensures that no stale responses are inserted concurrently.
+        self.0.responses.clear();
+        tracing::info!(
+            "Invalidated API response cache after rolling back to L1 batch #{last_l1_batch_to_keep}"
+        );
+    }
+
+    fn update_last_executed_l1_batch(&self, number: L1BatchNumber) {
+        let prev_number = self.state().last_executed_l1_batch;
+        if prev_number.map_or(false, |prev_number| prev_number > number) {
+            tracing::warn!(
+                "Last executed L1 batch has decreased from {prev_number:?} to #{number}; treating this as a rollback"
+            );
+            self.roll_back(number);
+        }
+        let mut state = self.state();
+        state.last_executed_l1_batch = Some(number);
+    }
+
+    fn update_last_pruned_l1_batch(&self, number: L1BatchNumber) {
+        let mut state = self.state();
+        if state
+            .last_pruned_l1_batch
+            .map_or(true, |prev| prev < number)
+        {
+            state.last_pruned_l1_batch = Some(number);
+        }
+    }
+
+    /// Returns the current cache generation. It should be passed to [`Self::insert()`] once the response is computed.
+    pub(crate) fn generation(&self) -> u64 {
+        self.state().generation
+    }
+
+    /// Returns a cached result for the specified key. Results with data from pruned L1 batches are evicted
+    /// and are not returned.
+    pub(crate) fn get(&self, key: &ResponseCacheKey) -> Option<CachedResult> {
+        let cached = self.0.responses.get(key)?;
+        let last_pruned_l1_batch = self.state().last_pruned_l1_batch;
+        if last_pruned_l1_batch.map_or(false, |number| cached.l1_batch_number <= number) {
+            self.0.responses.remove(key);
+            return None;
+        }
+        Some(cached)
+    }
+
+    /// Inserts a result extracted from the serialized JSON-RPC `response` if the returned data belongs to an executed
+    /// and non-pruned L1 batch, and the cache wasn't rolled back since the call started. Returns whether the result was inserted.
+    pub(crate) fn insert(
+        &self,
+        key: ResponseCacheKey,
+        generation: u64,
+        l1_batch_number: L1BatchNumber,
+        response: &str,
+    ) -> bool {
+        #[derive(Deserialize)]
+        struct ResponseResult<'a> {
+            #[serde(borrow)]
+            result: &'a RawValue,
+        }
+
+        let state = self.state();
+        let is_executed = state
+            .last_executed_l1_batch
+            .map_or(false, |number| number >= l1_batch_number);
+        let is_pruned = state
+            .last_pruned_l1_batch
+            .map_or(false, |number| number >= l1_batch_number);
+        if state.generation != generation || !is_executed || is_pruned {
+            return false;
+        }
+        let Ok(ResponseResult { result }) = serde_json::from_str(response) else {
+            return false;
+        };
+
+        let weight = key.params.len() + result.get().len() + mem::size_of::<ResponseCacheKey>();
+        let value = CachedResult {
+            result: result.to_owned().into(),
+            l1_batch_number,
+            weight: u32::try_from(weight).unwrap_or(u32::MAX),
+        };
+        self.0.responses.insert(key, value);
+        true
+    }
+}
+
+/// Task updating [`ResponseCache`]. Should be spawned as a Tokio task (exactly one task for the cache).
+#[derive(Debug)]
+pub struct ResponseCacheUpdateTask {
+    cache: ResponseCache,
+    connection_pool: ConnectionPool<Core>,
+}
+
+impl ResponseCacheUpdateTask {
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        loop {
+            let mut connection = self.connection_pool.connection_tagged("api").await?;
+            let last_executed_l1_batch = connection
+                .blocks_dal()
+                .get_number_of_last_l1_batch_executed_on_eth()
+                .await?;
+            let pruning_info = connection.pruning_dal().get_pruning_info().await?;
+            drop(connection);
+
+            if let Some(number) = last_executed_l1_batch {
+                self.cache.update_last_executed_l1_batch(number);
+            }
+            if let Some(number) = pruning_info.last_soft_pruned_l1_batch {
+                self.cache.update_last_pruned_l1_batch(number);
+            }
+            if tokio::time::timeout(UPDATE_INTERVAL, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::debug!("Stopping API response cache updates");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"{"jsonrpc":"2.0","result":{"number":"0x1"},"id":1}"#;
+
+    fn raw(json: &str) -> Box<RawValue> {
+        RawValue::from_string(json.to_owned()).unwrap()
+    }
+
+    #[test]
+    fn creating_cache_keys() {
+        assert!(ResponseCacheKey::new("eth_getBalance", None).is_none());
+        let params = raw(r#"{"b": 1, "a": [ "0x1", true ]}"#);
+
+        let key = ResponseCacheKey::new("eth_getBlockByNumber", Some(&params)).unwrap();
+        assert_eq!(key.method, "eth_getBlockByNumber");
+        assert_eq!(&*key.params, r#"{"a":["0x1",true],"b":1}"#);
+        let other_params = raw(r#"{ "a":["0x1",true],"b":1 }"#);
+        let other_key = ResponseCacheKey::new("eth_getBlockByNumber", Some(&other_params)).unwrap();
+        assert_eq!(key, other_key);
+    }
+
+    #[test]
+    fn caching_responses() {
+        let cache = ResponseCache::new(1 << 20);
+        let params = raw(r#"["0x1",false]"#);
+        let key = ResponseCacheKey::new("eth_getBlockByNumber", Some(&params)).unwrap();
+
+        // No executed batches yet.
+        let generation = cache.generation();
+        assert!(!cache.insert(key.clone(), generation, L1BatchNumber(1), RESPONSE));
+        cache.update_last_executed_l1_batch(L1BatchNumber(1));
+        assert!(!cache.insert(key.clone(), generation, L1BatchNumber(2), RESPONSE));
+        assert!(cache.insert(key.clone(), generation, L1BatchNumber(1), RESPONSE));
+        let cached = cache.get(&key).unwrap();
+        assert_eq!(cached.as_raw().get(), r#"{"number":"0x1"}"#);
+
+        cache.roll_back(L1BatchNumber(0));
+        assert!(cache.get(&key).is_none());
+        // Responses computed before the rollback must not be cached.
+        assert!(!cache.insert(key.clone(), generation, L1BatchNumber(0), RESPONSE));
+        let generation = cache.generation();
+        assert!(!cache.insert(key.clone(), generation, L1BatchNumber(1), RESPONSE));
+        assert!(cache.insert(key.clone(), generation, L1BatchNumber(0), RESPONSE));
+        assert!(cache.get(&key).is_some());
+    }
+
+    #[test]
+    fn decreasing_executed_batch_invalidates_cache() {
+        let cache = ResponseCache::new(1 << 20);
+        let key = ResponseCacheKey::new("zks_getL1BatchDetails", Some(&raw("[3]"))).unwrap();
+        cache.update_last_executed_l1_batch(L1BatchNumber(5));
+        assert!(cache.insert(key.clone(), 0, L1BatchNumber(3), RESPONSE));
+
+        cache.update_last_executed_l1_batch(L1BatchNumber(4));
+        assert!(cache.get(&key).is_none());
+        assert_eq!(cache.generation(), 1);
+    }
+
+    #[test]
+    fn pruned_responses_are_not_served() {
+        let cache = ResponseCache::new(1 << 20);
+        let key = ResponseCacheKey::new("zks_getL1BatchDetails", Some(&raw("[3]"))).unwrap();
+        let other_key = ResponseCacheKey::new("zks_getL1BatchDetails", Some(&raw("[4]"))).unwrap();
+        cache.update_last_executed_l1_batch(L1BatchNumber(5));
+        assert!(cache.insert(key.clone(), 0, L1BatchNumber(3), RESPONSE));
+        assert!(cache.insert(other_key.clone(), 0, L1BatchNumber(4), RESPONSE));
+
+        cache.update_last_pruned_l1_batch(L1BatchNumber(3));
+        assert!(cache.get(&key).is_none());
+        assert!(cache.get(&other_key).is_some());
+        assert!(!cache.insert(key.clone(), 0, L1BatchNumber(3), RESPONSE));
+        assert!(cache.get(&key).is_none());
+
+        // The pruned L1 batch never decreases.
+        cache.update_last_pruned_l1_batch(L1BatchNumber(2));
+        assert!(!cache.insert(key, 0, L1BatchNumber(3), RESPONSE));
+    }
+}
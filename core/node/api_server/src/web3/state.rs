@@ -19,6 +19,7 @@ use zksync_config::{
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
+use zksync_state_keeper::MempoolGuard;
 use zksync_types::{
     api, commitment::L1BatchCommitmentMode, l2::L2Tx, transaction_request::CallRequest, Address,
     L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
@@ -29,6 +30,7 @@ use super::{
     backend_jsonrpsee::MethodTracer,
     mempool_cache::MempoolCache,
     metrics::{FilterType, FILTER_METRICS},
+    namespaces::eth::EVENT_TOPIC_NUMBER_LIMIT,
     TypedFilter,
 };
 use crate::{
@@ -246,6 +248,8 @@ pub(crate) struct RpcState {
     /// from a snapshot.
     pub(super) start_info: BlockStartInfo,
     pub(super) mempool_cache: Option<MempoolCache>,
+    /// State keeper mempool; only available on the main node.
+    pub(super) mempool: Option<MempoolGuard>,
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
 }
 
@@ -402,18 +406,69 @@ impl RpcState {
         Ok(block_number)
     }
 
+    /// Converts a log filter into the DAL filter querying logs starting from `from_block`. The `to_block` of the filter
+    /// is capped by the latest block.
+    pub async fn resolve_get_logs_filter(
+        &self,
+        filter: &Filter,
+        from_block: L2BlockNumber,
+    ) -> Result<api::GetLogsFilter, Web3Error> {
+        let addresses = if let Some(addresses) = &filter.address {
+            addresses.0.clone()
+        } else {
+            vec![]
+        };
+        let topics = if let Some(topics) = &filter.topics {
+            if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
+                return Err(Web3Error::TooManyTopics);
+            }
+            let topics_by_idx = topics
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, topics)| Some((idx as u32 + 1, topics.as_ref()?.0.clone())));
+            topics_by_idx.collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+
+        let mut to_block = self.resolve_filter_block_number(filter.to_block).await?;
+        if matches!(filter.to_block, Some(api::BlockNumber::Number(_))) {
+            to_block = to_block.min(
+                self.resolve_filter_block_number(Some(api::BlockNumber::Latest))
+                    .await?,
+            );
+        }
+
+        Ok(api::GetLogsFilter {
+            from_block,
+            to_block,
+            addresses,
+            topics,
+        })
+    }
+
+    /// Sets the nonce for the call request if it's not specified explicitly. The nonce is taken
+    /// from the state override for the request initiator if it's present, or from the latest block otherwise.
     pub(crate) async fn set_nonce_for_call_request(
         &self,
         call_request: &mut CallRequest,
+        state_override: Option<&api::StateOverride>,
     ) -> Result<(), Web3Error> {
         if call_request.nonce.is_some() {
             return Ok(());
         }
+        let from = call_request.from.unwrap_or_default();
+        let overridden_nonce = state_override
+            .and_then(|state_override| state_override.get(&from))
+            .and_then(|account| account.nonce);
+        if let Some(nonce) = overridden_nonce {
+            call_request.nonce = Some(nonce);
+            return Ok(());
+        }
+
         let mut connection = self.acquire_connection().await?;
         let latest_block_id = api::BlockId::Number(api::BlockNumber::Latest);
         let latest_block_number = self.resolve_block(&mut connection, latest_block_id).await?;
-
-        let from = call_request.from.unwrap_or_default();
         let address_historical_nonce = connection
             .storage_web3_dal()
             .get_address_historical_nonce(from, latest_block_number)
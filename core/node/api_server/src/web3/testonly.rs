@@ -3,7 +3,11 @@
 use std::{pin::Pin, time::Instant};
 
 use tokio::sync::watch;
-use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig, wallets::Wallets};
+use zksync_config::configs::{
+    api::Web3JsonRpcConfig,
+    chain::{MempoolConfig, StateKeeperConfig},
+    wallets::Wallets,
+};
 use zksync_dal::ConnectionPool;
 use zksync_health_check::CheckHealth;
 use zksync_node_fee_model::MockBatchFeeParamsProvider;
@@ -26,10 +30,12 @@ pub(crate) async fn create_test_tx_sender(
 ) -> (TxSender, VmConcurrencyBarrier) {
     let web3_config = Web3JsonRpcConfig::for_tests();
     let state_keeper_config = StateKeeperConfig::for_tests();
+    let mempool_config = MempoolConfig::for_tests();
     let wallets = Wallets::for_tests();
     let tx_sender_config = TxSenderConfig::new(
         &state_keeper_config,
         &web3_config,
+        &mempool_config,
         wallets.state_keeper.unwrap().fee_account.address(),
         l2_chain_id,
     );
@@ -101,12 +107,32 @@ pub async fn spawn_http_server(
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
     stop_receiver: watch::Receiver<bool>,
+) -> ApiServerHandles {
+    spawn_configured_http_server(
+        api_config,
+        pool,
+        tx_executor,
+        method_tracer,
+        |builder| builder,
+        stop_receiver,
+    )
+    .await
+}
+
+/// Same as [`spawn_http_server()`], but allows to additionally configure the server before it is started.
+pub async fn spawn_configured_http_server(
+    api_config: InternalApiConfig,
+    pool: ConnectionPool<Core>,
+    tx_executor: MockTransactionExecutor,
+    method_tracer: Arc<MethodTracer>,
+    configure: impl FnOnce(ApiBuilder) -> ApiBuilder,
+    stop_receiver: watch::Receiver<bool>,
 ) -> ApiServerHandles {
     spawn_server(
         ApiTransportLabel::Http,
         api_config,
         pool,
-        None,
+        configure,
         tx_executor,
         method_tracer,
         stop_receiver,
@@ -125,7 +151,32 @@ pub async fn spawn_ws_server(
         ApiTransportLabel::Ws,
         api_config,
         pool,
-        websocket_requests_per_minute_limit,
+        |builder| {
+            if let Some(limit) = websocket_requests_per_minute_limit {
+                builder.with_websocket_requests_per_minute_limit(limit)
+            } else {
+                builder
+            }
+        },
+        MockTransactionExecutor::default(),
+        Arc::default(),
+        stop_receiver,
+    )
+    .await
+}
+
+/// Same as [`spawn_ws_server()`], but allows to additionally configure the server before it is started.
+pub async fn spawn_configured_ws_server(
+    api_config: InternalApiConfig,
+    pool: ConnectionPool<Core>,
+    configure: impl FnOnce(ApiBuilder) -> ApiBuilder,
+    stop_receiver: watch::Receiver<bool>,
+) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
+    spawn_server(
+        ApiTransportLabel::Ws,
+        api_config,
+        pool,
+        configure,
         MockTransactionExecutor::default(),
         Arc::default(),
         stop_receiver,
@@ -137,7 +188,7 @@ async fn spawn_server(
     transport: ApiTransportLabel,
     api_config: InternalApiConfig,
     pool: ConnectionPool<Core>,
-    websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    configure: impl FnOnce(ApiBuilder) -> ApiBuilder,
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
     stop_receiver: watch::Receiver<bool>,
@@ -147,22 +198,15 @@ async fn spawn_server(
     let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
-    namespaces.extend([Namespace::Debug, Namespace::Snapshots]);
+    namespaces.extend([Namespace::Debug, Namespace::Snapshots, Namespace::Txpool]);
 
     let server_builder = match transport {
         ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
-        ApiTransportLabel::Ws => {
-            let mut builder = ApiBuilder::jsonrpsee_backend(api_config, pool)
-                .ws(0)
-                .with_subscriptions_limit(100);
-            if let Some(websocket_requests_per_minute_limit) = websocket_requests_per_minute_limit {
-                builder = builder
-                    .with_websocket_requests_per_minute_limit(websocket_requests_per_minute_limit);
-            }
-            builder
-        }
+        ApiTransportLabel::Ws => ApiBuilder::jsonrpsee_backend(api_config, pool)
+            .ws(0)
+            .with_subscriptions_limit(100),
     };
-    let server_handles = server_builder
+    let server_handles = configure(server_builder)
         .with_polling_interval(POLL_INTERVAL)
         .with_tx_sender(tx_sender)
         .with_vm_barrier(vm_barrier)
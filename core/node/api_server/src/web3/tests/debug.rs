@@ -1,5 +1,8 @@
 //! Tests for the `debug` Web3 namespace.
 
+use std::sync::Mutex;
+
+use multivm::interface::ExecutionResult;
 use zksync_types::{tx::TransactionExecutionResult, vm_trace::Call, BOOTLOADER_ADDRESS};
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -61,7 +64,9 @@ impl HttpTest for TraceBlockTest {
 
             assert_eq!(block_traces.len(), tx_results.len()); // equals to the number of transactions in the block
             for (trace, tx_result) in block_traces.iter().zip(&tx_results) {
-                let api::ResultDebugCall { result } = trace;
+                let api::DebugTraceResult::CallTrace(result) = &trace.result else {
+                    panic!("Unexpected trace: {trace:?}");
+                };
                 assert_eq!(result.from, Address::zero());
                 assert_eq!(result.to, BOOTLOADER_ADDRESS);
                 assert_eq!(result.gas, tx_result.transaction.gas_limit());
@@ -203,7 +208,9 @@ impl HttpTest for TraceTransactionTest {
         let result = client
             .trace_transaction(tx_results[0].hash, None)
             .await?
-            .context("no transaction traces")?;
+            .context("no transaction traces")?
+            .into_call_trace()
+            .context("unexpected trace")?;
         assert_eq!(result.from, Address::zero());
         assert_eq!(result.to, BOOTLOADER_ADDRESS);
         assert_eq!(result.gas, tx_results[0].transaction.gas_limit());
@@ -218,6 +225,191 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug, Default)]
+struct TraceTransactionWithStructLoggerTest {
+    executed_txs: Arc<Mutex<Vec<H256>>>,
+}
+
+#[async_trait]
+impl HttpTest for TraceTransactionWithStructLoggerTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        let executed_txs = self.executed_txs.clone();
+        tx_executor.set_tx_responses(move |tx, _| {
+            executed_txs.lock().unwrap().push(tx.hash());
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [
+            execute_l2_transaction_with_traces(0),
+            execute_l2_transaction_with_traces(1),
+            execute_l2_transaction_with_traces(2),
+        ];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::StructLogger,
+            ..api::TracerConfig::default()
+        };
+        let trace = client
+            .trace_transaction(tx_results[1].hash, Some(options))
+            .await?
+            .context("no transaction traces")?;
+        assert_matches!(trace, api::DebugTraceResult::StructLogs(_));
+
+        // Preceding transactions in the same L2 block must be replayed before the traced one.
+        let executed_txs = self.executed_txs.lock().unwrap().clone();
+        assert_eq!(executed_txs, [tx_results[0].hash, tx_results[1].hash]);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_struct_logger() {
+    test_http_server(TraceTransactionWithStructLoggerTest::default()).await;
+}
+
+#[derive(Debug)]
+struct TraceTransactionFlatTest;
+
+#[async_trait]
+impl HttpTest for TraceTransactionFlatTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [execute_l2_transaction_with_traces(0)];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::FlatCallTracer,
+            ..api::TracerConfig::default()
+        };
+        let result = client
+            .trace_transaction(tx_results[0].hash, Some(options))
+            .await?
+            .context("no transaction traces")?;
+        let api::DebugTraceResult::FlatCallTrace(calls) = result else {
+            panic!("Unexpected trace: {result:?}");
+        };
+        assert_eq!(calls.len(), tx_results[0].call_traces.len() + 1);
+        assert_eq!(calls[0].action.to, BOOTLOADER_ADDRESS);
+        assert_eq!(calls[0].subtraces, 2);
+        assert_eq!(calls[1].traceaddress, [0, 0]);
+        assert_eq!(calls[2].traceaddress, [0, 1]);
+        assert_eq!(calls[2].action.value, 123.into());
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_flat_call_tracer() {
+    test_http_server(TraceTransactionFlatTest).await;
+}
+
+#[derive(Debug)]
+struct TracePrestateTest;
+
+impl TracePrestateTest {
+    fn options(diff_mode: bool) -> api::TracerConfig {
+        api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::CallTracerConfig {
+                only_top_call: false,
+                diff_mode,
+            },
+            ..api::TracerConfig::default()
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for TracePrestateTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [execute_l2_transaction_with_traces(0)];
+        let tx_hash = tx_results[0].hash;
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+
+        // The slot is first written by another transaction in the same L2 block, and then twice by the traced one.
+        let address = Address::repeat_byte(0x11);
+        let slot = H256::from_low_u64_be(1);
+        let key = StorageKey::new(AccountTreeId::new(address), slot);
+        let logs = [
+            (
+                H256::repeat_byte(0xff),
+                vec![StorageLog::new_write_log(key, H256::from_low_u64_be(1))],
+            ),
+            (
+                tx_hash,
+                vec![
+                    StorageLog::new_write_log(key, H256::from_low_u64_be(2)),
+                    StorageLog::new_write_log(key, H256::from_low_u64_be(3)),
+                ],
+            ),
+        ];
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &logs)
+            .await?;
+        drop(storage);
+
+        let result = client
+            .trace_transaction(tx_hash, Some(Self::options(false)))
+            .await?
+            .context("no transaction traces")?;
+        let api::DebugTraceResult::PrestateTrace(api::PrestateTrace::Prestate(prestate)) = result
+        else {
+            panic!("Unexpected trace: {result:?}");
+        };
+        assert_eq!(prestate.len(), 1);
+        let account = &prestate[&address];
+        assert_eq!(account.balance, Some(0.into()));
+        assert_eq!(account.nonce, Some(0.into()));
+        assert_eq!(account.code, None);
+        let expected_storage = HashMap::from([(slot, H256::from_low_u64_be(1))]);
+        assert_eq!(account.storage, Some(expected_storage));
+
+        let block_traces = client
+            .trace_block_by_number(1_u32.into(), Some(Self::options(true)))
+            .await?;
+        assert_eq!(block_traces.len(), 1);
+        let result = &block_traces[0].result;
+        let api::DebugTraceResult::PrestateTrace(api::PrestateTrace::Diff { pre, post }) = result
+        else {
+            panic!("Unexpected trace: {result:?}");
+        };
+        let pre_storage = pre[&address].storage.as_ref().unwrap();
+        assert_eq!(pre_storage[&slot], H256::from_low_u64_be(1));
+        let post_storage = post[&address].storage.as_ref().unwrap();
+        assert_eq!(post_storage[&slot], H256::from_low_u64_be(3));
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_prestate_tracer() {
+    test_http_server(TracePrestateTest).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
@@ -284,6 +284,95 @@ async fn log_filter_changes_with_block_boundaries() {
     test_http_server(LogFilterChangesWithBlockBoundariesTest).await;
 }
 
+#[derive(Debug)]
+struct LogsPaginationTest;
+
+impl LogsPaginationTest {
+    const LIMIT: u32 = 3;
+}
+
+#[async_trait]
+impl HttpTest for LogsPaginationTest {
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig {
+            req_entities_limit: Some(Self::LIMIT),
+            ..Web3JsonRpcConfig::for_tests()
+        }
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        let mut all_events = vec![];
+        for l2_block_number in 1..=3 {
+            let (_, events) =
+                store_events(&mut storage, l2_block_number, all_events.len() as u32).await?;
+            all_events.extend(events);
+        }
+        drop(storage);
+        let all_events: Vec<_> = all_events.iter().collect();
+
+        let filter = Filter {
+            from_block: Some(api::BlockNumber::Number(1.into())),
+            to_block: Some(api::BlockNumber::Number(3.into())),
+            ..Filter::default()
+        };
+        let err = client.get_logs(filter.clone()).await.unwrap_err();
+        let Error::Call(err) = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert_eq!(err.code(), ErrorCode::InvalidParams.code());
+        let data: serde_json::Value = serde_json::from_str(err.data().unwrap().get())?;
+        let error_cursor: api::LogsCursor = serde_json::from_value(data["cursor"].clone())?;
+        // The cursor must point to the start of the block, since the erroneous call returns no logs.
+        assert_eq!(
+            error_cursor,
+            api::LogsCursor {
+                block_number: 1.into(),
+                log_index: 0.into(),
+            }
+        );
+
+        // Continuing from the error cursor should return all logs.
+        let mut cursor = Some(error_cursor);
+        let mut logs = vec![];
+        let mut page_count = 0;
+        loop {
+            let page = client.get_logs_paginated(filter.clone(), cursor).await?;
+            assert!(page.logs.len() <= Self::LIMIT as usize, "{page:?}");
+            page_count += 1;
+            logs.extend(page.logs);
+            cursor = page.cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(page_count, all_events.len() / Self::LIMIT as usize);
+        assert_logs_match(&logs, &all_events);
+
+        // A cursor preceding the filter range should be ignored.
+        let filter = Filter {
+            from_block: Some(api::BlockNumber::Number(3.into())),
+            ..Filter::default()
+        };
+        let page = client
+            .get_logs_paginated(filter, Some(error_cursor))
+            .await?;
+        assert_logs_match(&page.logs, &all_events[8..11]);
+        let next_cursor = page.cursor.unwrap();
+        assert_eq!(next_cursor.block_number, 3.into());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn logs_pagination() {
+    test_http_server(LogsPaginationTest).await;
+}
+
 fn assert_not_implemented<T: fmt::Debug>(result: Result<T, Error>) {
     assert_matches!(result, Err(Error::Call(e)) => {
         assert_eq!(e.code(), ErrorCode::MethodNotFound.code());
@@ -24,6 +24,7 @@ use zksync_node_test_utils::{
     l1_batch_metadata_to_commitment_artifacts, prepare_recovery_snapshot,
 };
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     api,
     block::L2BlockHeader,
     fee::TransactionExecutionMetrics,
@@ -44,7 +45,7 @@ use zksync_web3_decl::{
     client::{Client, DynClient, L2},
     jsonrpsee::{
         core::{client::ClientT, params::BatchRequestBuilder, ClientError},
-        http_client::HttpClient,
+        http_client::{HttpClient, HttpClientBuilder},
         rpc_params,
         types::{
             error::{ErrorCode, OVERSIZED_RESPONSE_CODE},
@@ -57,7 +58,13 @@ use zksync_web3_decl::{
 use super::*;
 use crate::{
     execution_sandbox::testonly::MockTransactionExecutor,
-    web3::testonly::{spawn_http_server, spawn_ws_server},
+    web3::{
+        metrics::ApiTransportLabel,
+        testonly::{
+            spawn_configured_http_server, spawn_configured_ws_server, spawn_http_server,
+            spawn_ws_server,
+        },
+    },
 };
 
 mod debug;
@@ -144,6 +151,10 @@ trait HttpTest: Send + Sync {
         Arc::default()
     }
 
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig::for_tests()
+    }
+
     async fn test(&self, client: &DynClient<L2>, pool: &ConnectionPool<Core>)
         -> anyhow::Result<()>;
 
@@ -224,7 +235,7 @@ async fn test_http_server(test: impl HttpTest) {
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let contracts_config = ContractsConfig::for_tests();
-    let web3_config = Web3JsonRpcConfig::for_tests();
+    let web3_config = test.web3_config();
     let genesis = GenesisConfig::for_tests();
     let mut api_config = InternalApiConfig::new(&web3_config, &contracts_config, &genesis);
     api_config.filters_disabled = test.filters_disabled();
@@ -269,6 +280,14 @@ fn assert_logs_match(actual_logs: &[api::Log], expected_logs: &[&VmEvent]) {
     }
 }
 
+/// Checks the retry hint in the error data of a rate-limited response.
+fn assert_retry_hint(error_data: Option<&serde_json::value::RawValue>) {
+    let error_data = error_data.expect("no retry hint in rate-limited response");
+    let error_data: serde_json::Value = serde_json::from_str(error_data.get()).unwrap();
+    let retry_after_ms = error_data["retryAfterMs"].as_u64().unwrap();
+    assert!(retry_after_ms > 0, "{error_data}");
+}
+
 fn execute_l2_transaction(transaction: L2Tx) -> TransactionExecutionResult {
     TransactionExecutionResult {
         hash: transaction.hash(),
@@ -1075,3 +1094,300 @@ impl HttpTest for GenesisConfigTest {
 async fn tracing_genesis_config() {
     test_http_server(GenesisConfigTest).await;
 }
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+fn assert_rate_limited(err: ClientError) {
+    let ClientError::Call(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert_eq!(
+        err.code() as u16,
+        http::StatusCode::TOO_MANY_REQUESTS.as_u16()
+    );
+    assert_eq!(err.message(), "Too many requests");
+    assert_retry_hint(err.data());
+}
+
+async fn spawn_rate_limited_server(
+    transport: ApiTransportLabel,
+    stop_receiver: watch::Receiver<bool>,
+    configure: impl FnOnce(ClientRateLimits) -> ClientRateLimits,
+) -> ApiServerHandles {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    StorageInitialization::Genesis
+        .prepare_storage(&NetworkConfig::for_tests(), &mut storage)
+        .await
+        .unwrap();
+    drop(storage);
+
+    let api_config = InternalApiConfig::new(
+        &Web3JsonRpcConfig::for_tests(),
+        &ContractsConfig::for_tests(),
+        &GenesisConfig::for_tests(),
+    );
+    let client_rate_limits = configure(
+        ClientRateLimits::default()
+            .with_requests_per_minute_limit(NonZeroU32::new(5).unwrap())
+            .with_api_key_header(API_KEY_HEADER.to_owned())
+            .with_api_keys("first=5,second=5".parse().unwrap()),
+    );
+    let configure = |builder: ApiBuilder| {
+        builder
+            .with_client_rate_limits(client_rate_limits)
+            .with_method_weights("eth_blockNumber=3,zks_*=10".parse().unwrap())
+    };
+    match transport {
+        ApiTransportLabel::Http => {
+            spawn_configured_http_server(
+                api_config,
+                pool,
+                MockTransactionExecutor::default(),
+                Arc::default(),
+                configure,
+                stop_receiver,
+            )
+            .await
+        }
+        ApiTransportLabel::Ws => {
+            spawn_configured_ws_server(api_config, pool, configure, stop_receiver)
+                .await
+                .0
+        }
+    }
+}
+
+#[tokio::test]
+async fn per_client_rate_limiting() {
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let mut server_handles =
+        spawn_rate_limited_server(ApiTransportLabel::Http, stop_receiver, |limits| limits).await;
+    let local_addr = server_handles.wait_until_ready().await;
+    let url = format!("http://{local_addr}/");
+    let client_with_header = |name: &'static str, value: &str| {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        HttpClientBuilder::default()
+            .set_headers(headers)
+            .build(&url)
+            .unwrap()
+    };
+
+    let client = client_with_header(API_KEY_HEADER, "first");
+    let block_number: U64 = client
+        .request("eth_blockNumber", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(block_number, U64::zero());
+    for _ in 0..2 {
+        let _: U64 = client.request("eth_chainId", rpc_params![]).await.unwrap();
+    }
+    let err = client
+        .request::<U64, _>("eth_chainId", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_rate_limited(err);
+
+    // Quotas are tracked separately for each client.
+    let other_client = client_with_header(API_KEY_HEADER, "second");
+    let _: U64 = other_client
+        .request("eth_blockNumber", rpc_params![])
+        .await
+        .unwrap();
+    // A request with the weight exceeding the quota can never succeed.
+    let err = other_client
+        .request::<U64, _>("zks_L1BatchNumber", rpc_params![])
+        .await
+        .unwrap_err();
+    let ClientError::Call(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert_eq!(
+        err.code() as u16,
+        http::StatusCode::TOO_MANY_REQUESTS.as_u16()
+    );
+    assert!(err.data().is_none());
+
+    // Unknown API keys are ignored, so the client is identified by its IP address and cannot get a fresh quota
+    // by rotating keys.
+    let client = client_with_header(API_KEY_HEADER, "rotated-1");
+    for _ in 0..5 {
+        let _: U64 = client.request("eth_chainId", rpc_params![]).await.unwrap();
+    }
+    let err = client_with_header(API_KEY_HEADER, "rotated-2")
+        .request::<U64, _>("eth_chainId", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_rate_limited(err);
+    // Forwarding headers are ignored since the client is not a trusted proxy.
+    let err = client_with_header("x-forwarded-for", "10.0.0.1")
+        .request::<U64, _>("eth_chainId", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_rate_limited(err);
+    // Requests without identifying headers are limited by the peer address as well.
+    let anonymous_client = HttpClientBuilder::default().build(&url).unwrap();
+    let err = anonymous_client
+        .request::<U64, _>("eth_chainId", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_rate_limited(err);
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+}
+
+#[tokio::test]
+async fn per_client_rate_limiting_behind_proxy() {
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let mut server_handles =
+        spawn_rate_limited_server(ApiTransportLabel::Http, stop_receiver, |limits| {
+            limits.with_trusted_proxies(vec![Ipv4Addr::LOCALHOST.into()])
+        })
+        .await;
+    let local_addr = server_handles.wait_until_ready().await;
+    let url = format!("http://{local_addr}/");
+    let client_with_header = |name: &'static str, value: &str| {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        HttpClientBuilder::default()
+            .set_headers(headers)
+            .build(&url)
+            .unwrap()
+    };
+
+    // Clients can be identified by their IP address reported by a trusted proxy.
+    let proxied_client = client_with_header("x-forwarded-for", "10.0.0.1, 192.168.0.1");
+    for _ in 0..5 {
+        let _: U64 = proxied_client
+            .request("eth_chainId", rpc_params![])
+            .await
+            .unwrap();
+    }
+    let err = proxied_client
+        .request::<U64, _>("eth_chainId", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_rate_limited(err);
+    // Only the rightmost `X-Forwarded-For` entry is used.
+    let err = client_with_header("x-forwarded-for", "10.0.0.2, 192.168.0.1")
+        .request::<U64, _>("eth_chainId", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_rate_limited(err);
+
+    let _: U64 = client_with_header("x-real-ip", "10.0.0.3")
+        .request("eth_chainId", rpc_params![])
+        .await
+        .unwrap();
+    // Requests without forwarding headers are attributed to the proxy itself.
+    let anonymous_client = HttpClientBuilder::default().build(&url).unwrap();
+    let _: U64 = anonymous_client
+        .request("eth_chainId", rpc_params![])
+        .await
+        .unwrap();
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+}
+
+#[tokio::test]
+async fn caching_historical_responses() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    StorageInitialization::Genesis
+        .prepare_storage(&NetworkConfig::for_tests(), &mut storage)
+        .await
+        .unwrap();
+
+    let cache = ResponseCache::new(1 << 20);
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let api_config = InternalApiConfig::new(
+        &Web3JsonRpcConfig::for_tests(),
+        &ContractsConfig::for_tests(),
+        &GenesisConfig::for_tests(),
+    );
+    let mut server_handles = spawn_configured_http_server(
+        api_config,
+        pool.clone(),
+        MockTransactionExecutor::default(),
+        Arc::default(),
+        |builder| builder.with_response_cache(cache.clone()),
+        stop_receiver,
+    )
+    .await;
+    let local_addr = server_handles.wait_until_ready().await;
+    let client = Client::http(format!("http://{local_addr}/").parse().unwrap())
+        .unwrap()
+        .build();
+
+    let block_key = |params: &str| {
+        let params = serde_json::value::RawValue::from_string(params.to_owned()).unwrap();
+        response_cache::ResponseCacheKey::new("eth_getBlockByNumber", Some(&params)).unwrap()
+    };
+    let block_number = api::BlockNumber::Number(0.into());
+    let block = client
+        .get_block_by_number(block_number, false)
+        .await
+        .unwrap();
+    assert!(block.is_some());
+    // The genesis batch is not executed yet, so the response must not be cached.
+    assert!(cache.get(&block_key(r#"["0x0",false]"#)).is_none());
+
+    storage
+        .eth_sender_dal()
+        .insert_bogus_confirmed_eth_tx(
+            L1BatchNumber(0),
+            AggregatedActionType::Execute,
+            H256::zero(),
+            chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+    // Run a single cache update iteration.
+    let (_, stopped_receiver) = watch::channel(true);
+    cache
+        .update_task(pool.clone())
+        .run(stopped_receiver)
+        .await
+        .unwrap();
+
+    let block = client
+        .get_block_by_number(block_number, false)
+        .await
+        .unwrap();
+    let cached = cache.get(&block_key(r#"["0x0",false]"#)).unwrap();
+    let cached_block: api::Block<api::TransactionVariant> =
+        serde_json::from_str(cached.as_raw().get()).unwrap();
+    assert_eq!(Some(cached_block), block);
+    let cached_response = client
+        .get_block_by_number(block_number, false)
+        .await
+        .unwrap();
+    assert_eq!(cached_response, block);
+
+    // Responses for block tags must not be cached, even if the tag resolves to a block in an executed batch.
+    let latest_block = api::BlockNumber::Latest;
+    client
+        .get_block_by_number(latest_block, false)
+        .await
+        .unwrap();
+    assert!(cache.get(&block_key(r#"["latest",false]"#)).is_none());
+
+    let batch_details = client.get_l1_batch_details(L1BatchNumber(0)).await.unwrap();
+    assert!(batch_details.is_some());
+    let batch_key = response_cache::ResponseCacheKey::new(
+        "zks_getL1BatchDetails",
+        Some(&serde_json::value::RawValue::from_string("[0]".to_owned()).unwrap()),
+    )
+    .unwrap();
+    assert!(cache.get(&batch_key).is_some());
+
+    cache.roll_back(L1BatchNumber(0));
+    assert!(cache.get(&block_key(r#"["0x0",false]"#)).is_none());
+    assert!(cache.get(&batch_key).is_none());
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+}
@@ -4,17 +4,21 @@ use std::sync::atomic::{AtomicU32, Ordering};
 
 use itertools::Itertools;
 use multivm::{
-    interface::{ExecutionResult, VmRevertReason},
+    interface::{ExecutionResult, Halt, VmExecutionStatistics, VmRevertReason},
     vm_latest::{VmExecutionLogs, VmExecutionResultAndLogs},
 };
 use zksync_types::{
-    api::{ApiStorageLog, Log},
-    get_intrinsic_constants,
-    transaction_request::CallRequest,
+    api::{ApiStorageLog, Log, OverrideAccount, StateOverride},
+    ethabi, get_intrinsic_constants,
+    l2::TransactionType,
+    transaction_request::{CallRequest, PaymasterParams},
+    vm_trace::Call,
+    web3::AccessListItem,
     zk_evm_types::{LogQuery, Timestamp},
-    K256PrivateKey, L2ChainId, PackedEthSignature, StorageLogQuery, StorageLogQueryType, U256,
+    K256PrivateKey, L2ChainId, PackedEthSignature, StorageLogQuery, StorageLogQueryType,
+    BOOTLOADER_ADDRESS, L2_BASE_TOKEN_ADDRESS, NONCE_HOLDER_ADDRESS, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{address_to_h256, u256_to_h256};
 use zksync_web3_decl::namespaces::DebugNamespaceClient;
 
 use super::*;
@@ -63,7 +67,9 @@ impl HttpTest for CallTest {
         client: &DynClient<L2>,
         _pool: &ConnectionPool<Core>,
     ) -> anyhow::Result<()> {
-        let call_result = client.call(Self::call_request(b"pending"), None).await?;
+        let call_result = client
+            .call(Self::call_request(b"pending"), None, None)
+            .await?;
         assert_eq!(call_result.0, b"output");
 
         let valid_block_numbers_and_calldata = [
@@ -74,7 +80,7 @@ impl HttpTest for CallTest {
         for (number, calldata) in valid_block_numbers_and_calldata {
             let number = api::BlockIdVariant::BlockNumber(number);
             let call_result = client
-                .call(Self::call_request(calldata), Some(number))
+                .call(Self::call_request(calldata), Some(number), None)
                 .await?;
             assert_eq!(call_result.0, b"output");
         }
@@ -82,7 +88,7 @@ impl HttpTest for CallTest {
         let invalid_block_number = api::BlockNumber::from(100);
         let number = api::BlockIdVariant::BlockNumber(invalid_block_number);
         let error = client
-            .call(Self::call_request(b"100"), Some(number))
+            .call(Self::call_request(b"100"), Some(number), None)
             .await
             .unwrap_err();
         if let ClientError::Call(error) = error {
@@ -100,6 +106,317 @@ async fn call_method_basics() {
     test_http_server(CallTest).await;
 }
 
+#[derive(Debug)]
+struct CallWithInvalidStateOverrideTest;
+
+#[async_trait]
+impl HttpTest for CallWithInvalidStateOverrideTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        CallTest::create_executor(L2BlockNumber(0))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let invalid_overrides = [
+            // Bytecode length is not divisible by 32
+            OverrideAccount {
+                code: Some(vec![1; 31].into()),
+                ..OverrideAccount::default()
+            },
+            // Both `state` and `stateDiff` are specified
+            OverrideAccount {
+                state: Some(HashMap::new()),
+                state_diff: Some(HashMap::new()),
+                ..OverrideAccount::default()
+            },
+        ];
+
+        for account in invalid_overrides {
+            let state_override =
+                StateOverride::new(HashMap::from([(Address::repeat_byte(2), account)]));
+            let error = client
+                .call(
+                    CallTest::call_request(b"pending"),
+                    None,
+                    Some(state_override.clone()),
+                )
+                .await
+                .unwrap_err();
+            assert_invalid_state_override_error(&error);
+
+            let error = client
+                .estimate_gas(
+                    CallTest::call_request(b"pending"),
+                    None,
+                    Some(state_override),
+                )
+                .await
+                .unwrap_err();
+            assert_invalid_state_override_error(&error);
+        }
+        Ok(())
+    }
+}
+
+fn assert_invalid_state_override_error(error: &ClientError) {
+    if let ClientError::Call(error) = error {
+        assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+        assert!(
+            error.message().contains("state override"),
+            "{}",
+            error.message()
+        );
+    } else {
+        panic!("Unexpected error: {error:?}");
+    }
+}
+
+#[tokio::test]
+async fn call_method_with_invalid_state_override() {
+    test_http_server(CallWithInvalidStateOverrideTest).await;
+}
+
+#[derive(Debug)]
+struct SimulateTest;
+
+impl SimulateTest {
+    fn payload(calls: Vec<CallRequest>) -> api::SimulatePayload {
+        api::SimulatePayload {
+            block_state_calls: vec![api::SimulatedBlockCalls {
+                calls,
+                ..api::SimulatedBlockCalls::default()
+            }],
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for SimulateTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, block_args| {
+            assert_eq!(block_args.resolved_block_number(), L2BlockNumber(1));
+            match tx.execute.calldata() {
+                b"approve" => ExecutionResult::Success {
+                    output: b"output".to_vec(),
+                },
+                b"swap" => ExecutionResult::Revert {
+                    output: VmRevertReason::VmError,
+                },
+                data => panic!("Unexpected calldata: {data:?}"),
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let calls = vec![
+            CallTest::call_request(b"approve"),
+            CallTest::call_request(b"swap"),
+        ];
+        let blocks = client
+            .simulate_v1(Self::payload(calls.clone()), None)
+            .await?;
+        let [block] = blocks.as_slice() else {
+            panic!("Unexpected simulated blocks: {blocks:?}");
+        };
+        assert_eq!(block.number, 1.into());
+        let [approve_result, swap_result] = block.calls.as_slice() else {
+            panic!("Unexpected call results: {:?}", block.calls);
+        };
+        assert_eq!(approve_result.status, 1.into());
+        assert_eq!(approve_result.return_data.0, b"output");
+        assert_eq!(approve_result.error, None);
+        assert_eq!(swap_result.status, 0.into());
+        let swap_error = swap_result.error.as_ref().unwrap();
+        assert_eq!(swap_error.code, 3);
+
+        let empty_blocks = client
+            .simulate_v1(api::SimulatePayload::default(), None)
+            .await?;
+        assert!(empty_blocks.is_empty());
+
+        let invalid_payloads = [
+            Self::payload(vec![]),
+            api::SimulatePayload {
+                block_state_calls: vec![
+                    Self::payload(calls.clone()).block_state_calls[0].clone(),
+                    Self::payload(calls).block_state_calls[0].clone(),
+                ],
+            },
+        ];
+        for payload in invalid_payloads {
+            let error = client.simulate_v1(payload, None).await.unwrap_err();
+            if let ClientError::Call(error) = error {
+                assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+            } else {
+                panic!("Unexpected error: {error:?}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulate_method_basics() {
+    test_http_server(SimulateTest).await;
+}
+
+#[derive(Debug)]
+struct CreateAccessListTest;
+
+impl CreateAccessListTest {
+    fn storage_logs() -> Vec<StorageLogQuery> {
+        let log_query = LogQuery {
+            timestamp: Timestamp(100),
+            tx_number_in_block: 1,
+            aux_byte: 1,
+            shard_id: 0,
+            address: Address::repeat_byte(2),
+            key: U256::from(2),
+            read_value: U256::zero(),
+            written_value: U256::zero(),
+            rw_flag: false,
+            rollback: false,
+            is_service: false,
+        };
+        let accessed_slots = [
+            (Address::repeat_byte(2), 2),
+            (Address::repeat_byte(1), 5),
+            (Address::repeat_byte(2), 1),
+            (Address::repeat_byte(2), 2),
+            // System contract storage must not be included into the access list.
+            (NONCE_HOLDER_ADDRESS, 1),
+        ];
+        accessed_slots
+            .into_iter()
+            .map(|(address, key)| StorageLogQuery {
+                log_query: LogQuery {
+                    address,
+                    key: key.into(),
+                    ..log_query
+                },
+                log_type: StorageLogQueryType::Read,
+            })
+            .collect()
+    }
+
+    fn call_traces() -> Vec<Call> {
+        let call = |from, to, calls| Call {
+            from,
+            to,
+            calls,
+            ..Call::default()
+        };
+        let initiator = Address::repeat_byte(1);
+        let recipient = Address::repeat_byte(2);
+        let nested_calls = vec![
+            call(recipient, Address::repeat_byte(3), vec![]),
+            call(
+                recipient,
+                Address::repeat_byte(4),
+                vec![call(Address::repeat_byte(4), L2_BASE_TOKEN_ADDRESS, vec![])],
+            ),
+        ];
+        vec![call(
+            BOOTLOADER_ADDRESS,
+            initiator,
+            vec![call(initiator, recipient, nested_calls)],
+        )]
+    }
+}
+
+#[async_trait]
+impl HttpTest for CreateAccessListTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses_with_logs(|tx, _| {
+            let (result, storage_logs) = match tx.execute.calldata() {
+                b"call" => (
+                    ExecutionResult::Success {
+                        output: b"output".to_vec(),
+                    },
+                    Self::storage_logs(),
+                ),
+                b"revert" => (
+                    ExecutionResult::Revert {
+                        output: VmRevertReason::VmError,
+                    },
+                    vec![],
+                ),
+                data => panic!("Unexpected calldata: {data:?}"),
+            };
+            VmExecutionResultAndLogs {
+                result,
+                logs: VmExecutionLogs {
+                    storage_logs,
+                    ..VmExecutionLogs::default()
+                },
+                statistics: VmExecutionStatistics {
+                    gas_used: 100,
+                    ..VmExecutionStatistics::default()
+                },
+                refunds: Default::default(),
+            }
+        });
+        tx_executor.set_call_traces(|tx| match tx.execute.calldata() {
+            b"call" => Self::call_traces(),
+            _ => vec![],
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let result = client
+            .create_access_list(CallTest::call_request(b"call"), None)
+            .await?;
+        assert_eq!(result.gas_used, 100.into());
+        assert_eq!(result.error, None);
+        let expected_access_list = vec![
+            AccessListItem {
+                address: Address::repeat_byte(1),
+                storage_keys: vec![H256::from_low_u64_be(5)],
+            },
+            AccessListItem {
+                address: Address::repeat_byte(2),
+                storage_keys: vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)],
+            },
+            AccessListItem {
+                address: Address::repeat_byte(3),
+                storage_keys: vec![],
+            },
+            AccessListItem {
+                address: Address::repeat_byte(4),
+                storage_keys: vec![],
+            },
+        ];
+        assert_eq!(result.access_list, expected_access_list);
+
+        let result = client
+            .create_access_list(CallTest::call_request(b"revert"), None)
+            .await?;
+        assert!(result.access_list.is_empty());
+        assert!(result.error.is_some());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn create_access_list_basics() {
+    test_http_server(CreateAccessListTest).await;
+}
+
 #[derive(Debug)]
 struct CallTestAfterSnapshotRecovery;
 
@@ -120,7 +437,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         _pool: &ConnectionPool<Core>,
     ) -> anyhow::Result<()> {
         let call_result = client
-            .call(CallTest::call_request(b"pending"), None)
+            .call(CallTest::call_request(b"pending"), None, None)
             .await?;
         assert_eq!(call_result.0, b"output");
         let pending_block_number = api::BlockIdVariant::BlockNumber(api::BlockNumber::Pending);
@@ -128,6 +445,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
             .call(
                 CallTest::call_request(b"pending"),
                 Some(pending_block_number),
+                None,
             )
             .await?;
         assert_eq!(call_result.0, b"output");
@@ -137,7 +455,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         for number in pruned_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number.into());
             let error = client
-                .call(CallTest::call_request(b"pruned"), Some(number))
+                .call(CallTest::call_request(b"pruned"), Some(number), None)
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, first_local_l2_block);
@@ -147,7 +465,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         for number in first_l2_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number);
             let call_result = client
-                .call(CallTest::call_request(b"first"), Some(number))
+                .call(CallTest::call_request(b"first"), Some(number), None)
                 .await?;
             assert_eq!(call_result.0, b"output");
         }
@@ -269,6 +587,78 @@ async fn send_raw_transaction_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct TraceValidationTest {
+    fail_validation: bool,
+}
+
+#[async_trait]
+impl HttpTest for TraceValidationTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        let fail_validation = self.fail_validation;
+        tx_executor.set_tx_responses(move |tx, block_args| {
+            assert_eq!(
+                tx.hash(),
+                SendRawTransactionTest::transaction_bytes_and_hash().1
+            );
+            assert_eq!(block_args.resolved_block_number(), L2BlockNumber(1));
+            if fail_validation {
+                ExecutionResult::Halt {
+                    reason: Halt::ValidationFailed(VmRevertReason::General {
+                        msg: "invalid signature".to_owned(),
+                        data: vec![],
+                    }),
+                }
+            } else {
+                ExecutionResult::Success { output: vec![] }
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let (tx_bytes, tx_hash) = SendRawTransactionTest::transaction_bytes_and_hash();
+        let trace = client.trace_validation(tx_bytes.into()).await?;
+        assert!(trace.violations.is_empty(), "{trace:?}");
+        if self.fail_validation {
+            let error = trace.error.as_ref().unwrap();
+            assert!(error.contains("invalid signature"), "{error}");
+        } else {
+            assert!(trace.is_valid(), "{trace:?}");
+        }
+
+        // Validation tracing must not submit the transaction.
+        let mut storage = pool.connection().await?;
+        let tx = storage
+            .transactions_web3_dal()
+            .get_transaction_by_hash(tx_hash, L2ChainId::default())
+            .await?;
+        assert!(tx.is_none(), "{tx:?}");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_validation() {
+    test_http_server(TraceValidationTest {
+        fail_validation: false,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn tracing_failed_validation() {
+    test_http_server(TraceValidationTest {
+        fail_validation: true,
+    })
+    .await;
+}
+
 #[derive(Debug)]
 struct SendTransactionWithDetailedOutputTest;
 
@@ -400,7 +790,10 @@ async fn send_raw_transaction_with_detailed_output() {
 struct TraceCallTest;
 
 impl TraceCallTest {
-    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugCall) {
+    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugTraceResult) {
+        let api::DebugTraceResult::CallTrace(call_result) = call_result else {
+            panic!("Unexpected trace: {call_result:?}");
+        };
         assert_eq!(call_result.from, Address::zero());
         assert_eq!(call_result.gas, call_request.gas.unwrap());
         assert_eq!(call_result.value, call_request.value.unwrap());
@@ -477,6 +870,47 @@ async fn trace_call_basics() {
     test_http_server(TraceCallTest).await;
 }
 
+#[derive(Debug)]
+struct TraceCallWithStructLoggerTest;
+
+#[async_trait]
+impl HttpTest for TraceCallWithStructLoggerTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        CallTest::create_executor(L2BlockNumber(0))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let call_request = CallTest::call_request(b"pending");
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::StructLogger,
+            struct_logger_config: api::StructLoggerConfig {
+                disable_storage: true,
+                limit: 100,
+                ..api::StructLoggerConfig::default()
+            },
+            ..api::TracerConfig::default()
+        };
+        let call_result = client.trace_call(call_request, None, Some(options)).await?;
+        let api::DebugTraceResult::StructLogs(trace) = call_result else {
+            panic!("Unexpected trace: {call_result:?}");
+        };
+        assert!(!trace.failed);
+        assert_eq!(trace.return_value.0, b"output");
+        // The mock executor doesn't run tracers.
+        assert!(trace.struct_logs.is_empty());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn trace_call_with_struct_logger() {
+    test_http_server(TraceCallWithStructLoggerTest).await;
+}
+
 #[derive(Debug)]
 struct TraceCallTestAfterSnapshotRecovery;
 
@@ -510,7 +944,7 @@ impl HttpTest for TraceCallTestAfterSnapshotRecovery {
         for number in pruned_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number.into());
             let error = client
-                .call(CallTest::call_request(b"pruned"), Some(number))
+                .call(CallTest::call_request(b"pruned"), Some(number), None)
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, first_local_l2_block);
@@ -590,7 +1024,7 @@ impl HttpTest for EstimateGasTest {
         for threshold in [10_000, 50_000, 100_000, 1_000_000] {
             self.gas_limit_threshold.store(threshold, Ordering::Relaxed);
             let output = client
-                .estimate_gas(l2_transaction.clone().into(), None)
+                .estimate_gas(l2_transaction.clone().into(), None, None)
                 .await?;
             assert!(
                 output >= U256::from(threshold),
@@ -615,10 +1049,15 @@ impl HttpTest for EstimateGasTest {
         let mut call_request = CallRequest::from(l2_transaction);
         call_request.from = Some(SendRawTransactionTest::private_key().address());
         call_request.value = Some(1_000_000.into());
-        client.estimate_gas(call_request.clone(), None).await?;
+        client
+            .estimate_gas(call_request.clone(), None, None)
+            .await?;
 
         call_request.value = Some(U256::max_value());
-        let error = client.estimate_gas(call_request, None).await.unwrap_err();
+        let error = client
+            .estimate_gas(call_request, None, None)
+            .await
+            .unwrap_err();
         if let ClientError::Call(error) = error {
             let error_msg = error.message();
             assert!(
@@ -641,3 +1080,157 @@ async fn estimate_gas_basics() {
 async fn estimate_gas_after_snapshot_recovery() {
     test_http_server(EstimateGasTest::new(true)).await;
 }
+
+#[derive(Debug)]
+struct EstimateGasWithNonceOverrideTest;
+
+impl EstimateGasWithNonceOverrideTest {
+    const NONCE: u32 = 5;
+}
+
+#[async_trait]
+impl HttpTest for EstimateGasWithNonceOverrideTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, _| {
+            assert_eq!(tx.nonce(), Some(Nonce(Self::NONCE)));
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let l2_transaction = create_l2_transaction(10, 100);
+        let initiator = l2_transaction.initiator_account();
+        let mut call_request = CallRequest::from(l2_transaction);
+        call_request.nonce = None;
+        let account_override = OverrideAccount {
+            nonce: Some(Self::NONCE.into()),
+            ..OverrideAccount::default()
+        };
+        let state_override = StateOverride::new(HashMap::from([(initiator, account_override)]));
+
+        client
+            .estimate_gas(call_request, None, Some(state_override))
+            .await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn estimate_gas_with_nonce_override() {
+    test_http_server(EstimateGasWithNonceOverrideTest).await;
+}
+
+#[derive(Debug)]
+struct EstimateFeeWithPaymasterTest;
+
+impl EstimateFeeWithPaymasterTest {
+    const PAYMASTER: Address = Address::repeat_byte(0x22);
+    const TOKEN: Address = Address::repeat_byte(0x11);
+
+    fn transfer_event(token: Address, from: Address, to: Address, amount: u64) -> VmEvent {
+        let signature = ethabi::long_signature(
+            "Transfer",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        );
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: token,
+            indexed_topics: vec![signature, address_to_h256(&from), address_to_h256(&to)],
+            value: u256_to_h256(amount.into()).as_bytes().to_vec(),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for EstimateFeeWithPaymasterTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses_with_logs(|tx, _| {
+            assert_eq!(
+                tx.payer(),
+                Self::PAYMASTER,
+                "paymaster params are not propagated"
+            );
+            let initiator = tx.initiator_account();
+            let events = vec![
+                Self::transfer_event(Self::TOKEN, initiator, Self::PAYMASTER, 4_000),
+                Self::transfer_event(
+                    L2_BASE_TOKEN_ADDRESS,
+                    Self::PAYMASTER,
+                    BOOTLOADER_ADDRESS,
+                    1_000,
+                ),
+                Self::transfer_event(
+                    L2_BASE_TOKEN_ADDRESS,
+                    BOOTLOADER_ADDRESS,
+                    Self::PAYMASTER,
+                    400,
+                ),
+            ];
+            VmExecutionResultAndLogs {
+                result: ExecutionResult::Success { output: vec![] },
+                logs: VmExecutionLogs {
+                    events,
+                    ..VmExecutionLogs::default()
+                },
+                statistics: VmExecutionStatistics::default(),
+                refunds: Default::default(),
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut l2_transaction = create_l2_transaction(10, 100);
+        l2_transaction.common_data.transaction_type = TransactionType::EIP712Transaction;
+        let paymaster_input = ethabi::encode(&[
+            ethabi::Token::Address(Self::TOKEN),
+            ethabi::Token::Uint(5_000.into()),
+            ethabi::Token::Bytes(vec![]),
+        ]);
+        let selector = ethabi::short_signature(
+            "approvalBased",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+                ethabi::ParamType::Bytes,
+            ],
+        );
+        l2_transaction.common_data.paymaster_params = PaymasterParams {
+            paymaster: Self::PAYMASTER,
+            paymaster_input: [selector.as_slice(), &paymaster_input].concat(),
+        };
+
+        let estimate = client
+            .estimate_fee_with_paymaster(l2_transaction.into())
+            .await?;
+        assert!(estimate.fee.gas_limit > U256::zero());
+        assert_eq!(estimate.paymaster, Some(Self::PAYMASTER));
+        assert_eq!(estimate.paymaster_charged, 600.into());
+        assert_eq!(estimate.initiator_charged, 0.into());
+        let approval_based = estimate.approval_based.expect("no approval-based preview");
+        assert_eq!(approval_based.token, Self::TOKEN);
+        assert_eq!(approval_based.min_allowance, 5_000.into());
+        assert_eq!(approval_based.token_charged, 4_000.into());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn estimate_fee_with_paymaster() {
+    test_http_server(EstimateFeeWithPaymasterTest).await;
+}
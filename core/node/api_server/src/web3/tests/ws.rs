@@ -5,10 +5,14 @@ use std::{collections::HashSet, str::FromStr};
 use assert_matches::assert_matches;
 use async_trait::async_trait;
 use http::StatusCode;
+use serde_json::json;
 use tokio::sync::watch;
 use zksync_config::configs::chain::NetworkConfig;
 use zksync_dal::ConnectionPool;
-use zksync_types::{api, Address, L1BatchNumber, H160, H2048, H256, U64};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, api, web3::SyncState, Address, L1BatchNumber,
+    L2ChainId, H160, H2048, H256, U64,
+};
 use zksync_web3_decl::{
     client::{WsClient, L2},
     jsonrpsee::{
@@ -18,9 +22,10 @@ use zksync_web3_decl::{
             ClientError,
         },
         rpc_params,
+        ws_client::WsClientBuilder,
     },
     namespaces::{EthNamespaceClient, ZksNamespaceClient},
-    types::{BlockHeader, Bytes, PubSubFilter},
+    types::{BlockHeader, Bytes, L1BatchStatus, L1BatchStatusUpdate, PubSubFilter},
 };
 
 use super::*;
@@ -112,7 +117,7 @@ async fn notifiers_start_after_snapshot_recovery() {
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let (events_sender, mut events_receiver) = mpsc::unbounded_channel();
-    let mut subscribe_logic = EthSubscribe::new();
+    let mut subscribe_logic = EthSubscribe::new(L2ChainId::default());
     subscribe_logic.set_events_sender(events_sender);
     let notifier_handles =
         subscribe_logic.spawn_notifiers(pool.clone(), POLL_INTERVAL, stop_receiver);
@@ -136,6 +141,7 @@ async fn notifiers_start_after_snapshot_recovery() {
             SubscriptionType::Blocks,
             SubscriptionType::Txs,
             SubscriptionType::Logs,
+            SubscriptionType::L1Batches,
         ],
     )
     .await;
@@ -351,6 +357,198 @@ async fn basic_subscriptions_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct FilteredPendingTransactionsTest;
+
+#[async_trait]
+impl WsTest for FilteredPendingTransactionsTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::Txs]).await;
+
+        let tx = create_l2_transaction(1, 2);
+        let initiator = tx.initiator_account();
+        let recipient = tx.recipient_account();
+
+        let params = rpc_params!["newPendingTransactions", true];
+        let mut full_txs_subscription = client
+            .subscribe::<api::Transaction, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::FullTxs).await;
+
+        let params = rpc_params!["newPendingTransactions", json!({ "from": initiator })];
+        let mut filtered_hashes_subscription = client
+            .subscribe::<H256, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::FullTxs).await;
+
+        let filter =
+            json!({ "to": [Address::repeat_byte(1), recipient], "fullTransactions": true });
+        let params = rpc_params!["newPendingTransactions", filter];
+        let mut filtered_txs_subscription = client
+            .subscribe::<api::Transaction, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::FullTxs).await;
+
+        let params = rpc_params!["newPendingTransactions", json!({ "from": 1 })];
+        let err = client
+            .subscribe::<H256, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ClientError::Call(_));
+
+        let mut storage = pool.connection().await?;
+        let tx_result = execute_l2_transaction(tx);
+        let new_tx_hash = tx_result.hash;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[tx_result]).await?;
+        drop(storage);
+
+        let received_tx = tokio::time::timeout(TEST_TIMEOUT, full_txs_subscription.next())
+            .await
+            .context("Timed out waiting for new tx")?
+            .context("Pending txs subscription terminated")??;
+        assert_eq!(received_tx.hash, new_tx_hash);
+        assert_eq!(received_tx.from, Some(initiator));
+        assert_eq!(received_tx.to, Some(recipient));
+
+        let received_tx_hash =
+            tokio::time::timeout(TEST_TIMEOUT, filtered_hashes_subscription.next())
+                .await
+                .context("Timed out waiting for new tx hash")?
+                .context("Pending txs subscription terminated")??;
+        assert_eq!(received_tx_hash, new_tx_hash);
+
+        let received_tx = tokio::time::timeout(TEST_TIMEOUT, filtered_txs_subscription.next())
+            .await
+            .context("Timed out waiting for new tx")?
+            .context("Pending txs subscription terminated")??;
+        assert_eq!(received_tx.hash, new_tx_hash);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn filtered_pending_transactions_subscriptions() {
+    test_ws_server(FilteredPendingTransactionsTest).await;
+}
+
+#[derive(Debug)]
+struct L1BatchSubscriptionsTest;
+
+#[async_trait]
+impl WsTest for L1BatchSubscriptionsTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        let params = rpc_params!["l1Batches"];
+        let mut all_updates_subscription = client
+            .subscribe::<L1BatchStatusUpdate, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1Batches).await;
+
+        let params = rpc_params!["l1Batches", json!({ "statuses": "committed" })];
+        let mut commit_updates_subscription = client
+            .subscribe::<L1BatchStatusUpdate, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1Batches).await;
+
+        let params = rpc_params!["l1Batches", json!({ "statuses": "finalized" })];
+        let err = client
+            .subscribe::<L1BatchStatusUpdate, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ClientError::Call(_));
+
+        // The notifier only polls Postgres if there are subscribers. Wait for 2 notifier iterations so that
+        // at least one of them fully happens after subscribing and takes the genesis L1 batch as the starting point.
+        for _ in 0..2 {
+            wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::L1Batches]).await;
+        }
+
+        let mut storage = pool.connection().await?;
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        storage
+            .eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(1),
+                AggregatedActionType::Commit,
+                H256::repeat_byte(1),
+                chrono::Utc::now(),
+            )
+            .await?;
+        drop(storage);
+
+        let expected_updates = [
+            L1BatchStatusUpdate {
+                l1_batch_number: L1BatchNumber(1),
+                status: L1BatchStatus::Sealed,
+            },
+            L1BatchStatusUpdate {
+                l1_batch_number: L1BatchNumber(1),
+                status: L1BatchStatus::Committed,
+            },
+        ];
+        for expected_update in &expected_updates {
+            let update = tokio::time::timeout(TEST_TIMEOUT, all_updates_subscription.next())
+                .await
+                .context("Timed out waiting for L1 batch update")?
+                .context("L1 batches subscription terminated")??;
+            assert_eq!(update, *expected_update);
+        }
+        let update = tokio::time::timeout(TEST_TIMEOUT, commit_updates_subscription.next())
+            .await
+            .context("Timed out waiting for L1 batch update")?
+            .context("L1 batches subscription terminated")??;
+        assert_eq!(update, expected_updates[1]);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l1_batch_subscriptions() {
+    test_ws_server(L1BatchSubscriptionsTest).await;
+}
+
+#[derive(Debug)]
+struct SyncingSubscriptionTest;
+
+#[async_trait]
+impl WsTest for SyncingSubscriptionTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        _pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        let params = rpc_params!["syncing"];
+        let mut syncing_subscription = client
+            .subscribe::<SyncState, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Syncing).await;
+
+        // The main node is always synced.
+        let sync_state = tokio::time::timeout(TEST_TIMEOUT, syncing_subscription.next())
+            .await
+            .context("Timed out waiting for sync state")?
+            .context("Syncing subscription terminated")??;
+        assert_eq!(sync_state, SyncState::NotSyncing);
+        syncing_subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn syncing_subscription() {
+    test_ws_server(SyncingSubscriptionTest).await;
+}
+
 #[derive(Debug)]
 struct LogSubscriptionsTest {
     snapshot_recovery: bool,
@@ -681,7 +879,7 @@ impl WsTest for RateLimitingTest {
         if let ClientError::Call(error) = expected_err {
             assert_eq!(error.code() as u16, StatusCode::TOO_MANY_REQUESTS.as_u16());
             assert_eq!(error.message(), "Too many requests");
-            assert!(error.data().is_none());
+            assert_retry_hint(error.data());
         } else {
             panic!("Unexpected error returned: {expected_err}");
         }
@@ -728,7 +926,7 @@ impl WsTest for BatchGetsRateLimitedTest {
 
         assert_eq!(error.code() as u16, StatusCode::TOO_MANY_REQUESTS.as_u16());
         assert_eq!(error.message(), "Too many requests");
-        assert!(error.data().is_none());
+        assert_retry_hint(error.data());
 
         Ok(())
     }
@@ -742,3 +940,55 @@ impl WsTest for BatchGetsRateLimitedTest {
 async fn batch_rate_limiting() {
     test_ws_server(BatchGetsRateLimitedTest).await;
 }
+
+#[tokio::test]
+async fn per_client_rate_limiting() {
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let mut server_handles =
+        spawn_rate_limited_server(ApiTransportLabel::Ws, stop_receiver, |builder| builder).await;
+    let local_addr = server_handles.wait_until_ready().await;
+    let url = format!("ws://{local_addr}");
+    let connect = |api_key: Option<&str>| {
+        let mut headers = http::HeaderMap::new();
+        if let Some(api_key) = api_key {
+            headers.insert(API_KEY_HEADER, api_key.parse().unwrap());
+        }
+        WsClientBuilder::default().set_headers(headers).build(&url)
+    };
+
+    // Connections authenticated with the same API key share the quota.
+    let client = connect(Some("first")).await.unwrap();
+    let other_client = connect(Some("first")).await.unwrap();
+    let _: U64 = client
+        .request("eth_blockNumber", rpc_params![])
+        .await
+        .unwrap();
+    let _: U64 = other_client
+        .request("eth_chainId", rpc_params![])
+        .await
+        .unwrap();
+    let _: U64 = client.request("eth_chainId", rpc_params![]).await.unwrap();
+    let err = other_client
+        .request::<U64, _>("eth_chainId", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_rate_limited(err);
+
+    let client = connect(Some("second")).await.unwrap();
+    let _: U64 = client.request("eth_chainId", rpc_params![]).await.unwrap();
+
+    // Connections without a known API key are identified by the peer address.
+    let client = connect(None).await.unwrap();
+    let other_client = connect(Some("unknown")).await.unwrap();
+    for _ in 0..5 {
+        let _: U64 = client.request("eth_chainId", rpc_params![]).await.unwrap();
+    }
+    let err = other_client
+        .request::<U64, _>("eth_chainId", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_rate_limited(err);
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+}
@@ -1,4 +1,4 @@
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{fmt, path::Path, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use serde::Serialize;
@@ -97,6 +97,21 @@ pub struct BlockReverter {
     state_keeper_cache_path: Option<String>,
     merkle_tree_path: Option<String>,
     snapshots_object_store: Option<Arc<dyn ObjectStore>>,
+    rollback_hooks: RollbackHooks,
+}
+
+type RollbackHook = Box<dyn Fn(L1BatchNumber) + Send + Sync>;
+
+#[derive(Default)]
+struct RollbackHooks(Vec<RollbackHook>);
+
+impl fmt::Debug for RollbackHooks {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RollbackHooks")
+            .field("len", &self.0.len())
+            .finish()
+    }
 }
 
 impl BlockReverter {
@@ -109,6 +124,7 @@ impl BlockReverter {
             state_keeper_cache_path: None,
             merkle_tree_path: None,
             snapshots_object_store: None,
+            rollback_hooks: RollbackHooks::default(),
         }
     }
 
@@ -145,6 +161,16 @@ impl BlockReverter {
         self
     }
 
+    /// Adds a hook called after a successful rollback with the number of the last retained L1 batch. Hooks can be used
+    /// to invalidate in-memory data derived from the rolled back state in the same process (e.g., API server caches).
+    pub fn add_rollback_hook(
+        &mut self,
+        hook: impl Fn(L1BatchNumber) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.rollback_hooks.0.push(Box::new(hook));
+        self
+    }
+
     /// Rolls back previously enabled DBs (Postgres + RocksDB) and the snapshot object store to a previous state.
     pub async fn roll_back(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
         if !self.allow_rolling_back_executed_batches {
@@ -177,6 +203,9 @@ impl BlockReverter {
             );
         }
 
+        for hook in &self.rollback_hooks.0 {
+            hook(last_l1_batch_to_keep);
+        }
         Ok(())
     }
 
@@ -8,7 +8,7 @@ use test_casing::test_casing;
 use tokio::sync::watch;
 use zksync_dal::Connection;
 use zksync_merkle_tree::TreeInstruction;
-use zksync_object_store::{Bucket, MockObjectStore};
+use zksync_object_store::{Bucket, KeysPage, MockObjectStore};
 use zksync_state::ReadStorage;
 use zksync_types::{
     block::{L1BatchHeader, L2BlockHeader},
@@ -142,13 +142,17 @@ async fn block_reverter_basics(sync_merkle_tree: bool) {
         .await
         .unwrap();
 
+    let rolled_back_to = Arc::new(Mutex::new(vec![]));
+    let rolled_back_to_for_hook = rolled_back_to.clone();
     BlockReverter::new(NodeRole::External, pool.clone())
         .enable_rolling_back_postgres()
         .enable_rolling_back_merkle_tree(merkle_tree_path.to_str().unwrap().to_owned())
         .enable_rolling_back_state_keeper_cache(sk_cache_path.to_str().unwrap().to_owned())
+        .add_rollback_hook(move |number| rolled_back_to_for_hook.lock().unwrap().push(number))
         .roll_back(L1BatchNumber(5))
         .await
         .unwrap();
+    assert_eq!(*rolled_back_to.lock().unwrap(), [L1BatchNumber(5)]);
 
     let last_l1_batch_number = storage
         .blocks_dal()
@@ -386,6 +390,15 @@ impl ObjectStore for ErroneousStore {
         })
     }
 
+    async fn list_keys_raw(
+        &self,
+        _bucket: Bucket,
+        _prefix: &str,
+        _page_token: Option<&str>,
+    ) -> Result<KeysPage, ObjectStoreError> {
+        unreachable!("not called by reverter")
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         bucket.to_string()
     }
@@ -0,0 +1,343 @@
+//! Online Merkle tree consistency verification based on sampling subtrees.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_merkle_tree::Key;
+use zksync_types::L1BatchNumber;
+
+use crate::{
+    helpers::{AsyncTreeReader, LazyAsyncTreeReader},
+    metrics::CONSISTENCY_METRICS,
+};
+
+/// Default depth of verified subtrees measured in nibbles. With the depth of 4 nibbles,
+/// a single check covers ~1/65,536 of the tree.
+const DEFAULT_SUBTREE_DEPTH: usize = 4;
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+enum MerkleTreeConsistencyHealth {
+    Initialization,
+    Running {
+        verified_subtrees: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_verified_l1_batch: Option<L1BatchNumber>,
+    },
+    InconsistencyDetected {
+        verified_subtrees: u64,
+        l1_batch_number: L1BatchNumber,
+        subtree_key: Key,
+        subtree_depth: usize,
+        error: String,
+    },
+    ShuttingDown,
+}
+
+impl From<MerkleTreeConsistencyHealth> for Health {
+    fn from(health: MerkleTreeConsistencyHealth) -> Self {
+        let status = match &health {
+            MerkleTreeConsistencyHealth::Initialization
+            | MerkleTreeConsistencyHealth::InconsistencyDetected { .. } => HealthStatus::Affected,
+            MerkleTreeConsistencyHealth::Running { .. } => HealthStatus::Ready,
+            MerkleTreeConsistencyHealth::ShuttingDown => HealthStatus::ShuttingDown,
+        };
+        Health::from(status).with_details(health)
+    }
+}
+
+/// Outcome of a single subtree check.
+#[derive(Debug)]
+enum CheckOutcome {
+    /// The tree is empty; there's nothing to check.
+    EmptyTree,
+    Verified(L1BatchNumber),
+    /// The checked tree version was removed (e.g., reverted) during the check.
+    VersionRemoved(L1BatchNumber),
+    Inconsistency {
+        l1_batch_number: L1BatchNumber,
+        subtree_key: Key,
+        error: anyhow::Error,
+    },
+}
+
+/// Task continuously verifying consistency of the Merkle tree. On each iteration, the task samples a random subtree
+/// of the latest tree version and verifies it (i.e., checks that all nodes in the subtree are present and have
+/// expected hashes). Checks are throttled so that the task has a negligible impact on the tree performance.
+///
+/// Detected inconsistencies are reported via the health check (which is sticky, i.e., the task remains "affected"
+/// once an inconsistency is detected) and metrics; the task does not terminate on an inconsistency.
+#[derive(Debug)]
+#[must_use = "Task should `run()` in a managed Tokio task"]
+pub struct MerkleTreeConsistencyChecker {
+    tree_reader: LazyAsyncTreeReader,
+    tree_updating: watch::Receiver<bool>,
+    health_updater: HealthUpdater,
+    check_interval: Duration,
+    subtree_depth: usize,
+    pause_while_updating: bool,
+}
+
+impl MerkleTreeConsistencyChecker {
+    pub(super) fn new(
+        tree_reader: LazyAsyncTreeReader,
+        tree_updating: watch::Receiver<bool>,
+        check_interval: Duration,
+    ) -> Self {
+        Self {
+            tree_reader,
+            tree_updating,
+            health_updater: ReactiveHealthCheck::new("tree_consistency_checker").1,
+            check_interval,
+            subtree_depth: DEFAULT_SUBTREE_DEPTH,
+            pause_while_updating: true,
+        }
+    }
+
+    /// Sets the depth of verified subtrees measured in nibbles (i.e., 4-bit chunks of tree keys).
+    /// The greater the depth, the smaller the verified subtrees. The depth of 0 corresponds to verifying
+    /// the entire tree on each check, which is only reasonable for small trees.
+    pub fn with_subtree_depth(mut self, subtree_depth: usize) -> Self {
+        self.subtree_depth = subtree_depth;
+        self
+    }
+
+    /// Sets whether checks should be paused while the tree is processing L1 batches (by default, they are).
+    pub fn pause_while_updating(mut self, pause: bool) -> Self {
+        self.pause_while_updating = pause;
+        self
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn check_random_subtree(&self, reader: &AsyncTreeReader) -> CheckOutcome {
+        let tree_info = reader.clone().info().await;
+        let Some(l1_batch_number) = tree_info.next_l1_batch_number.0.checked_sub(1) else {
+            return CheckOutcome::EmptyTree;
+        };
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let subtree_key = Key::from_big_endian(&rand::random::<[u8; 32]>());
+
+        let started_at = Instant::now();
+        let result = reader
+            .clone()
+            .verify_subtree_consistency(l1_batch_number, subtree_key, self.subtree_depth)
+            .await;
+        let latency = started_at.elapsed();
+        let Err(error) = result else {
+            CONSISTENCY_METRICS.subtree_latency.observe(latency);
+            tracing::debug!(
+                "Verified subtree with key {subtree_key:x} and depth {} for L1 batch #{l1_batch_number} in {latency:?}",
+                self.subtree_depth
+            );
+            return CheckOutcome::Verified(l1_batch_number);
+        };
+
+        // The checked version may have been removed by a revert or pruning, in which case the error is expected.
+        let tree_info = reader.clone().info().await;
+        let version_removed = tree_info.next_l1_batch_number <= l1_batch_number
+            || tree_info
+                .min_l1_batch_number
+                .map_or(false, |min_number| min_number > l1_batch_number);
+        if version_removed {
+            CheckOutcome::VersionRemoved(l1_batch_number)
+        } else {
+            CheckOutcome::Inconsistency {
+                l1_batch_number,
+                subtree_key,
+                error,
+            }
+        }
+    }
+
+    /// Waits until the tree finishes processing L1 batches. Returns `false` if the task should terminate.
+    async fn wait_for_idle_tree(&mut self, stop_receiver: &mut watch::Receiver<bool>) -> bool {
+        if !self.pause_while_updating {
+            return true;
+        }
+        tokio::select! {
+            res = self.tree_updating.wait_for(|&updating| !updating) => res.is_ok(),
+            _ = stop_receiver.changed() => false,
+        }
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        // The task is "affected" (not functioning) until the Merkle tree is initialized.
+        self.health_updater
+            .update(MerkleTreeConsistencyHealth::Initialization.into());
+
+        let tree_reader = LazyAsyncTreeReader(self.tree_reader.0.clone());
+        let reader = tokio::select! {
+            res = tree_reader.wait() => {
+                match res {
+                    Ok(reader) => reader,
+                    Err(_) => {
+                        tracing::info!("Merkle tree dropped; shutting down tree consistency checker");
+                        return Ok(());
+                    }
+                }
+            }
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop signal received before Merkle tree is initialized; shutting down tree consistency checker");
+                return Ok(());
+            }
+        };
+        let health = MerkleTreeConsistencyHealth::Running {
+            verified_subtrees: 0,
+            last_verified_l1_batch: None,
+        };
+        self.health_updater.update(health.into());
+        tracing::info!(
+            "Starting Merkle tree consistency checks with interval {:?} and subtree depth {}",
+            self.check_interval,
+            self.subtree_depth
+        );
+
+        let mut verified_subtrees = 0_u64;
+        let mut inconsistency_detected = false;
+        while !*stop_receiver.borrow_and_update() {
+            if !self.wait_for_idle_tree(&mut stop_receiver).await {
+                break;
+            }
+
+            match self.check_random_subtree(&reader).await {
+                CheckOutcome::EmptyTree => {
+                    tracing::trace!("Merkle tree is empty; skipping consistency check");
+                }
+                CheckOutcome::Verified(l1_batch_number) => {
+                    verified_subtrees += 1;
+                    CONSISTENCY_METRICS.verified_subtrees.inc();
+                    CONSISTENCY_METRICS
+                        .last_verified_l1_batch
+                        .set(l1_batch_number.0.into());
+                    if !inconsistency_detected {
+                        let health = MerkleTreeConsistencyHealth::Running {
+                            verified_subtrees,
+                            last_verified_l1_batch: Some(l1_batch_number),
+                        };
+                        self.health_updater.update(health.into());
+                    }
+                }
+                CheckOutcome::VersionRemoved(l1_batch_number) => {
+                    tracing::info!(
+                        "Tree version for L1 batch #{l1_batch_number} was removed during consistency check; skipping"
+                    );
+                }
+                CheckOutcome::Inconsistency {
+                    l1_batch_number,
+                    subtree_key,
+                    error,
+                } => {
+                    CONSISTENCY_METRICS.detected_inconsistencies.inc();
+                    tracing::error!(
+                        "Merkle tree inconsistency detected for L1 batch #{l1_batch_number} in subtree with key \
+                         {subtree_key:x} and depth {}: {error:#}",
+                        self.subtree_depth
+                    );
+                    inconsistency_detected = true;
+                    let health = MerkleTreeConsistencyHealth::InconsistencyDetected {
+                        verified_subtrees,
+                        l1_batch_number,
+                        subtree_key,
+                        subtree_depth: self.subtree_depth,
+                        error: format!("{error:#}"),
+                    };
+                    self.health_updater.update(health.into());
+                }
+            }
+
+            if tokio::time::timeout(self.check_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        self.health_updater
+            .update(MerkleTreeConsistencyHealth::ShuttingDown.into());
+        tracing::info!("Stop signal received, Merkle tree consistency checker is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use zksync_dal::{ConnectionPool, Core};
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+
+    use super::*;
+    use crate::{
+        tests::{mock_config, reset_db_state},
+        MetadataCalculator,
+    };
+
+    const CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+    #[tokio::test]
+    async fn basic_consistency_checker_workflow() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+
+        let calculator = MetadataCalculator::new(config, None, pool.clone())
+            .await
+            .unwrap();
+        let checker = calculator
+            .consistency_checker(CHECK_INTERVAL)
+            .with_subtree_depth(1);
+        let mut health_check = checker.health_check();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver.clone()));
+        let checker_handle = tokio::spawn(checker.run(stop_receiver));
+
+        health_check
+            .wait_for(|health| {
+                if !matches!(health.status(), HealthStatus::Ready) {
+                    return false;
+                }
+                let details = health.details().unwrap();
+                details["verified_subtrees"].as_u64().unwrap() >= 5
+            })
+            .await;
+
+        stop_sender.send_replace(true);
+        calculator_handle.await.unwrap().unwrap();
+        checker_handle.await.unwrap().unwrap();
+        health_check
+            .wait_for(|health| matches!(health.status(), HealthStatus::ShutDown))
+            .await;
+    }
+
+    #[tokio::test]
+    async fn consistency_checker_premature_exit() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+
+        let calculator = MetadataCalculator::new(config, None, pool).await.unwrap();
+        let checker = calculator.consistency_checker(CHECK_INTERVAL);
+        let mut health_check = checker.health_check();
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let checker_handle = tokio::spawn(checker.run(stop_receiver));
+
+        health_check
+            .wait_for(|health| matches!(health.status(), HealthStatus::Affected))
+            .await;
+        drop(calculator);
+        health_check
+            .wait_for(|health| matches!(health.status(), HealthStatus::ShutDown))
+            .await;
+        checker_handle.await.unwrap().unwrap();
+    }
+}
@@ -342,6 +342,21 @@ impl AsyncTreeReader {
             .map_err(Into::into)
     }
 
+    pub(crate) async fn verify_subtree_consistency(
+        self,
+        l1_batch_number: L1BatchNumber,
+        key: Key,
+        nibble_count: usize,
+    ) -> anyhow::Result<()> {
+        tokio::task::spawn_blocking(move || {
+            self.inner
+                .verify_subtree_consistency(l1_batch_number, key, nibble_count)
+        })
+        .await
+        .context("subtree consistency verification panicked")?
+        .map_err(Into::into)
+    }
+
     pub async fn entries_with_proofs(
         self,
         l1_batch_number: L1BatchNumber,
@@ -3,6 +3,7 @@
 
 use std::{
     num::{NonZeroU32, NonZeroUsize},
+    path::PathBuf,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -17,18 +18,20 @@ use zksync_dal::{ConnectionPool, Core};
 use zksync_health_check::{CheckHealth, HealthUpdater, ReactiveHealthCheck};
 use zksync_object_store::ObjectStore;
 
+pub use self::{
+    consistency::MerkleTreeConsistencyChecker,
+    helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
+    pruning::MerkleTreePruningTask,
+};
 use self::{
     helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
     metrics::{ConfigLabels, METRICS},
     pruning::PruningHandles,
     updater::TreeUpdater,
 };
-pub use self::{
-    helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
-    pruning::MerkleTreePruningTask,
-};
 
 pub mod api_server;
+mod consistency;
 mod helpers;
 mod metrics;
 mod pruning;
@@ -50,6 +53,10 @@ pub struct MetadataCalculatorRecoveryConfig {
     ///
     /// If set to `None`, parallel persistence will be disabled.
     pub parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Path to a Merkle tree snapshot directory (as created by `MerkleTree::export_snapshot()`). If set and the tree
+    /// is empty, the tree is imported from this snapshot instead of being recovered from Postgres. The snapshot must
+    /// correspond to the L1 batch and root hash of the Postgres snapshot recovery.
+    pub tree_snapshot_path: Option<PathBuf>,
 }
 
 impl Default for MetadataCalculatorRecoveryConfig {
@@ -57,6 +64,7 @@ impl Default for MetadataCalculatorRecoveryConfig {
         Self {
             desired_chunk_size: 200_000,
             parallel_persistence_buffer: NonZeroUsize::new(4),
+            tree_snapshot_path: None,
         }
     }
 }
@@ -120,6 +128,7 @@ pub struct MetadataCalculator {
     config: MetadataCalculatorConfig,
     tree_reader: watch::Sender<Option<AsyncTreeReader>>,
     pruning_handles_sender: oneshot::Sender<PruningHandles>,
+    tree_updating: watch::Sender<bool>,
     object_store: Option<Arc<dyn ObjectStore>>,
     pool: ConnectionPool<Core>,
     recovery_pool: ConnectionPool<Core>,
@@ -161,6 +170,7 @@ impl MetadataCalculator {
         Ok(Self {
             tree_reader: watch::channel(None).0,
             pruning_handles_sender: oneshot::channel().0,
+            tree_updating: watch::channel(false).0,
             object_store,
             recovery_pool: pool.clone(),
             pool,
@@ -197,6 +207,16 @@ impl MetadataCalculator {
         MerkleTreePruningTask::new(pruning_handles, self.pool.clone(), poll_interval)
     }
 
+    /// Returns a task that continuously verifies consistency of randomly sampled subtrees of the latest tree version.
+    /// Each `check_interval`, a single subtree is verified.
+    pub fn consistency_checker(&self, check_interval: Duration) -> MerkleTreeConsistencyChecker {
+        MerkleTreeConsistencyChecker::new(
+            self.tree_reader(),
+            self.tree_updating.subscribe(),
+            check_interval,
+        )
+    }
+
     async fn create_tree(&self) -> anyhow::Result<GenericAsyncTree> {
         self.health_updater
             .update(MerkleTreeHealth::Initialization.into());
@@ -242,7 +262,12 @@ impl MetadataCalculator {
         self.health_updater
             .update(MerkleTreeHealth::MainLoop(tree_info).into());
 
-        let updater = TreeUpdater::new(tree, self.max_l1_batches_per_iter, self.object_store);
+        let updater = TreeUpdater::new(
+            tree,
+            self.max_l1_batches_per_iter,
+            self.object_store,
+            self.tree_updating,
+        );
         updater
             .loop_updating_tree(self.delayer, &self.pool, stop_receiver)
             .await
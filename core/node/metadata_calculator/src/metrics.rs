@@ -3,8 +3,8 @@
 use std::time::{Duration, Instant};
 
 use vise::{
-    Buckets, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Info,
-    LatencyObserver, Metrics, Unit,
+    Buckets, Counter, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram,
+    Info, LatencyObserver, Metrics, Unit,
 };
 use zksync_config::configs::database::MerkleTreeMode;
 use zksync_shared_metrics::{BlockStage, APP_METRICS};
@@ -253,3 +253,22 @@ pub(super) struct MetadataCalculatorRecoveryMetrics {
 #[vise::register]
 pub(super) static RECOVERY_METRICS: vise::Global<MetadataCalculatorRecoveryMetrics> =
     vise::Global::new();
+
+/// Metrics for the online Merkle tree consistency checker.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_metadata_calculator_consistency")]
+pub(super) struct MerkleTreeConsistencyMetrics {
+    /// Number of subtrees successfully verified.
+    pub verified_subtrees: Counter,
+    /// Number of detected tree inconsistencies.
+    pub detected_inconsistencies: Counter,
+    /// Latency of verifying a single subtree.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub subtree_latency: Histogram<Duration>,
+    /// Latest L1 batch for which a subtree was verified.
+    pub last_verified_l1_batch: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static CONSISTENCY_METRICS: vise::Global<MerkleTreeConsistencyMetrics> =
+    vise::Global::new();
@@ -1,13 +1,18 @@
 //! Merkle tree pruning logic.
 
-use std::time::Duration;
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use serde::Serialize;
 use tokio::sync::{oneshot, watch};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_merkle_tree::{MerkleTreePruner, MerkleTreePrunerHandle, RocksDBWrapper};
+use zksync_merkle_tree::{
+    CheckpointSchedule, MerkleTreePruner, MerkleTreePrunerHandle, RocksDBWrapper,
+};
 use zksync_types::L1BatchNumber;
 
 pub(super) type PruningHandles = (MerkleTreePruner<RocksDBWrapper>, MerkleTreePrunerHandle);
@@ -44,6 +49,7 @@ pub struct MerkleTreePruningTask {
     pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     poll_interval: Duration,
+    checkpoints: Option<CheckpointSchedule>,
 }
 
 impl MerkleTreePruningTask {
@@ -57,9 +63,27 @@ impl MerkleTreePruningTask {
             pool,
             health_updater: ReactiveHealthCheck::new("tree_pruner").1,
             poll_interval,
+            checkpoints: None,
         }
     }
 
+    /// Exempts each `interval`-th L1 batch from pruning, so that Merkle proofs can be served for it
+    /// even after it's pruned in Postgres. If `retained_l1_batches` is specified, a checkpoint batch
+    /// is pruned once the target retained L1 batch is more than `retained_l1_batches` ahead of it;
+    /// otherwise, checkpoints are retained indefinitely.
+    pub fn with_checkpoints(
+        mut self,
+        interval: NonZeroU32,
+        retained_l1_batches: Option<u32>,
+    ) -> Self {
+        let mut schedule = CheckpointSchedule::new(NonZeroU64::from(interval));
+        if let Some(retained_l1_batches) = retained_l1_batches {
+            schedule = schedule.with_retention(retained_l1_batches.into());
+        }
+        self.checkpoints = Some(schedule);
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
@@ -94,6 +118,10 @@ impl MerkleTreePruningTask {
         // Pruner is not allocated a managed task because it is blocking; its cancellation awareness inherently
         // depends on the pruner handle (i.e., this task).
         pruner.set_poll_interval(self.poll_interval);
+        if let Some(schedule) = self.checkpoints {
+            tracing::info!("Retaining Merkle tree checkpoints according to {schedule:?}");
+            pruner.set_checkpoint_schedule(schedule);
+        }
         let pruner_task_handle = tokio::task::spawn_blocking(|| pruner.run());
 
         while !*stop_receiver.borrow_and_update() {
@@ -148,7 +176,7 @@ mod tests {
     use test_casing::test_casing;
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
     use zksync_node_test_utils::prepare_recovery_snapshot;
-    use zksync_types::{L1BatchNumber, L2BlockNumber};
+    use zksync_types::{L1BatchNumber, L2BlockNumber, U256};
 
     use super::*;
     use crate::{
@@ -209,6 +237,78 @@ mod tests {
             .await;
     }
 
+    #[tokio::test]
+    async fn tree_pruning_with_checkpoints() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+
+        let mut calculator = MetadataCalculator::new(config, None, pool.clone())
+            .await
+            .unwrap();
+        let reader = calculator.tree_reader();
+        let pruning_task = calculator
+            .pruning_task(POLL_INTERVAL)
+            .with_checkpoints(NonZeroU32::new(2).unwrap(), None);
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver.clone()));
+        let pruning_task_handle = tokio::spawn(pruning_task.run(stop_receiver));
+
+        let reader = reader.wait().await.unwrap();
+        while reader.clone().info().await.next_l1_batch_number < L1BatchNumber(6) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        let hashed_keys = vec![U256::one(), U256::from(1_000)];
+        let checkpoint_proofs = reader
+            .clone()
+            .entries_with_proofs(L1BatchNumber(2), hashed_keys.clone())
+            .await
+            .unwrap();
+
+        storage
+            .pruning_dal()
+            .hard_prune_batches_range(L1BatchNumber(3), L2BlockNumber(3))
+            .await
+            .unwrap();
+        while reader.clone().info().await.min_l1_batch_number.unwrap() <= L1BatchNumber(3) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        // The checkpoint batch is retained, while other pruned batches are not.
+        reader
+            .clone()
+            .verify_consistency(L1BatchNumber(2))
+            .await
+            .unwrap();
+        let proofs = reader
+            .clone()
+            .entries_with_proofs(L1BatchNumber(2), hashed_keys.clone())
+            .await
+            .unwrap();
+        assert_eq!(proofs.len(), checkpoint_proofs.len());
+        for (proof, expected_proof) in proofs.iter().zip(&checkpoint_proofs) {
+            assert_eq!(proof.base, expected_proof.base);
+            assert_eq!(proof.merkle_path, expected_proof.merkle_path);
+        }
+        for pruned_number in [1, 3] {
+            let err = reader
+                .clone()
+                .entries_with_proofs(L1BatchNumber(pruned_number), hashed_keys.clone())
+                .await
+                .unwrap_err();
+            assert_eq!(err.missing_version, pruned_number.into());
+        }
+
+        stop_sender.send_replace(true);
+        calculator_handle.await.unwrap().unwrap();
+        pruning_task_handle.await.unwrap().unwrap();
+    }
+
     #[derive(Debug)]
     enum PrematureExitScenario {
         CalculatorDrop,
@@ -27,6 +27,7 @@
 
 use std::{
     fmt, ops,
+    path::PathBuf,
     sync::atomic::{AtomicU64, Ordering},
     time::Instant,
 };
@@ -34,9 +35,10 @@ use std::{
 use anyhow::Context as _;
 use futures::future;
 use tokio::sync::{watch, Mutex, Semaphore};
+use zksync_config::configs::database::MerkleTreeMode;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::HealthUpdater;
-use zksync_merkle_tree::TreeEntry;
+use zksync_merkle_tree::{domain::ZkSyncTree, RocksDBWrapper, TreeEntry, TreeSnapshotManifest};
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
 use zksync_types::{
     snapshots::{uniform_hashed_keys_chunk, SnapshotRecoveryStatus},
@@ -185,6 +187,17 @@ impl GenericAsyncTree {
             }
             Self::Empty { db, mode } => {
                 if let Some(snapshot_recovery) = get_snapshot_recovery(main_pool).await? {
+                    if let Some(snapshot_path) = &config.tree_snapshot_path {
+                        let tree = Self::import_snapshot(
+                            db,
+                            mode,
+                            snapshot_path.clone(),
+                            &snapshot_recovery,
+                        )
+                        .await?;
+                        return Ok(Some(tree));
+                    }
+
                     tracing::info!(
                         "Starting Merkle tree recovery with status {snapshot_recovery:?}"
                     );
@@ -218,6 +231,42 @@ impl GenericAsyncTree {
         }
         Ok(tree)
     }
+
+    /// Imports the tree from a snapshot created by `ZkSyncTreeReader::export_snapshot()` on another node.
+    /// The imported tree is checked to correspond to the Postgres snapshot recovery.
+    async fn import_snapshot(
+        db: RocksDBWrapper,
+        mode: MerkleTreeMode,
+        snapshot_path: PathBuf,
+        snapshot_recovery: &SnapshotRecoveryStatus,
+    ) -> anyhow::Result<AsyncTree> {
+        let started_at = Instant::now();
+        tracing::info!(
+            "Importing Merkle tree from snapshot at {snapshot_path:?} with recovery status {snapshot_recovery:?}"
+        );
+        let l1_batch_number = snapshot_recovery.l1_batch_number;
+        let expected_root_hash = snapshot_recovery.l1_batch_root_hash;
+        let db = tokio::task::spawn_blocking(move || {
+            // Check the snapshot version before importing, so that a mismatched snapshot doesn't get persisted.
+            let manifest = TreeSnapshotManifest::read(&snapshot_path)?;
+            anyhow::ensure!(
+                manifest.version == u64::from(l1_batch_number.0),
+                "Merkle tree snapshot at {snapshot_path:?} is for L1 batch #{}, while Postgres snapshot recovery \
+                 is for L1 batch #{l1_batch_number}",
+                manifest.version
+            );
+            ZkSyncTree::import_snapshot(&db, &snapshot_path, expected_root_hash)?;
+            anyhow::Ok(db)
+        })
+        .await
+        .context("panicked importing Merkle tree snapshot")?
+        .context("failed importing Merkle tree snapshot")?;
+
+        let elapsed = started_at.elapsed();
+        APP_METRICS.snapshot_recovery_latency[&SnapshotRecoveryStage::Tree].set(elapsed);
+        tracing::info!("Imported Merkle tree from snapshot in {elapsed:?}");
+        AsyncTree::new(db, mode)
+    }
 }
 
 impl AsyncTreeRecovery {
@@ -12,7 +12,11 @@ use zksync_config::configs::{
 };
 use zksync_dal::CoreDal;
 use zksync_health_check::{CheckHealth, HealthStatus, ReactiveHealthCheck};
-use zksync_merkle_tree::{domain::ZkSyncTree, recovery::PersistenceThreadHandle, TreeInstruction};
+use zksync_merkle_tree::{
+    domain::{ZkSyncTree, ZkSyncTreeReader},
+    recovery::PersistenceThreadHandle,
+    Database, TreeInstruction,
+};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::prepare_recovery_snapshot;
 use zksync_types::{L1BatchNumber, ProtocolVersionId, StorageLog};
@@ -128,6 +132,65 @@ async fn prepare_recovery_snapshot_with_genesis(
     }
 }
 
+#[tokio::test]
+async fn importing_tree_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let mut snapshot_recovery = prepare_recovery_snapshot_with_genesis(pool, &temp_dir).await;
+    let db = create_db(mock_config(&temp_dir.path().join("init")))
+        .await
+        .unwrap();
+    let snapshot_dir = temp_dir.path().join("snapshot");
+    ZkSyncTreeReader::new(db)
+        .unwrap()
+        .export_snapshot(L1BatchNumber(1), &snapshot_dir)
+        .unwrap();
+
+    let db = create_db(mock_config(&temp_dir.path().join("imported")))
+        .await
+        .unwrap();
+    let tree = GenericAsyncTree::import_snapshot(
+        db,
+        MerkleTreeMode::Full,
+        snapshot_dir.clone(),
+        &snapshot_recovery,
+    )
+    .await
+    .unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(2));
+    assert_eq!(tree.root_hash(), snapshot_recovery.l1_batch_root_hash);
+
+    // Check that the snapshot is not imported if it doesn't correspond to the Postgres snapshot recovery.
+    let expected_root_hash = snapshot_recovery.l1_batch_root_hash;
+    snapshot_recovery.l1_batch_root_hash = H256::repeat_byte(1);
+    let db = create_db(mock_config(&temp_dir.path().join("wrong-hash")))
+        .await
+        .unwrap();
+    let err = GenericAsyncTree::import_snapshot(
+        db.clone(),
+        MerkleTreeMode::Full,
+        snapshot_dir.clone(),
+        &snapshot_recovery,
+    )
+    .await
+    .unwrap_err();
+    assert!(format!("{err:#}").contains("expected root hash"), "{err:#}");
+    assert!(db.manifest().is_none());
+
+    snapshot_recovery.l1_batch_root_hash = expected_root_hash;
+    snapshot_recovery.l1_batch_number = L1BatchNumber(2);
+    let err = GenericAsyncTree::import_snapshot(
+        db.clone(),
+        MerkleTreeMode::Full,
+        snapshot_dir,
+        &snapshot_recovery,
+    )
+    .await
+    .unwrap_err();
+    assert!(format!("{err:#}").contains("L1 batch #2"), "{err:#}");
+    assert!(db.manifest().is_none());
+}
+
 #[derive(Debug)]
 struct TestEventListener {
     expected_recovered_chunks: u64,
@@ -24,6 +24,8 @@ pub(super) struct TreeUpdater {
     tree: AsyncTree,
     max_l1_batches_per_iter: usize,
     object_store: Option<Arc<dyn ObjectStore>>,
+    /// Set to `true` while the tree is processing L1 batches; used to pause background consistency checks.
+    tree_updating: watch::Sender<bool>,
 }
 
 impl TreeUpdater {
@@ -31,11 +33,13 @@ impl TreeUpdater {
         tree: AsyncTree,
         max_l1_batches_per_iter: usize,
         object_store: Option<Arc<dyn ObjectStore>>,
+        tree_updating: watch::Sender<bool>,
     ) -> Self {
         Self {
             tree,
             max_l1_batches_per_iter,
             object_store,
+            tree_updating,
         }
     }
 
@@ -191,9 +195,12 @@ impl TreeUpdater {
             );
         } else {
             tracing::info!("Updating Merkle tree with L1 batches #{l1_batch_numbers:?}");
-            *next_l1_batch_to_seal = self
+            self.tree_updating.send_replace(true);
+            let result = self
                 .process_multiple_batches(&mut storage, l1_batch_numbers)
-                .await?;
+                .await;
+            self.tree_updating.send_replace(false);
+            *next_l1_batch_to_seal = result?;
         }
         Ok(())
     }
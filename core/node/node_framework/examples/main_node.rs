@@ -46,7 +46,7 @@ use zksync_node_framework::{
             StateKeeperLayer,
         },
         web3_api::{
-            caches::MempoolCacheLayer,
+            caches::{MempoolCacheLayer, ResponseCacheLayer},
             server::{Web3ServerLayer, Web3ServerOptionalConfig},
             tree_api_client::TreeApiClientLayer,
             tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
@@ -197,11 +197,14 @@ impl MainNodeBuilder {
         let wallets = Wallets::from_env()?;
 
         // On main node we always use master pool sink.
-        self.node.add_layer(TxSinkLayer::MasterPoolSink);
+        self.node.add_layer(TxSinkLayer::MasterPoolSink {
+            replacement_fee_bump_percent: rpc_config.replacement_fee_bump_percent(),
+        });
         self.node.add_layer(TxSenderLayer::new(
             TxSenderConfig::new(
                 &state_keeper_config,
                 &rpc_config,
+                &MempoolConfig::from_env()?,
                 wallets
                     .state_keeper
                     .context("StateKeeper wallets")?
@@ -222,6 +225,9 @@ impl MainNodeBuilder {
             rpc_config.mempool_cache_size(),
             rpc_config.mempool_cache_update_interval(),
         ));
+        self.node.add_layer(ResponseCacheLayer::new(
+            rpc_config.response_cache_size() as u64
+        ));
         Ok(self)
     }
 
@@ -244,6 +250,9 @@ impl MainNodeBuilder {
             namespaces.push(Namespace::Debug)
         }
         namespaces.push(Namespace::Snapshots);
+        if rpc_config.txpool_namespace_enabled {
+            namespaces.push(Namespace::Txpool);
+        }
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -251,6 +260,8 @@ impl MainNodeBuilder {
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            client_rate_limits: (&rpc_config).into(),
+            method_weights: Some(rpc_config.method_weights.clone()),
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::http(
@@ -275,6 +286,9 @@ impl MainNodeBuilder {
             namespaces.push(Namespace::Debug)
         }
         namespaces.push(Namespace::Snapshots);
+        if rpc_config.txpool_namespace_enabled {
+            namespaces.push(Namespace::Txpool);
+        }
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -285,6 +299,8 @@ impl MainNodeBuilder {
             websocket_requests_per_minute_limit: Some(
                 rpc_config.websocket_requests_per_minute_limit(),
             ),
+            client_rate_limits: (&rpc_config).into(),
+            method_weights: Some(rpc_config.method_weights.clone()),
             replication_lag_limit: circuit_breaker_config.replication_lag_limit(),
         };
         self.node.add_layer(Web3ServerLayer::ws(
@@ -1,12 +1,13 @@
 use std::{
     net::{Ipv4Addr, SocketAddr},
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::Context as _;
 use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
 use zksync_metadata_calculator::{
-    LazyAsyncTreeReader, MetadataCalculator, MetadataCalculatorConfig,
+    LazyAsyncTreeReader, MerkleTreeConsistencyChecker, MetadataCalculator, MetadataCalculatorConfig,
 };
 use zksync_storage::RocksDB;
 
@@ -31,10 +32,13 @@ use crate::{
 /// - Resolves `ObjectStoreResource` (optional).
 /// - Adds `tree_health_check` to the `ResourceCollection<HealthCheckResource>`.
 /// - Adds `metadata_calculator` to the node.
+/// - Adds `tree_consistency_checker` to the node and its health check to the `AppHealthCheckResource`
+///   (if the consistency check interval is set).
 #[derive(Debug)]
 pub struct MetadataCalculatorLayer {
     config: MetadataCalculatorConfig,
     tree_api_config: Option<MerkleTreeApiConfig>,
+    consistency_check_interval: Option<Duration>,
 }
 
 impl MetadataCalculatorLayer {
@@ -42,6 +46,7 @@ impl MetadataCalculatorLayer {
         Self {
             config,
             tree_api_config: None,
+            consistency_check_interval: None,
         }
     }
 
@@ -49,6 +54,12 @@ impl MetadataCalculatorLayer {
         self.tree_api_config = Some(tree_api_config);
         self
     }
+
+    /// Enables online consistency checks of randomly sampled Merkle tree subtrees with the specified interval.
+    pub fn with_consistency_check_interval(mut self, check_interval: Duration) -> Self {
+        self.consistency_check_interval = Some(check_interval);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -98,6 +109,16 @@ impl WiringLayer for MetadataCalculatorLayer {
             }));
         }
 
+        if let Some(check_interval) = self.consistency_check_interval {
+            let consistency_checker = metadata_calculator.consistency_checker(check_interval);
+            app_health
+                .insert_component(consistency_checker.health_check())
+                .map_err(WiringError::internal)?;
+            context.add_task(Box::new(TreeConsistencyCheckerTask {
+                consistency_checker,
+            }));
+        }
+
         context.insert_resource(TreeApiClientResource(Arc::new(
             metadata_calculator.tree_reader(),
         )))?;
@@ -154,3 +175,19 @@ impl Task for TreeApiTask {
             .await
     }
 }
+
+#[derive(Debug)]
+pub struct TreeConsistencyCheckerTask {
+    consistency_checker: MerkleTreeConsistencyChecker,
+}
+
+#[async_trait::async_trait]
+impl Task for TreeConsistencyCheckerTask {
+    fn id(&self) -> TaskId {
+        "tree_consistency_checker".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.consistency_checker.run(stop_receiver.0).await
+    }
+}
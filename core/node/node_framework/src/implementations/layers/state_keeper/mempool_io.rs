@@ -18,7 +18,10 @@ use crate::{
     implementations::resources::{
         fee_input::FeeInputResource,
         pools::{MasterPool, PoolResource},
-        state_keeper::{ConditionalSealerResource, OutputHandlerResource, StateKeeperIOResource},
+        state_keeper::{
+            ConditionalSealerResource, MempoolResource, OutputHandlerResource,
+            StateKeeperIOResource,
+        },
     },
     resource::Unique,
     service::{ServiceContext, StopReceiver},
@@ -64,7 +67,7 @@ impl MempoolIOLayer {
             .connection()
             .await
             .context("Access storage to build mempool")?;
-        let mempool = MempoolGuard::from_storage(&mut storage, self.mempool_config.capacity).await;
+        let mempool = MempoolGuard::from_storage(&mut storage, &self.mempool_config).await;
         mempool.register_metrics();
         Ok(mempool)
     }
@@ -100,6 +103,7 @@ impl WiringLayer for MempoolIOLayer {
 
         // Create mempool fetcher task.
         let mempool_guard = self.build_mempool_guard(&master_pool).await?;
+        context.insert_resource(MempoolResource(mempool_guard.clone()))?;
         let mempool_fetcher_pool = master_pool
             .get_singleton()
             .await
@@ -1,11 +1,14 @@
 use std::time::Duration;
 
-use zksync_node_api_server::web3::mempool_cache::{self, MempoolCache};
+use zksync_node_api_server::web3::{
+    mempool_cache::{self, MempoolCache},
+    response_cache::{self, ResponseCache},
+};
 
 use crate::{
     implementations::resources::{
         pools::{PoolResource, ReplicaPool},
-        web3_api::MempoolCacheResource,
+        web3_api::{MempoolCacheResource, ResponseCacheResource},
     },
     service::{ServiceContext, StopReceiver},
     task::{Task, TaskId},
@@ -57,3 +60,46 @@ impl Task for MempoolCacheUpdateTask {
         self.0.run(stop_receiver.0).await
     }
 }
+
+/// Wiring layer for the API server response cache. If added, the cache is used by all API servers.
+#[derive(Debug)]
+pub struct ResponseCacheLayer {
+    capacity: u64,
+}
+
+impl ResponseCacheLayer {
+    pub fn new(capacity: u64) -> Self {
+        Self { capacity }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ResponseCacheLayer {
+    fn layer_name(&self) -> &'static str {
+        "response_cache_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool_resource = context.get_resource::<PoolResource<ReplicaPool>>().await?;
+        let replica_pool = pool_resource.get().await?;
+        let response_cache = ResponseCache::new(self.capacity);
+        let update_task = response_cache.update_task(replica_pool);
+        context.add_task(Box::new(ResponseCacheUpdateTask(update_task)));
+        context.insert_resource(ResponseCacheResource(response_cache))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ResponseCacheUpdateTask(response_cache::ResponseCacheUpdateTask);
+
+#[async_trait::async_trait]
+impl Task for ResponseCacheUpdateTask {
+    fn id(&self) -> TaskId {
+        "response_cache_update_task".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,78 @@
+use std::net::SocketAddr;
+
+use zksync_config::configs::api::Web3JsonRpcConfig;
+use zksync_node_api_server::{graphql::GraphQlServer, web3::backend_jsonrpsee::ClientRateLimits};
+use zksync_types::L2ChainId;
+
+use crate::{
+    implementations::resources::pools::{PoolResource, ReplicaPool},
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for a GraphQL API server.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<ReplicaPool>`.
+/// - Adds `graphql_server` to the node.
+#[derive(Debug)]
+pub struct GraphQlServerLayer {
+    port: u16,
+    chain_id: L2ChainId,
+    max_page_size: usize,
+    client_rate_limits: ClientRateLimits,
+}
+
+impl GraphQlServerLayer {
+    pub fn new(port: u16, chain_id: L2ChainId, max_page_size: usize) -> Self {
+        Self {
+            port,
+            chain_id,
+            max_page_size,
+            client_rate_limits: ClientRateLimits::default(),
+        }
+    }
+
+    /// Applies per-client rate limits configured for the JSON-RPC servers.
+    pub fn with_client_rate_limits(mut self, rpc_config: &Web3JsonRpcConfig) -> Self {
+        self.client_rate_limits = rpc_config.into();
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for GraphQlServerLayer {
+    fn layer_name(&self) -> &'static str {
+        "graphql_server_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let replica_pool = context
+            .get_resource::<PoolResource<ReplicaPool>>()
+            .await?
+            .get()
+            .await?;
+        let bind_address = SocketAddr::new([0, 0, 0, 0].into(), self.port);
+        let server = GraphQlServer::new(bind_address, replica_pool, self.chain_id)
+            .with_max_page_size(self.max_page_size)
+            .with_client_rate_limits(self.client_rate_limits);
+        context.add_task(Box::new(GraphQlServerTask(server)));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct GraphQlServerTask(GraphQlServer);
+
+#[async_trait::async_trait]
+impl Task for GraphQlServerTask {
+    fn id(&self) -> TaskId {
+        "graphql_server".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
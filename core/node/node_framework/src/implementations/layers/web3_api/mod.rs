@@ -1,4 +1,5 @@
 pub mod caches;
+pub mod graphql;
 pub mod server;
 pub mod tree_api_client;
 pub mod tx_sender;
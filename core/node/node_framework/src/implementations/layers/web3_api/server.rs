@@ -2,16 +2,21 @@ use std::{num::NonZeroU32, time::Duration};
 
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_circuit_breaker::replication_lag::ReplicationLagChecker;
-use zksync_config::configs::api::MaxResponseSize;
-use zksync_node_api_server::web3::{state::InternalApiConfig, ApiBuilder, ApiServer, Namespace};
+use zksync_config::configs::api::{MaxResponseSize, RpcMethodWeights};
+use zksync_node_api_server::web3::{
+    backend_jsonrpsee::ClientRateLimits, state::InternalApiConfig, ApiBuilder, ApiServer, Namespace,
+};
 
 use crate::{
     implementations::resources::{
         circuit_breakers::CircuitBreakersResource,
         healthcheck::AppHealthCheckResource,
         pools::{PoolResource, ReplicaPool},
+        state_keeper::MempoolResource,
         sync_state::SyncStateResource,
-        web3_api::{MempoolCacheResource, TreeApiClientResource, TxSenderResource},
+        web3_api::{
+            MempoolCacheResource, ResponseCacheResource, TreeApiClientResource, TxSenderResource,
+        },
     },
     service::{ServiceContext, StopReceiver},
     task::{Task, TaskId},
@@ -27,6 +32,8 @@ pub struct Web3ServerOptionalConfig {
     pub batch_request_size_limit: Option<usize>,
     pub response_body_size_limit: Option<MaxResponseSize>,
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    pub client_rate_limits: ClientRateLimits,
+    pub method_weights: Option<RpcMethodWeights>,
     // used by circuit breaker.
     pub replication_lag_limit: Option<Duration>,
 }
@@ -53,6 +60,10 @@ impl Web3ServerOptionalConfig {
             api_builder = api_builder
                 .with_websocket_requests_per_minute_limit(websocket_requests_per_minute_limit);
         }
+        api_builder = api_builder.with_client_rate_limits(self.client_rate_limits);
+        if let Some(method_weights) = self.method_weights {
+            api_builder = api_builder.with_method_weights(method_weights);
+        }
         api_builder
     }
 }
@@ -126,6 +137,16 @@ impl WiringLayer for Web3ServerLayer {
             Err(err) => return Err(err),
         };
         let MempoolCacheResource(mempool_cache) = context.get_resource().await?;
+        let mempool = match context.get_resource::<MempoolResource>().await {
+            Ok(mempool) => Some(mempool.0),
+            Err(WiringError::ResourceLacking { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        let response_cache = match context.get_resource::<ResponseCacheResource>().await {
+            Ok(cache) => Some(cache.0),
+            Err(WiringError::ResourceLacking { .. }) => None,
+            Err(err) => return Err(err),
+        };
 
         // Build server.
         let mut api_builder =
@@ -136,6 +157,12 @@ impl WiringLayer for Web3ServerLayer {
         if let Some(client) = tree_api_client {
             api_builder = api_builder.with_tree_api(client);
         }
+        if let Some(mempool) = mempool {
+            api_builder = api_builder.with_mempool(mempool);
+        }
+        if let Some(cache) = response_cache {
+            api_builder = api_builder.with_response_cache(cache);
+        }
         match self.transport {
             Transport::Http => {
                 api_builder = api_builder.http(self.port);
@@ -15,7 +15,7 @@ use crate::{
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum TxSinkLayer {
-    MasterPoolSink,
+    MasterPoolSink { replacement_fee_bump_percent: u32 },
     ProxySink,
 }
 
@@ -27,13 +27,18 @@ impl WiringLayer for TxSinkLayer {
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
         let tx_sink = match self.as_ref() {
-            TxSinkLayer::MasterPoolSink => {
+            TxSinkLayer::MasterPoolSink {
+                replacement_fee_bump_percent,
+            } => {
                 let pool = context
                     .get_resource::<PoolResource<MasterPool>>()
                     .await?
                     .get()
                     .await?;
-                TxSinkResource(Arc::new(MasterPoolSink::new(pool)))
+                TxSinkResource(Arc::new(MasterPoolSink::new(
+                    pool,
+                    *replacement_fee_bump_percent,
+                )))
             }
             TxSinkLayer::ProxySink => {
                 let MainNodeClientResource(client) = context.get_resource().await?;
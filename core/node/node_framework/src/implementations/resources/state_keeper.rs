@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use zksync_state_keeper::{
-    seal_criteria::ConditionalSealer, BatchExecutor, OutputHandler, StateKeeperIO,
+    seal_criteria::ConditionalSealer, BatchExecutor, MempoolGuard, OutputHandler, StateKeeperIO,
 };
 
 use crate::resource::{Resource, Unique};
@@ -41,3 +41,13 @@ impl Resource for ConditionalSealerResource {
         "state_keeper/conditional_sealer".into()
     }
 }
+
+/// A resource that provides access to the state keeper mempool.
+#[derive(Debug, Clone)]
+pub struct MempoolResource(pub MempoolGuard);
+
+impl Resource for MempoolResource {
+    fn name() -> String {
+        "state_keeper/mempool".into()
+    }
+}
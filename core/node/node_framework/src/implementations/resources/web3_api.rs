@@ -3,7 +3,7 @@ use std::sync::Arc;
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_api_server::{
     tx_sender::{tx_sink::TxSink, TxSender},
-    web3::mempool_cache::MempoolCache,
+    web3::{mempool_cache::MempoolCache, response_cache::ResponseCache},
 };
 
 use crate::resource::Resource;
@@ -43,3 +43,12 @@ impl Resource for MempoolCacheResource {
         "api/mempool_cache".into()
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct ResponseCacheResource(pub ResponseCache);
+
+impl Resource for ResponseCacheResource {
+    fn name() -> String {
+        "api/response_cache".into()
+    }
+}
@@ -18,7 +18,7 @@ pub use self::{
         StateKeeperOutputHandler, StateKeeperPersistence, TreeWritesPersistence,
     },
     keeper::ZkSyncStateKeeper,
-    mempool_actor::MempoolFetcher,
+    mempool_actor::{mempool_eviction_policy, MempoolFetcher},
     seal_criteria::SequencerSealer,
     state_keeper_storage::AsyncRocksdbCache,
     types::{ExecutionMetricsForCriteria, MempoolGuard},
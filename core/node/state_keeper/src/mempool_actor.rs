@@ -7,7 +7,7 @@ use tokio::sync::mpsc;
 use tokio::sync::watch;
 use zksync_config::configs::chain::MempoolConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_mempool::L2TxFilter;
+use zksync_mempool::{EvictionPolicy, L2TxFilter};
 use zksync_node_fee_model::BatchFeeModelInputProvider;
 #[cfg(test)]
 use zksync_types::H256;
@@ -31,6 +31,16 @@ pub async fn l2_tx_filter(
     })
 }
 
+/// Creates a mempool eviction policy based on the mempool configuration.
+pub fn mempool_eviction_policy(config: &MempoolConfig) -> EvictionPolicy {
+    EvictionPolicy {
+        max_txs_per_account: config.max_txs_per_account,
+        max_nonce_gap: config.max_nonce_gap,
+        tx_ttl: config.tx_ttl(),
+        evict_lowest_fee: config.evict_lowest_fee,
+    }
+}
+
 #[derive(Debug)]
 pub struct MempoolFetcher {
     mempool: MempoolGuard,
@@ -56,7 +66,7 @@ impl MempoolFetcher {
             batch_fee_input_provider,
             sync_interval: config.sync_interval(),
             sync_batch_size: config.sync_batch_size,
-            stuck_tx_timeout: config.remove_stuck_txs.then(|| config.stuck_tx_timeout()),
+            stuck_tx_timeout: config.tx_ttl(),
             #[cfg(test)]
             transaction_hashes_sender: mpsc::unbounded_channel().0,
         }
@@ -108,6 +118,22 @@ impl MempoolFetcher {
                 .await
                 .context("failed syncing mempool")?;
             let nonces = get_transaction_nonces(&mut storage, &transactions).await?;
+            if !mempool_info.evicted_txs.is_empty() {
+                let rejected_txs: Vec<_> = mempool_info
+                    .evicted_txs
+                    .iter()
+                    .map(|tx| {
+                        KEEPER_METRICS.mempool_evicted_txs[&tx.reason.as_metric_label()].inc();
+                        (tx.hash, format!("evicted: {}", tx.reason))
+                    })
+                    .collect();
+                storage
+                    .transactions_dal()
+                    .mark_txs_as_rejected(&rejected_txs)
+                    .await
+                    .context("failed marking evicted transactions as rejected")?;
+                tracing::info!("Evicted {} transactions from mempool", rejected_txs.len());
+            }
             drop(storage);
 
             #[cfg(test)]
@@ -176,6 +202,9 @@ mod tests {
         stuck_tx_timeout: 0,
         remove_stuck_txs: false,
         delay_interval: 10,
+        max_txs_per_account: None,
+        max_nonce_gap: None,
+        evict_lowest_fee: false,
     };
 
     #[tokio::test]
@@ -7,8 +7,8 @@ use std::{
 
 use multivm::interface::{VmExecutionResultAndLogs, VmRevertReason};
 use vise::{
-    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LatencyObserver,
-    Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    LatencyObserver, Metrics,
 };
 use zksync_mempool::MempoolStore;
 use zksync_shared_metrics::InteractionType;
@@ -89,6 +89,9 @@ pub struct StateKeeperMetrics {
     pub gas_price_too_high: Counter,
     /// Number of times blob base fee was reported as too high.
     pub blob_base_fee_too_high: Counter,
+    /// Number of L2 transactions evicted from the mempool, grouped by the eviction reason.
+    #[metrics(labels = ["reason"])]
+    pub mempool_evicted_txs: LabeledFamily<&'static str, Counter>,
 }
 
 fn vm_revert_reason_as_metric_label(reason: &VmRevertReason) -> &'static str {
@@ -4,13 +4,15 @@ use std::{
 };
 
 use multivm::interface::VmExecutionResultAndLogs;
+use zksync_config::configs::chain::MempoolConfig;
 use zksync_dal::{Connection, Core, CoreDal};
-use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
+use zksync_mempool::{L2TxFilter, MempoolContents, MempoolInfo, MempoolStore};
 use zksync_types::{
     block::BlockGasCount, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
 };
 
 use super::{
+    mempool_actor::mempool_eviction_policy,
     metrics::StateKeeperGauges,
     utils::{gas_count_from_metrics, gas_count_from_tx_and_metrics},
 };
@@ -19,12 +21,17 @@ use super::{
 pub struct MempoolGuard(Arc<Mutex<MempoolStore>>);
 
 impl MempoolGuard {
-    pub async fn from_storage(storage_processor: &mut Connection<'_, Core>, capacity: u64) -> Self {
+    pub async fn from_storage(
+        storage_processor: &mut Connection<'_, Core>,
+        config: &MempoolConfig,
+    ) -> Self {
         let next_priority_id = storage_processor
             .transactions_dal()
             .next_priority_id()
             .await;
-        Self::new(next_priority_id, capacity)
+        let store = MempoolStore::new(next_priority_id, config.capacity)
+            .with_eviction_policy(mempool_eviction_policy(config));
+        Self(Arc::new(Mutex::new(store)))
     }
 
     pub(super) fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
@@ -67,6 +74,13 @@ impl MempoolGuard {
             .get_mempool_info()
     }
 
+    pub fn contents(&self) -> MempoolContents {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .contents()
+    }
+
     #[cfg(test)]
     pub fn stats(&self) -> zksync_mempool::MempoolStats {
         self.0
@@ -32,6 +32,11 @@ async fn prover_and_assert_base_layer(
         },
         max_retries: 5,
         local_mirror_path: None,
+        local_mirror_max_size_mb: None,
+        local_mirror_buckets: vec![],
+        compress_objects: false,
+        deduplicate_objects: false,
+        deduplication_gc_interval_sec: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()